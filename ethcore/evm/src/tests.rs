@@ -20,6 +20,7 @@ use std::hash::Hash;
 use std::sync::Arc;
 use std::collections::{HashMap, HashSet};
 use rustc_hex::FromHex;
+use hash::keccak;
 use ethereum_types::{U256, H256, Address};
 use vm::{self, ActionParams, ActionValue};
 use vm::tests::{FakeExt, FakeCall, FakeCallType, test_finalize};
@@ -716,7 +717,7 @@ fn test_jumps(factory: super::Factory) {
 		test_finalize(vm.exec(params, &mut ext)).unwrap()
 	};
 
-	assert_eq!(ext.sstore_clears, 1);
+	assert_eq!(ext.sstore_clears, ext.schedule.sstore_refund_gas as i64);
 	assert_store(&ext, 0, "0000000000000000000000000000000000000000000000000000000000000000"); // 5!
 	assert_store(&ext, 1, "0000000000000000000000000000000000000000000000000000000000000078"); // 5!
 	assert_eq!(gas_left, U256::from(54_117));
@@ -787,6 +788,408 @@ fn test_create_in_staticcall(factory: super::Factory) {
 	assert_eq!(ext.calls.len(), 0);
 }
 
+evm_test!{ignorejit => test_shl: test_shl_jit, test_shl_int}
+fn test_shl(factory: super::Factory) {
+	let gas_left = push_two_pop_one_constantinople_test(
+		&factory,
+		0x1b,
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"00",
+		"0000000000000000000000000000000000000000000000000000000000000001");
+	assert_eq!(gas_left, U256::from(79_988));
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1b,
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"01",
+		"0000000000000000000000000000000000000000000000000000000000000002");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1b,
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"ff",
+		"8000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1b,
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"0100",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1b,
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"0101",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1b,
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"00",
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1b,
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"01",
+		"fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1b,
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"ff",
+		"8000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1b,
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"0100",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1b,
+		"0000000000000000000000000000000000000000000000000000000000000000",
+		"01",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1b,
+		"7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"01",
+		"fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe");
+}
+
+evm_test!{ignorejit => test_shr: test_shr_jit, test_shr_int}
+fn test_shr(factory: super::Factory) {
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1c,
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"00",
+		"0000000000000000000000000000000000000000000000000000000000000001");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1c,
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"01",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1c,
+		"8000000000000000000000000000000000000000000000000000000000000000",
+		"01",
+		"4000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1c,
+		"8000000000000000000000000000000000000000000000000000000000000000",
+		"ff",
+		"0000000000000000000000000000000000000000000000000000000000000001");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1c,
+		"8000000000000000000000000000000000000000000000000000000000000000",
+		"0100",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1c,
+		"8000000000000000000000000000000000000000000000000000000000000000",
+		"0101",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1c,
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"00",
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1c,
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"01",
+		"7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1c,
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"ff",
+		"0000000000000000000000000000000000000000000000000000000000000001");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1c,
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"0100",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1c,
+		"0000000000000000000000000000000000000000000000000000000000000000",
+		"01",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+}
+
+evm_test!{ignorejit => test_sar: test_sar_jit, test_sar_int}
+fn test_sar(factory: super::Factory) {
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"00",
+		"0000000000000000000000000000000000000000000000000000000000000001");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"0000000000000000000000000000000000000000000000000000000000000001",
+		"01",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"8000000000000000000000000000000000000000000000000000000000000000",
+		"01",
+		"c000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"8000000000000000000000000000000000000000000000000000000000000000",
+		"ff",
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"8000000000000000000000000000000000000000000000000000000000000000",
+		"0100",
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"8000000000000000000000000000000000000000000000000000000000000000",
+		"0101",
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"00",
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"01",
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"0100",
+		"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"0000000000000000000000000000000000000000000000000000000000000000",
+		"01",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"4000000000000000000000000000000000000000000000000000000000000000",
+		"fe",
+		"0000000000000000000000000000000000000000000000000000000000000001");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"f8",
+		"000000000000000000000000000000000000000000000000000000000000007f");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"fe",
+		"0000000000000000000000000000000000000000000000000000000000000001");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"ff",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_constantinople_test(
+		&factory,
+		0x1d,
+		"7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+		"0100",
+		"0000000000000000000000000000000000000000000000000000000000000000");
+}
+
+evm_test!{ignorejit => test_bitwise_shifting_disabled: test_bitwise_shifting_disabled_jit, test_bitwise_shifting_disabled_int}
+fn test_bitwise_shifting_disabled(factory: super::Factory) {
+	// PUSH1 1 PUSH1 1 SHL
+	let code = "600160011b".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new_byzantium();
+
+	let err = {
+		let mut vm = factory.create(&params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap_err()
+	};
+
+	assert_eq!(err, vm::Error::BadInstruction { instruction: 0x1b });
+}
+
+evm_test!{ignorejit => test_extcodehash: test_extcodehash_jit, test_extcodehash_int}
+fn test_extcodehash(factory: super::Factory) {
+	// 33 - caller
+	// 3f - extcodehash
+	// 60 00 - push 0
+	// 55 - sstore
+	// 30 - address
+	// 3f - extcodehash
+	// 60 01 - push 1
+	// 55 - sstore
+	let sender = Address::from_str("cd1722f2947def4cf144679da39c4c32bdc35681").unwrap();
+	let code = "333f600055303f600155".from_hex().unwrap();
+	let sender_code = "6005600055".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.sender = sender.clone();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new_constantinople();
+	ext.codes.insert(sender, Arc::new(sender_code.clone()));
+
+	let gas_left = {
+		let mut vm = factory.create(&params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap()
+	};
+
+	assert_eq!(gas_left, U256::from(78_990));
+	assert_eq!(ext.store.get(&H256::from(0)).unwrap(), &keccak(&sender_code));
+	assert_eq!(ext.store.get(&H256::from(1)).unwrap(), &H256::zero());
+}
+
+evm_test!{ignorejit => test_extcodehash_disabled: test_extcodehash_disabled_jit, test_extcodehash_disabled_int}
+fn test_extcodehash_disabled(factory: super::Factory) {
+	let code = "333f".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new_byzantium();
+
+	let err = {
+		let mut vm = factory.create(&params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap_err()
+	};
+
+	assert_eq!(err, vm::Error::BadInstruction { instruction: 0x3f });
+}
+
+evm_test!{ignorejit => test_create2: test_create2_jit, test_create2_int}
+fn test_create2(factory: super::Factory) {
+	// 60 00 - push 0 (salt)
+	// 60 20 - push 32 (init code size)
+	// 60 00 - push 0 (init code offset)
+	// 60 00 - push 0 (endowment)
+	// f5 - create2
+	let code = "6000602060006000f5".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new_constantinople();
+
+	let gas_left = {
+		let mut vm = factory.create(&params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap()
+	};
+
+	// create2 costs 32000 plus 6 per word of hashed init code, 63/64 of the rest is passed on
+	assert_eq!(gas_left, U256::from(1_062));
+	assert_set_contains(&ext.calls, &FakeCall {
+		call_type: FakeCallType::Create,
+		gas: U256::from(66_917),
+		sender_address: None,
+		receive_address: None,
+		value: Some(U256::zero()),
+		data: vec![0; 32],
+		code_address: None,
+	});
+}
+
+evm_test!{ignorejit => test_sstore_eip1283: test_sstore_eip1283_jit, test_sstore_eip1283_int}
+fn test_sstore_eip1283(factory: super::Factory) {
+	// test vectors from EIP-1283
+	sstore_eip1283_test(&factory, "60006000556000600055", 0, 412, 0);
+	sstore_eip1283_test(&factory, "60006000556001600055", 0, 20212, 0);
+	sstore_eip1283_test(&factory, "60016000556000600055", 0, 20212, 19800);
+	sstore_eip1283_test(&factory, "60016000556002600055", 0, 20212, 0);
+	sstore_eip1283_test(&factory, "60016000556001600055", 0, 20212, 0);
+	sstore_eip1283_test(&factory, "60006000556000600055", 1, 5212, 15000);
+	sstore_eip1283_test(&factory, "60006000556001600055", 1, 5212, 4800);
+	sstore_eip1283_test(&factory, "60006000556002600055", 1, 5212, 0);
+	sstore_eip1283_test(&factory, "60026000556000600055", 1, 5212, 15000);
+	sstore_eip1283_test(&factory, "60026000556003600055", 1, 5212, 0);
+	sstore_eip1283_test(&factory, "60026000556001600055", 1, 5212, 4800);
+	sstore_eip1283_test(&factory, "60026000556002600055", 1, 5212, 0);
+	sstore_eip1283_test(&factory, "60016000556000600055", 1, 5212, 15000);
+	sstore_eip1283_test(&factory, "60016000556002600055", 1, 5212, 0);
+	sstore_eip1283_test(&factory, "60016000556001600055", 1, 412, 0);
+	sstore_eip1283_test(&factory, "600160005560006000556001600055", 0, 40218, 19800);
+	sstore_eip1283_test(&factory, "600060005560016000556000600055", 1, 10218, 19800);
+}
+
+fn sstore_eip1283_test(factory: &super::Factory, code: &str, original: u64, gas_used: u64, refund: i64) {
+	let code = code.from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new_constantinople();
+	ext.initial_store.insert(H256::from(0), H256::from(original));
+	ext.store.insert(H256::from(0), H256::from(original));
+
+	let gas_left = {
+		let mut vm = factory.create(&params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap()
+	};
+
+	assert_eq!(gas_left, U256::from(100_000 - gas_used));
+	assert_eq!(ext.sstore_clears, refund);
+}
+
+fn push_two_pop_one_constantinople_test(factory: &super::Factory, opcode: u8, push1: &str, push2: &str, result: &str) -> U256 {
+	let mut push1 = push1.from_hex().unwrap();
+	let mut push2 = push2.from_hex().unwrap();
+	assert!(push1.len() <= 32 && push1.len() != 0);
+	assert!(push2.len() <= 32 && push2.len() != 0);
+
+	let mut code = Vec::new();
+	code.push(0x60 + ((push1.len() - 1) as u8));
+	code.append(&mut push1);
+	code.push(0x60 + ((push2.len() - 1) as u8));
+	code.append(&mut push2);
+	code.push(opcode);
+	code.append(&mut vec![0x60, 0x00, 0x55]);
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new_constantinople();
+
+	let gas_left = {
+		let mut vm = factory.create(&params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap()
+	};
+
+	assert_store(&ext, 0, result);
+	gas_left
+}
+
 fn assert_set_contains<T : Debug + Eq + PartialEq + Hash>(set: &HashSet<T>, val: &T) {
 	let contains = set.contains(val);
 	if !contains {
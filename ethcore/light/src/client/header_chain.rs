@@ -500,6 +500,8 @@ impl HeaderChain {
 			BlockId::Latest | BlockId::Pending => {
 				Some(self.best_block.read().hash)
 			}
+			// ancient headers are not kept, so timestamps can't be searched.
+			BlockId::Timestamp(_) => None,
 		}
 	}
 
@@ -554,6 +556,7 @@ impl HeaderChain {
 
 				load_from_db(hash)
 			}
+			BlockId::Timestamp(_) => None,
 		}
 	}
 
@@ -575,7 +578,8 @@ impl HeaderChain {
 				if self.best_block.read().number < num { return None }
 				candidates.get(&num).map(|era| era.candidates[0].total_difficulty)
 			}
-			BlockId::Latest | BlockId::Pending => Some(self.best_block.read().total_difficulty)
+			BlockId::Latest | BlockId::Pending => Some(self.best_block.read().total_difficulty),
+			BlockId::Timestamp(_) => None,
 		}
 	}
 
@@ -22,7 +22,7 @@ use std::mem;
 use itertools::Itertools;
 use bloomchain as bc;
 use heapsize::HeapSizeOf;
-use ethereum_types::{H256, Bloom, U256};
+use ethereum_types::{H256, Bloom, U256, Address};
 use parking_lot::{Mutex, RwLock};
 use bytes::Bytes;
 use rlp::*;
@@ -35,12 +35,12 @@ use receipt::Receipt;
 use blooms::{BloomGroup, GroupPosition};
 use blockchain::best_block::{BestBlock, BestAncientBlock};
 use blockchain::block_info::{BlockInfo, BlockLocation, BranchBecomingCanonChainData};
-use blockchain::extras::{BlockReceipts, BlockDetails, TransactionAddress, EPOCH_KEY_PREFIX, EpochTransitions};
+use blockchain::extras::{BlockReceipts, BlockDetails, TransactionAddress, AccountTransaction, EPOCH_KEY_PREFIX, EpochTransitions};
 use types::blockchain_info::BlockChainInfo;
 use types::tree_route::TreeRoute;
 use blockchain::update::ExtrasUpdate;
 use blockchain::{CacheSize, ImportRoute, Config};
-use db::{self, Writable, Readable, CacheUpdatePolicy};
+use db::{self, Writable, Readable, CacheUpdatePolicy, Key};
 use cache_manager::CacheManager;
 use encoded;
 use engines::epoch::{Transition as EpochTransition, PendingTransition as PendingEpochTransition};
@@ -211,6 +211,9 @@ pub struct BlockChain {
 
 	db: Arc<KeyValueDB>,
 
+	// whether the account transaction index is maintained.
+	account_tx_index: bool,
+
 	cache_man: Mutex<CacheManager<CacheId>>,
 
 	pending_best_block: RwLock<Option<BestBlock>>,
@@ -511,6 +514,7 @@ impl BlockChain {
 			blocks_blooms: RwLock::new(HashMap::new()),
 			block_receipts: RwLock::new(HashMap::new()),
 			db: db.clone(),
+			account_tx_index: config.account_tx_index,
 			cache_man: Mutex::new(cache_man),
 			pending_best_block: RwLock::new(None),
 			pending_block_hashes: RwLock::new(HashMap::new()),
@@ -761,6 +765,7 @@ impl BlockChain {
 				block_receipts: self.prepare_block_receipts_update(receipts, &info),
 				blocks_blooms: self.prepare_block_blooms_update(bytes, &info),
 				transactions_addresses: self.prepare_transaction_addresses_update(bytes, &info),
+				account_transactions: self.prepare_account_transactions_update(bytes, &info),
 				info: info,
 				timestamp: header.timestamp(),
 				block: bytes
@@ -810,6 +815,7 @@ impl BlockChain {
 				block_receipts: self.prepare_block_receipts_update(receipts, &info),
 				blocks_blooms: self.prepare_block_blooms_update(bytes, &info),
 				transactions_addresses: self.prepare_transaction_addresses_update(bytes, &info),
+				account_transactions: self.prepare_account_transactions_update(bytes, &info),
 				info: info,
 				timestamp: header.timestamp(),
 				block: bytes,
@@ -961,6 +967,7 @@ impl BlockChain {
 			block_receipts: self.prepare_block_receipts_update(receipts, &info),
 			blocks_blooms: self.prepare_block_blooms_update(bytes, &info),
 			transactions_addresses: self.prepare_transaction_addresses_update(bytes, &info),
+			account_transactions: self.prepare_account_transactions_update(bytes, &info),
 			info: info.clone(),
 			timestamp: header.timestamp(),
 			block: bytes,
@@ -1048,6 +1055,13 @@ impl BlockChain {
 			}
 		}
 
+		for (position, block_hash) in update.account_transactions {
+			match block_hash {
+				Some(ref hash) => batch.write(db::COL_ACCOUNT_TX, &position, hash),
+				None => batch.delete(db::COL_ACCOUNT_TX, &position.key()),
+			}
+		}
+
 		// These cached values must be updated last with all four locks taken to avoid
 		// cache decoherence
 		{
@@ -1166,6 +1180,64 @@ impl BlockChain {
 		Some(ret)
 	}
 
+	/// Returns the number of the last canon chain block with timestamp at or before `timestamp`.
+	/// `None` if all known blocks are newer.
+	pub fn block_number_at_timestamp(&self, timestamp: u64) -> Option<BlockNumber> {
+		let timestamp_at = |number| self.block_hash(number)
+			.and_then(|hash| self.block_header_data(&hash))
+			.map(|header| header.timestamp());
+
+		// blocks before the first one may be missing after a snapshot restoration.
+		let mut lo = self.first_block_number().unwrap_or(0);
+		let mut hi = self.best_block_number();
+
+		if timestamp_at(lo)? > timestamp {
+			return None;
+		}
+
+		if self.best_block_timestamp() <= timestamp {
+			return Some(hi);
+		}
+
+		// timestamps strictly increase along the chain, so a binary search
+		// keeping `timestamp_at(lo) <= timestamp < timestamp_at(hi)` converges.
+		while hi - lo > 1 {
+			let mid = lo + (hi - lo) / 2;
+			if timestamp_at(mid)? <= timestamp {
+				lo = mid;
+			} else {
+				hi = mid;
+			}
+		}
+
+		Some(lo)
+	}
+
+	/// Returns the addresses of canon chain transactions sent from or to `address` within blocks
+	/// `from_block..=to_block`, in chain order and at most `limit` of them.
+	/// `None` if the account transaction index is not maintained.
+	pub fn account_transactions(&self, address: &Address, from_block: BlockNumber, to_block: BlockNumber, limit: usize) -> Option<Vec<TransactionAddress>> {
+		if !self.account_tx_index {
+			return None;
+		}
+
+		let addresses = self.db.iter_from_prefix(db::COL_ACCOUNT_TX, &address[..])
+			.map(|(key, value)| (AccountTransaction::from_key(&key), value))
+			.skip_while(|&(ref position, _)| position.address == *address && position.block_number < from_block)
+			// iterator may continue beyond entries of this account.
+			.take_while(|&(ref position, _)| position.address == *address && position.block_number <= to_block)
+			.map(|(position, value)| TransactionAddress {
+				block_hash: ::rlp::decode(&value),
+				index: position.index,
+			})
+			// entries of retracted blocks may still be visible while a reorg is being committed.
+			.filter(|tx_address| self.is_canon(&tx_address.block_hash))
+			.take(limit)
+			.collect();
+
+		Some(addresses)
+	}
+
 	/// This function returns modified block hashes.
 	fn prepare_block_hashes_update(&self, block_bytes: &[u8], info: &BlockInfo) -> HashMap<BlockNumber, H256> {
 		let mut block_hashes = HashMap::new();
@@ -1279,6 +1351,60 @@ impl BlockChain {
 		}
 	}
 
+	/// This function returns modified account transaction index entries.
+	fn prepare_account_transactions_update(&self, block_bytes: &[u8], info: &BlockInfo) -> HashMap<AccountTransaction, Option<H256>> {
+		if !self.account_tx_index {
+			return HashMap::new();
+		}
+
+		let route = ImportRoute::from(info.clone());
+
+		let retracted = route.retracted.iter().flat_map(|hash| {
+			let number = self.block_number(hash).expect("Retracted block must be in database.");
+			let body = self.block_body(hash).expect("Retracted block must be in database.");
+			Self::account_transactions_of(number, body.transactions(), None)
+		});
+
+		let enacted = route.enacted.iter().flat_map(|hash| {
+			// the inserted block itself is not in the database yet.
+			if *hash == info.hash {
+				let block = BlockView::new(block_bytes);
+				return Self::account_transactions_of(info.number, block.transactions(), Some(info.hash));
+			}
+
+			let number = self.block_number(hash).expect("Enacted block must be in database.");
+			let body = self.block_body(hash).expect("Enacted block must be in database.");
+			Self::account_transactions_of(number, body.transactions(), Some(*hash))
+		});
+
+		// The order here is important! Don't remove entries which are re-added by enacted blocks.
+		retracted.chain(enacted).collect()
+	}
+
+	/// Returns account transaction index entries for the senders and recipients of given transactions.
+	/// Contract creations are indexed under their sender only, the created address depends on the engine.
+	fn account_transactions_of(number: BlockNumber, transactions: Vec<UnverifiedTransaction>, block_hash: Option<H256>) -> Vec<(AccountTransaction, Option<H256>)> {
+		let mut entries = Vec::new();
+		for (index, tx) in transactions.into_iter().enumerate() {
+			let recipient = match tx.action {
+				Action::Call(ref to) => Some(*to),
+				Action::Create => None,
+			};
+
+			// blocks are verified before insertion, so signatures are valid.
+			let sender = SignedTransaction::new(tx).ok().map(|tx| tx.sender());
+
+			for address in sender.into_iter().chain(recipient) {
+				entries.push((AccountTransaction {
+					address: address,
+					block_number: number,
+					index: index,
+				}, block_hash));
+			}
+		}
+		entries
+	}
+
 	/// This functions returns modified blocks blooms.
 	///
 	/// To accelerate blooms lookups, blomms are stored in multiple
@@ -1451,6 +1577,8 @@ mod tests {
 	use hash::keccak;
 	use kvdb::KeyValueDB;
 	use kvdb_memorydb;
+	use kvdb_rocksdb::{Database, DatabaseConfig};
+	use tempdir::TempDir;
 	use ethereum_types::*;
 	use receipt::{Receipt, TransactionOutcome};
 	use blockchain::{BlockProvider, BlockChain, Config, ImportRoute};
@@ -1548,6 +1676,32 @@ mod tests {
 		assert_eq!(block_hashes.len(), 11);
 	}
 
+	#[test]
+	fn test_block_number_at_timestamp() {
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block_with_timestamp(10);
+		let b2 = b1.add_block_with_timestamp(20);
+		let b3 = b2.add_block_with_timestamp(30);
+		let generator = BlockGenerator::new(vec![b1, b2, b3]);
+
+		let db = new_db();
+		let bc = new_chain(&genesis.last().encoded(), db.clone());
+
+		let mut batch = db.transaction();
+		for block in generator {
+			bc.insert_block(&mut batch, &block.encoded(), vec![]);
+			bc.commit();
+		}
+		db.write(batch).unwrap();
+
+		assert_eq!(bc.block_number_at_timestamp(0), Some(0));
+		assert_eq!(bc.block_number_at_timestamp(9), Some(0));
+		assert_eq!(bc.block_number_at_timestamp(10), Some(1));
+		assert_eq!(bc.block_number_at_timestamp(25), Some(2));
+		assert_eq!(bc.block_number_at_timestamp(30), Some(3));
+		assert_eq!(bc.block_number_at_timestamp(1000), Some(3));
+	}
+
 	#[test]
 	fn test_find_uncles() {
 		let genesis = BlockBuilder::genesis();
@@ -1637,6 +1791,117 @@ mod tests {
 		assert_eq!(bc.transaction_address(&t1_hash), None);
 	}
 
+	#[test]
+	fn test_fork_account_transactions() {
+		let recipient = Address::from(0x1234);
+		let t1 = Transaction {
+			nonce: 0.into(),
+			gas_price: 0.into(),
+			gas: 100_000.into(),
+			action: Action::Call(recipient),
+			value: 100.into(),
+			data: vec![],
+		}.sign(&secret(), None);
+
+		let sender = t1.sender();
+
+		let genesis = BlockBuilder::genesis();
+		let b1a = genesis.add_block_with_transactions(iter::once(t1));
+		let b1b = genesis.add_block_with_difficulty(9);
+		let b2 = b1b.add_block();
+
+		let b1a_hash = b1a.last().hash();
+
+		let db = new_db();
+		let bc = BlockChain::new(Config {
+			account_tx_index: true,
+			..Default::default()
+		}, &genesis.last().encoded(), db.clone());
+
+		let mut batch = db.transaction();
+		let _ = bc.insert_block(&mut batch, &b1a.last().encoded(), vec![]);
+		bc.commit();
+		let _ = bc.insert_block(&mut batch, &b1b.last().encoded(), vec![]);
+		bc.commit();
+		db.write(batch).unwrap();
+
+		let expected = vec![TransactionAddress {
+			block_hash: b1a_hash,
+			index: 0,
+		}];
+		assert_eq!(bc.account_transactions(&sender, 0, 10, 10), Some(expected.clone()));
+		assert_eq!(bc.account_transactions(&recipient, 0, 10, 10), Some(expected));
+		assert_eq!(bc.account_transactions(&recipient, 2, 10, 10), Some(vec![]));
+		assert_eq!(bc.account_transactions(&recipient, 0, 10, 0), Some(vec![]));
+		assert_eq!(bc.account_transactions(&Address::from(0x5678), 0, 10, 10), Some(vec![]));
+
+		// now let's make forked chain the canon chain
+		let mut batch = db.transaction();
+		let _ = bc.insert_block(&mut batch, &b2.last().encoded(), vec![]);
+		bc.commit();
+		db.write(batch).unwrap();
+
+		// Index entries should be retracted
+		assert_eq!(bc.account_transactions(&sender, 0, 10, 10), Some(vec![]));
+		assert_eq!(bc.account_transactions(&recipient, 0, 10, 10), Some(vec![]));
+
+		// and the index is not available when disabled.
+		let bc = new_chain(&genesis.last().encoded(), db.clone());
+		assert_eq!(bc.account_transactions(&sender, 0, 10, 10), None);
+	}
+
+	#[test]
+	fn test_fork_account_transactions_buffered() {
+		let recipient = Address::from(0x1234);
+		let t1 = Transaction {
+			nonce: 0.into(),
+			gas_price: 0.into(),
+			gas: 100_000.into(),
+			action: Action::Call(recipient),
+			value: 100.into(),
+			data: vec![],
+		}.sign(&secret(), None);
+
+		let genesis = BlockBuilder::genesis();
+		let b1a = genesis.add_block_with_transactions(iter::once(t1));
+		let b1b = genesis.add_block_with_difficulty(9);
+		let b2 = b1b.add_block();
+
+		let b1a_hash = b1a.last().hash();
+
+		// the index must be visible before the client flushes the write buffer.
+		let tempdir = TempDir::new("").unwrap();
+		let db_config = DatabaseConfig::with_columns(::db::NUM_COLUMNS);
+		let db: Arc<KeyValueDB> = Arc::new(Database::open(&db_config, tempdir.path().to_str().unwrap()).unwrap());
+		let bc = BlockChain::new(Config {
+			account_tx_index: true,
+			..Default::default()
+		}, &genesis.last().encoded(), db.clone());
+
+		let mut batch = db.transaction();
+		let _ = bc.insert_block(&mut batch, &b1a.last().encoded(), vec![]);
+		bc.commit();
+		db.write_buffered(batch);
+
+		let expected = vec![TransactionAddress {
+			block_hash: b1a_hash,
+			index: 0,
+		}];
+		assert_eq!(bc.account_transactions(&recipient, 0, 10, 10), Some(expected));
+
+		let mut batch = db.transaction();
+		let _ = bc.insert_block(&mut batch, &b1b.last().encoded(), vec![]);
+		bc.commit();
+		let _ = bc.insert_block(&mut batch, &b2.last().encoded(), vec![]);
+		bc.commit();
+		db.write_buffered(batch);
+
+		assert_eq!(bc.account_transactions(&recipient, 0, 10, 10), Some(vec![]));
+
+		db.flush().unwrap();
+		assert_eq!(bc.account_transactions(&recipient, 0, 10, 10), Some(vec![]));
+	}
+
 	#[test]
 	fn test_overwriting_transaction_addresses() {
 		let t1 = Transaction {
@@ -25,7 +25,7 @@ use header::BlockNumber;
 use receipt::Receipt;
 
 use heapsize::HeapSizeOf;
-use ethereum_types::{H256, H264, U256};
+use ethereum_types::{H256, H264, U256, Address};
 use kvdb::PREFIX_LEN as DB_PREFIX_LEN;
 
 /// Represents index of extra data in database
@@ -166,6 +166,72 @@ impl Key<EpochTransitions> for u64 {
 	}
 }
 
+/// Length of account transaction index keys.
+pub const ACCOUNT_TRANSACTION_KEY_LEN: usize = 20 + 8 + 4;
+
+/// Position of a transaction sent from or to an account.
+///
+/// Index keys are ordered by account, then block number and transaction index,
+/// which allows walking the transactions of a single account in chain order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccountTransaction {
+	/// Sender or recipient of the transaction.
+	pub address: Address,
+	/// Number of the block containing the transaction.
+	pub block_number: BlockNumber,
+	/// Transaction index within the block.
+	pub index: usize,
+}
+
+impl AccountTransaction {
+	/// Decode the position from an account transaction index key.
+	pub fn from_key(key: &[u8]) -> Self {
+		assert_eq!(key.len(), ACCOUNT_TRANSACTION_KEY_LEN, "account transaction index contains only fixed-length keys; qed");
+
+		let mut block_number = 0u64;
+		for byte in &key[20..28] {
+			block_number = (block_number << 8) | *byte as u64;
+		}
+
+		let mut index = 0u32;
+		for byte in &key[28..] {
+			index = (index << 8) | *byte as u32;
+		}
+
+		AccountTransaction {
+			address: Address::from_slice(&key[..20]),
+			block_number: block_number,
+			index: index as usize,
+		}
+	}
+}
+
+pub struct AccountTransactionKey([u8; ACCOUNT_TRANSACTION_KEY_LEN]);
+
+impl ops::Deref for AccountTransactionKey {
+	type Target = [u8];
+
+	fn deref(&self) -> &[u8] { &self.0[..] }
+}
+
+/// Account transaction index entries point at the hash of the containing block.
+impl Key<H256> for AccountTransaction {
+	type Target = AccountTransactionKey;
+
+	fn key(&self) -> Self::Target {
+		let mut arr = [0u8; ACCOUNT_TRANSACTION_KEY_LEN];
+		arr[..20].copy_from_slice(&self.address);
+		for i in 0..8 {
+			arr[20 + i] = (self.block_number >> (8 * (7 - i))) as u8;
+		}
+		for i in 0..4 {
+			arr[28 + i] = (self.index as u32 >> (8 * (3 - i))) as u8;
+		}
+
+		AccountTransactionKey(arr)
+	}
+}
+
 /// Familial details concerning a block
 #[derive(Debug, Clone, RlpEncodable, RlpDecodable)]
 pub struct BlockDetails {
@@ -228,7 +294,8 @@ pub struct EpochTransitions {
 #[cfg(test)]
 mod tests {
 	use rlp::*;
-	use super::BlockReceipts;
+	use db::Key;
+	use super::{BlockReceipts, AccountTransaction};
 
 	#[test]
 	fn encode_block_receipts() {
@@ -241,4 +308,18 @@ mod tests {
 		assert!(s.is_finished(), "List should be finished now");
 		s.out();
 	}
+
+	#[test]
+	fn account_transaction_key_roundtrip() {
+		let position = AccountTransaction {
+			address: 0x1234.into(),
+			block_number: 0x0102030405,
+			index: 0x0607,
+		};
+
+		let key = position.key();
+		assert_eq!(&key[..20], &*position.address);
+		assert_eq!(&key[20..], &[0, 0, 0, 1, 2, 3, 4, 5, 0, 0, 6, 7]);
+		assert_eq!(AccountTransaction::from_key(&key), position);
+	}
 }
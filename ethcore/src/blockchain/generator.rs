@@ -65,6 +65,7 @@ pub struct BlockOptions {
 	pub difficulty: U256,
 	pub bloom: Bloom,
 	pub transactions: Vec<SignedTransaction>,
+	pub timestamp: u64,
 }
 
 impl Default for BlockOptions {
@@ -73,6 +74,7 @@ impl Default for BlockOptions {
 			difficulty: 10.into(),
 			bloom: Bloom::default(),
 			transactions: Vec::new(),
+			timestamp: 0,
 		}
 	}
 }
@@ -116,6 +118,14 @@ impl BlockBuilder {
 		})
 	}
 
+	#[inline]
+	pub fn add_block_with_timestamp(&self, timestamp: u64) -> Self {
+		self.add_blocks_with(1, move || BlockOptions {
+			timestamp,
+			..Default::default()
+		})
+	}
+
 	#[inline]
 	pub fn add_block_with_transactions<T>(&self, transactions: T) -> Self
 		where T: IntoIterator<Item = SignedTransaction> {
@@ -147,6 +157,7 @@ impl BlockBuilder {
 			block.header.set_number(block_number);
 			block.header.set_log_bloom(metadata.bloom);
 			block.header.set_difficulty(metadata.difficulty);
+			block.header.set_timestamp(metadata.timestamp);
 			block.transactions = metadata.transactions;
 
 			parent_hash = block.hash();
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use ethereum_types::H256;
 use header::BlockNumber;
 use blockchain::block_info::BlockInfo;
-use blockchain::extras::{BlockDetails, BlockReceipts, TransactionAddress};
+use blockchain::extras::{BlockDetails, BlockReceipts, TransactionAddress, AccountTransaction};
 use blooms::{BloomGroup, GroupPosition};
 
 /// Block extras update info.
@@ -23,4 +23,6 @@ pub struct ExtrasUpdate<'a> {
 	pub blocks_blooms: HashMap<GroupPosition, BloomGroup>,
 	/// Modified transaction addresses (None signifies removed transactions).
 	pub transactions_addresses: HashMap<H256, Option<TransactionAddress>>,
+	/// Modified account transaction index entries (None signifies removed entries).
+	pub account_transactions: HashMap<AccountTransaction, Option<H256>>,
 }
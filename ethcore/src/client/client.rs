@@ -27,7 +27,7 @@ use hash::keccak;
 use bytes::Bytes;
 use journaldb;
 use util_error::UtilError;
-use trie::{TrieSpec, TrieFactory, Trie};
+use trie::{self, TrieSpec, TrieFactory, Trie};
 use kvdb::{DBValue, KeyValueDB, DBTransaction};
 
 // other
@@ -39,7 +39,8 @@ use client::Error as ClientError;
 use client::{
 	BlockId, TransactionId, UncleId, TraceId, ClientConfig, BlockChainClient,
 	MiningBlockChainClient, TraceFilter, CallAnalytics, BlockImportError, Mode,
-	ChainNotify, PruningInfo, ProvingBlockChainClient,
+	ChainNotify, PruningInfo, ProvingBlockChainClient, StateOverride,
+	GasPriceOracle, BlockFees, FeeHistory, GAS_PRICE_ORACLE_HISTORY, TokenIndex, TokenHolding,
 };
 use encoded;
 use engines::{EthEngine, EpochTransition};
@@ -52,6 +53,8 @@ use header::{BlockNumber, Header, Seal};
 use io::*;
 use log_entry::LocalizedLogEntry;
 use miner::{Miner, MinerService};
+use pod_account::PodAccount;
+use pod_state::PodState;
 use parking_lot::{Mutex, RwLock};
 use rand::OsRng;
 use receipt::{Receipt, LocalizedReceipt};
@@ -64,7 +67,7 @@ use state::{self, State};
 use trace;
 use trace::{TraceDB, ImportRequest as TraceImportRequest, LocalizedTrace, Database as TraceDatabase};
 use transaction::{self, LocalizedTransaction, UnverifiedTransaction, SignedTransaction, Transaction, PendingTransaction, Action};
-use types::filter::Filter;
+use types::filter::{Filter, LogCursor};
 use types::mode::Mode as IpcMode;
 use verification;
 use verification::{PreverifiedBlock, Verifier};
@@ -82,6 +85,8 @@ use_contract!(registry, "Registry", "res/contracts/registrar.json");
 const MAX_TX_QUEUE_SIZE: usize = 4096;
 const MAX_QUEUE_SIZE_TO_SLEEP_ON: usize = 2;
 const MIN_HISTORY_SIZE: u64 = 8;
+/// Number of blocks fetched at once when paging through logs.
+const LOGS_PAGE_BLOCKS: usize = 128;
 
 /// Report on the status of a client.
 #[derive(Default, Clone, Debug, Eq, PartialEq)]
@@ -165,6 +170,8 @@ pub struct Client {
 	registrar: registry::Registry,
 	registrar_address: Option<Address>,
 	exit_handler: Mutex<Option<Box<Fn(bool, Option<String>) + 'static + Send>>>,
+	gas_price_oracle: RwLock<GasPriceOracle>,
+	token_index: RwLock<Option<Arc<TokenIndex>>>,
 }
 
 impl Client {
@@ -257,8 +264,30 @@ impl Client {
 			registrar: registry::Registry::default(),
 			registrar_address,
 			exit_handler: Mutex::new(None),
+			gas_price_oracle: RwLock::new(GasPriceOracle::default()),
+			token_index: RwLock::new(None),
 		});
 
+		// seed the gas price oracle with recent blocks.
+		{
+			let chain = client.chain.read();
+			let best = chain.best_block_number();
+			let mut oracle = client.gas_price_oracle.write();
+			for number in best.saturating_sub(GAS_PRICE_ORACLE_HISTORY as u64 - 1)..best + 1 {
+				if let Some(fees) = chain.block_hash(number).and_then(|hash| Self::block_fees(&chain, &hash)) {
+					oracle.note_block(fees);
+				}
+			}
+		}
+
+		if client.config.token_index {
+			let best_block = client.chain.read().best_block_hash();
+			let db = client.db.read().clone();
+			let index = Arc::new(TokenIndex::new(Arc::downgrade(&client), db, best_block));
+			client.add_notify(index.clone());
+			*client.token_index.write() = Some(index);
+		}
+
 		// prune old states.
 		{
 			let state_db = client.state_db.read().boxed_clone();
@@ -695,6 +724,15 @@ impl Client {
 
 		self.update_last_hashes(&parent, hash);
 
+		{
+			let mut oracle = self.gas_price_oracle.write();
+			for hash in &route.enacted {
+				if let Some(fees) = Self::block_fees(&chain, hash) {
+					oracle.note_block(fees);
+				}
+			}
+		}
+
 		if let Err(e) = self.prune_ancient(state, &chain) {
 			warn!("Failed to prune ancient state data: {}", e);
 		}
@@ -702,6 +740,25 @@ impl Client {
 		route
 	}
 
+	// gas prices and gas used by transactions of given block, for the gas price oracle.
+	fn block_fees(chain: &BlockChain, hash: &H256) -> Option<BlockFees> {
+		let header = chain.block_header_data(hash)?;
+		let body = chain.block_body(hash)?;
+		let receipts = chain.block_receipts(hash)?.receipts;
+
+		let mut prev_gas_used = U256::zero();
+		let transactions = body.transaction_views().into_iter()
+			.zip(receipts)
+			.map(|(tx, receipt)| {
+				let gas_used = receipt.gas_used - prev_gas_used;
+				prev_gas_used = receipt.gas_used;
+				(tx.gas_price(), gas_used)
+			})
+			.collect::<Vec<_>>();
+
+		Some(BlockFees::new(header.number(), *hash, header.gas_used(), header.gas_limit(), transactions))
+	}
+
 	// check for epoch end signal and write pending transition if it occurs.
 	// state for the given block must be available.
 	fn check_epoch_end_signal(
@@ -1054,7 +1111,7 @@ impl Client {
 			},
 		};
 
-		snapshot::take_snapshot(&*self.engine, &self.chain.read(), start_hash, db.as_hashdb(), writer, p)?;
+		snapshot::take_snapshot(&*self.engine, &self.chain.read(), start_hash, db.as_hashdb(), self.config.snapshot_compression, writer, p)?;
 
 		Ok(())
 	}
@@ -1070,7 +1127,8 @@ impl Client {
 			BlockId::Number(number) => chain.block_hash(number),
 			BlockId::Earliest => chain.block_hash(0),
 			BlockId::Latest => Some(chain.best_block_hash()),
-			BlockId::Pending => miner.pending_block_header(chain.best_block_number()).map(|header| header.hash())
+			BlockId::Pending => miner.pending_block_header(chain.best_block_number()).map(|header| header.hash()),
+			BlockId::Timestamp(timestamp) => chain.block_number_at_timestamp(timestamp).and_then(|number| chain.block_hash(number)),
 		}
 	}
 
@@ -1128,38 +1186,147 @@ impl Client {
 		t: &SignedTransaction,
 		analytics: CallAnalytics,
 	) -> Result<Executed, CallError> {
-		fn call<V, T>(
-			state: &mut State<StateDB>,
-			env_info: &EnvInfo,
-			machine: &::machine::EthereumMachine,
-			state_diff: bool,
-			transaction: &SignedTransaction,
-			options: TransactOptions<T, V>,
-		) -> Result<Executed<T::Output, V::Output>, CallError> where
-			T: trace::Tracer,
-			V: trace::VMTracer,
-		{
-			let options = options
-				.dont_check_nonce()
-				.save_output_from_contract();
-			let original_state = if state_diff { Some(state.clone()) } else { None };
+		let state_diff = analytics.state_diffing;
 
-			let mut ret = Executive::new(state, env_info, machine).transact_virtual(transaction, options)?;
+		match (analytics.transaction_tracing, analytics.vm_tracing) {
+			(true, true) => Self::do_virtual_call_with_options(machine, env_info, state, t, state_diff, TransactOptions::with_tracing_and_vm_tracing()),
+			(true, false) => Self::do_virtual_call_with_options(machine, env_info, state, t, state_diff, TransactOptions::with_tracing()),
+			(false, true) => Self::do_virtual_call_with_options(machine, env_info, state, t, state_diff, TransactOptions::with_vm_tracing()),
+			(false, false) => Self::do_virtual_call_with_options(machine, env_info, state, t, state_diff, TransactOptions::with_no_tracing()),
+		}
+	}
 
-			if let Some(original) = original_state {
-				ret.state_diff = Some(state.diff_from(original).map_err(ExecutionError::from)?);
-			}
-			Ok(ret)
+	fn do_virtual_call_with_options<T, V>(
+		machine: &::machine::EthereumMachine,
+		env_info: &EnvInfo,
+		state: &mut State<StateDB>,
+		t: &SignedTransaction,
+		state_diff: bool,
+		options: TransactOptions<T, V>,
+	) -> Result<Executed<T::Output, V::Output>, CallError> where
+		T: trace::Tracer,
+		V: trace::VMTracer,
+	{
+		let options = options
+			.dont_check_nonce()
+			.save_output_from_contract();
+		let original_state = if state_diff { Some(state.clone()) } else { None };
+
+		let mut ret = Executive::new(state, env_info, machine).transact_virtual(t, options)?;
+
+		if let Some(original) = original_state {
+			ret.state_diff = Some(state.diff_from(original).map_err(ExecutionError::from)?);
 		}
+		Ok(ret)
+	}
+
+	fn do_virtual_call_with_presets(
+		machine: &::machine::EthereumMachine,
+		env_info: &EnvInfo,
+		state: &mut State<StateDB>,
+		t: &SignedTransaction,
+		analytics: CallAnalytics,
+		presets: &[trace::TracerPreset],
+	) -> Result<(Executed, Vec<trace::PresetTrace>), CallError> {
+		let original_state = if presets.contains(&trace::TracerPreset::Prestate) {
+			let original = state.clone();
+			state.record_access();
+			Some(original)
+		} else {
+			None
+		};
 
+		// presets are traced alongside the analytics, so that the transaction is executed once.
 		let state_diff = analytics.state_diffing;
+		let tracer = trace::PresetTracer::default();
+		let mut ret = if analytics.vm_tracing {
+			let options = TransactOptions::new(tracer, trace::ExecutiveVMTracer::toplevel());
+			Self::do_virtual_call_with_options(machine, env_info, state, t, state_diff, options)?
+		} else {
+			let options = TransactOptions::new(tracer, trace::NoopVMTracer);
+			Self::do_virtual_call_with_options(machine, env_info, state, t, state_diff, options)?
+		};
 
-		match (analytics.transaction_tracing, analytics.vm_tracing) {
-			(true, true) => call(state, env_info, machine, state_diff, t, TransactOptions::with_tracing_and_vm_tracing()),
-			(true, false) => call(state, env_info, machine, state_diff, t, TransactOptions::with_tracing()),
-			(false, true) => call(state, env_info, machine, state_diff, t, TransactOptions::with_vm_tracing()),
-			(false, false) => call(state, env_info, machine, state_diff, t, TransactOptions::with_no_tracing()),
+		let (traces, calls) = ::std::mem::replace(&mut ret.trace, Vec::new()).into_iter().next().unwrap_or_default();
+		let traces = if analytics.transaction_tracing { traces } else { Vec::new() };
+		let (ret, _) = ret.replace_trace(traces);
+
+		let mut preset_traces = Vec::with_capacity(presets.len());
+		for preset in presets {
+			let preset_trace = match *preset {
+				trace::TracerPreset::Call => trace::PresetTrace::Call(calls.first().cloned()),
+				trace::TracerPreset::Prestate => {
+					let original = original_state.as_ref().expect("original state is kept when the prestate is traced; qed");
+					let prestate = Self::prestate(original, &env_info.author, state.accessed())
+						.map_err(ExecutionError::from)?;
+					trace::PresetTrace::Prestate(prestate)
+				},
+			};
+			preset_traces.push(preset_trace);
 		}
+
+		Ok((ret, preset_traces))
+	}
+
+	// Pre-execution state of the accounts a transaction read or wrote, with the storage it accessed.
+	fn prestate(
+		original: &State<StateDB>,
+		author: &Address,
+		accessed: BTreeMap<Address, BTreeSet<H256>>,
+	) -> ::trie::Result<PodState> {
+		let mut addresses: BTreeSet<Address> = accessed.keys().cloned().collect();
+		addresses.insert(*author);
+
+		let mut accounts = BTreeMap::new();
+		for address in addresses {
+			if !original.exists(&address)? {
+				continue;
+			}
+
+			let mut storage = BTreeMap::new();
+			if let Some(keys) = accessed.get(&address) {
+				for key in keys {
+					storage.insert(*key, original.storage_at(&address, key)?);
+				}
+			}
+
+			accounts.insert(address, PodAccount {
+				balance: original.balance(&address)?,
+				nonce: original.nonce(&address)?,
+				code: original.code(&address)?.map(|code| (*code).clone()),
+				storage: storage,
+			});
+		}
+
+		Ok(PodState::from(accounts))
+	}
+
+	/// Replays the transaction with given id on top of the state left by the preceding
+	/// transactions of its block.
+	fn replay_with<F, R>(&self, id: TransactionId, f: F) -> Result<R, CallError> where
+		F: FnOnce(&::machine::EthereumMachine, &EnvInfo, &mut State<StateDB>, &SignedTransaction) -> Result<R, CallError>,
+	{
+		let address = self.transaction_address(id).ok_or(CallError::TransactionNotFound)?;
+		let block = BlockId::Hash(address.block_hash);
+
+		let mut env_info = self.env_info(block).ok_or(CallError::StatePruned)?;
+		let body = self.block_body(block).ok_or(CallError::StatePruned)?;
+		let mut state = self.state_at_beginning(block).ok_or(CallError::StatePruned)?;
+		let mut txs = body.transactions();
+		let machine = self.engine.machine();
+
+		const PROOF: &'static str = "Transactions fetched from blockchain; blockchain transactions are valid; qed";
+		const INDEX_PROOF: &'static str = "The transaction address contains a valid index within block; qed";
+
+		let t = txs.drain(address.index..).next().expect(INDEX_PROOF);
+		for t in txs {
+			let t = SignedTransaction::new(t).expect(PROOF);
+			let x = Self::do_virtual_call(machine, &env_info, &mut state, &t, Default::default())?;
+			env_info.gas_used = env_info.gas_used + x.gas_used;
+		}
+
+		let t = SignedTransaction::new(t).expect(PROOF);
+		f(machine, &env_info, &mut state, &t)
 	}
 
 	fn block_number_ref(&self, id: &BlockId) -> Option<BlockNumber> {
@@ -1169,8 +1336,85 @@ impl Client {
 			BlockId::Earliest => Some(0),
 			BlockId::Latest => Some(self.chain.read().best_block_number()),
 			BlockId::Pending => Some(self.chain.read().best_block_number() + 1),
+			BlockId::Timestamp(timestamp) => self.chain.read().block_number_at_timestamp(timestamp),
 		}
 	}
+
+	/// Returns hashes of the blocks which may contain logs matching given filter, in ascending order.
+	fn log_blocks(&self, filter: &Filter) -> Option<Vec<H256>> {
+		let chain = self.chain.read();
+
+		// First, check whether `filter.from_block` and `filter.to_block` is on the canon chain. If so, we can use the
+		// optimized version.
+		let is_canon = |id| {
+			match id {
+				&BlockId::Pending => true,
+				// If it is referred by number, then it is always on the canon chain.
+				&BlockId::Earliest | &BlockId::Latest | &BlockId::Number(_) | &BlockId::Timestamp(_) => true,
+				// If it is referred by hash, we see whether a hash -> number -> hash conversion gives us the same
+				// result.
+				&BlockId::Hash(ref hash) => chain.is_canon(hash),
+			}
+		};
+
+		let blocks = if is_canon(&filter.from_block) && is_canon(&filter.to_block) {
+			// If we are on the canon chain, use bloom filter to fetch required hashes.
+			let from = self.block_number_ref(&filter.from_block)?;
+			let to = self.block_number_ref(&filter.to_block)?;
+
+			filter.bloom_possibilities().iter()
+				.map(|bloom| {
+					chain.blocks_with_bloom(bloom, from, to)
+				})
+				.flat_map(|m| m)
+				// remove duplicate elements
+				.collect::<BTreeSet<u64>>()
+				.into_iter()
+				.filter_map(|n| chain.block_hash(n))
+				.collect::<Vec<H256>>()
+
+		} else {
+			// Otherwise, we use a slower version that finds a link between from_block and to_block.
+			let from_hash = Self::block_hash(&chain, &*self.miner, filter.from_block)?;
+			let from_number = chain.block_number(&from_hash)?;
+			let to_hash = Self::block_hash(&chain, &*self.miner, filter.from_block)?;
+
+			let blooms = filter.bloom_possibilities();
+			let bloom_match = |header: &encoded::Header| {
+				blooms.iter().any(|bloom| header.log_bloom().contains_bloom(bloom))
+			};
+
+			let (blocks, last_hash) = {
+				let mut blocks = Vec::new();
+				let mut current_hash = to_hash;
+
+				loop {
+					let header = chain.block_header_data(&current_hash)?;
+					if bloom_match(&header) {
+						blocks.push(current_hash);
+					}
+
+					// Stop if `from` block is reached.
+					if header.number() <= from_number {
+						break;
+					}
+					current_hash = header.parent_hash();
+				}
+
+				blocks.reverse();
+				(blocks, current_hash)
+			};
+
+			// Check if we've actually reached the expected `from` block.
+			if last_hash != from_hash || blocks.is_empty() {
+				return None;
+			}
+
+			blocks
+		};
+
+		Some(blocks)
+	}
 }
 
 impl snapshot::DatabaseRestore for Client {
@@ -1195,18 +1439,31 @@ impl snapshot::DatabaseRestore for Client {
 }
 
 impl BlockChainClient for Client {
-	fn call(&self, transaction: &SignedTransaction, analytics: CallAnalytics, block: BlockId) -> Result<Executed, CallError> {
+	fn call(&self, transaction: &SignedTransaction, analytics: CallAnalytics, block: BlockId, state_override: &StateOverride) -> Result<Executed, CallError> {
 		let mut env_info = self.env_info(block).ok_or(CallError::StatePruned)?;
 		env_info.gas_limit = U256::max_value();
 
 		// that's just a copy of the state.
 		let mut state = self.state_at(block).ok_or(CallError::StatePruned)?;
+		state.apply_overrides(state_override).map_err(|_| CallError::StateCorrupt)?;
 		let machine = self.engine.machine();
 
 		Self::do_virtual_call(machine, &env_info, &mut state, transaction, analytics)
 	}
 
-	fn call_many(&self, transactions: &[(SignedTransaction, CallAnalytics)], block: BlockId) -> Result<Vec<Executed>, CallError> {
+	fn call_with_presets(&self, transaction: &SignedTransaction, analytics: CallAnalytics, presets: &[trace::TracerPreset], block: BlockId, state_override: &StateOverride) -> Result<(Executed, Vec<trace::PresetTrace>), CallError> {
+		let mut env_info = self.env_info(block).ok_or(CallError::StatePruned)?;
+		env_info.gas_limit = U256::max_value();
+
+		// that's just a copy of the state.
+		let mut state = self.state_at(block).ok_or(CallError::StatePruned)?;
+		state.apply_overrides(state_override).map_err(|_| CallError::StateCorrupt)?;
+		let machine = self.engine.machine();
+
+		Self::do_virtual_call_with_presets(machine, &env_info, &mut state, transaction, analytics, presets)
+	}
+
+	fn call_many(&self, transactions: &[(SignedTransaction, CallAnalytics, StateOverride)], block: BlockId) -> Result<Vec<Executed>, CallError> {
 		let mut env_info = self.env_info(block).ok_or(CallError::StatePruned)?;
 		env_info.gas_limit = U256::max_value();
 
@@ -1215,7 +1472,8 @@ impl BlockChainClient for Client {
 		let mut results = Vec::with_capacity(transactions.len());
 		let machine = self.engine.machine();
 
-		for &(ref t, analytics) in transactions {
+		for &(ref t, analytics, ref state_override) in transactions {
+			state.apply_overrides(state_override).map_err(|_| CallError::StateCorrupt)?;
 			let ret = Self::do_virtual_call(machine, &env_info, &mut state, t, analytics)?;
 			env_info.gas_used = ret.cumulative_gas_used;
 			results.push(ret);
@@ -1224,7 +1482,7 @@ impl BlockChainClient for Client {
 		Ok(results)
 	}
 
-	fn estimate_gas(&self, t: &SignedTransaction, block: BlockId) -> Result<U256, CallError> {
+	fn estimate_gas(&self, t: &SignedTransaction, block: BlockId, state_override: &StateOverride) -> Result<U256, CallError> {
 		let (mut upper, max_upper, env_info)  = {
 			let mut env_info = self.env_info(block).ok_or(CallError::StatePruned)?;
 			let init = env_info.gas_limit;
@@ -1234,7 +1492,8 @@ impl BlockChainClient for Client {
 		};
 
 		// that's just a copy of the state.
-		let original_state = self.state_at(block).ok_or(CallError::StatePruned)?;
+		let mut original_state = self.state_at(block).ok_or(CallError::StatePruned)?;
+		original_state.apply_overrides(state_override).map_err(|_| CallError::StateCorrupt)?;
 		let sender = t.sender();
 		let options = || TransactOptions::with_tracing().dont_check_nonce();
 
@@ -1316,6 +1575,19 @@ impl BlockChainClient for Client {
 			})))
 	}
 
+	fn replay_struct_logs(&self, id: TransactionId, options: trace::StructLoggerOptions) -> Result<Executed<trace::FlatTrace, Vec<trace::StructLog>>, CallError> {
+		self.replay_with(id, |machine, env_info, state, t| {
+			let options = TransactOptions::new(trace::NoopTracer, trace::StructLogger::toplevel(options))
+				.dont_check_nonce()
+				.save_output_from_contract();
+			Ok(Executive::new(state, env_info, machine).transact_virtual(t, options)?)
+		})
+	}
+
+	fn replay_with_presets(&self, id: TransactionId, analytics: CallAnalytics, presets: &[trace::TracerPreset]) -> Result<(Executed, Vec<trace::PresetTrace>), CallError> {
+		self.replay_with(id, |machine, env_info, state, t| Self::do_virtual_call_with_presets(machine, env_info, state, t, analytics, presets))
+	}
+
 
 	fn mode(&self) -> IpcMode {
 		let r = self.mode.lock().clone().into();
@@ -1475,6 +1747,16 @@ impl BlockChainClient for Client {
 		self.state_at(id).and_then(|s| s.storage_at(address, position).ok())
 	}
 
+	fn balances(&self, addresses: &[Address], id: BlockId) -> Option<Vec<trie::Result<U256>>> {
+		let state = self.state_at(id)?;
+		Some(addresses.iter().map(|address| state.balance(address)).collect())
+	}
+
+	fn storage_batch(&self, entries: &[(Address, H256)], id: BlockId) -> Option<Vec<trie::Result<H256>>> {
+		let state = self.state_at(id)?;
+		Some(entries.iter().map(|&(ref address, ref position)| state.storage_at(address, position)).collect())
+	}
+
 	fn list_accounts(&self, id: BlockId, after: Option<&Address>, count: u64) -> Option<Vec<Address>> {
 		if !self.factories.trie.is_fat() {
 			trace!(target: "fatdb", "list_accounts: Not a fat DB");
@@ -1565,6 +1847,23 @@ impl BlockChainClient for Client {
 		self.transaction_address(id).map(|addr| addr.block_hash)
 	}
 
+	fn account_transactions(&self, address: &Address, from_block: BlockId, to_block: BlockId, limit: usize) -> Option<Vec<LocalizedTransaction>> {
+		let from = self.block_number_ref(&from_block)?;
+		let to = self.block_number_ref(&to_block)?;
+
+		let chain = self.chain.read();
+		let addresses = chain.account_transactions(address, from, to, limit)?;
+		Some(addresses.into_iter().filter_map(|tx_address| chain.transaction(&tx_address)).collect())
+	}
+
+	fn token_holdings(&self, holder: &Address) -> Option<Vec<TokenHolding>> {
+		self.token_index.read().as_ref().map(|index| index.holdings(holder))
+	}
+
+	fn token_holders(&self, token: &Address, limit: usize) -> Option<Vec<TokenHolding>> {
+		self.token_index.read().as_ref().map(|index| index.top_holders(token, limit))
+	}
+
 	fn uncle(&self, id: UncleId) -> Option<encoded::Header> {
 		let index = id.position;
 		self.block_body(id.block).and_then(|body| body.view().uncle_rlp_at(index))
@@ -1603,7 +1902,9 @@ impl BlockChainClient for Client {
 	}
 
 	fn find_uncles(&self, hash: &H256) -> Option<Vec<H256>> {
-		self.chain.read().find_uncle_hashes(hash, self.engine.maximum_uncle_age())
+		let chain = self.chain.read();
+		let child_number = chain.block_number(hash)? + 1;
+		chain.find_uncle_hashes(hash, self.engine.maximum_uncle_age(child_number))
 	}
 
 	fn state_data(&self, hash: &H256) -> Option<Bytes> {
@@ -1667,83 +1968,30 @@ impl BlockChainClient for Client {
 	}
 
 	fn logs(&self, filter: Filter) -> Vec<LocalizedLogEntry> {
-		// Wrap the logic inside a closure so that we can take advantage of question mark syntax.
-		let fetch_logs = || {
-			let chain = self.chain.read();
-
-			// First, check whether `filter.from_block` and `filter.to_block` is on the canon chain. If so, we can use the
-			// optimized version.
-			let is_canon = |id| {
-				match id {
-					&BlockId::Pending => true,
-					// If it is referred by number, then it is always on the canon chain.
-					&BlockId::Earliest | &BlockId::Latest | &BlockId::Number(_) => true,
-					// If it is referred by hash, we see whether a hash -> number -> hash conversion gives us the same
-					// result.
-					&BlockId::Hash(ref hash) => chain.is_canon(hash),
-				}
-			};
-
-			let blocks = if is_canon(&filter.from_block) && is_canon(&filter.to_block) {
-				// If we are on the canon chain, use bloom filter to fetch required hashes.
-				let from = self.block_number_ref(&filter.from_block)?;
-				let to = self.block_number_ref(&filter.to_block)?;
-
-				filter.bloom_possibilities().iter()
-					.map(|bloom| {
-						chain.blocks_with_bloom(bloom, from, to)
-					})
-					.flat_map(|m| m)
-					// remove duplicate elements
-					.collect::<BTreeSet<u64>>()
-					.into_iter()
-					.filter_map(|n| chain.block_hash(n))
-					.collect::<Vec<H256>>()
-
-			} else {
-				// Otherwise, we use a slower version that finds a link between from_block and to_block.
-				let from_hash = Self::block_hash(&chain, &*self.miner, filter.from_block)?;
-				let from_number = chain.block_number(&from_hash)?;
-				let to_hash = Self::block_hash(&chain, &*self.miner, filter.from_block)?;
-
-				let blooms = filter.bloom_possibilities();
-				let bloom_match = |header: &encoded::Header| {
-					blooms.iter().any(|bloom| header.log_bloom().contains_bloom(bloom))
-				};
-
-				let (blocks, last_hash) = {
-					let mut blocks = Vec::new();
-					let mut current_hash = to_hash;
-
-					loop {
-						let header = chain.block_header_data(&current_hash)?;
-						if bloom_match(&header) {
-							blocks.push(current_hash);
-						}
-
-						// Stop if `from` block is reached.
-						if header.number() <= from_number {
-							break;
-						}
-						current_hash = header.parent_hash();
-					}
+		self.log_blocks(&filter)
+			.map(|blocks| self.chain.read().logs(blocks, |entry| filter.matches(entry), filter.limit))
+			.unwrap_or_default()
+	}
 
-					blocks.reverse();
-					(blocks, current_hash)
-				};
+	fn logs_paged(&self, filter: Filter, cursor: Option<LogCursor>, max_results: usize) -> (Vec<LocalizedLogEntry>, Option<LogCursor>) {
+		let blocks = self.log_blocks(&filter).unwrap_or_default();
+		let mut logs = Vec::new();
 
-				// Check if we've actually reached the expected `from` block.
-				if last_hash != from_hash || blocks.is_empty() {
-					return None;
+		// fetch the logs a chunk of blocks at a time, so we never hold more than needed.
+		for blocks in blocks.chunks(LOGS_PAGE_BLOCKS) {
+			let chunk = self.chain.read().logs(blocks.to_vec(), |entry| filter.matches(entry), None);
+			for log in chunk {
+				if cursor.map_or(false, |cursor| LogCursor::from(&log) < cursor) {
+					continue;
 				}
+				if logs.len() == max_results {
+					return (logs, Some(LogCursor::from(&log)));
+				}
+				logs.push(log);
+			}
+		}
 
-				blocks
-			};
-
-			Some(self.chain.read().logs(blocks, |entry| filter.matches(entry), filter.limit))
-		};
-
-		fetch_logs().unwrap_or_default()
+		(logs, None)
 	}
 
 	fn filter_traces(&self, filter: TraceFilter) -> Option<Vec<LocalizedTrace>> {
@@ -1824,6 +2072,10 @@ impl BlockChainClient for Client {
 		}
 	}
 
+	fn fee_history(&self, block_count: usize, percentiles: &[f64]) -> FeeHistory {
+		self.gas_price_oracle.read().fee_history(block_count, percentiles)
+	}
+
 	fn signing_chain_id(&self) -> Option<u64> {
 		self.engine.signing_chain_id(&self.latest_env_info())
 	}
@@ -1848,7 +2100,7 @@ impl BlockChainClient for Client {
 	fn call_contract(&self, block_id: BlockId, address: Address, data: Bytes) -> Result<Bytes, String> {
 		let transaction = self.contract_call_tx(block_id, address, data);
 
-		self.call(&transaction, Default::default(), block_id)
+		self.call(&transaction, Default::default(), block_id, &Default::default())
 			.map_err(|e| format!("{:?}", e))
 			.map(|executed| {
 				executed.output
@@ -1926,7 +2178,7 @@ impl MiningBlockChainClient for Client {
 
 		// Add uncles
 		chain
-			.find_uncle_headers(&h, engine.maximum_uncle_age())
+			.find_uncle_headers(&h, engine.maximum_uncle_age(open_block.header().number()))
 			.unwrap_or_else(Vec::new)
 			.into_iter()
 			.take(engine.maximum_uncle_count(open_block.header().number()))
@@ -1950,7 +2202,7 @@ impl MiningBlockChainClient for Client {
 			let h = chain.best_block_hash();
 			// Add new uncles
 			let uncles = chain
-				.find_uncle_hashes(&h, engine.maximum_uncle_age())
+				.find_uncle_hashes(&h, engine.maximum_uncle_age(block.header().number()))
 				.unwrap_or_else(Vec::new);
 
 			for h in uncles {
@@ -16,12 +16,16 @@
 
 use std::str::FromStr;
 use std::path::Path;
+use std::sync::Arc;
 use std::fmt::{Display, Formatter, Error as FmtError};
 
 use mode::Mode as IpcMode;
 use verification::{VerifierType, QueueConfig};
 use journaldb;
-use kvdb_rocksdb::CompactionProfile;
+use snapshot::ChunkCompression;
+use kvdb::{self, KeyValueDB};
+use kvdb_logdb;
+use kvdb_rocksdb::{self, CompactionProfile, DatabaseConfig};
 
 pub use std::time::Duration;
 pub use blockchain::Config as BlockChainConfig;
@@ -69,6 +73,68 @@ impl FromStr for DatabaseCompactionProfile {
 	}
 }
 
+/// Key-value store backing the client database.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DatabaseBackend {
+	/// RocksDB
+	RocksDb,
+	/// Pure-Rust append-only log store
+	LogDb,
+}
+
+impl Default for DatabaseBackend {
+	fn default() -> Self {
+		DatabaseBackend::RocksDb
+	}
+}
+
+impl DatabaseBackend {
+	/// Returns the backend of an existing database at given path.
+	pub fn detect(db_path: &Path) -> Option<Self> {
+		if kvdb_logdb::is_database(db_path) {
+			Some(DatabaseBackend::LogDb)
+		} else if db_path.join("CURRENT").is_file() {
+			Some(DatabaseBackend::RocksDb)
+		} else {
+			None
+		}
+	}
+
+	/// Opens the database at given path, creating it if it does not exist.
+	/// Only the columns are taken from `config` by backends other than RocksDB.
+	pub fn open(&self, config: &DatabaseConfig, db_path: &Path) -> kvdb::Result<Arc<KeyValueDB>> {
+		let path = db_path.to_string_lossy();
+		Ok(match *self {
+			DatabaseBackend::RocksDb => Arc::new(kvdb_rocksdb::Database::open(config, &path)?),
+			DatabaseBackend::LogDb => {
+				let config = kvdb_logdb::DatabaseConfig::with_columns(config.columns);
+				Arc::new(kvdb_logdb::Database::open(&config, &path)?)
+			},
+		})
+	}
+}
+
+impl FromStr for DatabaseBackend {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"rocksdb" => Ok(DatabaseBackend::RocksDb),
+			"logdb" => Ok(DatabaseBackend::LogDb),
+			_ => Err("Invalid database backend given. Expected rocksdb/logdb.".into()),
+		}
+	}
+}
+
+impl Display for DatabaseBackend {
+	fn fmt(&self, f: &mut Formatter) -> Result<(), FmtError> {
+		match *self {
+			DatabaseBackend::RocksDb => write!(f, "rocksdb"),
+			DatabaseBackend::LogDb => write!(f, "logdb"),
+		}
+	}
+}
+
 /// Operating mode for the client.
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum Mode {
@@ -145,6 +211,8 @@ pub struct ClientConfig {
 	pub db_cache_size: Option<usize>,
 	/// State db compaction profile
 	pub db_compaction: DatabaseCompactionProfile,
+	/// Key-value store backend
+	pub db_backend: DatabaseBackend,
 	/// Should db have WAL enabled?
 	pub db_wal: bool,
 	/// Operating mode
@@ -163,11 +231,15 @@ pub struct ClientConfig {
 	pub history_mem: usize,
 	/// Check seal valididity on block import
 	pub check_seal: bool,
+	/// Maintain an index of ERC-20 token balances.
+	pub token_index: bool,
+	/// Compression of chunks in snapshots taken by the client.
+	pub snapshot_compression: ChunkCompression,
 }
 
 #[cfg(test)]
 mod test {
-	use super::{DatabaseCompactionProfile, Mode};
+	use super::{DatabaseCompactionProfile, DatabaseBackend, Mode};
 
 	#[test]
 	fn test_default_compaction_profile() {
@@ -181,6 +253,14 @@ mod test {
 		assert_eq!(DatabaseCompactionProfile::HDD, "hdd".parse().unwrap());
 	}
 
+	#[test]
+	fn test_parsing_database_backend() {
+		assert_eq!(DatabaseBackend::default(), DatabaseBackend::RocksDb);
+		assert_eq!(DatabaseBackend::RocksDb, "rocksdb".parse().unwrap());
+		assert_eq!(DatabaseBackend::LogDb, "logdb".parse().unwrap());
+		assert!("lmdb".parse::<DatabaseBackend>().is_err());
+	}
+
 	#[test]
 	fn test_mode_default() {
 		assert_eq!(Mode::default(), Mode::Active);
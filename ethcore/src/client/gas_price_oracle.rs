@@ -0,0 +1,233 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Gas price oracle tracking gas prices paid in recent canonical blocks.
+
+use std::collections::VecDeque;
+use ethereum_types::{H256, U256};
+use header::BlockNumber;
+use stats::Corpus;
+
+/// Number of most recent blocks tracked by the oracle.
+pub const GAS_PRICE_ORACLE_HISTORY: usize = 1024;
+
+const SLOW_PERCENTILE: usize = 25;
+const STANDARD_PERCENTILE: usize = 50;
+const FAST_PERCENTILE: usize = 90;
+
+/// Gas prices paid in a single block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockFees {
+	/// Block number.
+	pub number: BlockNumber,
+	/// Block hash.
+	pub hash: H256,
+	/// Gas used by the block.
+	pub gas_used: U256,
+	/// Gas limit of the block.
+	pub gas_limit: U256,
+	/// Gas price and gas used of every transaction, ordered by gas price.
+	prices: Vec<(U256, U256)>,
+}
+
+impl BlockFees {
+	/// Create fees of a block given gas price and gas used of each transaction.
+	pub fn new<I>(number: BlockNumber, hash: H256, gas_used: U256, gas_limit: U256, transactions: I) -> Self
+		where I: IntoIterator<Item=(U256, U256)>
+	{
+		let mut prices: Vec<_> = transactions.into_iter().collect();
+		prices.sort();
+
+		BlockFees {
+			number: number,
+			hash: hash,
+			gas_used: gas_used,
+			gas_limit: gas_limit,
+			prices: prices,
+		}
+	}
+
+	/// Ratio of the gas used to the gas limit.
+	pub fn gas_used_ratio(&self) -> f64 {
+		if self.gas_limit.is_zero() {
+			return 0.0;
+		}
+		self.gas_used.low_u64() as f64 / self.gas_limit.low_u64() as f64
+	}
+
+	/// Gas price at given percentile, where transactions are weighted by the gas they used.
+	/// Returns zero for blocks without transactions.
+	pub fn percentile(&self, percentile: f64) -> U256 {
+		let total_gas = self.prices.iter().fold(0u64, |sum, &(_, gas)| sum + gas.low_u64());
+		let threshold = total_gas as f64 * percentile / 100.0;
+
+		let mut cumulative_gas = 0u64;
+		for &(price, gas) in &self.prices {
+			cumulative_gas += gas.low_u64();
+			if cumulative_gas as f64 >= threshold {
+				return price;
+			}
+		}
+
+		self.prices.last().map_or_else(U256::zero, |&(price, _)| price)
+	}
+}
+
+/// Recommended gas prices for different confirmation speeds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasPriceRecommendation {
+	/// Gas price likely to be included within a few minutes.
+	pub slow: U256,
+	/// Gas price likely to be included within a few blocks.
+	pub standard: U256,
+	/// Gas price likely to be included in the next block.
+	pub fast: U256,
+}
+
+/// Gas price history of a range of recent blocks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeHistory {
+	/// Number of the oldest block in the range, `None` if no blocks are known.
+	pub oldest_block: Option<BlockNumber>,
+	/// Requested gas price percentiles of each block.
+	pub gas_prices: Vec<Vec<U256>>,
+	/// Gas used ratio of each block.
+	pub gas_used_ratio: Vec<f64>,
+	/// Recommended gas prices, `None` if there were no transactions in the range.
+	pub recommended: Option<GasPriceRecommendation>,
+}
+
+/// Gas price oracle, updated with every new canonical block.
+#[derive(Debug, Default)]
+pub struct GasPriceOracle {
+	blocks: VecDeque<BlockFees>,
+}
+
+impl GasPriceOracle {
+	/// Note a new canonical block.
+	/// Previously noted blocks at the same height or above are discarded as retracted.
+	pub fn note_block(&mut self, fees: BlockFees) {
+		while self.blocks.back().map_or(false, |last| last.number >= fees.number) {
+			self.blocks.pop_back();
+		}
+
+		// blocks in between are not known (e.g. after restoring a snapshot), start over.
+		if self.blocks.back().map_or(false, |last| last.number + 1 != fees.number) {
+			self.blocks.clear();
+		}
+
+		self.blocks.push_back(fees);
+		if self.blocks.len() > GAS_PRICE_ORACLE_HISTORY {
+			self.blocks.pop_front();
+		}
+	}
+
+	/// Returns history of the last `block_count` blocks with given gas price percentiles.
+	pub fn fee_history(&self, block_count: usize, percentiles: &[f64]) -> FeeHistory {
+		let skip = self.blocks.len().saturating_sub(block_count);
+		let blocks = self.blocks.iter().skip(skip).collect::<Vec<_>>();
+
+		let corpus: Corpus<U256> = blocks.iter()
+			.flat_map(|block| block.prices.iter().map(|&(price, _)| price))
+			.collect();
+		let recommended = match corpus.first() {
+			Some(lowest) => {
+				let pick = |percentile| corpus.percentile(percentile).cloned().unwrap_or(*lowest);
+				Some(GasPriceRecommendation {
+					slow: pick(SLOW_PERCENTILE),
+					standard: pick(STANDARD_PERCENTILE),
+					fast: pick(FAST_PERCENTILE),
+				})
+			},
+			None => None,
+		};
+
+		FeeHistory {
+			oldest_block: blocks.first().map(|block| block.number),
+			gas_prices: blocks.iter()
+				.map(|block| percentiles.iter().map(|p| block.percentile(*p)).collect())
+				.collect(),
+			gas_used_ratio: blocks.iter().map(|block| block.gas_used_ratio()).collect(),
+			recommended: recommended,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ethereum_types::{H256, U256};
+	use super::{BlockFees, GasPriceOracle, GasPriceRecommendation};
+
+	fn fees(number: u64, transactions: Vec<(u64, u64)>) -> BlockFees {
+		let gas_used = transactions.iter().fold(0, |sum, &(_, gas)| sum + gas);
+		BlockFees::new(
+			number,
+			H256::from(number),
+			gas_used.into(),
+			100_000.into(),
+			transactions.into_iter().map(|(price, gas)| (price.into(), gas.into())),
+		)
+	}
+
+	#[test]
+	fn should_weight_percentiles_by_gas_used() {
+		let block = fees(1, vec![(30, 21_000), (10, 21_000), (20, 58_000)]);
+
+		assert_eq!(block.percentile(0.0), U256::from(10));
+		assert_eq!(block.percentile(25.0), U256::from(20));
+		assert_eq!(block.percentile(50.0), U256::from(20));
+		assert_eq!(block.percentile(90.0), U256::from(30));
+		assert_eq!(block.gas_used_ratio(), 1.0);
+		assert_eq!(fees(2, vec![]).percentile(50.0), U256::zero());
+	}
+
+	#[test]
+	fn should_return_history_of_recent_blocks() {
+		let mut oracle = GasPriceOracle::default();
+		oracle.note_block(fees(1, vec![(100, 50_000)]));
+		oracle.note_block(fees(2, vec![(10, 21_000), (20, 21_000)]));
+		oracle.note_block(fees(3, vec![]));
+
+		let history = oracle.fee_history(2, &[50.0]);
+		assert_eq!(history.oldest_block, Some(2));
+		assert_eq!(history.gas_prices, vec![vec![U256::from(10)], vec![U256::zero()]]);
+		assert_eq!(history.gas_used_ratio, vec![0.42, 0.0]);
+		assert_eq!(history.recommended, Some(GasPriceRecommendation {
+			slow: 10.into(),
+			standard: 10.into(),
+			fast: 10.into(),
+		}));
+
+		let history = oracle.fee_history(10, &[]);
+		assert_eq!(history.oldest_block, Some(1));
+		assert_eq!(history.gas_prices.len(), 3);
+	}
+
+	#[test]
+	fn should_replace_retracted_blocks() {
+		let mut oracle = GasPriceOracle::default();
+		oracle.note_block(fees(1, vec![(100, 21_000)]));
+		oracle.note_block(fees(2, vec![(100, 21_000)]));
+		oracle.note_block(fees(2, vec![(5, 21_000)]));
+
+		let history = oracle.fee_history(10, &[50.0]);
+		assert_eq!(history.oldest_block, Some(1));
+		assert_eq!(history.gas_prices, vec![vec![U256::from(100)], vec![U256::from(5)]]);
+
+		oracle.note_block(fees(5, vec![]));
+		assert_eq!(oracle.fee_history(10, &[]).oldest_block, Some(5));
+	}
+}
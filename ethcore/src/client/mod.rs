@@ -20,15 +20,19 @@ mod ancient_import;
 mod config;
 mod error;
 mod evm_test_client;
+mod gas_price_oracle;
 mod test_client;
+mod token_index;
 mod trace;
 mod client;
 
 pub use self::client::*;
-pub use self::config::{Mode, ClientConfig, DatabaseCompactionProfile, BlockChainConfig, VMType};
+pub use self::config::{Mode, ClientConfig, DatabaseCompactionProfile, DatabaseBackend, BlockChainConfig, VMType};
 pub use self::error::Error;
 pub use self::evm_test_client::{EvmTestClient, EvmTestError, TransactResult};
+pub use self::gas_price_oracle::{GasPriceOracle, BlockFees, FeeHistory, GasPriceRecommendation, GAS_PRICE_ORACLE_HISTORY};
 pub use self::test_client::{TestBlockChainClient, EachBlockWith};
+pub use self::token_index::{TokenIndex, TokenHolding};
 pub use self::chain_notify::ChainNotify;
 pub use self::traits::{BlockChainClient, MiningBlockChainClient, EngineClient};
 
@@ -38,6 +42,7 @@ pub use types::ids::*;
 pub use types::trace_filter::Filter as TraceFilter;
 pub use types::pruning_info::PruningInfo;
 pub use types::call_analytics::CallAnalytics;
+pub use types::state_override::{StateOverride, AccountOverride, StorageOverride};
 
 pub use executive::{Executed, Executive, TransactOptions};
 pub use vm::{LastHashes, EnvInfo};
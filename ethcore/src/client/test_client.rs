@@ -26,6 +26,7 @@ use hash::keccak;
 use ethereum_types::{H256, U256, Address};
 use parking_lot::RwLock;
 use journaldb;
+use trie;
 use kvdb::DBValue;
 use kvdb_rocksdb::{Database, DatabaseConfig};
 use bytes::Bytes;
@@ -37,11 +38,11 @@ use blockchain::{TreeRoute, BlockReceipts};
 use client::{
 	BlockChainClient, MiningBlockChainClient, BlockChainInfo, BlockStatus, BlockId,
 	TransactionId, UncleId, TraceId, TraceFilter, LastHashes, CallAnalytics, BlockImportError,
-	ProvingBlockChainClient,
+	ProvingBlockChainClient, StateOverride, GasPriceOracle, BlockFees, FeeHistory, TokenHolding,
 };
 use db::{NUM_COLUMNS, COL_STATE};
 use header::{Header as BlockHeader, BlockNumber};
-use filter::Filter;
+use filter::{Filter, LogCursor};
 use log_entry::LocalizedLogEntry;
 use receipt::{Receipt, LocalizedReceipt, TransactionOutcome};
 use error::{ImportResult, Error as EthcoreError};
@@ -58,9 +59,10 @@ use verification::queue::QueueInfo;
 use block::{OpenBlock, SealedBlock, ClosedBlock};
 use executive::Executed;
 use error::CallError;
-use trace::LocalizedTrace;
+use trace::{FlatTrace, LocalizedTrace, StructLog, StructLoggerOptions, TracerPreset, PresetTrace};
 use state_db::StateDB;
 use encoded;
+use views::BlockView;
 
 /// Test client.
 pub struct TestBlockChainClient {
@@ -108,6 +110,10 @@ pub struct TestBlockChainClient {
 	pub traces: RwLock<Option<Vec<LocalizedTrace>>>,
 	/// Pruning history size to report.
 	pub history: RwLock<Option<u64>>,
+	/// Tree route to return instead of walking the numbered blocks.
+	pub tree_route: RwLock<Option<TreeRoute>>,
+	/// Encoded receipts of blocks.
+	pub block_receipts: RwLock<HashMap<H256, Bytes>>,
 }
 
 /// Used for generating test client blocks.
@@ -174,6 +180,8 @@ impl TestBlockChainClient {
 			first_block: RwLock::new(None),
 			traces: RwLock::new(None),
 			history: RwLock::new(None),
+			tree_route: RwLock::new(None),
+			block_receipts: RwLock::new(HashMap::new()),
 		};
 
 		// insert genesis hash.
@@ -317,10 +325,19 @@ impl TestBlockChainClient {
 			BlockId::Hash(hash) => Some(hash),
 			BlockId::Number(n) => self.numbers.read().get(&(n as usize)).cloned(),
 			BlockId::Earliest => self.numbers.read().get(&0).cloned(),
-			BlockId::Latest | BlockId::Pending => self.numbers.read().get(&(self.numbers.read().len() - 1)).cloned()
+			BlockId::Latest | BlockId::Pending => self.numbers.read().get(&(self.numbers.read().len() - 1)).cloned(),
+			BlockId::Timestamp(timestamp) => self.block_number_at_timestamp(timestamp).and_then(|n| self.numbers.read().get(&(n as usize)).cloned()),
 		}
 	}
 
+	fn block_number_at_timestamp(&self, timestamp: u64) -> Option<BlockNumber> {
+		let numbers = self.numbers.read();
+		let blocks = self.blocks.read();
+		(0..numbers.len()).rev()
+			.find(|n| BlockView::new(&blocks[&numbers[n]]).header_view().timestamp() <= timestamp)
+			.map(|n| n as BlockNumber)
+	}
+
 	/// Inserts a transaction with given gas price to miners transactions queue.
 	pub fn insert_transaction_with_gas_price_to_queue(&self, gas_price: U256) -> H256 {
 		let keypair = Random.generate().unwrap();
@@ -407,19 +424,23 @@ impl MiningBlockChainClient for TestBlockChainClient {
 }
 
 impl BlockChainClient for TestBlockChainClient {
-	fn call(&self, _t: &SignedTransaction, _analytics: CallAnalytics, _block: BlockId) -> Result<Executed, CallError> {
+	fn call(&self, _t: &SignedTransaction, _analytics: CallAnalytics, _block: BlockId, _state_override: &StateOverride) -> Result<Executed, CallError> {
 		self.execution_result.read().clone().unwrap()
 	}
 
-	fn call_many(&self, txs: &[(SignedTransaction, CallAnalytics)], block: BlockId) -> Result<Vec<Executed>, CallError> {
+	fn call_with_presets(&self, _t: &SignedTransaction, _analytics: CallAnalytics, _presets: &[TracerPreset], _block: BlockId, _state_override: &StateOverride) -> Result<(Executed, Vec<PresetTrace>), CallError> {
+		Err(CallError::StatePruned)
+	}
+
+	fn call_many(&self, txs: &[(SignedTransaction, CallAnalytics, StateOverride)], block: BlockId) -> Result<Vec<Executed>, CallError> {
 		let mut res = Vec::with_capacity(txs.len());
-		for &(ref tx, analytics) in txs {
-			res.push(self.call(tx, analytics, block)?);
+		for &(ref tx, analytics, ref state_override) in txs {
+			res.push(self.call(tx, analytics, block, state_override)?);
 		}
 		Ok(res)
 	}
 
-	fn estimate_gas(&self, _t: &SignedTransaction, _block: BlockId) -> Result<U256, CallError> {
+	fn estimate_gas(&self, _t: &SignedTransaction, _block: BlockId, _state_override: &StateOverride) -> Result<U256, CallError> {
 		Ok(21000.into())
 	}
 
@@ -431,6 +452,14 @@ impl BlockChainClient for TestBlockChainClient {
 		Ok(Box::new(self.execution_result.read().clone().unwrap().into_iter()))
 	}
 
+	fn replay_with_presets(&self, _id: TransactionId, _analytics: CallAnalytics, _presets: &[TracerPreset]) -> Result<(Executed, Vec<PresetTrace>), CallError> {
+		Err(CallError::TransactionNotFound)
+	}
+
+	fn replay_struct_logs(&self, _id: TransactionId, _options: StructLoggerOptions) -> Result<Executed<FlatTrace, Vec<StructLog>>, CallError> {
+		Err(CallError::TransactionNotFound)
+	}
+
 	fn block_total_difficulty(&self, _id: BlockId) -> Option<U256> {
 		Some(U256::zero())
 	}
@@ -486,6 +515,14 @@ impl BlockChainClient for TestBlockChainClient {
 		}
 	}
 
+	fn balances(&self, addresses: &[Address], id: BlockId) -> Option<Vec<trie::Result<U256>>> {
+		addresses.iter().map(|address| self.balance(address, id).map(Ok)).collect()
+	}
+
+	fn storage_batch(&self, entries: &[(Address, H256)], id: BlockId) -> Option<Vec<trie::Result<H256>>> {
+		entries.iter().map(|&(ref address, ref position)| self.storage_at(address, position, id).map(Ok)).collect()
+	}
+
 	fn list_accounts(&self, _id: BlockId, _after: Option<&Address>, _count: u64) -> Option<Vec<Address>> {
 		None
 	}
@@ -501,6 +538,30 @@ impl BlockChainClient for TestBlockChainClient {
 		None	// Simple default.
 	}
 
+	fn account_transactions(&self, address: &Address, from_block: BlockId, to_block: BlockId, limit: usize) -> Option<Vec<LocalizedTransaction>> {
+		let from = BlockChainClient::block_number(self, from_block)?;
+		let to = BlockChainClient::block_number(self, to_block)?;
+
+		let transactions = (from..to + 1)
+			.filter_map(|number| self.block(BlockId::Number(number)))
+			.flat_map(|block| block.view().localized_transactions())
+			.filter_map(|mut tx| {
+				let touches = tx.sender() == *address || tx.action == Action::Call(*address);
+				if touches { Some(tx) } else { None }
+			})
+			.take(limit)
+			.collect();
+		Some(transactions)
+	}
+
+	fn token_holdings(&self, _holder: &Address) -> Option<Vec<TokenHolding>> {
+		None
+	}
+
+	fn token_holders(&self, _token: &Address, _limit: usize) -> Option<Vec<TokenHolding>> {
+		None
+	}
+
 	fn uncle(&self, _id: UncleId) -> Option<encoded::Header> {
 		None	// Simple default.
 	}
@@ -522,6 +583,19 @@ impl BlockChainClient for TestBlockChainClient {
 		}
 	}
 
+	fn logs_paged(&self, filter: Filter, cursor: Option<LogCursor>, max_results: usize) -> (Vec<LocalizedLogEntry>, Option<LogCursor>) {
+		let from = match filter.from_block { BlockId::Number(number) => number, _ => 0 };
+		let to = match filter.to_block { BlockId::Number(number) => number, _ => BlockNumber::max_value() };
+		let mut logs = self.logs.read().clone();
+		logs.retain(|log| log.block_number >= from && log.block_number <= to);
+		if let Some(cursor) = cursor {
+			logs.retain(|log| LogCursor::from(log) >= cursor);
+		}
+		let next = logs.get(max_results).map(LogCursor::from);
+		logs.truncate(max_results);
+		(logs, next)
+	}
+
 	fn last_hashes(&self) -> LastHashes {
 		unimplemented!();
 	}
@@ -537,8 +611,15 @@ impl BlockChainClient for TestBlockChainClient {
 			.map(encoded::Header::new)
 	}
 
-	fn block_number(&self, _id: BlockId) -> Option<BlockNumber> {
-		unimplemented!()
+	fn block_number(&self, id: BlockId) -> Option<BlockNumber> {
+		match id {
+			BlockId::Number(number) => Some(number),
+			BlockId::Earliest => Some(0),
+			BlockId::Latest => Some(self.chain_info().best_block_number),
+			BlockId::Pending => Some(self.chain_info().best_block_number + 1),
+			BlockId::Hash(hash) => self.numbers.read().iter().find(|&(_, h)| *h == hash).map(|(n, _)| *n as BlockNumber),
+			BlockId::Timestamp(timestamp) => self.block_number_at_timestamp(timestamp),
+		}
 	}
 
 	fn block_body(&self, id: BlockId) -> Option<encoded::Body> {
@@ -568,6 +649,7 @@ impl BlockChainClient for TestBlockChainClient {
 			BlockId::Number(number) if (number as usize) < self.blocks.read().len() => BlockStatus::InChain,
 			BlockId::Hash(ref hash) if self.blocks.read().get(hash).is_some() => BlockStatus::InChain,
 			BlockId::Latest | BlockId::Earliest => BlockStatus::InChain,
+			BlockId::Timestamp(timestamp) if self.block_number_at_timestamp(timestamp).is_some() => BlockStatus::InChain,
 			BlockId::Pending => BlockStatus::Pending,
 			_ => BlockStatus::Unknown,
 		}
@@ -575,6 +657,14 @@ impl BlockChainClient for TestBlockChainClient {
 
 	// works only if blocks are one after another 1 -> 2 -> 3
 	fn tree_route(&self, from: &H256, to: &H256) -> Option<TreeRoute> {
+		if let Some(ref route) = *self.tree_route.read() {
+			return Some(TreeRoute {
+				blocks: route.blocks.clone(),
+				ancestor: route.ancestor,
+				index: route.index,
+			});
+		}
+
 		Some(TreeRoute {
 			ancestor: H256::new(),
 			index: 0,
@@ -619,6 +709,10 @@ impl BlockChainClient for TestBlockChainClient {
 	}
 
 	fn block_receipts(&self, hash: &H256) -> Option<Bytes> {
+		if let Some(receipts) = self.block_receipts.read().get(hash) {
+			return Some(receipts.clone());
+		}
+
 		// starts with 'f' ?
 		if *hash > H256::from("f000000000000000000000000000000000000000000000000000000000000000") {
 			let receipt = BlockReceipts::new(vec![Receipt::new(
@@ -746,6 +840,19 @@ impl BlockChainClient for TestBlockChainClient {
 		self.miner.ready_transactions(info.best_block_number, info.best_block_timestamp)
 	}
 
+	fn fee_history(&self, block_count: usize, percentiles: &[f64]) -> FeeHistory {
+		// no receipts here, so transactions are assumed to use all the gas provided.
+		let mut oracle = GasPriceOracle::default();
+		let best = self.chain_info().best_block_number;
+		for number in best.saturating_sub(block_count as u64)..best + 1 {
+			if let Some(block) = self.block(BlockId::Number(number)) {
+				let transactions = block.transaction_views().iter().map(|tx| (tx.gas_price(), tx.gas())).collect::<Vec<_>>();
+				oracle.note_block(BlockFees::new(number, block.hash(), block.gas_used(), block.gas_limit(), transactions));
+			}
+		}
+		oracle.fee_history(block_count, percentiles)
+	}
+
 	fn signing_chain_id(&self) -> Option<u64> { None }
 
 	fn mode(&self) -> Mode { Mode::Active }
@@ -0,0 +1,619 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Optional index of ERC-20 token balances, maintained from `Transfer` logs of canonical blocks.
+
+use std::cmp;
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use ethereum_types::{H256, U256, Address};
+use hash::keccak;
+use bytes::Bytes;
+use kvdb::{DBTransaction, KeyValueDB};
+use parking_lot::Mutex;
+use rlp;
+use blockchain::BlockReceipts;
+use client::{BlockChainClient, BlockId, ChainNotify, Client};
+use db;
+use log_entry::LogEntry;
+use receipt::Receipt;
+
+/// Prefix of `holder ++ token => balance` entries.
+const HOLDING_PREFIX: u8 = 0;
+/// Prefix of `token ++ !balance ++ holder` entries, ordering holders by descending balance.
+const RANK_PREFIX: u8 = 1;
+/// Prefix of `holder ++ token => deficit` entries of holders which spent tokens not seen by the index.
+const DEFICIT_PREFIX: u8 = 2;
+/// Key of the hash of the last indexed block.
+const HEAD_KEY: &'static [u8] = b"head";
+/// Maximal number of blocks indexed on a single notification, so that catching up
+/// after a gap doesn't hold up the thread announcing new blocks.
+const MAX_BLOCKS_PER_UPDATE: u64 = 256;
+
+/// A single ERC-20 `Transfer(address,address,uint256)` event.
+#[derive(Debug, PartialEq)]
+struct Transfer {
+	token: Address,
+	from: Address,
+	to: Address,
+	value: U256,
+}
+
+impl Transfer {
+	/// Decode a transfer from a log entry, given the `Transfer` event topic.
+	/// ERC-721 transfers, which index the token id as a third argument, are ignored.
+	fn from_log(log: &LogEntry, topic: &H256) -> Option<Self> {
+		if log.topics.len() != 3 || log.topics[0] != *topic || log.data.len() != 32 {
+			return None;
+		}
+
+		Some(Transfer {
+			token: log.address,
+			from: Address::from_slice(&log.topics[1][12..]),
+			to: Address::from_slice(&log.topics[2][12..]),
+			value: U256::from(&log.data[..]),
+		})
+	}
+}
+
+/// Indexed balance of a holder of an ERC-20 token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenHolding {
+	/// Token contract address.
+	pub token: Address,
+	/// Holder address.
+	pub holder: Address,
+	/// Balance computed from indexed transfers.
+	pub balance: U256,
+	/// False if the holder has ever spent more than the index saw it receive. It then held
+	/// tokens before indexing started and `balance` understates its real balance.
+	pub reliable: bool,
+}
+
+/// Indexed state of a single holding. The net amount of indexed transfers is `balance - deficit`,
+/// so that applying and reverting transfers is exact while the balance itself never goes negative.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Holding {
+	balance: U256,
+	/// Set once the holder spends more than its indexed balance and kept from then on.
+	deficit: Option<U256>,
+}
+
+impl Holding {
+	fn credit(&mut self, value: U256) {
+		let repaid = self.deficit.map_or_else(U256::zero, |deficit| cmp::min(deficit, value));
+		if let Some(ref mut deficit) = self.deficit {
+			*deficit = *deficit - repaid;
+		}
+		self.balance = self.balance.saturating_add(value - repaid);
+	}
+
+	fn debit(&mut self, value: U256) {
+		if value <= self.balance {
+			self.balance = self.balance - value;
+		} else {
+			let shortfall = value - self.balance;
+			self.deficit = Some(self.deficit.unwrap_or_else(U256::zero).saturating_add(shortfall));
+			self.balance = U256::zero();
+		}
+	}
+}
+
+fn holder_key(prefix: u8, holder: &Address, token: &Address) -> Vec<u8> {
+	let mut key = Vec::with_capacity(41);
+	key.push(prefix);
+	key.extend_from_slice(&holder[..]);
+	key.extend_from_slice(&token[..]);
+	key
+}
+
+fn holding_key(holder: &Address, token: &Address) -> Vec<u8> {
+	holder_key(HOLDING_PREFIX, holder, token)
+}
+
+fn deficit_key(holder: &Address, token: &Address) -> Vec<u8> {
+	holder_key(DEFICIT_PREFIX, holder, token)
+}
+
+fn rank_key(token: &Address, balance: &U256, holder: &Address) -> Vec<u8> {
+	let mut inverted = [0u8; 32];
+	(U256::max_value() - *balance).to_big_endian(&mut inverted);
+
+	let mut key = Vec::with_capacity(73);
+	key.push(RANK_PREFIX);
+	key.extend_from_slice(&token[..]);
+	key.extend_from_slice(&inverted);
+	key.extend_from_slice(&holder[..]);
+	key
+}
+
+fn prefixed(prefix: u8, address: &Address) -> Vec<u8> {
+	let mut key = Vec::with_capacity(21);
+	key.push(prefix);
+	key.extend_from_slice(&address[..]);
+	key
+}
+
+/// Index of ERC-20 token balances of every holder, stored in its own database column.
+///
+/// Balances are updated from `Transfer` logs of blocks becoming canonical and reverted
+/// for retracted ones. Only transfers in blocks imported while the index is enabled are
+/// counted, so balances are complete only for tokens created after that point. Holders
+/// spending tokens they received before are detected and reported as unreliable.
+pub struct TokenIndex {
+	db: Arc<KeyValueDB>,
+	client: Weak<Client>,
+	update_lock: Mutex<()>,
+}
+
+impl TokenIndex {
+	/// Create a new token index for the given client, indexing blocks after `best_block`
+	/// if nothing has been indexed yet.
+	pub fn new(client: Weak<Client>, db: Arc<KeyValueDB>, best_block: H256) -> Self {
+		let index = TokenIndex {
+			db: db,
+			client: client,
+			update_lock: Mutex::new(()),
+		};
+
+		if index.head().is_none() {
+			let mut batch = DBTransaction::new();
+			batch.put(db::COL_TOKEN_INDEX, HEAD_KEY, &best_block);
+			index.db.write(batch).expect("Low level database error. Some issue with disk?");
+		}
+
+		index
+	}
+
+	/// Hash of the last indexed block.
+	fn head(&self) -> Option<H256> {
+		self.db.get(db::COL_TOKEN_INDEX, HEAD_KEY)
+			.expect("Low level database error. Some issue with disk?")
+			.map(|hash| H256::from_slice(&hash))
+	}
+
+	/// Balance of the given holder of the given token.
+	pub fn balance(&self, token: &Address, holder: &Address) -> U256 {
+		self.holding(token, holder).balance
+	}
+
+	fn deficit(&self, token: &Address, holder: &Address) -> Option<U256> {
+		self.db.get(db::COL_TOKEN_INDEX, &deficit_key(holder, token))
+			.expect("Low level database error. Some issue with disk?")
+			.map(|deficit| rlp::decode(&deficit))
+	}
+
+	fn holding(&self, token: &Address, holder: &Address) -> Holding {
+		let balance = self.db.get(db::COL_TOKEN_INDEX, &holding_key(holder, token))
+			.expect("Low level database error. Some issue with disk?")
+			.map_or_else(U256::zero, |balance| rlp::decode(&balance));
+
+		Holding {
+			balance: balance,
+			deficit: self.deficit(token, holder),
+		}
+	}
+
+	/// Tokens held by the given address along with their non-zero balances, ordered by token address.
+	pub fn holdings(&self, holder: &Address) -> Vec<TokenHolding> {
+		let prefix = prefixed(HOLDING_PREFIX, holder);
+		self.db.iter_from_prefix(db::COL_TOKEN_INDEX, &prefix)
+			// iterator may continue beyond entries of this holder.
+			.take_while(|&(ref key, _)| key.starts_with(&prefix))
+			.map(|(key, balance)| {
+				let token = Address::from_slice(&key[21..41]);
+				TokenHolding {
+					token: token,
+					holder: *holder,
+					balance: rlp::decode(&balance),
+					reliable: self.deficit(&token, holder).is_none(),
+				}
+			})
+			.collect()
+	}
+
+	/// Up to `limit` holders of the given token with the highest balances, in descending order.
+	pub fn top_holders(&self, token: &Address, limit: usize) -> Vec<TokenHolding> {
+		let prefix = prefixed(RANK_PREFIX, token);
+		self.db.iter_from_prefix(db::COL_TOKEN_INDEX, &prefix)
+			.take_while(|&(ref key, _)| key.starts_with(&prefix))
+			.take(limit)
+			.map(|(key, _)| {
+				let holder = Address::from_slice(&key[53..73]);
+				TokenHolding {
+					token: *token,
+					holder: holder,
+					balance: U256::max_value() - U256::from(&key[21..53]),
+					reliable: self.deficit(token, &holder).is_none(),
+				}
+			})
+			.collect()
+	}
+
+	/// Bring the index closer to the best block of the given client, by at most
+	/// `MAX_BLOCKS_PER_UPDATE` blocks. A larger gap is closed over the following notifications.
+	///
+	/// New blocks are not announced while the import queue is busy, so the route is taken
+	/// from the last indexed block rather than from the announcement itself.
+	fn update(&self, client: &BlockChainClient) {
+		let _lock = self.update_lock.lock();
+
+		let chain_info = client.chain_info();
+		let best = chain_info.best_block_hash;
+		let head = match self.head() {
+			Some(head) if head != best => head,
+			_ => return,
+		};
+
+		let target = client.block_number(BlockId::Hash(head))
+			.and_then(|number| match number + MAX_BLOCKS_PER_UPDATE {
+				step if step < chain_info.best_block_number => client.block_hash(BlockId::Number(step)),
+				_ => None,
+			})
+			.unwrap_or(best);
+
+		let route = match client.tree_route(&head, &target) {
+			Some(route) => route,
+			None => {
+				// e.g. after restoring a snapshot.
+				warn!(target: "token_index", "Last indexed block {:x} is unknown, continuing from #{:x}", head, best);
+				let mut batch = DBTransaction::new();
+				batch.put(db::COL_TOKEN_INDEX, HEAD_KEY, &best);
+				self.db.write(batch).expect("Low level database error. Some issue with disk?");
+				return;
+			}
+		};
+
+		let topic = transfer_topic();
+		let (retracted, enacted) = route.blocks.split_at(route.index);
+
+		// blocks are retracted from the last indexed one down to the common ancestor.
+		for (i, hash) in retracted.iter().enumerate() {
+			let parent = retracted.get(i + 1).unwrap_or(&route.ancestor);
+			self.apply_block(&topic, &receipts_of(client, hash), true, parent);
+		}
+
+		for hash in enacted {
+			self.apply_block(&topic, &receipts_of(client, hash), false, hash);
+		}
+
+		trace!(target: "token_index", "Indexed {} retracted and {} enacted blocks", retracted.len(), enacted.len());
+		if target != best {
+			debug!(target: "token_index", "Index is behind the best block, continuing from {:x} on the next block", target);
+		}
+	}
+
+	/// Apply (or revert) token transfers of a single block, moving the head to `new_head`.
+	fn apply_block(&self, topic: &H256, receipts: &[Receipt], revert: bool, new_head: &H256) {
+		// (holder, token) => (previous holding, new holding)
+		let mut changes: HashMap<(Address, Address), (Holding, Holding)> = HashMap::new();
+
+		{
+			let mut adjust = |holder: Address, token: Address, value: U256, credit: bool| {
+				// transfers from and to the zero address mint and burn tokens.
+				if holder.is_zero() {
+					return;
+				}
+
+				let entry = changes.entry((holder, token)).or_insert_with(|| {
+					let holding = self.holding(&token, &holder);
+					(holding, holding)
+				});
+
+				match credit {
+					true => entry.1.credit(value),
+					false => entry.1.debit(value),
+				}
+			};
+
+			let mut transfers: Vec<_> = receipts.iter()
+				.flat_map(|receipt| receipt.logs.iter())
+				.filter_map(|log| Transfer::from_log(log, topic))
+				.collect();
+
+			// transfers are reverted in reverse order, so that no balance dips below zero on the way.
+			if revert {
+				transfers.reverse();
+			}
+
+			for transfer in transfers {
+				adjust(transfer.from, transfer.token, transfer.value, revert);
+				adjust(transfer.to, transfer.token, transfer.value, !revert);
+			}
+		}
+
+		let mut batch = DBTransaction::new();
+		for ((holder, token), (old, new)) in changes {
+			if old.deficit != new.deficit {
+				if let Some(ref deficit) = new.deficit {
+					batch.put(db::COL_TOKEN_INDEX, &deficit_key(&holder, &token), &rlp::encode(deficit));
+				}
+			}
+
+			let (old, new) = (old.balance, new.balance);
+			if old == new {
+				continue;
+			}
+
+			if !old.is_zero() {
+				batch.delete(db::COL_TOKEN_INDEX, &rank_key(&token, &old, &holder));
+			}
+
+			match new.is_zero() {
+				true => batch.delete(db::COL_TOKEN_INDEX, &holding_key(&holder, &token)),
+				false => {
+					batch.put(db::COL_TOKEN_INDEX, &holding_key(&holder, &token), &rlp::encode(&new));
+					batch.put(db::COL_TOKEN_INDEX, &rank_key(&token, &new, &holder), &[]);
+				}
+			}
+		}
+
+		batch.put(db::COL_TOKEN_INDEX, HEAD_KEY, new_head);
+		self.db.write(batch).expect("Low level database error. Some issue with disk?");
+	}
+}
+
+/// Topic of the ERC-20 `Transfer(address,address,uint256)` event.
+fn transfer_topic() -> H256 {
+	keccak("Transfer(address,address,uint256)")
+}
+
+fn receipts_of(client: &BlockChainClient, hash: &H256) -> Vec<Receipt> {
+	client.block_receipts(hash)
+		.map(|receipts| rlp::decode::<BlockReceipts>(&receipts).receipts)
+		.unwrap_or_else(|| {
+			warn!(target: "token_index", "Receipts of block {:x} are missing, its transfers are not indexed", hash);
+			Vec::new()
+		})
+}
+
+impl ChainNotify for TokenIndex {
+	fn new_blocks(
+		&self,
+		_imported: Vec<H256>,
+		_invalid: Vec<H256>,
+		enacted: Vec<H256>,
+		retracted: Vec<H256>,
+		_sealed: Vec<H256>,
+		_proposed: Vec<Bytes>,
+		_duration: u64)
+	{
+		if enacted.is_empty() && retracted.is_empty() {
+			return;
+		}
+
+		if let Some(client) = self.client.upgrade() {
+			self.update(&*client);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::{Arc, Weak};
+	use ethereum_types::{H256, U256, Address};
+	use kvdb_memorydb;
+	use rlp;
+	use blockchain::{BlockReceipts, TreeRoute};
+	use client::{BlockChainClient, BlockId, EachBlockWith, TestBlockChainClient};
+	use log_entry::LogEntry;
+	use receipt::{Receipt, TransactionOutcome};
+	use super::{TokenIndex, TokenHolding, Transfer, transfer_topic, MAX_BLOCKS_PER_UPDATE};
+
+	fn new_index() -> TokenIndex {
+		let db = Arc::new(kvdb_memorydb::create(::db::NUM_COLUMNS.unwrap_or(0)));
+		TokenIndex::new(Weak::new(), db, H256::zero())
+	}
+
+	fn holding(token: Address, holder: Address, balance: u64, reliable: bool) -> TokenHolding {
+		TokenHolding {
+			token: token,
+			holder: holder,
+			balance: balance.into(),
+			reliable: reliable,
+		}
+	}
+
+	fn address_topic(address: Address) -> H256 {
+		let mut topic = H256::zero();
+		topic[12..].copy_from_slice(&address);
+		topic
+	}
+
+	fn transfer_log(token: Address, from: Address, to: Address, value: u64) -> LogEntry {
+		LogEntry {
+			address: token,
+			topics: vec![transfer_topic(), address_topic(from), address_topic(to)],
+			data: H256::from(value).to_vec(),
+		}
+	}
+
+	fn receipt(logs: Vec<LogEntry>) -> Receipt {
+		Receipt::new(TransactionOutcome::StatusCode(1), 21_000.into(), logs)
+	}
+
+	#[test]
+	fn should_decode_transfer_logs_only() {
+		let topic = transfer_topic();
+		let log = transfer_log(1.into(), 2.into(), 3.into(), 100);
+		assert_eq!(Transfer::from_log(&log, &topic), Some(Transfer {
+			token: 1.into(),
+			from: 2.into(),
+			to: 3.into(),
+			value: 100.into(),
+		}));
+
+		let mut nft = log.clone();
+		nft.topics.push(H256::from(7));
+		assert_eq!(Transfer::from_log(&nft, &topic), None);
+
+		let mut other = log.clone();
+		other.topics[0] = H256::from(1);
+		assert_eq!(Transfer::from_log(&other, &topic), None);
+	}
+
+	#[test]
+	fn should_track_balances_and_revert_retracted_blocks() {
+		let index = new_index();
+		let topic = transfer_topic();
+		let (token, alice, bob) = (Address::from(10), Address::from(1), Address::from(2));
+
+		let mint = vec![receipt(vec![transfer_log(token, Address::zero(), alice, 1000)])];
+		index.apply_block(&topic, &mint, false, &H256::from(1));
+
+		let send = vec![
+			receipt(vec![transfer_log(token, alice, bob, 300)]),
+			receipt(vec![transfer_log(token, alice, bob, 100)]),
+		];
+		index.apply_block(&topic, &send, false, &H256::from(2));
+
+		assert_eq!(index.balance(&token, &alice), 600.into());
+		assert_eq!(index.balance(&token, &bob), 400.into());
+		assert_eq!(index.balance(&token, &Address::zero()), U256::zero());
+		assert_eq!(index.holdings(&bob), vec![holding(token, bob, 400, true)]);
+		assert_eq!(index.top_holders(&token, 10), vec![holding(token, alice, 600, true), holding(token, bob, 400, true)]);
+		assert_eq!(index.top_holders(&token, 1), vec![holding(token, alice, 600, true)]);
+		assert_eq!(index.head(), Some(H256::from(2)));
+
+		index.apply_block(&topic, &send, true, &H256::from(1));
+
+		assert_eq!(index.balance(&token, &alice), 1000.into());
+		assert!(index.holdings(&bob).is_empty());
+		assert_eq!(index.top_holders(&token, 10), vec![holding(token, alice, 1000, true)]);
+		assert_eq!(index.head(), Some(H256::from(1)));
+	}
+
+	#[test]
+	fn should_list_tokens_of_holder() {
+		let index = new_index();
+		let topic = transfer_topic();
+		let (first, second, holder) = (Address::from(10), Address::from(11), Address::from(1));
+
+		index.apply_block(&topic, &[receipt(vec![
+			transfer_log(second, Address::zero(), holder, 5),
+			transfer_log(first, Address::zero(), holder, 7),
+			transfer_log(first, Address::zero(), Address::from(2), 9),
+		])], false, &H256::from(1));
+
+		assert_eq!(index.holdings(&holder), vec![holding(first, holder, 7, true), holding(second, holder, 5, true)]);
+		assert!(index.holdings(&Address::from(3)).is_empty());
+	}
+
+	#[test]
+	fn should_saturate_and_flag_holders_of_unindexed_tokens() {
+		let index = new_index();
+		let topic = transfer_topic();
+		let (token, alice, bob) = (Address::from(10), Address::from(1), Address::from(2));
+
+		// alice received her tokens before the index was enabled.
+		let spend = vec![receipt(vec![transfer_log(token, alice, bob, 100)])];
+		index.apply_block(&topic, &spend, false, &H256::from(1));
+
+		assert_eq!(index.balance(&token, &alice), U256::zero());
+		assert!(index.holdings(&alice).is_empty());
+		assert_eq!(index.top_holders(&token, 10), vec![holding(token, bob, 100, true)]);
+
+		let receive = vec![receipt(vec![transfer_log(token, bob, alice, 30)])];
+		index.apply_block(&topic, &receive, false, &H256::from(2));
+
+		// the deficit is paid off first.
+		assert_eq!(index.balance(&token, &alice), U256::zero());
+		assert_eq!(index.balance(&token, &bob), 70.into());
+
+		let receive_more = vec![receipt(vec![transfer_log(token, bob, alice, 70)])];
+		let mint = vec![receipt(vec![transfer_log(token, Address::zero(), alice, 5)])];
+		index.apply_block(&topic, &receive_more, false, &H256::from(3));
+		index.apply_block(&topic, &mint, false, &H256::from(4));
+
+		assert_eq!(index.holdings(&alice), vec![holding(token, alice, 5, false)]);
+		assert!(index.holdings(&bob).is_empty());
+
+		// reverting is exact, but the holder stays flagged.
+		index.apply_block(&topic, &mint, true, &H256::from(3));
+		index.apply_block(&topic, &receive_more, true, &H256::from(2));
+		assert_eq!(index.balance(&token, &alice), U256::zero());
+		assert_eq!(index.balance(&token, &bob), 70.into());
+
+		index.apply_block(&topic, &receive, true, &H256::from(1));
+		index.apply_block(&topic, &spend, true, &H256::zero());
+		assert_eq!(index.balance(&token, &alice), U256::zero());
+		assert_eq!(index.balance(&token, &bob), U256::zero());
+		assert_eq!(index.deficit(&token, &alice), Some(U256::zero()));
+		assert_eq!(index.deficit(&token, &bob), None);
+	}
+
+	#[test]
+	fn should_follow_reorganisations_of_client() {
+		let client = TestBlockChainClient::new();
+		let db = Arc::new(kvdb_memorydb::create(::db::NUM_COLUMNS.unwrap_or(0)));
+		let (ancestor, a2, b2, b3) = (H256::from(1), H256::from(2), H256::from(3), H256::from(4));
+		let index = TokenIndex::new(Weak::new(), db, ancestor);
+		let (token, alice, bob, carol) = (Address::from(10), Address::from(1), Address::from(2), Address::from(3));
+
+		let set_receipts = |hash: H256, logs: Vec<LogEntry>| {
+			let receipts = BlockReceipts::new(vec![receipt(logs)]);
+			client.block_receipts.write().insert(hash, rlp::encode(&receipts).into_vec());
+		};
+		set_receipts(a2, vec![
+			transfer_log(token, Address::zero(), alice, 1000),
+			transfer_log(token, alice, bob, 300),
+		]);
+		set_receipts(b2, vec![transfer_log(token, Address::zero(), bob, 50)]);
+		set_receipts(b3, vec![transfer_log(token, bob, carol, 20)]);
+
+		*client.last_hash.write() = a2;
+		*client.tree_route.write() = Some(TreeRoute {
+			blocks: vec![a2],
+			ancestor: ancestor,
+			index: 0,
+		});
+		index.update(&client);
+
+		assert_eq!(index.top_holders(&token, 10), vec![holding(token, alice, 700, true), holding(token, bob, 300, true)]);
+		assert_eq!(index.head(), Some(a2));
+
+		// a2 is retracted in favour of b2 and b3.
+		*client.last_hash.write() = b3;
+		*client.tree_route.write() = Some(TreeRoute {
+			blocks: vec![a2, b2, b3],
+			ancestor: ancestor,
+			index: 1,
+		});
+		index.update(&client);
+
+		assert_eq!(index.balance(&token, &alice), U256::zero());
+		assert_eq!(index.top_holders(&token, 10), vec![holding(token, bob, 30, true), holding(token, carol, 20, true)]);
+		assert_eq!(index.head(), Some(b3));
+	}
+
+	#[test]
+	fn should_catch_up_in_bounded_steps() {
+		let client = TestBlockChainClient::new();
+		client.add_blocks(2 * MAX_BLOCKS_PER_UPDATE as usize + 10, EachBlockWith::Nothing);
+		let db = Arc::new(kvdb_memorydb::create(::db::NUM_COLUMNS.unwrap_or(0)));
+		let genesis = BlockChainClient::block_hash(&client, BlockId::Number(0)).unwrap();
+		let index = TokenIndex::new(Weak::new(), db, genesis);
+
+		index.update(&client);
+		assert_eq!(index.head(), BlockChainClient::block_hash(&client, BlockId::Number(MAX_BLOCKS_PER_UPDATE)));
+
+		index.update(&client);
+		assert_eq!(index.head(), BlockChainClient::block_hash(&client, BlockId::Number(2 * MAX_BLOCKS_PER_UPDATE)));
+
+		index.update(&client);
+		assert_eq!(index.head(), Some(client.chain_info().best_block_hash));
+	}
+}
@@ -25,22 +25,26 @@ use error::{ImportResult, CallError, Error as EthcoreError, BlockImportError};
 use evm::Schedule;
 use factory::VmFactory;
 use executive::Executed;
-use filter::Filter;
+use filter::{Filter, LogCursor};
 use header::{BlockNumber};
 use log_entry::LocalizedLogEntry;
 use receipt::LocalizedReceipt;
-use trace::LocalizedTrace;
+use trace::{FlatTrace, LocalizedTrace, StructLog, StructLoggerOptions, TracerPreset, PresetTrace};
 use transaction::{LocalizedTransaction, PendingTransaction, SignedTransaction, ImportResult as TransactionImportResult};
 use verification::queue::QueueInfo as BlockQueueInfo;
+use super::gas_price_oracle::FeeHistory;
+use super::token_index::TokenHolding;
 
 use ethereum_types::{H256, U256, Address};
 use bytes::Bytes;
 use hashdb::DBValue;
+use trie;
 
 use types::ids::*;
 use types::basic_account::BasicAccount;
 use types::trace_filter::Filter as TraceFilter;
 use types::call_analytics::CallAnalytics;
+use types::state_override::StateOverride;
 use types::blockchain_info::BlockChainInfo;
 use types::block_status::BlockStatus;
 use types::mode::Mode;
@@ -124,6 +128,18 @@ pub trait BlockChainClient : Sync + Send {
 			Therefore storage_at has returned Some; qed")
 	}
 
+	/// Get balances of the given addresses at the given block's state, looked up in order
+	/// through a single state instance.
+	///
+	/// Returns None if and only if the block's root hash has been pruned from the DB.
+	fn balances(&self, addresses: &[Address], id: BlockId) -> Option<Vec<trie::Result<U256>>>;
+
+	/// Get values of the storage at given (address, position) pairs at the given block's state,
+	/// looked up in order through a single state instance.
+	///
+	/// Returns None if and only if the block's root hash has been pruned from the DB.
+	fn storage_batch(&self, entries: &[(Address, H256)], id: BlockId) -> Option<Vec<trie::Result<H256>>>;
+
 	/// Get a list of all accounts in the block `id`, if fat DB is in operation, otherwise `None`.
 	/// If `after` is set the list starts with the following item.
 	fn list_accounts(&self, id: BlockId, after: Option<&Address>, count: u64) -> Option<Vec<Address>>;
@@ -138,6 +154,19 @@ pub trait BlockChainClient : Sync + Send {
 	/// Get the hash of block that contains the transaction, if any.
 	fn transaction_block(&self, id: TransactionId) -> Option<H256>;
 
+	/// Get canon chain transactions sent from or to given account within the given block range,
+	/// oldest first and at most `limit` of them.
+	/// Returns `None` if the account transaction index is disabled or the range is unknown.
+	fn account_transactions(&self, address: &Address, from_block: BlockId, to_block: BlockId, limit: usize) -> Option<Vec<LocalizedTransaction>>;
+
+	/// Get the ERC-20 tokens held by the given address along with their balances,
+	/// or `None` if the token index is disabled.
+	fn token_holdings(&self, holder: &Address) -> Option<Vec<TokenHolding>>;
+
+	/// Get up to `limit` holders of the given ERC-20 token with the highest balances,
+	/// or `None` if the token index is disabled.
+	fn token_holders(&self, token: &Address, limit: usize) -> Option<Vec<TokenHolding>>;
+
 	/// Get uncle with given id.
 	fn uncle(&self, id: UncleId) -> Option<encoded::Header>;
 
@@ -181,15 +210,24 @@ pub trait BlockChainClient : Sync + Send {
 	/// Returns logs matching given filter.
 	fn logs(&self, filter: Filter) -> Vec<LocalizedLogEntry>;
 
-	/// Makes a non-persistent transaction call.
-	fn call(&self, tx: &SignedTransaction, analytics: CallAnalytics, block: BlockId) -> Result<Executed, CallError>;
+	/// Returns at most `max_results` logs matching given filter in ascending order, skipping the ones before `cursor`.
+	/// The position of the first log that didn't fit is returned along with the page.
+	fn logs_paged(&self, filter: Filter, cursor: Option<LogCursor>, max_results: usize) -> (Vec<LocalizedLogEntry>, Option<LogCursor>);
+
+	/// Makes a non-persistent transaction call on top of the state with given overrides applied.
+	fn call(&self, tx: &SignedTransaction, analytics: CallAnalytics, block: BlockId, state_override: &StateOverride) -> Result<Executed, CallError>;
+
+	/// Makes a non-persistent transaction call like `call`, also tracing it with given tracer presets.
+	/// All of the tracing is done while executing the transaction once.
+	fn call_with_presets(&self, tx: &SignedTransaction, analytics: CallAnalytics, presets: &[TracerPreset], block: BlockId, state_override: &StateOverride) -> Result<(Executed, Vec<PresetTrace>), CallError>;
 
 	/// Makes multiple non-persistent but dependent transaction calls.
+	/// State overrides of each transaction are applied right before it is executed.
 	/// Returns a vector of successes or a failure if any of the transaction fails.
-	fn call_many(&self, txs: &[(SignedTransaction, CallAnalytics)], block: BlockId) -> Result<Vec<Executed>, CallError>;
+	fn call_many(&self, txs: &[(SignedTransaction, CallAnalytics, StateOverride)], block: BlockId) -> Result<Vec<Executed>, CallError>;
 
 	/// Estimates how much gas will be necessary for a call.
-	fn estimate_gas(&self, t: &SignedTransaction, block: BlockId) -> Result<U256, CallError>;
+	fn estimate_gas(&self, t: &SignedTransaction, block: BlockId, state_override: &StateOverride) -> Result<U256, CallError>;
 
 	/// Replays a given transaction for inspection.
 	fn replay(&self, t: TransactionId, analytics: CallAnalytics) -> Result<Executed, CallError>;
@@ -197,6 +235,12 @@ pub trait BlockChainClient : Sync + Send {
 	/// Replays all the transactions in a given block for inspection.
 	fn replay_block_transactions(&self, block: BlockId, analytics: CallAnalytics) -> Result<Box<Iterator<Item = Executed>>, CallError>;
 
+	/// Replays a given transaction like `replay`, also tracing it with given tracer presets.
+	fn replay_with_presets(&self, t: TransactionId, analytics: CallAnalytics, presets: &[TracerPreset]) -> Result<(Executed, Vec<PresetTrace>), CallError>;
+
+	/// Replays a given transaction collecting geth-style struct logs of its execution.
+	fn replay_struct_logs(&self, t: TransactionId, options: StructLoggerOptions) -> Result<Executed<FlatTrace, Vec<StructLog>>, CallError>;
+
 	/// Returns traces matching given filter.
 	fn filter_traces(&self, filter: TraceFilter) -> Option<Vec<LocalizedTrace>>;
 
@@ -242,6 +286,10 @@ pub trait BlockChainClient : Sync + Send {
 		corpus.into()
 	}
 
+	/// Returns gas price percentiles and gas used ratios of the last `block_count` canonical blocks
+	/// along with recommended gas prices.
+	fn fee_history(&self, block_count: usize, percentiles: &[f64]) -> FeeHistory;
+
 	/// Get the preferred chain ID to sign on
 	fn signing_chain_id(&self) -> Option<u64>;
 
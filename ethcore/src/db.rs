@@ -41,8 +41,12 @@ pub const COL_ACCOUNT_BLOOM: Option<u32> = Some(5);
 pub const COL_NODE_INFO: Option<u32> = Some(6);
 /// Column for the light client chain.
 pub const COL_LIGHT_CHAIN: Option<u32> = Some(7);
+/// Column for the optional account transaction index.
+pub const COL_ACCOUNT_TX: Option<u32> = Some(8);
+/// Column for the optional ERC-20 token balance index.
+pub const COL_TOKEN_INDEX: Option<u32> = Some(9);
 /// Number of columns in DB
-pub const NUM_COLUMNS: Option<u32> = Some(8);
+pub const NUM_COLUMNS: Option<u32> = Some(10);
 
 /// Modes for updating caches.
 #[derive(Clone, Copy)]
@@ -1019,7 +1019,7 @@ impl Engine<EthereumMachine> for AuthorityRound {
 		for &(ref author, ref block_reward) in rewards.iter() {
 			self.machine.add_balance(block, author, block_reward)?;
 		}
-		self.machine.note_rewards(block, &rewards, &[])
+		self.machine.note_rewards(block, &rewards, &[], &[])
 	}
 
 	/// Check the number of seal fields.
@@ -189,8 +189,8 @@ pub trait Engine<M: Machine>: Sync + Send {
 	/// Maximum number of uncles a block is allowed to declare.
 	fn maximum_uncle_count(&self, _block: BlockNumber) -> usize { 0 }
 
-	/// The number of generations back that uncles can be.
-	fn maximum_uncle_age(&self) -> usize { 6 }
+	/// The number of generations back that uncles of the given block can be.
+	fn maximum_uncle_age(&self, _block: BlockNumber) -> usize { 6 }
 
 	/// Block transformation functions, before the transactions.
 	/// `epoch_begin` set to true if this block kicks off an epoch.
@@ -93,7 +93,7 @@ impl<M: WithBalances> Engine<M> for NullEngine<M> {
 		}
 
 		// note and trace.
-		self.machine.note_rewards(block, &[(author, result_block_reward)], &uncle_rewards)
+		self.machine.note_rewards(block, &[(author, result_block_reward)], &uncle_rewards, &[])
 	}
 
 	fn maximum_uncle_count(&self, _block: BlockNumber) -> usize { 2 }
@@ -447,7 +447,7 @@ impl Engine<EthereumMachine> for Tendermint {
 
 	fn maximum_uncle_count(&self, _block: BlockNumber) -> usize { 0 }
 
-	fn maximum_uncle_age(&self) -> usize { 0 }
+	fn maximum_uncle_age(&self, _block: BlockNumber) -> usize { 0 }
 
 	fn populate_from_parent(&self, header: &mut Header, parent: &Header) {
 		// Chain scoring: total weight is sqrt(U256::max_value())*height - view
@@ -553,7 +553,7 @@ impl Engine<EthereumMachine> for Tendermint {
 		use parity_machine::WithBalances;
 		let author = *block.header().author();
 		self.machine.add_balance(block, &author, &self.block_reward)?;
-		self.machine.note_rewards(block, &[(author, self.block_reward)], &[])
+		self.machine.note_rewards(block, &[(author, self.block_reward)], &[], &[])
 	}
 
 	fn verify_local_seal(&self, _header: &Header) -> Result<(), Error> {
@@ -38,6 +38,18 @@ const MAX_SNAPSHOT_BLOCKS: u64 = 30000;
 
 const DEFAULT_EIP649_DELAY: u64 = 3_000_000;
 
+/// Maximum number of uncles per block unless an uncle policy says otherwise.
+const DEFAULT_MAX_UNCLES: usize = 2;
+/// Maximum uncle depth unless an uncle policy says otherwise.
+const DEFAULT_MAX_UNCLE_DEPTH: usize = 6;
+
+/// Bound divisor of the Nekonium HF01 difficulty rule.
+const NEKONIUM_HF01_BOUND_DIVISOR: u64 = 1024;
+/// Block time shift of the Nekonium HF01 difficulty rule.
+const NEKONIUM_HF01_SHIFT_SEC: u64 = 9;
+/// HF01 transition of specs which configure neither `nekoniumTransition` nor `difficultyRules`.
+const DEFAULT_NEKONIUM_TRANSITION: u64 = 7777;
+
 /// Ethash specific seal
 #[derive(Debug, PartialEq)]
 pub struct Seal {
@@ -70,6 +82,219 @@ impl Seal {
 	}
 }
 
+/// Difficulty calculation rules in effect from a given block onwards.
+/// Unset values fall back to the engine-wide params.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DifficultyRule {
+	/// First block the rule applies to.
+	pub transition: u64,
+	/// Block duration target used before Homestead.
+	pub duration_limit: Option<u64>,
+	/// Difficulty bound divisor used from Homestead.
+	pub bound_divisor: Option<U256>,
+	/// Difficulty increment divisor used from Homestead.
+	pub increment_divisor: Option<u64>,
+	/// Seconds subtracted from the block time before adjusting difficulty, from Homestead.
+	pub shift: u64,
+}
+
+impl From<ethjson::spec::EthashDifficultyRule> for DifficultyRule {
+	fn from(r: ethjson::spec::EthashDifficultyRule) -> Self {
+		DifficultyRule {
+			transition: r.transition.into(),
+			duration_limit: r.duration_limit.map(Into::into),
+			bound_divisor: r.bound_divisor.map(Into::into),
+			increment_divisor: r.increment_divisor.map(Into::into),
+			shift: r.shift.map_or(0, Into::into),
+		}
+	}
+}
+
+impl DifficultyRule {
+	/// The difficulty rule introduced by the Nekonium HF01 hard-fork.
+	pub fn nekonium_hf01(transition: u64) -> Self {
+		DifficultyRule {
+			transition,
+			duration_limit: None,
+			bound_divisor: Some(NEKONIUM_HF01_BOUND_DIVISOR.into()),
+			increment_divisor: None,
+			shift: NEKONIUM_HF01_SHIFT_SEC,
+		}
+	}
+}
+
+/// Share of the block reward paid to a recipient.
+#[derive(Debug, PartialEq, Clone)]
+pub enum RewardShare {
+	/// Fixed amount in base units.
+	Amount(U256),
+	/// Percentage of the era block reward.
+	Percentage(u64),
+}
+
+/// Block reward split in effect from a given block onwards.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RewardEra {
+	/// First block the era applies to.
+	pub transition: u64,
+	/// Total reward per block. Falls back to the engine block reward.
+	pub block_reward: Option<U256>,
+	/// Fixed author reward. When unset the author receives what the recipients leave.
+	pub miner_reward: Option<U256>,
+	/// Additional reward recipients.
+	pub recipients: Vec<(Address, RewardShare)>,
+	/// Number of blocks after which all payouts decay.
+	pub decay_era_rounds: u64,
+	/// Percentage by which all payouts decrease each decay era.
+	pub decay_percent: u64,
+	/// Whether the author receives `reward >> 5` per included uncle when no uncle policy
+	/// sets a nephew reward. Only MCIP-3 eras pay nothing by default.
+	pub nephew_reward: bool,
+}
+
+impl From<ethjson::spec::EthashRewardEra> for RewardEra {
+	fn from(e: ethjson::spec::EthashRewardEra) -> Self {
+		RewardEra {
+			transition: e.transition.into(),
+			block_reward: e.block_reward.map(Into::into),
+			miner_reward: e.miner_reward.map(Into::into),
+			recipients: e.recipients.unwrap_or_default().into_iter().map(|r| {
+				let share = match r.percentage {
+					Some(percentage) => RewardShare::Percentage(cmp::min::<u64>(percentage.into(), 100)),
+					None => RewardShare::Amount(r.amount.map_or_else(Default::default, Into::into)),
+				};
+				(r.address.into(), share)
+			}).collect(),
+			decay_era_rounds: e.decay_era_rounds.map_or(u64::max_value(), Into::into),
+			decay_percent: e.decay_percent.map_or(0, |p| cmp::min::<u64>(p.into(), 100)),
+			nephew_reward: true,
+		}
+	}
+}
+
+impl RewardEra {
+	/// Split the block reward for the given block between the author and the other recipients.
+	/// `block_reward` is used unless the era overrides it.
+	pub fn payouts(&self, block_number: BlockNumber, block_reward: U256) -> (U256, Vec<(Address, U256)>) {
+		let eras = block_number.saturating_sub(self.transition) / self.decay_era_rounds;
+		let decay = |amount: U256| decayed_reward(amount, eras, self.decay_percent);
+
+		let total = decay(self.block_reward.unwrap_or(block_reward));
+		let recipients: Vec<_> = self.recipients.iter().map(|&(address, ref share)| {
+			let amount = match *share {
+				RewardShare::Amount(amount) => decay(amount),
+				RewardShare::Percentage(percentage) => percent_of(total, percentage),
+			};
+			(address, amount)
+		}).collect();
+
+		let author = match self.miner_reward {
+			Some(reward) => decay(reward),
+			None => recipients.iter().fold(total, |left, &(_, amount)| left.saturating_sub(amount)),
+		};
+
+		(author, recipients)
+	}
+
+	/// Check that the recipients never take more than the block reward when the
+	/// author is paid the remainder. `fallback_rewards` are the engine block rewards
+	/// which may apply during the era when it does not set its own.
+	fn verify(&self, fallback_rewards: &[U256], ecip1017: bool) -> Result<(), String> {
+		if self.miner_reward.is_some() {
+			return Ok(());
+		}
+
+		let mut percentage = 0u64;
+		let mut amount = U256::zero();
+		for &(_, ref share) in &self.recipients {
+			match *share {
+				RewardShare::Percentage(p) => percentage += p,
+				RewardShare::Amount(a) => match amount.overflowing_add(a) {
+					(sum, false) => amount = sum,
+					(_, true) => return Err(format!("Reward era {}: recipient amounts overflow", self.transition)),
+				},
+			}
+		}
+		if percentage > 100 {
+			return Err(format!("Reward era {}: recipient percentages exceed 100", self.transition));
+		}
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		let totals = match self.block_reward {
+			Some(reward) => vec![reward],
+			None if ecip1017 => return Err(format!("Reward era {}: fixed recipient amounts need an explicit blockReward while ECIP-1017 eras apply", self.transition)),
+			None => fallback_rewards.to_vec(),
+		};
+		for total in totals {
+			if amount > total - percent_of(total, percentage) {
+				return Err(format!("Reward era {}: recipients are paid more than the block reward {}", self.transition, total));
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Uncle reward curve.
+#[derive(Debug, PartialEq, Clone)]
+pub enum UncleRewardCurve {
+	/// `reward * (n - depth) / n`
+	Depth(u64),
+	/// `reward / n` regardless of depth.
+	Flat(u64),
+}
+
+impl From<ethjson::spec::EthashUncleReward> for UncleRewardCurve {
+	fn from(r: ethjson::spec::EthashUncleReward) -> Self {
+		use ethjson::spec::EthashUncleReward;
+
+		match r {
+			EthashUncleReward::Depth(n) => UncleRewardCurve::Depth(n.into()),
+			EthashUncleReward::Flat(n) => UncleRewardCurve::Flat(n.into()),
+		}
+	}
+}
+
+impl UncleRewardCurve {
+	/// Reward for an uncle `depth` generations behind the including block.
+	pub fn reward(&self, block_reward: U256, depth: u64) -> U256 {
+		match *self {
+			UncleRewardCurve::Depth(0) | UncleRewardCurve::Flat(0) => U256::zero(),
+			UncleRewardCurve::Depth(n) => block_reward * U256::from(n.saturating_sub(depth)) / U256::from(n),
+			UncleRewardCurve::Flat(n) => block_reward / U256::from(n),
+		}
+	}
+}
+
+/// Uncle rules in effect from a given block onwards.
+/// Unset values fall back to the Ethereum defaults.
+#[derive(Debug, PartialEq, Clone)]
+pub struct UnclePolicy {
+	/// First block the policy applies to.
+	pub transition: u64,
+	/// Maximum number of uncles per block.
+	pub max_uncles: Option<usize>,
+	/// Maximum number of generations an uncle can be behind the including block.
+	pub max_uncle_depth: Option<usize>,
+	/// Divisor of the block reward paid to the author per included uncle.
+	pub nephew_reward_divisor: Option<u64>,
+	/// Uncle reward curve.
+	pub uncle_reward: Option<UncleRewardCurve>,
+}
+
+impl From<ethjson::spec::EthashUnclePolicy> for UnclePolicy {
+	fn from(p: ethjson::spec::EthashUnclePolicy) -> Self {
+		UnclePolicy {
+			transition: p.transition.into(),
+			max_uncles: p.max_uncles.map(Into::into),
+			max_uncle_depth: p.max_uncle_depth.map(Into::into),
+			nephew_reward_divisor: p.nephew_reward_divisor.map(Into::into),
+			uncle_reward: p.uncle_reward.map(Into::into),
+		}
+	}
+}
+
 /// Ethash params.
 #[derive(Debug, PartialEq)]
 pub struct EthashParams {
@@ -83,12 +308,8 @@ pub struct EthashParams {
 	pub metropolis_difficulty_increment_divisor: u64,
 	/// Block duration.
 	pub duration_limit: u64,
-
-
-	/// Nekonium transition block number.
-	pub nekonium_transition: u64,
-	
-		
+	/// Difficulty rule eras, sorted by transition block.
+	pub difficulty_rules: Vec<DifficultyRule>,
 	/// Homestead transition block number.
 	pub homestead_transition: u64,
 	/// Transition block for a change of difficulty params (currently just bound_divisor).
@@ -105,18 +326,10 @@ pub struct EthashParams {
 	pub ecip1010_continue_transition: u64,
 	/// Total block number for one ECIP-1017 era.
 	pub ecip1017_era_rounds: u64,
-	/// Number of first block where MCIP-3 begins.
-	pub mcip3_transition: u64,
-	/// MCIP-3 Block reward coin-base for miners.
-	pub mcip3_miner_reward: U256,
-	/// MCIP-3 Block reward ubi-base for basic income.
-	pub mcip3_ubi_reward: U256,
-	/// MCIP-3 contract address for universal basic income.
-	pub mcip3_ubi_contract: Address,
-	/// MCIP-3 Block reward dev-base for dev funds.
-	pub mcip3_dev_reward: U256,
-	/// MCIP-3 contract address for the developer funds.
-	pub mcip3_dev_contract: Address,
+	/// Block reward split eras, sorted by transition block.
+	pub reward_schedule: Vec<RewardEra>,
+	/// Uncle policies, sorted by transition block.
+	pub uncle_policy: Vec<UnclePolicy>,
 	/// Block reward in base units.
 	pub block_reward: U256,
 	/// EIP-649 transition block.
@@ -133,13 +346,49 @@ pub struct EthashParams {
 
 impl From<ethjson::spec::EthashParams> for EthashParams {
 	fn from(p: ethjson::spec::EthashParams) -> Self {
+		let mut difficulty_rules: Vec<DifficultyRule> = p.difficulty_rules
+			.map_or_else(Vec::new, |rules| rules.into_iter().map(Into::into).collect());
+		// Specs predating difficulty rules always had HF01 at block 7777.
+		match p.nekonium_transition {
+			Some(transition) => difficulty_rules.push(DifficultyRule::nekonium_hf01(transition.into())),
+			None if difficulty_rules.is_empty() => difficulty_rules.push(DifficultyRule::nekonium_hf01(DEFAULT_NEKONIUM_TRANSITION)),
+			None => {},
+		}
+		difficulty_rules.sort_by_key(|rule| rule.transition);
+
+		let mut reward_schedule: Vec<RewardEra> = p.reward_schedule
+			.map_or_else(Vec::new, |eras| eras.into_iter().map(Into::into).collect());
+		if let Some(transition) = p.mcip3_transition {
+			let ubi_contract = p.mcip3_ubi_contract.map_or_else(Address::new, Into::into);
+			let ubi_reward = p.mcip3_ubi_reward.map_or(U256::from(0), Into::into);
+			let dev_contract = p.mcip3_dev_contract.map_or_else(Address::new, Into::into);
+			let dev_reward = p.mcip3_dev_reward.map_or(U256::from(0), Into::into);
+			reward_schedule.push(RewardEra {
+				transition: transition.into(),
+				block_reward: None,
+				miner_reward: Some(p.mcip3_miner_reward.map_or_else(Default::default, Into::into)),
+				recipients: vec![
+					(ubi_contract, RewardShare::Amount(ubi_reward)),
+					(dev_contract, RewardShare::Amount(dev_reward)),
+				],
+				decay_era_rounds: u64::max_value(),
+				decay_percent: 0,
+				nephew_reward: false,
+			});
+		}
+		reward_schedule.sort_by_key(|era| era.transition);
+
+		let mut uncle_policy: Vec<UnclePolicy> = p.uncle_policy
+			.map_or_else(Vec::new, |policies| policies.into_iter().map(Into::into).collect());
+		uncle_policy.sort_by_key(|policy| policy.transition);
+
 		EthashParams {
 			minimum_difficulty: p.minimum_difficulty.into(),
 			difficulty_bound_divisor: p.difficulty_bound_divisor.into(),
 			difficulty_increment_divisor: p.difficulty_increment_divisor.map_or(10, Into::into),
 			metropolis_difficulty_increment_divisor: p.metropolis_difficulty_increment_divisor.map_or(9, Into::into),
 			duration_limit: p.duration_limit.map_or(0, Into::into),
-			nekonium_transition: p.nekonium_transition.map_or(7777, Into::into),
+			difficulty_rules: difficulty_rules,
 			homestead_transition: p.homestead_transition.map_or(0, Into::into),
 			difficulty_hardfork_transition: p.difficulty_hardfork_transition.map_or(u64::max_value(), Into::into),
 			difficulty_hardfork_bound_divisor: p.difficulty_hardfork_bound_divisor.map_or(p.difficulty_bound_divisor.into(), Into::into),
@@ -148,12 +397,8 @@ impl From<ethjson::spec::EthashParams> for EthashParams {
 			ecip1010_pause_transition: p.ecip1010_pause_transition.map_or(u64::max_value(), Into::into),
 			ecip1010_continue_transition: p.ecip1010_continue_transition.map_or(u64::max_value(), Into::into),
 			ecip1017_era_rounds: p.ecip1017_era_rounds.map_or(u64::max_value(), Into::into),
-			mcip3_transition: p.mcip3_transition.map_or(u64::max_value(), Into::into),
-			mcip3_miner_reward: p.mcip3_miner_reward.map_or_else(Default::default, Into::into),
-			mcip3_ubi_reward: p.mcip3_ubi_reward.map_or(U256::from(0), Into::into),
-			mcip3_ubi_contract: p.mcip3_ubi_contract.map_or_else(Address::new, Into::into),
-			mcip3_dev_reward: p.mcip3_dev_reward.map_or(U256::from(0), Into::into),
-			mcip3_dev_contract: p.mcip3_dev_contract.map_or_else(Address::new, Into::into),
+			reward_schedule: reward_schedule,
+			uncle_policy: uncle_policy,
 			block_reward: p.block_reward.map_or_else(Default::default, Into::into),
 			eip649_transition: p.eip649_transition.map_or(u64::max_value(), Into::into),
 			eip649_delay: p.eip649_delay.map_or(DEFAULT_EIP649_DELAY, Into::into),
@@ -221,7 +466,17 @@ impl Engine<EthereumMachine> for Arc<Ethash> {
 		}
 	}
 
-	fn maximum_uncle_count(&self, _block: BlockNumber) -> usize { 2 }
+	fn maximum_uncle_count(&self, block: BlockNumber) -> usize {
+		self.ethash_params.uncle_policy(block)
+			.and_then(|policy| policy.max_uncles)
+			.unwrap_or(DEFAULT_MAX_UNCLES)
+	}
+
+	fn maximum_uncle_age(&self, block: BlockNumber) -> usize {
+		self.ethash_params.uncle_policy(block)
+			.and_then(|policy| policy.max_uncle_depth)
+			.unwrap_or(DEFAULT_MAX_UNCLE_DEPTH)
+	}
 
 	fn populate_from_parent(&self, header: &mut Header, parent: &Header) {
 		let difficulty = self.calculate_difficulty(header, parent);
@@ -258,34 +513,42 @@ impl Engine<EthereumMachine> for Arc<Ethash> {
 
 		let n_uncles = LiveBlock::uncles(&*block).len();
 
-		// Bestow block rewards.
-		let mut result_block_reward = reward + reward.shr(5) * U256::from(n_uncles);
-		let mut uncle_rewards = Vec::with_capacity(n_uncles);
+		let uncle_policy = self.ethash_params.uncle_policy(number);
 
-		if number >= self.ethash_params.mcip3_transition {
-			result_block_reward = self.ethash_params.mcip3_miner_reward;
-			let ubi_contract = self.ethash_params.mcip3_ubi_contract;
-			let ubi_reward = self.ethash_params.mcip3_ubi_reward;
-			let dev_contract = self.ethash_params.mcip3_dev_contract;
-			let dev_reward = self.ethash_params.mcip3_dev_reward;
+		// Reward for including uncles, `reward >> 5` unless an uncle policy overrides it.
+		let nephew_reward = uncle_policy
+			.and_then(|policy| policy.nephew_reward_divisor)
+			.map(|divisor| reward / U256::from(divisor));
+		let uncles_inclusion_reward = |default: Option<U256>| {
+			nephew_reward.or(default).unwrap_or_else(U256::zero) * U256::from(n_uncles)
+		};
 
-			self.machine.add_balance(block, &author, &result_block_reward)?;
-			self.machine.add_balance(block, &ubi_contract, &ubi_reward)?;
-			self.machine.add_balance(block, &dev_contract, &dev_reward)?;
-		} else {
-			self.machine.add_balance(block, &author, &result_block_reward)?;
+		// Bestow block rewards, split according to the reward schedule.
+		let (result_block_reward, external_rewards) = match self.ethash_params.reward_era(number) {
+			Some(era) => {
+				let (author_reward, external_rewards) = era.payouts(number, reward);
+				let default_nephew_reward = if era.nephew_reward { Some(reward.shr(5)) } else { None };
+				(author_reward + uncles_inclusion_reward(default_nephew_reward), external_rewards)
+			},
+			None => (reward + uncles_inclusion_reward(Some(reward.shr(5))), Vec::new()),
+		};
+		let mut uncle_rewards = Vec::with_capacity(n_uncles);
+
+		self.machine.add_balance(block, &author, &result_block_reward)?;
+		for &(ref a, ref reward) in &external_rewards {
+			self.machine.add_balance(block, a, reward)?;
 		}
 
 		// Bestow uncle rewards.
+		let uncle_reward_curve = match uncle_policy.and_then(|policy| policy.uncle_reward.clone()) {
+			Some(curve) => curve,
+			None if eras == 0 => UncleRewardCurve::Depth(8),
+			None => UncleRewardCurve::Flat(32),
+		};
 		for u in LiveBlock::uncles(&*block) {
 			let uncle_author = u.author();
-			let result_uncle_reward = if eras == 0 {
-				(reward * U256::from(8 + u.number() - number)).shr(3)
-			} else {
-				reward.shr(5)
-			};
-
-			uncle_rewards.push((*uncle_author, result_uncle_reward));
+			let depth = number.saturating_sub(u.number());
+			uncle_rewards.push((*uncle_author, uncle_reward_curve.reward(reward, depth)));
 		}
 
 		for &(ref a, ref reward) in &uncle_rewards {
@@ -293,7 +556,7 @@ impl Engine<EthereumMachine> for Arc<Ethash> {
 		}
 
 		// Note and trace.
-		self.machine.note_rewards(block, &[(author, result_block_reward)], &uncle_rewards)
+		self.machine.note_rewards(block, &[(author, result_block_reward)], &uncle_rewards, &external_rewards)
 	}
 
 	fn verify_local_seal(&self, header: &Header) -> Result<(), Error> {
@@ -374,6 +637,40 @@ impl Engine<EthereumMachine> for Arc<Ethash> {
 	}
 }
 
+impl EthashParams {
+	/// The difficulty rule in effect at the given block, if any.
+	pub fn difficulty_rule(&self, block_number: BlockNumber) -> Option<&DifficultyRule> {
+		self.difficulty_rules.iter().rev().find(|rule| block_number >= rule.transition)
+	}
+
+	/// The block reward era in effect at the given block, if any.
+	pub fn reward_era(&self, block_number: BlockNumber) -> Option<&RewardEra> {
+		self.reward_schedule.iter().rev().find(|era| block_number >= era.transition)
+	}
+
+	/// Reject reward schedules which would pay recipients more than the block reward.
+	pub fn verify_reward_schedule(&self) -> Result<(), String> {
+		let ecip1017 = self.ecip1017_era_rounds != u64::max_value();
+		for (i, era) in self.reward_schedule.iter().enumerate() {
+			let end = self.reward_schedule.get(i + 1).map_or(u64::max_value(), |next| next.transition);
+			let mut fallback_rewards = Vec::new();
+			if era.transition < self.eip649_transition {
+				fallback_rewards.push(self.block_reward);
+			}
+			if self.eip649_transition < end {
+				fallback_rewards.push(self.eip649_reward.unwrap_or(self.block_reward));
+			}
+			era.verify(&fallback_rewards, ecip1017)?;
+		}
+		Ok(())
+	}
+
+	/// The uncle policy in effect at the given block, if any.
+	pub fn uncle_policy(&self, block_number: BlockNumber) -> Option<&UnclePolicy> {
+		self.uncle_policy.iter().rev().find(|policy| block_number >= policy.transition)
+	}
+}
+
 impl Ethash {
 	fn calculate_difficulty(&self, header: &Header, parent: &Header) -> U256 {
 		const EXP_DIFF_PERIOD: u64 = 100_000;
@@ -381,12 +678,10 @@ impl Ethash {
 			panic!("Can't calculate genesis block difficulty");
 		}
 		
-		const NEKONIUM_HF01_SHIFT_SEC: u64 = 9;
-		let nekonium_hf01_diff_div =  U256::from(1024);
-
 		let parent_has_uncles = parent.uncles_hash() != &KECCAK_EMPTY_LIST_RLP;
 
 		let min_difficulty = self.ethash_params.minimum_difficulty;
+		let rule = self.ethash_params.difficulty_rule(header.number());
 
 		let difficulty_hardfork = header.number() >= self.ethash_params.difficulty_hardfork_transition;
 		let difficulty_bound_divisor = if difficulty_hardfork {
@@ -396,10 +691,10 @@ impl Ethash {
 		};
 
 		let expip2_hardfork = header.number() >= self.ethash_params.expip2_transition;
-		let duration_limit = if expip2_hardfork {
-			self.ethash_params.expip2_duration_limit
-		} else {
-			self.ethash_params.duration_limit
+		let duration_limit = match rule.and_then(|r| r.duration_limit) {
+			Some(duration_limit) => duration_limit,
+			None if expip2_hardfork => self.ethash_params.expip2_duration_limit,
+			None => self.ethash_params.duration_limit,
 		};
 
 		let frontier_limit = self.ethash_params.homestead_transition;
@@ -420,26 +715,14 @@ impl Ethash {
 			} else {
 				(self.ethash_params.metropolis_difficulty_increment_divisor, 1)
 			};
+			let increment_divisor = rule.and_then(|r| r.increment_divisor).unwrap_or(increment_divisor);
+			let difficulty_bound_divisor = rule.and_then(|r| r.bound_divisor).unwrap_or(difficulty_bound_divisor);
 
-			//let diff_inc = (header.timestamp() - parent.timestamp()) / increment_divisor;
-
+			// Block time as seen by the adjustment, never earlier than the parent.
+			let shift = rule.map_or(0, |r| r.shift);
+			let timestamp = cmp::max(header.timestamp().saturating_sub(shift), parent.timestamp());
 
-			let mut diff_inc = 0;
-			if header.number() < self.ethash_params.nekonium_transition {
-				diff_inc = (header.timestamp() - parent.timestamp()) / increment_divisor;
-			} else {
-				//let bigParentTime = parent.timestamp();
-			
-				let mut a  = header.timestamp();
-				a = a - NEKONIUM_HF01_SHIFT_SEC;
-				if a < parent.timestamp(){
-						a = parent.timestamp();
-				}	
-				diff_inc = (a - parent.timestamp()) / increment_divisor;
-			};
-
-/*
-			
+			let diff_inc = (timestamp - parent.timestamp()) / increment_divisor;
 			if diff_inc <= threshold {
 				*parent.difficulty() + *parent.difficulty() / difficulty_bound_divisor * U256::from(threshold - diff_inc)
 			} else {
@@ -448,30 +731,6 @@ impl Ethash {
 					*parent.difficulty() / difficulty_bound_divisor * multiplier
 				)
 			}
-			*/
-		
-			if diff_inc <= threshold {
-				//nekoniumHF01DiffDiv
-				if header.number() < self.ethash_params.nekonium_transition{
-					*parent.difficulty() + *parent.difficulty() / difficulty_bound_divisor* U256::from(threshold - diff_inc)
-				}else{
-					*parent.difficulty() + *parent.difficulty() / nekonium_hf01_diff_div * U256::from(threshold - diff_inc)
-					}
-					
-			} else {
-				let multiplier: U256  = cmp::min(diff_inc - threshold, 99).into();
-				if header.number() < self.ethash_params.nekonium_transition{
-					parent.difficulty().saturating_sub(
-						*parent.difficulty() / difficulty_bound_divisor * multiplier
-					)
-				} else{
-						parent.difficulty().saturating_sub(
-							*parent.difficulty() / nekonium_hf01_diff_div * multiplier
-						)					
-					}
-			}			
-			
-			
 		};
 		target = cmp::max(min_difficulty, target);
 		if header.number() < self.ethash_params.bomb_defuse_transition {
@@ -518,6 +777,26 @@ impl Ethash {
 	}
 }
 
+fn decayed_reward(mut reward: U256, eras: u64, percent: u64) -> U256 {
+	if percent == 0 {
+		return reward;
+	}
+	let mut era = 0;
+	while era < eras && !reward.is_zero() {
+		reward = percent_of(reward, 100 - percent);
+		era += 1;
+	}
+	reward
+}
+
+/// `value * percent / 100`, dividing first when the product would overflow.
+fn percent_of(value: U256, percent: u64) -> U256 {
+	match value.overflowing_mul(U256::from(percent)) {
+		(product, false) => product / U256::from(100),
+		(_, true) => value / U256::from(100) * U256::from(percent),
+	}
+}
+
 fn ecip1017_eras_block_reward(era_rounds: u64, mut reward: U256, block_number:u64) -> (u64, U256) {
 	let eras = if block_number != 0 && block_number % era_rounds == 0 {
 		block_number / era_rounds - 1
@@ -544,8 +823,10 @@ mod tests {
 	use header::Header;
 	use spec::Spec;
 	use engines::Engine;
-	use super::super::{new_morden, new_mcip3_test, new_homestead_test_machine};
-	use super::{Ethash, EthashParams, ecip1017_eras_block_reward};
+	use super::super::{new_morden, new_mcip3_test, new_reward_schedule_test, new_uncle_policy_test, new_homestead_test_machine};
+	use super::{Ethash, EthashParams, DifficultyRule, RewardEra, RewardShare, UnclePolicy, UncleRewardCurve, ecip1017_eras_block_reward};
+	use trace::{Tracing, FlatTrace, RewardType};
+	use trace::trace::Action;
 	use rlp;
 
 	fn test_spec() -> Spec {
@@ -619,6 +900,63 @@ mod tests {
 		assert_eq!(b.state().balance(&uncle_author).unwrap(), "3cb71f51fc558000".into());
 	}
 
+	#[test]
+	fn on_close_block_with_uncle_policy() {
+		let spec = new_uncle_policy_test();
+		let engine = &*spec.engine;
+		let genesis_header = spec.genesis_header();
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let last_hashes = Arc::new(vec![genesis_header.hash()]);
+		let mut b = OpenBlock::new(engine, Default::default(), false, db, &genesis_header, last_hashes, Address::zero(), (3141562.into(), 31415620.into()), vec![], false).unwrap();
+		let mut uncle = Header::new();
+		let uncle_author: Address = "ef2d6d194084c2de36e0dabfce45d046b37d1106".into();
+		uncle.set_author(uncle_author);
+		b.push_uncle(uncle).unwrap();
+
+		let b = b.close();
+		assert_eq!(b.state().balance(&Address::zero()).unwrap(), "49b9ca9a69434000".into());
+		assert_eq!(b.state().balance(&uncle_author).unwrap(), "1158e460913d0000".into());
+	}
+
+	#[test]
+	fn uncle_policy_limits() {
+		let machine = new_homestead_test_machine();
+		let ethparams = EthashParams {
+			uncle_policy: vec![
+				UnclePolicy {
+					transition: 100,
+					max_uncles: Some(4),
+					max_uncle_depth: None,
+					nephew_reward_divisor: None,
+					uncle_reward: None,
+				},
+				UnclePolicy {
+					transition: 200,
+					max_uncles: None,
+					max_uncle_depth: Some(2),
+					nephew_reward_divisor: None,
+					uncle_reward: None,
+				},
+			],
+			..get_default_ethash_params()
+		};
+		let ethash = Ethash::new(&::std::env::temp_dir(), ethparams, machine, None);
+
+		assert_eq!((ethash.maximum_uncle_count(99), ethash.maximum_uncle_age(99)), (2, 6));
+		assert_eq!((ethash.maximum_uncle_count(100), ethash.maximum_uncle_age(100)), (4, 6));
+		assert_eq!((ethash.maximum_uncle_count(200), ethash.maximum_uncle_age(200)), (2, 2));
+	}
+
+	#[test]
+	fn uncle_reward_curves() {
+		let reward = U256::from(800);
+		assert_eq!(UncleRewardCurve::Depth(8).reward(reward, 1), 700.into());
+		assert_eq!(UncleRewardCurve::Depth(8).reward(reward, 6), 200.into());
+		assert_eq!(UncleRewardCurve::Depth(4).reward(reward, 6), 0.into());
+		assert_eq!(UncleRewardCurve::Flat(32).reward(reward, 1), 25.into());
+		assert_eq!(UncleRewardCurve::Flat(32).reward(reward, 6), 25.into());
+	}
+
 	#[test]
 	fn has_valid_mcip3_era_block_rewards() {
 		let spec = new_mcip3_test();
@@ -636,6 +974,122 @@ mod tests {
 		assert_eq!(b.state().balance(&dev_contract).unwrap(), U256::from_str("c249fdd327780000").unwrap());
 	}
 
+	#[test]
+	fn reward_schedule_pays_and_traces_all_recipients() {
+		let spec = new_reward_schedule_test();
+		let engine = &*spec.engine;
+		let genesis_header = spec.genesis_header();
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let last_hashes = Arc::new(vec![genesis_header.hash()]);
+		let b = OpenBlock::new(engine, Default::default(), true, db, &genesis_header, last_hashes, Address::zero(), (3141562.into(), 31415620.into()), vec![], false).unwrap();
+		let b = b.close();
+
+		let percentage_recipient: Address = "00efdd5883ec628983e9063c7d969fe268bbf310".into();
+		let amount_recipient: Address = "00756cf8159095948496617f5fb17ed95059f536".into();
+		assert_eq!(b.state().balance(&Address::zero()).unwrap(), U256::from_str("26db992a3b180000").unwrap());
+		assert_eq!(b.state().balance(&percentage_recipient).unwrap(), U256::from_str("58d15e176280000").unwrap());
+		assert_eq!(b.state().balance(&amount_recipient).unwrap(), U256::from_str("b1a2bc2ec500000").unwrap());
+
+		let traces: Vec<FlatTrace> = match *b.traces() {
+			Tracing::Enabled(ref traces) => traces.iter().cloned().flat_map(|t| Into::<Vec<FlatTrace>>::into(t)).collect(),
+			Tracing::Disabled => panic!("tracing should be enabled"),
+		};
+		let rewards: Vec<_> = traces.into_iter().filter_map(|t| match t.action {
+			Action::Reward(reward) => Some((reward.author, reward.reward_type)),
+			_ => None,
+		}).collect();
+		assert_eq!(rewards, vec![
+			(Address::zero(), RewardType::Block),
+			(percentage_recipient, RewardType::External),
+			(amount_recipient, RewardType::External),
+		]);
+	}
+
+	#[test]
+	fn reward_schedule_pays_default_nephew_reward() {
+		let spec = new_reward_schedule_test();
+		let engine = &*spec.engine;
+		let genesis_header = spec.genesis_header();
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let last_hashes = Arc::new(vec![genesis_header.hash()]);
+		let mut b = OpenBlock::new(engine, Default::default(), false, db, &genesis_header, last_hashes, Address::zero(), (3141562.into(), 31415620.into()), vec![], false).unwrap();
+		let mut uncle = Header::new();
+		let uncle_author: Address = "ef2d6d194084c2de36e0dabfce45d046b37d1106".into();
+		uncle.set_author(uncle_author);
+		b.push_uncle(uncle).unwrap();
+
+		let b = b.close();
+		assert_eq!(b.state().balance(&Address::zero()).unwrap(), U256::from_str("2906b5b64d3fa000").unwrap());
+		assert_eq!(b.state().balance(&uncle_author).unwrap(), U256::from_str("3cb71f51fc558000").unwrap());
+	}
+
+	#[test]
+	fn reward_schedule_rejects_overpaying_recipients() {
+		let recipient: Address = 1.into();
+		let era = RewardEra {
+			transition: 0,
+			block_reward: Some(1000.into()),
+			miner_reward: None,
+			recipients: vec![(recipient, RewardShare::Percentage(50)), (recipient, RewardShare::Amount(500.into()))],
+			decay_era_rounds: u64::max_value(),
+			decay_percent: 0,
+			nephew_reward: true,
+		};
+		let params = |era: RewardEra| EthashParams {
+			block_reward: 1000.into(),
+			reward_schedule: vec![era],
+			..get_default_ethash_params()
+		};
+		assert_eq!(params(era.clone()).verify_reward_schedule(), Ok(()));
+
+		let overpaying = RewardEra { recipients: vec![(recipient, RewardShare::Percentage(50)), (recipient, RewardShare::Amount(501.into()))], ..era.clone() };
+		assert!(params(overpaying).verify_reward_schedule().is_err());
+
+		let engine_reward = RewardEra { block_reward: None, recipients: vec![(recipient, RewardShare::Amount(1001.into()))], ..era.clone() };
+		assert!(params(engine_reward).verify_reward_schedule().is_err());
+
+		let overflowing = RewardEra { recipients: vec![(recipient, RewardShare::Amount(U256::max_value())), (recipient, RewardShare::Amount(1.into()))], ..era.clone() };
+		assert!(params(overflowing).verify_reward_schedule().is_err());
+
+		// a fixed author reward is paid independently of the recipients
+		let fixed_author = RewardEra { miner_reward: Some(10.into()), recipients: vec![(recipient, RewardShare::Amount(2000.into()))], ..era };
+		assert_eq!(params(fixed_author).verify_reward_schedule(), Ok(()));
+	}
+
+	#[test]
+	fn decayed_reward_does_not_overflow() {
+		let reward = U256::max_value();
+		assert_eq!(super::decayed_reward(reward, 1, 50), reward / U256::from(100) * U256::from(50));
+		assert_eq!(super::decayed_reward(1000.into(), 2, 50), 250.into());
+	}
+
+	#[test]
+	fn reward_era_payouts_decay() {
+		let recipient: Address = 1.into();
+		let era = RewardEra {
+			transition: 100,
+			block_reward: None,
+			miner_reward: None,
+			recipients: vec![(recipient, RewardShare::Percentage(25))],
+			decay_era_rounds: 10,
+			decay_percent: 50,
+			nephew_reward: true,
+		};
+
+		assert_eq!(era.payouts(100, 1000.into()), (750.into(), vec![(recipient, 250.into())]));
+		assert_eq!(era.payouts(109, 1000.into()), (750.into(), vec![(recipient, 250.into())]));
+		assert_eq!(era.payouts(110, 1000.into()), (375.into(), vec![(recipient, 125.into())]));
+		assert_eq!(era.payouts(130, 1000.into()), (94.into(), vec![(recipient, 31.into())]));
+
+		let era = RewardEra {
+			miner_reward: Some(10.into()),
+			recipients: vec![(recipient, RewardShare::Amount(20.into()))],
+			decay_percent: 0,
+			..era
+		};
+		assert_eq!(era.payouts(1_000_000, 1000.into()), (10.into(), vec![(recipient, 20.into())]));
+	}
+
 	#[test]
 	fn has_valid_metadata() {
 		let engine = test_spec().engine;
@@ -931,6 +1385,82 @@ mod tests {
 		assert_eq!(U256::from(12543204905719u64), difficulty);
 	}
 
+	#[test]
+	fn difficulty_rule_eras() {
+		let machine = new_homestead_test_machine();
+		let ethparams = EthashParams {
+			homestead_transition: 0,
+			difficulty_bound_divisor: U256::from(512),
+			difficulty_rules: vec![
+				DifficultyRule::nekonium_hf01(7777),
+				DifficultyRule {
+					transition: 20000,
+					duration_limit: None,
+					bound_divisor: Some(U256::from(2048)),
+					increment_divisor: Some(15),
+					shift: 0,
+				},
+			],
+			..get_default_ethash_params()
+		};
+		let ethash = Ethash::new(&::std::env::temp_dir(), ethparams, machine, None);
+
+		let mut parent_header = Header::default();
+		parent_header.set_difficulty(U256::from(0x10000000));
+		parent_header.set_timestamp(1000);
+		let mut header = Header::default();
+
+		let mut check = |number: u64, delay: u64, expected: u64| {
+			parent_header.set_number(number - 1);
+			header.set_number(number);
+			header.set_timestamp(parent_header.timestamp() + delay);
+			assert_eq!(U256::from(expected), ethash.calculate_difficulty(&header, &parent_header), "block {}, delay {}", number, delay);
+		};
+
+		// base params
+		check(7000, 5, 268959744);
+		check(7000, 30, 267386880);
+		check(7776, 25, 267911168);
+		// Nekonium HF01
+		check(7777, 25, 268435456);
+		check(8000, 5, 268697600);
+		check(8000, 30, 268173312);
+		// following retune
+		check(20000, 5, 268566528);
+		check(20000, 30, 268304384);
+	}
+
+	#[test]
+	fn nekonium_hf01_defaults_to_block_7777() {
+		use super::super::{new_homestead_test, new_frontier_test};
+
+		let check = |spec: &Spec, number: u64, delay: u64, expected: u64| {
+			let mut parent_header = Header::default();
+			parent_header.set_number(number - 1);
+			parent_header.set_difficulty(U256::from(0x10000000));
+			parent_header.set_timestamp(1000);
+			let mut header = Header::default();
+			header.set_number(number);
+			header.set_timestamp(parent_header.timestamp() + delay);
+			spec.engine.populate_from_parent(&mut header, &parent_header);
+			assert_eq!(&U256::from(expected), header.difficulty(), "block {}, delay {}", number, delay);
+		};
+
+		// specs without `nekoniumTransition` or `difficultyRules` switch at 7777
+		let homestead = new_homestead_test();
+		check(&homestead, 7776, 5, 268566528);
+		check(&homestead, 7776, 25, 268304384);
+		check(&homestead, 7777, 5, 268697600);
+		check(&homestead, 7777, 25, 268435456);
+		check(&homestead, 7777, 40, 267911168);
+
+		// the shift and divisor never applied before Homestead
+		let frontier = new_frontier_test();
+		check(&frontier, 7776, 15, 268304384);
+		check(&frontier, 7777, 5, 268566528);
+		check(&frontier, 7777, 15, 268304384);
+	}
+
 	#[test]
 	fn test_extra_info() {
 		let machine = new_homestead_test_machine();
@@ -118,6 +118,12 @@ pub fn new_constantinople_test() -> Spec { load(None, include_bytes!("../../res/
 /// Create a new Musicoin-MCIP3-era spec.
 pub fn new_mcip3_test() -> Spec { load(None, include_bytes!("../../res/ethereum/mcip3_test.json")) }
 
+/// Create a new spec with a decaying multi-recipient block reward schedule.
+pub fn new_reward_schedule_test() -> Spec { load(None, include_bytes!("../../res/ethereum/reward_schedule_test.json")) }
+
+/// Create a new spec with a custom uncle policy.
+pub fn new_uncle_policy_test() -> Spec { load(None, include_bytes!("../../res/ethereum/uncle_policy_test.json")) }
+
 // For tests
 
 /// Create a new Foundation Frontier-era chain spec as though it never changes to Homestead.
@@ -69,6 +69,26 @@ pub struct Executed<T = FlatTrace, V = VMTrace> {
 	pub state_diff: Option<StateDiff>,
 }
 
+impl<T, V> Executed<T, V> {
+	/// Returns this receipt with its trace replaced by `trace`, together with the previous trace.
+	pub fn replace_trace<U>(self, trace: Vec<U>) -> (Executed<U, V>, Vec<T>) {
+		let executed = Executed {
+			exception: self.exception,
+			gas: self.gas,
+			gas_used: self.gas_used,
+			refunded: self.refunded,
+			cumulative_gas_used: self.cumulative_gas_used,
+			logs: self.logs,
+			contracts_created: self.contracts_created,
+			output: self.output,
+			trace: trace,
+			vm_trace: self.vm_trace,
+			state_diff: self.state_diff,
+		};
+		(executed, self.trace)
+	}
+}
+
 /// Result of executing the transaction.
 #[derive(PartialEq, Debug, Clone)]
 pub enum ExecutionError {
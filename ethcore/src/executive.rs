@@ -74,6 +74,15 @@ pub fn contract_address(address_scheme: CreateContractAddress, sender: &Address,
 			&mut buffer[20..].copy_from_slice(&code_hash[..]);
 			(From::from(keccak(&buffer[..])), Some(code_hash))
 		},
+		CreateContractAddress::FromSenderSaltAndCodeHash(salt) => {
+			let code_hash = keccak(code);
+			let mut buffer = [0u8; 1 + 20 + 32 + 32];
+			buffer[0] = 0xff;
+			&mut buffer[1..(1+20)].copy_from_slice(&sender[..]);
+			&mut buffer[(1+20)..(1+20+32)].copy_from_slice(&salt[..]);
+			&mut buffer[(1+20+32)..].copy_from_slice(&code_hash[..]);
+			(From::from(keccak(&buffer[..])), Some(code_hash))
+		},
 	}
 }
 
@@ -241,6 +250,9 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 		mut tracer: T,
 		mut vm_tracer: V
 	) -> Result<Executed<T::Output, V::Output>, ExecutionError> where T: Tracer, V: VMTracer {
+		// storage left by earlier transactions is the original storage of this one.
+		self.state.clear_original_storage();
+
 		let sender = t.sender();
 		let nonce = self.state.nonce(&sender)?;
 
@@ -494,7 +506,12 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 						trace_output,
 						traces
 					),
-					Ok(_) => tracer.trace_failed_call(trace_info, traces, vm::Error::Reverted.into()),
+					Ok(ref res) => tracer.trace_reverted_call(
+						trace_info,
+						gas - res.gas_left,
+						trace_output.map(|_| res.return_data.to_vec()),
+						traces
+					),
 					Err(ref e) => tracer.trace_failed_call(trace_info, traces, e.into()),
 				};
 
@@ -530,7 +547,7 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 	) -> vm::Result<FinalizationResult> where T: Tracer, V: VMTracer {
 
 		// EIP-684: If a contract creation is attempted, due to either a creation transaction or the
-		// CREATE (or future CREATE2) opcode, and the destination address already has either
+		// CREATE or CREATE2 opcode, and the destination address already has either
 		// nonzero nonce, or nonempty code, then the creation throws immediately, with exactly
 		// the same behavior as would arise if the first byte in the init code were an invalid
 		// opcode. This applies retroactively starting from genesis.
@@ -589,7 +606,12 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 				created,
 				subtracer.drain()
 			),
-			Ok(_) => tracer.trace_failed_create(trace_info, subtracer.drain(), vm::Error::Reverted.into()),
+			Ok(ref res) => tracer.trace_reverted_create(
+				trace_info,
+				gas - res.gas_left,
+				trace_output.map(|_| res.return_data.to_vec()),
+				subtracer.drain()
+			),
 			Err(ref e) => tracer.trace_failed_create(trace_info, subtracer.drain(), e.into())
 		};
 
@@ -609,8 +631,8 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 	) -> Result<Executed<T, V>, ExecutionError> {
 		let schedule = self.machine.schedule(self.info.number);
 
-		// refunds from SSTORE nonzero -> zero
-		let sstore_refunds = U256::from(schedule.sstore_refund_gas) * substate.sstore_clears_count;
+		// refunds from SSTORE, never negative at the transaction level
+		let sstore_refunds = U256::from(cmp::max(substate.sstore_clears_refund, 0) as u64);
 		// refunds from contract suicides
 		let suicide_refunds = U256::from(schedule.suicide_refund_gas) * U256::from(substate.suicides.len());
 		let refunds_bound = sstore_refunds + suicide_refunds;
@@ -706,6 +728,7 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 mod tests {
 	use std::sync::Arc;
 	use std::str::FromStr;
+	use std::collections::BTreeMap;
 	use rustc_hex::FromHex;
 	use ethkey::{Generator, Random};
 	use super::*;
@@ -718,8 +741,9 @@ mod tests {
 	use state::{Substate, CleanupMode};
 	use tests::helpers::*;
 	use trace::trace;
-	use trace::{FlatTrace, Tracer, NoopTracer, ExecutiveTracer};
+	use trace::{FlatTrace, Tracer, NoopTracer, ExecutiveTracer, CallTracer};
 	use trace::{VMTrace, VMOperation, VMExecutedOperation, MemoryDiff, StorageDiff, VMTracer, NoopVMTracer, ExecutiveVMTracer};
+	use trace::{StructLogger, StructLoggerOptions, StructLog, CallFrame, CallFrameType};
 	use transaction::{Action, Transaction};
 
 	fn make_frontier_machine(max_depth: usize) -> EthereumMachine {
@@ -741,6 +765,23 @@ mod tests {
 		assert_eq!(expected_address, contract_address(CreateContractAddress::FromSenderAndNonce, &address, &U256::from(88), &[]).0);
 	}
 
+	#[test]
+	fn test_contract_address_from_sender_salt_and_code_hash() {
+		// test vectors from EIP-1014
+		let zero = Address::zero();
+		let deadbeef = Address::from_str("deadbeef00000000000000000000000000000000").unwrap();
+
+		let (address, _) = contract_address(CreateContractAddress::FromSenderSaltAndCodeHash(H256::zero()), &zero, &U256::zero(), &[0x00]);
+		assert_eq!(address, Address::from_str("4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38").unwrap());
+
+		let (address, _) = contract_address(CreateContractAddress::FromSenderSaltAndCodeHash(H256::zero()), &deadbeef, &U256::zero(), &[0x00]);
+		assert_eq!(address, Address::from_str("b928f69bb1d91cd65274e3c79d8986362984fda3").unwrap());
+
+		let (address, code_hash) = contract_address(CreateContractAddress::FromSenderSaltAndCodeHash(H256::zero()), &zero, &U256::zero(), &[0xde, 0xad, 0xbe, 0xef]);
+		assert_eq!(address, Address::from_str("70f2b2914a2a4b783faefb75f459a580616fcb5e").unwrap());
+		assert_eq!(code_hash, Some(keccak(&[0xde, 0xad, 0xbe, 0xef])));
+	}
+
 	// TODO: replace params with transactions!
 	evm_test!{test_sender_balance: test_sender_balance_jit, test_sender_balance_int}
 	fn test_sender_balance(factory: Factory) {
@@ -1019,6 +1060,210 @@ mod tests {
 		assert_eq!(vm_tracer.drain().unwrap(), expected_vm_trace);
 	}
 
+	#[test]
+	fn test_struct_logger() {
+		// 60 2a - push 42
+		// 60 00 - push 0
+		// 55 - sstore
+		// 60 00 - push 0
+		// 54 - sload
+		// 60 00 - push 0
+		// 52 - mstore
+		// 00 - stop
+		let code = "602a60005560005460005200".from_hex().unwrap();
+
+		let sender = Address::from_str("cd1722f3947def4cf144679da39c4c32bdc35681").unwrap();
+		let address = contract_address(CreateContractAddress::FromSenderAndNonce, &sender, &U256::zero(), &[]).0;
+		let mut params = ActionParams::default();
+		params.address = address.clone();
+		params.code_address = address.clone();
+		params.sender = sender.clone();
+		params.origin = sender.clone();
+		params.gas = U256::from(100_000);
+		params.code = Some(Arc::new(code));
+		params.call_type = CallType::Call;
+		let mut state = get_temp_state();
+		let info = EnvInfo::default();
+		let machine = make_frontier_machine(5);
+		let mut substate = Substate::new();
+		let mut tracer = NoopTracer;
+		let mut vm_tracer = StructLogger::toplevel(StructLoggerOptions::default());
+
+		let FinalizationResult { gas_left, .. } = {
+			let mut ex = Executive::new(&mut state, &info, &machine);
+			let output = BytesRef::Fixed(&mut[0u8;0]);
+			ex.call(params, &mut substate, output, &mut tracer, &mut vm_tracer).unwrap()
+		};
+
+		assert_eq!(gas_left, U256::from(79_932));
+
+		let logs = vm_tracer.drain().unwrap();
+		assert_eq!(logs.len(), 8);
+		assert_eq!(logs.iter().map(|l| l.instruction).collect::<Vec<_>>(), vec![0x60, 0x60, 0x55, 0x60, 0x54, 0x60, 0x52, 0x00]);
+		assert!(logs.iter().all(|l| l.depth == 1));
+
+		let stored: BTreeMap<H256, H256> = vec![(H256::from(0), H256::from(42))].into_iter().collect();
+		assert_eq!(logs[2], StructLog {
+			pc: 4,
+			instruction: 0x55,
+			gas: 99_994.into(),
+			gas_cost: 20_000.into(),
+			depth: 1,
+			stack: Some(vec_into![42, 0]),
+			memory: Some(vec![]),
+			storage: Some(stored.clone()),
+		});
+
+		let mut memory = vec![0u8; 32];
+		memory[31] = 42;
+		assert_eq!(logs[7], StructLog {
+			pc: 11,
+			instruction: 0x00,
+			gas: 79_932.into(),
+			gas_cost: 0.into(),
+			depth: 1,
+			stack: Some(vec![]),
+			memory: Some(memory),
+			storage: Some(stored),
+		});
+	}
+
+	#[test]
+	fn test_struct_logger_disabled_capture() {
+		let code = "602a60005560005460005200".from_hex().unwrap();
+
+		let mut params = ActionParams::default();
+		params.gas = U256::from(100_000);
+		params.code = Some(Arc::new(code));
+		let mut state = get_temp_state();
+		let info = EnvInfo::default();
+		let machine = make_frontier_machine(5);
+		let mut substate = Substate::new();
+		let mut tracer = NoopTracer;
+		let mut vm_tracer = StructLogger::toplevel(StructLoggerOptions {
+			disable_stack: true,
+			disable_memory: true,
+			disable_storage: true,
+		});
+
+		{
+			let mut ex = Executive::new(&mut state, &info, &machine);
+			let output = BytesRef::Fixed(&mut[0u8;0]);
+			ex.call(params, &mut substate, output, &mut tracer, &mut vm_tracer).unwrap();
+		}
+
+		let logs = vm_tracer.drain().unwrap();
+		assert_eq!(logs.len(), 8);
+		assert!(logs.iter().all(|l| l.stack.is_none() && l.memory.is_none() && l.storage.is_none()));
+	}
+
+	#[test]
+	fn test_call_tree() {
+		// same code as in `test_call_to_create`, creates a contract from within a call.
+		let code = "7c601080600c6000396000f3006000355415600957005b60203560003555600052601d60036017f0600055".from_hex().unwrap();
+
+		let sender = Address::from_str("cd1722f3947def4cf144679da39c4c32bdc35681").unwrap();
+		let address = contract_address(CreateContractAddress::FromSenderAndNonce, &sender, &U256::zero(), &[]).0;
+		let mut params = ActionParams::default();
+		params.address = address.clone();
+		params.code_address = address.clone();
+		params.sender = sender.clone();
+		params.origin = sender.clone();
+		params.gas = U256::from(100_000);
+		params.code = Some(Arc::new(code));
+		params.value = ActionValue::Transfer(U256::from(100));
+		params.call_type = CallType::Call;
+		let mut state = get_temp_state();
+		state.add_balance(&sender, &U256::from(100), CleanupMode::NoEmpty).unwrap();
+		let info = EnvInfo::default();
+		let machine = make_frontier_machine(5);
+		let mut substate = Substate::new();
+		let mut tracer = CallTracer::default();
+		let mut vm_tracer = NoopVMTracer;
+
+		let FinalizationResult { gas_left, .. } = {
+			let mut ex = Executive::new(&mut state, &info, &machine);
+			let output = BytesRef::Fixed(&mut[0u8;0]);
+			ex.call(params, &mut substate, output, &mut tracer, &mut vm_tracer).unwrap()
+		};
+
+		assert_eq!(gas_left, U256::from(44_752));
+
+		let expected_tree = CallFrame {
+			frame_type: CallFrameType::Call(CallType::Call),
+			from: "cd1722f3947def4cf144679da39c4c32bdc35681".into(),
+			to: Some("b010143a42d5980c7e5ef0e4a4416dc098a4fed3".into()),
+			value: 100.into(),
+			gas: 100_000.into(),
+			gas_used: 55_248.into(),
+			input: vec![],
+			output: vec![],
+			error: None,
+			calls: vec![CallFrame {
+				frame_type: CallFrameType::Create,
+				from: "b010143a42d5980c7e5ef0e4a4416dc098a4fed3".into(),
+				to: Some("c6d80f262ae5e0f164e5fde365044d7ada2bfa34".into()),
+				value: 23.into(),
+				gas: 67_979.into(),
+				gas_used: 3_224.into(),
+				input: vec![96, 16, 128, 96, 12, 96, 0, 57, 96, 0, 243, 0, 96, 0, 53, 84, 21, 96, 9, 87, 0, 91, 96, 32, 53, 96, 0, 53, 85],
+				output: vec![96, 0, 53, 84, 21, 96, 9, 87, 0, 91, 96, 32, 53, 96, 0, 53],
+				error: None,
+				calls: vec![],
+			}],
+		};
+
+		assert_eq!(tracer.drain(), vec![expected_tree]);
+	}
+
+	#[test]
+	fn test_call_tree_reverted_create() {
+		// same code as in `test_trace_reverted_create`, the created contract reverts with one byte.
+		let code = "6460016000fd6000526005601b6017f0600055".from_hex().unwrap();
+
+		let sender = Address::from_str("cd1722f3947def4cf144679da39c4c32bdc35681").unwrap();
+		let address = contract_address(CreateContractAddress::FromSenderAndNonce, &sender, &U256::zero(), &[]).0;
+		let mut params = ActionParams::default();
+		params.address = address.clone();
+		params.code_address = address.clone();
+		params.sender = sender.clone();
+		params.origin = sender.clone();
+		params.gas = U256::from(100_000);
+		params.code = Some(Arc::new(code));
+		params.value = ActionValue::Transfer(U256::from(100));
+		params.call_type = CallType::Call;
+		let mut state = get_temp_state();
+		state.add_balance(&sender, &U256::from(100), CleanupMode::NoEmpty).unwrap();
+		let info = EnvInfo::default();
+		let machine = ::ethereum::new_byzantium_test_machine();
+		let mut substate = Substate::new();
+		let mut tracer = CallTracer::default();
+		let mut vm_tracer = NoopVMTracer;
+
+		{
+			let mut ex = Executive::new(&mut state, &info, &machine);
+			let output = BytesRef::Fixed(&mut[0u8;0]);
+			ex.call(params, &mut substate, output, &mut tracer, &mut vm_tracer).unwrap();
+		}
+
+		let frames = tracer.drain();
+		assert_eq!(frames.len(), 1);
+		assert_eq!(frames[0].gas_used, U256::from(37_033));
+		assert_eq!(frames[0].calls, vec![CallFrame {
+			frame_type: CallFrameType::Create,
+			from: "b010143a42d5980c7e5ef0e4a4416dc098a4fed3".into(),
+			to: None,
+			value: 23.into(),
+			gas: 66_917.into(),
+			// PUSH1, PUSH1, REVERT and one word of memory.
+			gas_used: 9.into(),
+			input: vec![0x60, 0x01, 0x60, 0x00, 0xfd],
+			output: vec![0],
+			error: Some(vm::Error::Reverted.into()),
+			calls: vec![],
+		}]);
+	}
+
 	#[test]
 	fn test_trace_reverted_create() {
 		// code:
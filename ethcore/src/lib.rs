@@ -93,10 +93,12 @@ extern crate triehash;
 extern crate ansi_term;
 extern crate unexpected;
 extern crate kvdb;
+extern crate kvdb_logdb;
 extern crate kvdb_rocksdb;
 extern crate kvdb_memorydb;
 extern crate util_error;
 extern crate snappy;
+extern crate zstd;
 extern crate migration;
 
 extern crate ethabi;
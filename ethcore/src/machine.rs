@@ -432,6 +432,7 @@ impl ::parity_machine::WithBalances for EthereumMachine {
 		live: &mut Self::LiveBlock,
 		direct: &[(Address, U256)],
 		indirect: &[(Address, U256)],
+		external: &[(Address, U256)],
 	) -> Result<(), Self::Error> {
 		if let Tracing::Enabled(ref mut traces) = *live.traces_mut() {
 			let mut tracer = ExecutiveTracer::default();
@@ -444,6 +445,10 @@ impl ::parity_machine::WithBalances for EthereumMachine {
 				tracer.trace_reward(address, amount, RewardType::Uncle);
 			}
 
+			for &(address, amount) in external {
+				tracer.trace_reward(address, amount, RewardType::External);
+			}
+
 			traces.push(tracer.drain().into());
 		}
 
@@ -20,12 +20,12 @@ use std::sync::Arc;
 use std::path::Path;
 use ethereum_types::H256;
 use kvdb::KeyValueDB;
-use kvdb_rocksdb::{Database, DatabaseConfig};
+use kvdb_rocksdb::DatabaseConfig;
 use bytes::Bytes;
 use io::*;
 use spec::Spec;
 use error::*;
-use client::{Client, ClientConfig, ChainNotify};
+use client::{Client, ClientConfig, ChainNotify, DatabaseBackend};
 use miner::Miner;
 
 use snapshot::{ManifestData, RestorationStatus};
@@ -59,7 +59,7 @@ pub struct ClientService {
 	io_service: Arc<IoService<ClientIoMessage>>,
 	client: Arc<Client>,
 	snapshot: Arc<SnapshotService>,
-	database: Arc<Database>,
+	database: Arc<KeyValueDB>,
 	_stop_guard: StopGuard,
 }
 
@@ -84,11 +84,17 @@ impl ClientService {
 		db_config.compaction = config.db_compaction.compaction_profile(client_path);
 		db_config.wal = config.db_wal;
 
-		let db = Arc::new(Database::open(
-			&db_config,
-			&client_path.to_str().expect("DB path could not be converted to string.")
-		).map_err(::client::Error::Database)?);
+		let db_backend = config.db_backend;
+		match DatabaseBackend::detect(client_path) {
+			Some(backend) if backend != db_backend => {
+				let msg = format!("Database at {} uses {} backend, but {} was requested. Convert it with `parity db convert --to={}`.",
+					client_path.display(), backend, db_backend, db_backend);
+				return Err(::client::Error::Database(msg.into()).into());
+			},
+			_ => {},
+		}
 
+		let db = db_backend.open(&db_config, client_path).map_err(::client::Error::Database)?;
 
 		let pruning = config.pruning;
 		let client = Client::new(config, &spec, db.clone(), miner, io_service.channel())?;
@@ -97,6 +103,7 @@ impl ClientService {
 			engine: spec.engine.clone(),
 			genesis_block: spec.genesis_block(),
 			db_config: db_config.clone(),
+			db_backend: db_backend,
 			pruning: pruning,
 			channel: io_service.channel(),
 			snapshot_root: snapshot_path.into(),
@@ -26,6 +26,7 @@ use hashdb::HashDB;
 use bytes::Bytes;
 use trie::{TrieDB, Trie};
 use rlp::{RlpStream, UntrustedRlp};
+use parking_lot::Mutex;
 
 use std::collections::HashSet;
 
@@ -66,7 +67,7 @@ impl CodeState {
 // walk the account's storage trie, returning a vector of RLP items containing the
 // account address hash, account properties and the storage. Each item contains at most `max_storage_items`
 // storage records split according to snapshot format definition.
-pub fn to_fat_rlps(account_hash: &H256, acc: &BasicAccount, acct_db: &AccountDB, used_code: &mut HashSet<H256>, first_chunk_size: usize, max_chunk_size: usize) -> Result<Vec<Bytes>, Error> {
+pub fn to_fat_rlps(account_hash: &H256, acc: &BasicAccount, acct_db: &AccountDB, used_code: &Mutex<HashSet<H256>>, first_chunk_size: usize, max_chunk_size: usize) -> Result<Vec<Bytes>, Error> {
 	let db = TrieDB::new(acct_db, &acc.storage_root)?;
 	let mut chunks = Vec::new();
 	let mut db_iter = db.iter()?;
@@ -83,17 +84,21 @@ pub fn to_fat_rlps(account_hash: &H256, acc: &BasicAccount, acct_db: &AccountDB,
 		// [has_code, code_hash].
 		if acc.code_hash == KECCAK_EMPTY {
 			account_stream.append(&CodeState::Empty.raw()).append_empty_data();
-		} else if used_code.contains(&acc.code_hash) {
-			account_stream.append(&CodeState::Hash.raw()).append(&acc.code_hash);
 		} else {
-			match acct_db.get(&acc.code_hash) {
-				Some(c) => {
-					used_code.insert(acc.code_hash.clone());
-					account_stream.append(&CodeState::Inline.raw()).append(&&*c);
-				}
-				None => {
-					warn!("code lookup failed during snapshot");
-					account_stream.append(&false).append_empty_data();
+			// the set may be shared by parallel chunkers, keep it locked until the code is marked as used.
+			let mut used_code = used_code.lock();
+			if used_code.contains(&acc.code_hash) {
+				account_stream.append(&CodeState::Hash.raw()).append(&acc.code_hash);
+			} else {
+				match acct_db.get(&acc.code_hash) {
+					Some(c) => {
+						used_code.insert(acc.code_hash.clone());
+						account_stream.append(&CodeState::Inline.raw()).append(&&*c);
+					}
+					None => {
+						warn!("code lookup failed during snapshot");
+						account_stream.append(&false).append_empty_data();
+					}
 				}
 			}
 		}
@@ -218,6 +223,7 @@ mod tests {
 	use hashdb::HashDB;
 	use kvdb::DBValue;
 	use rlp::UntrustedRlp;
+	use parking_lot::Mutex;
 
 	use std::collections::HashSet;
 
@@ -238,7 +244,7 @@ mod tests {
 		let thin_rlp = ::rlp::encode(&account);
 		assert_eq!(::rlp::decode::<BasicAccount>(&thin_rlp), account);
 
-		let fat_rlps = to_fat_rlps(&keccak(&addr), &account, &AccountDB::new(db.as_hashdb(), &addr), &mut Default::default(), usize::max_value(), usize::max_value()).unwrap();
+		let fat_rlps = to_fat_rlps(&keccak(&addr), &account, &AccountDB::new(db.as_hashdb(), &addr), &Default::default(), usize::max_value(), usize::max_value()).unwrap();
 		let fat_rlp = UntrustedRlp::new(&fat_rlps[0]).at(1).unwrap();
 		assert_eq!(from_fat_rlp(&mut AccountDBMut::new(db.as_hashdb_mut(), &addr), fat_rlp, H256::zero()).unwrap().0, account);
 	}
@@ -263,7 +269,7 @@ mod tests {
 		let thin_rlp = ::rlp::encode(&account);
 		assert_eq!(::rlp::decode::<BasicAccount>(&thin_rlp), account);
 
-		let fat_rlp = to_fat_rlps(&keccak(&addr), &account, &AccountDB::new(db.as_hashdb(), &addr), &mut Default::default(), usize::max_value(), usize::max_value()).unwrap();
+		let fat_rlp = to_fat_rlps(&keccak(&addr), &account, &AccountDB::new(db.as_hashdb(), &addr), &Default::default(), usize::max_value(), usize::max_value()).unwrap();
 		let fat_rlp = UntrustedRlp::new(&fat_rlp[0]).at(1).unwrap();
 		assert_eq!(from_fat_rlp(&mut AccountDBMut::new(db.as_hashdb_mut(), &addr), fat_rlp, H256::zero()).unwrap().0, account);
 	}
@@ -288,7 +294,7 @@ mod tests {
 		let thin_rlp = ::rlp::encode(&account);
 		assert_eq!(::rlp::decode::<BasicAccount>(&thin_rlp), account);
 
-		let fat_rlps = to_fat_rlps(&keccak(addr), &account, &AccountDB::new(db.as_hashdb(), &addr), &mut Default::default(), 500, 1000).unwrap();
+		let fat_rlps = to_fat_rlps(&keccak(addr), &account, &AccountDB::new(db.as_hashdb(), &addr), &Default::default(), 500, 1000).unwrap();
 		let mut root = KECCAK_NULL_RLP;
 		let mut restored_account = None;
 		for rlp in fat_rlps {
@@ -330,11 +336,11 @@ mod tests {
 			code_hash: code_hash,
 		};
 
-		let mut used_code = HashSet::new();
+		let used_code = Mutex::new(HashSet::new());
 
-		let fat_rlp1 = to_fat_rlps(&keccak(&addr1), &account1, &AccountDB::new(db.as_hashdb(), &addr1), &mut used_code, usize::max_value(), usize::max_value()).unwrap();
-		let fat_rlp2 = to_fat_rlps(&keccak(&addr2), &account2, &AccountDB::new(db.as_hashdb(), &addr2), &mut used_code, usize::max_value(), usize::max_value()).unwrap();
-		assert_eq!(used_code.len(), 1);
+		let fat_rlp1 = to_fat_rlps(&keccak(&addr1), &account1, &AccountDB::new(db.as_hashdb(), &addr1), &used_code, usize::max_value(), usize::max_value()).unwrap();
+		let fat_rlp2 = to_fat_rlps(&keccak(&addr2), &account2, &AccountDB::new(db.as_hashdb(), &addr2), &used_code, usize::max_value(), usize::max_value()).unwrap();
+		assert_eq!(used_code.lock().len(), 1);
 
 		let fat_rlp1 = UntrustedRlp::new(&fat_rlp1[0]).at(1).unwrap();
 		let fat_rlp2 = UntrustedRlp::new(&fat_rlp2[0]).at(1).unwrap();
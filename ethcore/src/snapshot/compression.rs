@@ -0,0 +1,104 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Chunk compression.
+
+use std::io::{self, Read};
+
+use bytes::Bytes;
+use snappy;
+use zstd;
+
+use super::{ChunkCompression, Error};
+
+/// Zstandard compression level of snapshot chunks.
+const ZSTD_LEVEL: i32 = 9;
+
+/// Compresses chunks, reusing its buffer between them.
+pub struct Compressor {
+	compression: ChunkCompression,
+	buffer: Bytes,
+}
+
+impl Compressor {
+	/// Create a new compressor for given compression.
+	pub fn new(compression: ChunkCompression) -> Self {
+		Compressor {
+			compression: compression,
+			buffer: Vec::new(),
+		}
+	}
+
+	/// Compress the chunk. The returned slice is valid until the next call.
+	pub fn compress(&mut self, raw: &[u8]) -> io::Result<&[u8]> {
+		match self.compression {
+			ChunkCompression::Snappy => {
+				let len = snappy::compress_into(raw, &mut self.buffer);
+				Ok(&self.buffer[..len])
+			},
+			ChunkCompression::Zstd => {
+				self.buffer = zstd::stream::encode_all(raw, ZSTD_LEVEL)?;
+				Ok(&self.buffer)
+			},
+		}
+	}
+}
+
+/// Decompress the chunk into `buffer`, returning the decompressed length.
+/// Fails with `ChunkTooLarge` if the decompressed chunk exceeds `max_len`.
+pub fn decompress(compression: ChunkCompression, chunk: &[u8], buffer: &mut Bytes, max_len: usize) -> Result<usize, ::error::Error> {
+	match compression {
+		ChunkCompression::Snappy => {
+			let expected_len = snappy::decompressed_len(chunk)?;
+			if expected_len > max_len {
+				trace!(target: "snapshot", "Discarding large chunk: {} vs {}", expected_len, max_len);
+				return Err(Error::ChunkTooLarge.into());
+			}
+			Ok(snappy::decompress_into(chunk, buffer)?)
+		},
+		ChunkCompression::Zstd => {
+			// zstd frames don't have to declare their size, so the output is capped while reading.
+			buffer.clear();
+			let decoder = zstd::stream::Decoder::new(chunk).map_err(Error::Io)?;
+			decoder.take(max_len as u64 + 1).read_to_end(buffer).map_err(Error::Io)?;
+			if buffer.len() > max_len {
+				trace!(target: "snapshot", "Discarding large chunk: over {}", max_len);
+				return Err(Error::ChunkTooLarge.into());
+			}
+			Ok(buffer.len())
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use snapshot::ChunkCompression;
+	use super::{Compressor, decompress};
+
+	#[test]
+	fn roundtrip_all_compressions() {
+		let raw = (0..10_000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+
+		for compression in ChunkCompression::supported() {
+			let compressed = Compressor::new(compression).compress(&raw).unwrap().to_vec();
+			let mut buffer = Vec::new();
+
+			let len = decompress(compression, &compressed, &mut buffer, raw.len()).unwrap();
+			assert_eq!(&buffer[..len], &raw[..]);
+			assert!(decompress(compression, &compressed, &mut buffer, raw.len() - 1).is_err());
+		}
+	}
+}
@@ -65,6 +65,8 @@ pub enum Error {
 	BadEpochProof(u64),
 	/// Wrong chunk format.
 	WrongChunkFormat(String),
+	/// Packed snapshot manifest doesn't match its checksum.
+	ManifestChecksumMismatch(H256, H256),
 }
 
 impl fmt::Display for Error {
@@ -91,6 +93,8 @@ impl fmt::Display for Error {
 			Error::SnapshotsUnsupported => write!(f, "Snapshots unsupported by consensus engine."),
 			Error::BadEpochProof(i) => write!(f, "Bad epoch proof for transition to epoch {}", i),
 			Error::WrongChunkFormat(ref msg) => write!(f, "Wrong chunk format: {}", msg),
+			Error::ManifestChecksumMismatch(ref expected, ref found) =>
+				write!(f, "Snapshot manifest is corrupted. Expected checksum {:?}, got {:?}", expected, found),
 		}
 	}
 }
@@ -27,11 +27,16 @@ use std::path::{Path, PathBuf};
 
 use bytes::Bytes;
 use ethereum_types::H256;
+use hash::keccak;
 use rlp::{RlpStream, UntrustedRlp};
 
-use super::ManifestData;
+use super::{ManifestData, ChunkCompression};
 
-const SNAPSHOT_VERSION: u64 = 2;
+// Version of packed snapshots with snappy-compressed chunks.
+const SNAPPY_SNAPSHOT_VERSION: u64 = 2;
+
+// Packed snapshots since this version also record the chunk compression and the manifest version.
+const SNAPSHOT_VERSION: u64 = 3;
 
 /// Something which can write snapshots.
 /// Writing the same chunk multiple times will lead to implementation-defined
@@ -62,11 +67,18 @@ struct ChunkInfo(H256, u64, u64);
 /// The manifest contains all the same information as a standard `ManifestData`,
 /// but also maps chunk hashes to their lengths and offsets in the file
 /// for easy reading.
+///
+/// Snapshots with snappy-compressed chunks are written as version 2, so that
+/// older clients can still read them. Other compressions require version 3.
+///
+/// Snapshots written for a known chain append the genesis hash, the chain spec hash
+/// and a checksum of all preceding manifest items. Older clients ignore these trailing items.
 pub struct PackedWriter {
 	file: File,
 	state_hashes: Vec<ChunkInfo>,
 	block_hashes: Vec<ChunkInfo>,
 	cur_len: u64,
+	chain: Option<(H256, H256)>, // genesis hash, spec hash
 }
 
 impl PackedWriter {
@@ -77,8 +89,43 @@ impl PackedWriter {
 			state_hashes: Vec::new(),
 			block_hashes: Vec::new(),
 			cur_len: 0,
+			chain: None,
 		})
 	}
+
+	/// Create a new "PackedWriter" for a snapshot of the chain with given genesis and spec hash.
+	/// The manifest will carry both hashes and a checksum.
+	pub fn with_chain(path: &Path, genesis_hash: H256, spec_hash: H256) -> io::Result<Self> {
+		let mut writer = PackedWriter::new(path)?;
+		writer.chain = Some((genesis_hash, spec_hash));
+		Ok(writer)
+	}
+}
+
+// checksum of the given raw manifest items.
+fn manifest_checksum(items: &[&[u8]]) -> H256 {
+	let mut stream = RlpStream::new_list(items.len());
+	for item in items {
+		stream.append_raw(item, 1);
+	}
+	keccak(stream.out())
+}
+
+// appends the genesis hash, the spec hash and the checksum to the packed manifest.
+fn append_checksum(manifest_rlp: &[u8], genesis_hash: &H256, spec_hash: &H256) -> Bytes {
+	let genesis_rlp = ::rlp::encode(genesis_hash);
+	let spec_rlp = ::rlp::encode(spec_hash);
+	let mut items = UntrustedRlp::new(manifest_rlp).iter().map(|item| item.as_raw()).collect::<Vec<_>>();
+	items.push(&genesis_rlp);
+	items.push(&spec_rlp);
+	let checksum_rlp = ::rlp::encode(&manifest_checksum(&items));
+	items.push(&checksum_rlp);
+
+	let mut stream = RlpStream::new_list(items.len());
+	for item in items {
+		stream.append_raw(item, 1);
+	}
+	stream.out()
 }
 
 impl SnapshotWriter for PackedWriter {
@@ -105,16 +152,26 @@ impl SnapshotWriter for PackedWriter {
 	fn finish(mut self, manifest: ManifestData) -> io::Result<()> {
 		// we ignore the hashes fields of the manifest under the assumption that
 		// they are consistent with ours.
-		let mut stream = RlpStream::new_list(6);
+		let snappy = manifest.compression == ChunkCompression::Snappy;
+		let mut stream = RlpStream::new_list(if snappy { 6 } else { 8 });
 		stream
-			.append(&SNAPSHOT_VERSION)
+			.append(&if snappy { SNAPPY_SNAPSHOT_VERSION } else { SNAPSHOT_VERSION })
 			.append_list(&self.state_hashes)
 			.append_list(&self.block_hashes)
 			.append(&manifest.state_root)
 			.append(&manifest.block_number)
 			.append(&manifest.block_hash);
 
-		let manifest_rlp = stream.out();
+		if !snappy {
+			stream
+				.append(&manifest.compression.id())
+				.append(&manifest.version);
+		}
+
+		let manifest_rlp = match self.chain {
+			Some((ref genesis_hash, ref spec_hash)) => append_checksum(&stream.out(), genesis_hash, spec_hash),
+			None => stream.out(),
+		};
 
 		self.file.write_all(&manifest_rlp)?;
 		let off = self.cur_len;
@@ -200,6 +257,7 @@ pub struct PackedReader {
 	state_hashes: HashMap<H256, (u64, u64)>, // len, offset
 	block_hashes: HashMap<H256, (u64, u64)>, // len, offset
 	manifest: ManifestData,
+	chain: Option<(H256, H256)>, // genesis hash, spec hash
 }
 
 impl PackedReader {
@@ -253,6 +311,28 @@ impl PackedReader {
 		let state: Vec<ChunkInfo> = rlp.list_at(0 + start)?;
 		let blocks: Vec<ChunkInfo> = rlp.list_at(1 + start)?;
 
+		// items preceding the genesis hash, spec hash and checksum.
+		let num_items = if version > SNAPPY_SNAPSHOT_VERSION { 8 } else { 5 + start };
+		let chain = if rlp.item_count()? == num_items + 3 {
+			let items = rlp.iter().map(|item| item.as_raw()).collect::<Vec<_>>();
+			let expected: H256 = rlp.val_at(num_items + 2)?;
+			let found = manifest_checksum(&items[..num_items + 2]);
+			if expected != found {
+				return Err(::snapshot::error::Error::ManifestChecksumMismatch(expected, found));
+			}
+			Some((rlp.val_at(num_items)?, rlp.val_at(num_items + 1)?))
+		} else {
+			None
+		};
+
+		let (compression, version) = if version > SNAPPY_SNAPSHOT_VERSION {
+			let compression = ChunkCompression::from_id(rlp.val_at(5 + start)?)
+				.ok_or(::snapshot::error::Error::WrongChunkFormat("Unknown chunk compression".into()))?;
+			(compression, rlp.val_at(6 + start)?)
+		} else {
+			(ChunkCompression::Snappy, version)
+		};
+
 		let manifest = ManifestData {
 			version: version,
 			state_hashes: state.iter().map(|c| c.0).collect(),
@@ -260,15 +340,29 @@ impl PackedReader {
 			state_root: rlp.val_at(2 + start)?,
 			block_number: rlp.val_at(3 + start)?,
 			block_hash: rlp.val_at(4 + start)?,
+			compression: compression,
 		};
 
 		Ok(Some(PackedReader {
 			file: file,
 			state_hashes: state.into_iter().map(|c| (c.0, (c.1, c.2))).collect(),
 			block_hashes: blocks.into_iter().map(|c| (c.0, (c.1, c.2))).collect(),
-			manifest: manifest
+			manifest: manifest,
+			chain: chain,
 		}))
 	}
+
+	/// Genesis hash of the chain the snapshot was taken of.
+	/// `None` for snapshots written without a checksum.
+	pub fn genesis_hash(&self) -> Option<H256> {
+		self.chain.map(|(genesis_hash, _)| genesis_hash)
+	}
+
+	/// Hash of the chain spec the snapshot was taken with, see `Spec::hash`.
+	/// `None` for snapshots written without a checksum.
+	pub fn spec_hash(&self) -> Option<H256> {
+		self.chain.map(|(_, spec_hash)| spec_hash)
+	}
 }
 
 impl SnapshotReader for PackedReader {
@@ -334,52 +428,100 @@ impl SnapshotReader for LooseReader {
 
 #[cfg(test)]
 mod tests {
+	use std::fs::File;
+	use std::io::{Read, Write};
 	use tempdir::TempDir;
 	use hash::keccak;
 
-	use snapshot::ManifestData;
-	use super::{SnapshotWriter, SnapshotReader, PackedWriter, PackedReader, LooseWriter, LooseReader, SNAPSHOT_VERSION};
+	use snapshot::{ManifestData, ChunkCompression, Error};
+	use super::{SnapshotWriter, SnapshotReader, PackedWriter, PackedReader, LooseWriter, LooseReader, SNAPPY_SNAPSHOT_VERSION};
 
 	const STATE_CHUNKS: &'static [&'static [u8]] = &[b"dog", b"cat", b"hello world", b"hi", b"notarealchunk"];
 	const BLOCK_CHUNKS: &'static [&'static [u8]] = &[b"hello!", b"goodbye!", b"abcdefg", b"hijklmnop", b"qrstuvwxy", b"and", b"z"];
 
 	#[test]
 	fn packed_write_and_read() {
-		let tempdir = TempDir::new("").unwrap();
-		let path = tempdir.path().join("packed");
-		let mut writer = PackedWriter::new(&path).unwrap();
-
-		let mut state_hashes = Vec::new();
-		let mut block_hashes = Vec::new();
-
-		for chunk in STATE_CHUNKS {
-			let hash = keccak(&chunk);
-			state_hashes.push(hash.clone());
-			writer.write_state_chunk(hash, chunk).unwrap();
-		}
-
-		for chunk in BLOCK_CHUNKS {
-			let hash = keccak(&chunk);
-			block_hashes.push(hash.clone());
-			writer.write_block_chunk(keccak(&chunk), chunk).unwrap();
+		for compression in ChunkCompression::supported() {
+			let tempdir = TempDir::new("").unwrap();
+			let path = tempdir.path().join("packed");
+			let mut writer = PackedWriter::new(&path).unwrap();
+
+			let mut state_hashes = Vec::new();
+			let mut block_hashes = Vec::new();
+
+			for chunk in STATE_CHUNKS {
+				let hash = keccak(&chunk);
+				state_hashes.push(hash.clone());
+				writer.write_state_chunk(hash, chunk).unwrap();
+			}
+
+			for chunk in BLOCK_CHUNKS {
+				let hash = keccak(&chunk);
+				block_hashes.push(hash.clone());
+				writer.write_block_chunk(keccak(&chunk), chunk).unwrap();
+			}
+
+			let manifest = ManifestData {
+				version: SNAPPY_SNAPSHOT_VERSION,
+				state_hashes: state_hashes,
+				block_hashes: block_hashes,
+				state_root: keccak(b"notarealroot"),
+				block_number: 12345678987654321,
+				block_hash: keccak(b"notarealblock"),
+				compression: compression,
+			};
+
+			writer.finish(manifest.clone()).unwrap();
+
+			let reader = PackedReader::new(&path).unwrap().unwrap();
+			assert_eq!(reader.manifest(), &manifest);
+
+			for hash in manifest.state_hashes.iter().chain(&manifest.block_hashes) {
+				reader.chunk(hash.clone()).unwrap();
+			}
 		}
+	}
 
-		let manifest = ManifestData {
-			version: SNAPSHOT_VERSION,
-			state_hashes: state_hashes,
-			block_hashes: block_hashes,
-			state_root: keccak(b"notarealroot"),
-			block_number: 12345678987654321,
-			block_hash: keccak(b"notarealblock"),
-		};
-
-		writer.finish(manifest.clone()).unwrap();
-
-		let reader = PackedReader::new(&path).unwrap().unwrap();
-		assert_eq!(reader.manifest(), &manifest);
-
-		for hash in manifest.state_hashes.iter().chain(&manifest.block_hashes) {
-			reader.chunk(hash.clone()).unwrap();
+	#[test]
+	fn packed_manifest_checksum() {
+		for compression in ChunkCompression::supported() {
+			let tempdir = TempDir::new("").unwrap();
+			let path = tempdir.path().join("packed");
+			let genesis_hash = keccak(b"genesis");
+			let spec_hash = keccak(b"spec");
+			let mut writer = PackedWriter::with_chain(&path, genesis_hash, spec_hash).unwrap();
+
+			let chunk = STATE_CHUNKS[0];
+			writer.write_state_chunk(keccak(chunk), chunk).unwrap();
+
+			let manifest = ManifestData {
+				version: SNAPPY_SNAPSHOT_VERSION,
+				state_hashes: vec![keccak(chunk)],
+				block_hashes: Vec::new(),
+				state_root: keccak(b"notarealroot"),
+				block_number: 12345678987654321,
+				block_hash: keccak(b"notarealblock"),
+				compression: compression,
+			};
+
+			writer.finish(manifest.clone()).unwrap();
+
+			let reader = PackedReader::new(&path).unwrap().unwrap();
+			assert_eq!(reader.manifest(), &manifest);
+			assert_eq!(reader.genesis_hash(), Some(genesis_hash));
+			assert_eq!(reader.spec_hash(), Some(spec_hash));
+
+			// corrupt the state root in the manifest.
+			let mut data = Vec::new();
+			File::open(&path).unwrap().read_to_end(&mut data).unwrap();
+			let pos = data.windows(32).position(|w| w == &manifest.state_root[..]).unwrap();
+			data[pos] ^= 1;
+			File::create(&path).unwrap().write_all(&data).unwrap();
+
+			match PackedReader::new(&path) {
+				Err(Error::ManifestChecksumMismatch(..)) => {},
+				_ => panic!("corrupted manifest accepted"),
+			}
 		}
 	}
 
@@ -404,12 +546,13 @@ mod tests {
 		}
 
 		let manifest = ManifestData {
-			version: SNAPSHOT_VERSION,
+			version: SNAPPY_SNAPSHOT_VERSION,
 			state_hashes: state_hashes,
 			block_hashes: block_hashes,
 			state_root: keccak(b"notarealroot"),
 			block_number: 12345678987654321,
 			block_hash: keccak(b"notarealblock)"),
+			compression: ChunkCompression::Zstd,
 		};
 
 		writer.finish(manifest.clone()).unwrap();
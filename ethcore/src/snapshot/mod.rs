@@ -19,6 +19,7 @@
 //! Documentation of the format can be found at
 //! https://github.com/paritytech/parity/wiki/Warp-Sync-Snapshot-Format
 
+use std::cmp;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
@@ -33,19 +34,20 @@ use ids::BlockId;
 use ethereum_types::{H256, U256};
 use hashdb::HashDB;
 use kvdb::DBValue;
-use snappy;
 use bytes::Bytes;
 use parking_lot::Mutex;
 use journaldb::{self, Algorithm, JournalDB};
 use kvdb::KeyValueDB;
-use trie::{TrieDB, TrieDBMut, Trie, TrieMut};
+use trie::{TrieDBMut, TrieMut, TrieError};
 use rlp::{RlpStream, UntrustedRlp};
 use bloom_journal::Bloom;
 
+use self::compression::Compressor;
 use self::io::SnapshotWriter;
 
 use super::state_db::StateDB;
 use super::state::Account as StateAccount;
+use super::state::walk::{TrieVisitor, walk_trie_prefix, nibbles_to_hash};
 
 use crossbeam::scope;
 use rand::{Rng, OsRng};
@@ -56,7 +58,7 @@ pub use self::consensus::*;
 pub use self::service::{Service, DatabaseRestore};
 pub use self::traits::SnapshotService;
 pub use self::watcher::Watcher;
-pub use types::snapshot_manifest::ManifestData;
+pub use types::snapshot_manifest::{ManifestData, ChunkCompression};
 pub use types::restoration_status::RestorationStatus;
 pub use types::basic_account::BasicAccount;
 
@@ -65,6 +67,7 @@ pub mod service;
 
 mod account;
 mod block;
+mod compression;
 mod consensus;
 mod error;
 mod watcher;
@@ -82,6 +85,9 @@ const PREFERRED_CHUNK_SIZE: usize = 4 * 1024 * 1024;
 // than PREFERRED_CHUNK_SIZE so allow some threshold here.
 const MAX_CHUNK_SIZE: usize = PREFERRED_CHUNK_SIZE / 4 * 5;
 
+// Number of account hash ranges chunked in parallel. Ranges are split on the first nibble.
+const STATE_PARTS: usize = 16;
+
 // Minimum supported state chunk version.
 const MIN_SUPPORTED_STATE_CHUNK_VERSION: u64 = 1;
 // current state chunk version.
@@ -127,6 +133,7 @@ pub fn take_snapshot<W: SnapshotWriter + Send>(
 	chain: &BlockChain,
 	block_at: H256,
 	state_db: &HashDB,
+	compression: ChunkCompression,
 	writer: W,
 	p: &Progress
 ) -> Result<(), Error> {
@@ -142,8 +149,8 @@ pub fn take_snapshot<W: SnapshotWriter + Send>(
 	let snapshot_version = chunker.current_version();
 	let (state_hashes, block_hashes) = scope(|scope| {
 		let writer = &writer;
-		let block_guard = scope.spawn(move || chunk_secondary(chunker, chain, block_at, writer, p));
-		let state_res = chunk_state(state_db, state_root, writer, p);
+		let block_guard = scope.spawn(move || chunk_secondary(chunker, chain, block_at, compression, writer, p));
+		let state_res = chunk_state(state_db, state_root, compression, writer, p);
 
 		state_res.and_then(|state_hashes| {
 			block_guard.join().map(|block_hashes| (state_hashes, block_hashes))
@@ -159,6 +166,7 @@ pub fn take_snapshot<W: SnapshotWriter + Send>(
 		state_root: *state_root,
 		block_number: number,
 		block_hash: block_at,
+		compression: compression,
 	};
 
 	writer.into_inner().finish(manifest_data)?;
@@ -174,14 +182,13 @@ pub fn take_snapshot<W: SnapshotWriter + Send>(
 /// Secondary chunks are engine-specific, but they intend to corroborate the state data
 /// in the state chunks.
 /// Returns a list of chunk hashes, with the first having the blocks furthest from the genesis.
-pub fn chunk_secondary<'a>(mut chunker: Box<SnapshotComponents>, chain: &'a BlockChain, start_hash: H256, writer: &Mutex<SnapshotWriter + 'a>, progress: &'a Progress) -> Result<Vec<H256>, Error> {
+pub fn chunk_secondary<'a>(mut chunker: Box<SnapshotComponents>, chain: &'a BlockChain, start_hash: H256, compression: ChunkCompression, writer: &Mutex<SnapshotWriter + 'a>, progress: &'a Progress) -> Result<Vec<H256>, Error> {
 	let mut chunk_hashes = Vec::new();
-	let mut snappy_buffer = vec![0; snappy::max_compressed_len(PREFERRED_CHUNK_SIZE)];
+	let mut compressor = Compressor::new(compression);
 
 	{
 		let mut chunk_sink = |raw_data: &[u8]| {
-			let compressed_size = snappy::compress_into(raw_data, &mut snappy_buffer);
-			let compressed = &snappy_buffer[..compressed_size];
+			let compressed = compressor.compress(raw_data)?;
 			let hash = keccak(&compressed);
 			let size = compressed.len();
 
@@ -210,8 +217,8 @@ struct StateChunker<'a> {
 	hashes: Vec<H256>,
 	rlps: Vec<Bytes>,
 	cur_size: usize,
-	snappy_buffer: Vec<u8>,
-	writer: &'a Mutex<SnapshotWriter + 'a>,
+	compressor: Compressor,
+	writer: &'a Mutex<SnapshotWriter + Send + 'a>,
 	progress: &'a Progress,
 }
 
@@ -237,8 +244,8 @@ impl<'a> StateChunker<'a> {
 
 		let raw_data = stream.out();
 
-		let compressed_size = snappy::compress_into(&raw_data, &mut self.snappy_buffer);
-		let compressed = &self.snappy_buffer[..compressed_size];
+		let compressed = self.compressor.compress(&raw_data)?;
+		let compressed_size = compressed.len();
 		let hash = keccak(&compressed);
 
 		self.writer.lock().write_state_chunk(hash, compressed)?;
@@ -259,42 +266,126 @@ impl<'a> StateChunker<'a> {
 	}
 }
 
+/// Feeds accounts met while walking the account trie into the chunker.
+struct AccountChunker<'a> {
+	db: &'a HashDB,
+	chunker: StateChunker<'a>,
+	used_code: &'a Mutex<HashSet<H256>>,
+	result: Result<(), Error>,
+}
+
+impl<'a> AccountChunker<'a> {
+	fn chunk_account(&mut self, account_key_hash: H256, account_data: &[u8]) -> Result<(), Error> {
+		let account = ::rlp::decode(account_data);
+		let account_db = AccountDB::from_hash(self.db, account_key_hash);
+
+		let fat_rlps = account::to_fat_rlps(&account_key_hash, &account, &account_db, self.used_code, PREFERRED_CHUNK_SIZE - self.chunker.chunk_size(), PREFERRED_CHUNK_SIZE)?;
+		for (i, fat_rlp) in fat_rlps.into_iter().enumerate() {
+			if i > 0 {
+				self.chunker.write_chunk()?;
+			}
+			self.chunker.push(fat_rlp)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl<'a> TrieVisitor for AccountChunker<'a> {
+	fn node(&mut self, _hash: &H256, _path: &[u8], _rlp: &[u8]) -> bool {
+		self.result.is_ok()
+	}
+
+	fn missing(&mut self, hash: &H256, _path: &[u8]) {
+		if self.result.is_ok() {
+			self.result = Err(TrieError::IncompleteDatabase(*hash).into());
+		}
+	}
+
+	fn value(&mut self, key: &[u8], value: &[u8]) {
+		if self.result.is_err() {
+			return;
+		}
+
+		// account_key here is the address' hash.
+		self.result = match nibbles_to_hash(key) {
+			Some(account_key_hash) => self.chunk_account(account_key_hash, value),
+			None => Err(Error::WrongChunkFormat(format!("Invalid account key length: {} nibbles", key.len()))),
+		};
+	}
+}
+
 /// Walk the given state database starting from the given root,
 /// creating chunks and writing them out.
 ///
+/// Accounts are split into ranges by the first nibble of their hash and the ranges
+/// are chunked in parallel. Chunks of each range are listed in order. Code shared by
+/// accounts is inlined only once, so the restored state doesn't depend on the number of threads.
+///
 /// Returns a list of hashes of chunks created, or any error it may
 /// have encountered.
-pub fn chunk_state<'a>(db: &HashDB, root: &H256, writer: &Mutex<SnapshotWriter + 'a>, progress: &'a Progress) -> Result<Vec<H256>, Error> {
-	let account_trie = TrieDB::new(db, &root)?;
-
-	let mut chunker = StateChunker {
-		hashes: Vec::new(),
-		rlps: Vec::new(),
-		cur_size: 0,
-		snappy_buffer: vec![0; snappy::max_compressed_len(PREFERRED_CHUNK_SIZE)],
-		writer: writer,
-		progress: progress,
-	};
+pub fn chunk_state<'a>(db: &HashDB, root: &H256, compression: ChunkCompression, writer: &Mutex<SnapshotWriter + Send + 'a>, progress: &'a Progress) -> Result<Vec<H256>, Error> {
+	chunk_state_in_threads(db, root, compression, writer, progress, cmp::min(::num_cpus::get(), STATE_PARTS))
+}
 
-	let mut used_code = HashSet::new();
+fn chunk_state_in_threads<'a>(db: &HashDB, root: &H256, compression: ChunkCompression, writer: &Mutex<SnapshotWriter + Send + 'a>, progress: &'a Progress, threads: usize) -> Result<Vec<H256>, Error> {
+	let next_part = AtomicUsize::new(0);
+	let used_code = Mutex::new(HashSet::new());
+
+	let mut parts = scope(|scope| {
+		let next_part = &next_part;
+		let used_code = &used_code;
+		let workers = (0..threads).map(move |_| scope.spawn(move || {
+			let mut done = Vec::new();
+			loop {
+				let part = next_part.fetch_add(1, Ordering::SeqCst);
+				if part >= STATE_PARTS {
+					break;
+				}
+
+				let res = chunk_state_part(db, root, part as u8, compression, writer, progress, used_code);
+				if res.is_err() {
+					// make the other workers stop early
+					next_part.store(STATE_PARTS, Ordering::SeqCst);
+				}
+				done.push((part, res));
+			}
+			done
+		})).collect::<Vec<_>>();
 
-	// account_key here is the address' hash.
-	for item in account_trie.iter()? {
-		let (account_key, account_data) = item?;
-		let account = ::rlp::decode(&*account_data);
-		let account_key_hash = H256::from_slice(&account_key);
+		workers.into_iter().flat_map(|worker| worker.join()).collect::<Vec<_>>()
+	});
 
-		let account_db = AccountDB::from_hash(db, account_key_hash);
+	parts.sort_by_key(|&(part, _)| part);
 
-		let fat_rlps = account::to_fat_rlps(&account_key_hash, &account, &account_db, &mut used_code, PREFERRED_CHUNK_SIZE - chunker.chunk_size(), PREFERRED_CHUNK_SIZE)?;
-		for (i, fat_rlp) in fat_rlps.into_iter().enumerate() {
-			if i > 0 {
-				chunker.write_chunk()?;
-			}
-			chunker.push(fat_rlp)?;
-		}
+	let mut hashes = Vec::new();
+	for (_, part_hashes) in parts {
+		hashes.extend(part_hashes?);
 	}
 
+	Ok(hashes)
+}
+
+// Chunk accounts which hash starts with `nibble`.
+fn chunk_state_part<'a>(db: &HashDB, root: &H256, nibble: u8, compression: ChunkCompression, writer: &Mutex<SnapshotWriter + Send + 'a>, progress: &'a Progress, used_code: &Mutex<HashSet<H256>>) -> Result<Vec<H256>, Error> {
+	let mut account_chunker = AccountChunker {
+		db: db,
+		chunker: StateChunker {
+			hashes: Vec::new(),
+			rlps: Vec::new(),
+			cur_size: 0,
+			compressor: Compressor::new(compression),
+			writer: writer,
+			progress: progress,
+		},
+		used_code: used_code,
+		result: Ok(()),
+	};
+
+	walk_trie_prefix(db, root, &[nibble], &mut account_chunker);
+	account_chunker.result?;
+
+	let mut chunker = account_chunker.chunker;
 	if chunker.cur_size != 0 {
 		chunker.write_chunk()?;
 	}
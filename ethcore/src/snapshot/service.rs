@@ -25,9 +25,10 @@ use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use super::{ManifestData, StateRebuilder, Rebuilder, RestorationStatus, SnapshotService, MAX_CHUNK_SIZE};
 use super::io::{SnapshotReader, LooseReader, SnapshotWriter, LooseWriter};
+use super::compression::decompress;
 
 use blockchain::BlockChain;
-use client::{BlockChainClient, Client};
+use client::{BlockChainClient, Client, DatabaseBackend};
 use engines::EthEngine;
 use error::Error;
 use ids::BlockId;
@@ -40,8 +41,8 @@ use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use util_error::UtilError;
 use bytes::Bytes;
 use journaldb::Algorithm;
-use kvdb_rocksdb::{Database, DatabaseConfig};
-use snappy;
+use kvdb::KeyValueDB;
+use kvdb_rocksdb::DatabaseConfig;
 
 /// Helper for removing directories in case of error.
 struct Guard(bool, PathBuf);
@@ -77,10 +78,10 @@ struct Restoration {
 	state: StateRebuilder,
 	secondary: Box<Rebuilder>,
 	writer: Option<LooseWriter>,
-	snappy_buffer: Bytes,
+	buffer: Bytes,
 	final_state_root: H256,
 	guard: Guard,
-	db: Arc<Database>,
+	db: Arc<KeyValueDB>,
 }
 
 struct RestorationParams<'a> {
@@ -88,6 +89,7 @@ struct RestorationParams<'a> {
 	pruning: Algorithm, // pruning algorithm for the database.
 	db_path: PathBuf, // database path
 	db_config: &'a DatabaseConfig, // configuration for the database.
+	db_backend: DatabaseBackend, // key-value store to restore into.
 	writer: Option<LooseWriter>, // writer for recovered snapshot.
 	genesis: &'a [u8], // genesis block of the chain.
 	guard: Guard, // guard for the restoration directory.
@@ -102,8 +104,8 @@ impl Restoration {
 		let state_chunks = manifest.state_hashes.iter().cloned().collect();
 		let block_chunks = manifest.block_hashes.iter().cloned().collect();
 
-		let raw_db = Arc::new(Database::open(params.db_config, &*params.db_path.to_string_lossy())
-			.map_err(UtilError::from)?);
+		let raw_db = params.db_backend.open(params.db_config, &params.db_path)
+			.map_err(UtilError::from)?;
 
 		let chain = BlockChain::new(Default::default(), params.genesis, raw_db.clone());
 		let components = params.engine.snapshot_components()
@@ -120,7 +122,7 @@ impl Restoration {
 			state: StateRebuilder::new(raw_db.clone(), params.pruning),
 			secondary: secondary,
 			writer: params.writer,
-			snappy_buffer: Vec::new(),
+			buffer: Vec::new(),
 			final_state_root: root,
 			guard: params.guard,
 			db: raw_db,
@@ -130,14 +132,9 @@ impl Restoration {
 	// feeds a state chunk, aborts early if `flag` becomes false.
 	fn feed_state(&mut self, hash: H256, chunk: &[u8], flag: &AtomicBool) -> Result<(), Error> {
 		if self.state_chunks_left.contains(&hash) {
-			let expected_len = snappy::decompressed_len(chunk)?;
-			if expected_len > MAX_CHUNK_SIZE {
-				trace!(target: "snapshot", "Discarding large chunk: {} vs {}", expected_len, MAX_CHUNK_SIZE);
-				return Err(::snapshot::Error::ChunkTooLarge.into());
-			}
-			let len = snappy::decompress_into(chunk, &mut self.snappy_buffer)?;
+			let len = decompress(self.manifest.compression, chunk, &mut self.buffer, MAX_CHUNK_SIZE)?;
 
-			self.state.feed(&self.snappy_buffer[..len], flag)?;
+			self.state.feed(&self.buffer[..len], flag)?;
 
 			if let Some(ref mut writer) = self.writer.as_mut() {
 				writer.write_state_chunk(hash, chunk)?;
@@ -152,14 +149,9 @@ impl Restoration {
 	// feeds a block chunk
 	fn feed_blocks(&mut self, hash: H256, chunk: &[u8], engine: &EthEngine, flag: &AtomicBool) -> Result<(), Error> {
 		if self.block_chunks_left.contains(&hash) {
-			let expected_len = snappy::decompressed_len(chunk)?;
-			if expected_len > MAX_CHUNK_SIZE {
-				trace!(target: "snapshot", "Discarding large chunk: {} vs {}", expected_len, MAX_CHUNK_SIZE);
-				return Err(::snapshot::Error::ChunkTooLarge.into());
-			}
-			let len = snappy::decompress_into(chunk, &mut self.snappy_buffer)?;
+			let len = decompress(self.manifest.compression, chunk, &mut self.buffer, MAX_CHUNK_SIZE)?;
 
-			self.secondary.feed(&self.snappy_buffer[..len], engine, flag)?;
+			self.secondary.feed(&self.buffer[..len], engine, flag)?;
 			if let Some(ref mut writer) = self.writer.as_mut() {
 				 writer.write_block_chunk(hash, chunk)?;
 			}
@@ -214,6 +206,8 @@ pub struct ServiceParams {
 	pub genesis_block: Bytes,
 	/// Database configuration options.
 	pub db_config: DatabaseConfig,
+	/// Database backend to restore into.
+	pub db_backend: DatabaseBackend,
 	/// State pruning algorithm.
 	pub pruning: Algorithm,
 	/// Async IO channel for sending messages.
@@ -231,6 +225,7 @@ pub struct Service {
 	restoration: Mutex<Option<Restoration>>,
 	snapshot_root: PathBuf,
 	db_config: DatabaseConfig,
+	db_backend: DatabaseBackend,
 	io_channel: Mutex<Channel>,
 	pruning: Algorithm,
 	status: Mutex<RestorationStatus>,
@@ -252,6 +247,7 @@ impl Service {
 			restoration: Mutex::new(None),
 			snapshot_root: params.snapshot_root,
 			db_config: params.db_config,
+			db_backend: params.db_backend,
 			io_channel: Mutex::new(params.channel),
 			pruning: params.pruning,
 			status: Mutex::new(RestorationStatus::Inactive),
@@ -440,6 +436,7 @@ impl Service {
 			pruning: self.pruning,
 			db_path: self.restoration_db(),
 			db_config: &self.db_config,
+			db_backend: self.db_backend,
 			writer: writer,
 			genesis: &self.genesis_block,
 			guard: Guard::new(rest_dir),
@@ -659,6 +656,7 @@ mod tests {
 			engine: spec.engine.clone(),
 			genesis_block: spec.genesis_block(),
 			db_config: Default::default(),
+			db_backend: Default::default(),
 			pruning: Algorithm::Archive,
 			channel: service.channel(),
 			snapshot_root: dir,
@@ -678,6 +676,7 @@ mod tests {
 			state_root: Default::default(),
 			block_number: 0,
 			block_hash: Default::default(),
+			compression: Default::default(),
 		};
 
 		service.begin_restore(manifest);
@@ -708,10 +707,12 @@ mod tests {
 				state_root: H256::default(),
 				block_number: 100000,
 				block_hash: H256::default(),
+				compression: Default::default(),
 			},
 			pruning: Algorithm::Archive,
 			db_path: tempdir.path().to_owned(),
 			db_config: &db_config,
+			db_backend: Default::default(),
 			writer: None,
 			genesis: &gb,
 			guard: Guard::benign(),
@@ -164,7 +164,8 @@ pub fn restore(
 	genesis: &[u8],
 ) -> Result<(), ::error::Error> {
 	use std::sync::atomic::AtomicBool;
-	use snappy;
+	use snapshot::MAX_CHUNK_SIZE;
+	use snapshot::compression::decompress;
 
 	let flag = AtomicBool::new(true);
 	let components = engine.snapshot_components().unwrap();
@@ -176,21 +177,21 @@ pub fn restore(
 		components.rebuilder(chain, db, manifest).unwrap()
 	};
 
-	let mut snappy_buffer = Vec::new();
+	let mut buffer = Vec::new();
 
 	trace!(target: "snapshot", "restoring state");
 	for state_chunk_hash in manifest.state_hashes.iter() {
 		trace!(target: "snapshot", "state chunk hash: {}", state_chunk_hash);
 		let chunk = reader.chunk(*state_chunk_hash).unwrap();
-		let len = snappy::decompress_into(&chunk, &mut snappy_buffer).unwrap();
-		state.feed(&snappy_buffer[..len], &flag)?;
+		let len = decompress(manifest.compression, &chunk, &mut buffer, MAX_CHUNK_SIZE).unwrap();
+		state.feed(&buffer[..len], &flag)?;
 	}
 
 	trace!(target: "snapshot", "restoring secondary");
 	for chunk_hash in manifest.block_hashes.iter() {
 		let chunk = reader.chunk(*chunk_hash).unwrap();
-		let len = snappy::decompress_into(&chunk, &mut snappy_buffer).unwrap();
-		secondary.feed(&snappy_buffer[..len], engine, &flag)?;
+		let len = decompress(manifest.compression, &chunk, &mut buffer, MAX_CHUNK_SIZE).unwrap();
+		secondary.feed(&buffer[..len], engine, &flag)?;
 	}
 
 	trace!(target: "snapshot", "finalizing");
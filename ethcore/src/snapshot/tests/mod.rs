@@ -34,6 +34,7 @@ fn manifest_rlp() {
 		block_number: 1234567,
 		state_root: Default::default(),
 		block_hash: Default::default(),
+		compression: Default::default(),
 	};
 	let raw = manifest.clone().into_rlp();
 	assert_eq!(ManifestData::from_rlp(&raw).unwrap(), manifest);
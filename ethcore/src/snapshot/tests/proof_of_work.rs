@@ -23,7 +23,7 @@ use error::Error;
 
 use blockchain::generator::{BlockGenerator, BlockBuilder};
 use blockchain::BlockChain;
-use snapshot::{chunk_secondary, Error as SnapshotError, Progress, SnapshotComponents};
+use snapshot::{chunk_secondary, ChunkCompression, Error as SnapshotError, Progress, SnapshotComponents};
 use snapshot::io::{PackedReader, PackedWriter, SnapshotReader, SnapshotWriter};
 
 use parking_lot::Mutex;
@@ -63,6 +63,7 @@ fn chunk_and_restore(amount: u64) {
 		Box::new(SNAPSHOT_MODE),
 		&bc,
 		best_hash,
+		ChunkCompression::Snappy,
 		&writer,
 		&Progress::default()
 	).unwrap();
@@ -74,6 +75,7 @@ fn chunk_and_restore(amount: u64) {
 		state_root: ::hash::KECCAK_NULL_RLP,
 		block_number: amount,
 		block_hash: best_hash,
+		compression: ChunkCompression::Snappy,
 	};
 
 	writer.into_inner().finish(manifest.clone()).unwrap();
@@ -136,6 +138,7 @@ fn checks_flag() {
 		state_root: ::hash::KECCAK_NULL_RLP,
 		block_number: 102,
 		block_hash: H256::default(),
+		compression: ChunkCompression::Snappy,
 	};
 
 	let mut rebuilder = SNAPSHOT_MODE.rebuilder(chain, db.clone(), &manifest).unwrap();
@@ -66,6 +66,7 @@ fn restored_is_equivalent() {
 		engine: spec.engine.clone(),
 		genesis_block: spec.genesis_block(),
 		db_config: db_config,
+		db_backend: Default::default(),
 		pruning: ::journaldb::Algorithm::Archive,
 		channel: IoChannel::disconnected(),
 		snapshot_root: path,
@@ -108,6 +109,7 @@ fn guards_delete_folders() {
 		engine: spec.engine.clone(),
 		genesis_block: spec.genesis_block(),
 		db_config: DatabaseConfig::with_columns(::db::NUM_COLUMNS),
+		db_backend: Default::default(),
 		pruning: ::journaldb::Algorithm::Archive,
 		channel: IoChannel::disconnected(),
 		snapshot_root: tempdir.path().to_owned(),
@@ -124,6 +126,7 @@ fn guards_delete_folders() {
 		block_number: 0,
 		block_hash: Default::default(),
 		state_root: Default::default(),
+		compression: Default::default(),
 	};
 
 	service.init_restore(manifest.clone(), true).unwrap();
@@ -20,9 +20,11 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use hash::{KECCAK_NULL_RLP, keccak};
 
+use account_db::AccountDBMut;
 use basic_account::BasicAccount;
 use snapshot::account;
-use snapshot::{chunk_state, Error as SnapshotError, Progress, StateRebuilder};
+use snapshot::{chunk_state, chunk_state_in_threads, ChunkCompression, Error as SnapshotError, Progress, StateRebuilder, MAX_CHUNK_SIZE, STATE_PARTS};
+use snapshot::compression::decompress;
 use snapshot::io::{PackedReader, PackedWriter, SnapshotReader, SnapshotWriter};
 use super::helpers::{compare_dbs, StateProducer};
 
@@ -30,11 +32,13 @@ use error::Error;
 
 use rand::{XorShiftRng, SeedableRng};
 use ethereum_types::H256;
+use hashdb::HashDB;
 use journaldb::{self, Algorithm};
 use kvdb_rocksdb::{Database, DatabaseConfig};
 use memorydb::MemoryDB;
 use parking_lot::Mutex;
 use tempdir::TempDir;
+use trie::{TrieMut, TrieDBMut};
 
 #[test]
 fn snap_and_restore() {
@@ -53,7 +57,7 @@ fn snap_and_restore() {
 	let state_root = producer.state_root();
 	let writer = Mutex::new(PackedWriter::new(&snap_file).unwrap());
 
-	let state_hashes = chunk_state(&old_db, &state_root, &writer, &Progress::default()).unwrap();
+	let state_hashes = chunk_state(&old_db, &state_root, ChunkCompression::Zstd, &writer, &Progress::default()).unwrap();
 
 	writer.into_inner().finish(::snapshot::ManifestData {
 		version: 2,
@@ -62,6 +66,7 @@ fn snap_and_restore() {
 		state_root: state_root,
 		block_number: 1000,
 		block_hash: H256::default(),
+		compression: ChunkCompression::Zstd,
 	}).unwrap();
 
 	let db_path = tempdir.path().join("db");
@@ -71,12 +76,13 @@ fn snap_and_restore() {
 		let reader = PackedReader::new(&snap_file).unwrap().unwrap();
 
 		let flag = AtomicBool::new(true);
+		let mut buffer = Vec::new();
 
 		for chunk_hash in &reader.manifest().state_hashes {
 			let raw = reader.chunk(*chunk_hash).unwrap();
-			let chunk = ::snappy::decompress(&raw).unwrap();
+			let len = decompress(reader.manifest().compression, &raw, &mut buffer, MAX_CHUNK_SIZE).unwrap();
 
-			rebuilder.feed(&chunk, &flag).unwrap();
+			rebuilder.feed(&buffer[..len], &flag).unwrap();
 		}
 
 		assert_eq!(rebuilder.state_root(), state_root);
@@ -101,7 +107,7 @@ fn get_code_from_prev_chunk() {
 	use account_db::{AccountDBMut, AccountDB};
 
 	let code = b"this is definitely code";
-	let mut used_code = HashSet::new();
+	let used_code = Mutex::new(HashSet::new());
 	let mut acc_stream = RlpStream::new_list(4);
 	acc_stream.append(&U256::default())
 		.append(&U256::default())
@@ -116,11 +122,11 @@ fn get_code_from_prev_chunk() {
 	let thin_rlp = acc_stream.out();
 	let acc: BasicAccount = ::rlp::decode(&thin_rlp);
 
-	let mut make_chunk = |acc, hash| {
+	let make_chunk = |acc, hash| {
 		let mut db = MemoryDB::new();
 		AccountDBMut::from_hash(&mut db, hash).insert(&code[..]);
 
-		let fat_rlp = account::to_fat_rlps(&hash, &acc, &AccountDB::from_hash(&db, hash), &mut used_code, usize::max_value(), usize::max_value()).unwrap();
+		let fat_rlp = account::to_fat_rlps(&hash, &acc, &AccountDB::from_hash(&db, hash), &used_code, usize::max_value(), usize::max_value()).unwrap();
 		let mut stream = RlpStream::new_list(1);
 		stream.append_raw(&fat_rlp[0], 1);
 		stream.out()
@@ -164,7 +170,7 @@ fn checks_flag() {
 	let state_root = producer.state_root();
 	let writer = Mutex::new(PackedWriter::new(&snap_file).unwrap());
 
-	let state_hashes = chunk_state(&old_db, &state_root, &writer, &Progress::default()).unwrap();
+	let state_hashes = chunk_state(&old_db, &state_root, ChunkCompression::Snappy, &writer, &Progress::default()).unwrap();
 
 	writer.into_inner().finish(::snapshot::ManifestData {
 		version: 2,
@@ -173,6 +179,7 @@ fn checks_flag() {
 		state_root: state_root,
 		block_number: 0,
 		block_hash: H256::default(),
+		compression: ChunkCompression::Snappy,
 	}).unwrap();
 
 	let tempdir = TempDir::new("").unwrap();
@@ -195,3 +202,69 @@ fn checks_flag() {
 		}
 	}
 }
+
+// chunks the state in given number of threads and restores it.
+// returns the restored state root and how many times `code` was inlined.
+fn chunk_and_restore(db: &MemoryDB, state_root: &H256, threads: usize, code: &[u8]) -> (H256, usize) {
+	let tempdir = TempDir::new("").unwrap();
+	let snap_file = tempdir.path().join("SNAP");
+	let writer = Mutex::new(PackedWriter::new(&snap_file).unwrap());
+
+	let state_hashes = chunk_state_in_threads(db, state_root, ChunkCompression::Snappy, &writer, &Progress::default(), threads).unwrap();
+
+	writer.into_inner().finish(::snapshot::ManifestData {
+		version: 2,
+		state_hashes: state_hashes,
+		block_hashes: Vec::new(),
+		state_root: *state_root,
+		block_number: 1000,
+		block_hash: H256::default(),
+		compression: ChunkCompression::Snappy,
+	}).unwrap();
+
+	let new_db = Arc::new(::kvdb_memorydb::create(::db::NUM_COLUMNS.unwrap()));
+	let mut rebuilder = StateRebuilder::new(new_db, Algorithm::OverlayRecent);
+	let reader = PackedReader::new(&snap_file).unwrap().unwrap();
+	let flag = AtomicBool::new(true);
+	let mut inlined = 0;
+
+	for chunk_hash in &reader.manifest().state_hashes {
+		let chunk = ::snappy::decompress(&reader.chunk(*chunk_hash).unwrap()).unwrap();
+		inlined += chunk.windows(code.len()).filter(|window| *window == code).count();
+		rebuilder.feed(&chunk, &flag).unwrap();
+	}
+
+	let restored_root = rebuilder.state_root();
+	rebuilder.finalize(1000, H256::default()).unwrap();
+	(restored_root, inlined)
+}
+
+#[test]
+fn same_state_in_one_and_many_threads() {
+	let code = b"code of accounts in every part of the state";
+	let mut producer = StateProducer::new();
+	let mut rng = XorShiftRng::from_seed([9, 10, 11, 12]);
+	let mut db = MemoryDB::new();
+
+	for _ in 0..50 {
+		producer.tick(&mut rng, &mut db);
+	}
+
+	let address_hashes: Vec<_> = (0..64u64).map(|i| keccak(H256::from(i))).collect();
+	let code_hash = keccak(&code[..]);
+	for address_hash in &address_hashes {
+		AccountDBMut::from_hash(&mut db, *address_hash).insert(&code[..]);
+	}
+
+	let mut state_root = producer.state_root();
+	{
+		let mut trie = TrieDBMut::from_existing(&mut db, &mut state_root).unwrap();
+		for address_hash in &address_hashes {
+			let account = BasicAccount { nonce: 0.into(), balance: 1.into(), storage_root: KECCAK_NULL_RLP, code_hash: code_hash };
+			trie.insert(&address_hash[..], &::rlp::encode(&account)).unwrap();
+		}
+	}
+
+	assert_eq!(chunk_and_restore(&db, &state_root, 1, code), (state_root, 1));
+	assert_eq!(chunk_and_restore(&db, &state_root, STATE_PARTS, code), (state_root, 1));
+}
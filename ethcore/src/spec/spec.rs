@@ -102,6 +102,14 @@ pub struct CommonParams {
 	pub eip211_transition: BlockNumber,
 	/// Number of first block where EIP-214 rules begin.
 	pub eip214_transition: BlockNumber,
+	/// Number of first block where EIP-145 (Constantinople: bitwise shifting) rules begin.
+	pub eip145_transition: BlockNumber,
+	/// Number of first block where EIP-1014 (Constantinople: CREATE2) rules begin.
+	pub eip1014_transition: BlockNumber,
+	/// Number of first block where EIP-1052 (Constantinople: EXTCODEHASH) rules begin.
+	pub eip1052_transition: BlockNumber,
+	/// Number of first block where EIP-1283 (Constantinople: net gas metering for SSTORE) rules begin.
+	pub eip1283_transition: BlockNumber,
 	/// Number of first block where dust cleanup rules (EIP-168 and EIP169) begin.
 	pub dust_protection_transition: BlockNumber,
 	/// Nonce cap increase per block. Nonce cap is only checked if dust protection is enabled.
@@ -143,10 +151,13 @@ impl CommonParams {
 
 	/// Apply common spec config parameters to the schedule.
 	pub fn update_schedule(&self, block_number: u64, schedule: &mut ::vm::Schedule) {
-		schedule.have_create2 = block_number >= self.eip86_transition;
+		schedule.have_create2 = block_number >= self.eip1014_transition;
 		schedule.have_revert = block_number >= self.eip140_transition;
 		schedule.have_static_call = block_number >= self.eip214_transition;
 		schedule.have_return_data = block_number >= self.eip211_transition;
+		schedule.have_bitwise_shifting = block_number >= self.eip145_transition;
+		schedule.have_extcodehash = block_number >= self.eip1052_transition;
+		schedule.eip1283 = block_number >= self.eip1283_transition;
 		if block_number >= self.eip210_transition {
 			schedule.blockhash_gas = 800;
 		}
@@ -223,6 +234,22 @@ impl From<ethjson::spec::Params> for CommonParams {
 				BlockNumber::max_value(),
 				Into::into,
 			),
+			eip145_transition: p.eip145_transition.map_or(
+				BlockNumber::max_value(),
+				Into::into,
+			),
+			eip1014_transition: p.eip1014_transition.map_or(
+				BlockNumber::max_value(),
+				Into::into,
+			),
+			eip1052_transition: p.eip1052_transition.map_or(
+				BlockNumber::max_value(),
+				Into::into,
+			),
+			eip1283_transition: p.eip1283_transition.map_or(
+				BlockNumber::max_value(),
+				Into::into,
+			),
 			eip658_transition: p.eip658_transition.map_or(
 				BlockNumber::max_value(),
 				Into::into,
@@ -290,6 +317,8 @@ impl<'a, T: AsRef<Path>> From<&'a T> for SpecParams<'a> {
 pub struct Spec {
 	/// User friendly spec name
 	pub name: String,
+	/// Hash of all parts of the spec affecting consensus, engine params included.
+	pub hash: H256,
 	/// What engine are we using for this?
 	pub engine: Arc<EthEngine>,
 	/// Name of the subdir inside the main data dir to use for chain data and settings.
@@ -334,6 +363,7 @@ impl Clone for Spec {
 	fn clone(&self) -> Spec {
 		Spec {
 			name: self.name.clone(),
+			hash: self.hash.clone(),
 			engine: self.engine.clone(),
 			data_dir: self.data_dir.clone(),
 			nodes: self.nodes.clone(),
@@ -361,8 +391,8 @@ fn load_machine_from(s: ethjson::spec::Spec) -> EthereumMachine {
 	Spec::machine(&s.engine, params, builtins)
 }
 
-/// Load from JSON object.
-fn load_from(spec_params: SpecParams, s: ethjson::spec::Spec) -> Result<Spec, Error> {
+/// Load from JSON object. `hash` is the hash of the consensus json of the spec.
+fn load_from(spec_params: SpecParams, s: ethjson::spec::Spec, hash: H256) -> Result<Spec, Error> {
 	let builtins = s.accounts
 		.builtins()
 		.into_iter()
@@ -374,6 +404,7 @@ fn load_from(spec_params: SpecParams, s: ethjson::spec::Spec) -> Result<Spec, Er
 
 	let mut s = Spec {
 		name: s.name.clone().into(),
+		hash: hash,
 		engine: Spec::engine(spec_params, s.engine, params, builtins),
 		data_dir: s.data_dir.unwrap_or(s.name).into(),
 		nodes: s.nodes.unwrap_or_else(Vec::new),
@@ -453,7 +484,13 @@ impl Spec {
 
 		match engine_spec {
 			ethjson::spec::Engine::Null(null) => Arc::new(NullEngine::new(null.params.into(), machine)),
-			ethjson::spec::Engine::Ethash(ethash) => Arc::new(::ethereum::Ethash::new(spec_params.cache_dir, ethash.params.into(), machine, spec_params.optimization_setting)),
+			ethjson::spec::Engine::Ethash(ethash) => {
+				let params: ::ethereum::ethash::EthashParams = ethash.params.into();
+				if let Err(e) = params.verify_reward_schedule() {
+					panic!("Failed to start Ethash consensus engine: {}", e);
+				}
+				Arc::new(::ethereum::Ethash::new(spec_params.cache_dir, params, machine, spec_params.optimization_setting))
+			},
 			ethjson::spec::Engine::InstantSeal => Arc::new(InstantSeal::new(machine)),
 			ethjson::spec::Engine::BasicAuthority(basic_authority) => Arc::new(BasicAuthority::new(basic_authority.params.into(), machine)),
 			ethjson::spec::Engine::AuthorityRound(authority_round) => AuthorityRound::new(authority_round.params.into(), machine)
@@ -672,13 +709,17 @@ impl Spec {
 
 	/// Loads spec from json file. Provide factories for executing contracts and ensuring
 	/// storage goes to the right place.
-	pub fn load<'a, T: Into<SpecParams<'a>>, R>(params: T, reader: R) -> Result<Self, String>
+	pub fn load<'a, T: Into<SpecParams<'a>>, R>(params: T, mut reader: R) -> Result<Self, String>
 	where
 		R: Read,
 	{
-		ethjson::spec::Spec::load(reader).map_err(fmt_err).and_then(
+		let mut json = Vec::new();
+		reader.read_to_end(&mut json).map_err(fmt_err)?;
+		let hash = ethjson::spec::Spec::consensus_json(&json[..]).map(keccak).map_err(fmt_err)?;
+
+		ethjson::spec::Spec::load(&json[..]).map_err(fmt_err).and_then(
 			|x| {
-				load_from(params.into(), x).map_err(fmt_err)
+				load_from(params.into(), x, hash).map_err(fmt_err)
 			},
 		)
 	}
@@ -854,6 +895,16 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn hash_includes_engine_params() {
+		let test_spec = Spec::new_test();
+		let with_reward = Spec::new_test_with_reward();
+
+		assert_eq!(test_spec.genesis_header().hash(), with_reward.genesis_header().hash());
+		assert!(test_spec.hash != with_reward.hash);
+		assert_eq!(test_spec.hash, Spec::new_test().hash);
+	}
+
 	#[test]
 	fn genesis_constructor() {
 		::ethcore_logger::init_log();
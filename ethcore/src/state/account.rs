@@ -60,6 +60,9 @@ pub struct Account {
 	// Modified storage. Accumulates changes to storage made in `set_storage`
 	// Takes precedence over `storage_cache`.
 	storage_changes: HashMap<H256, H256>,
+	// Values of the keys modified in the current transaction as they were when it started.
+	// Recorded on the first change of each key, used for EIP-1283 gas metering.
+	original_storage_cache: HashMap<H256, H256>,
 	// Code hash of the account.
 	code_hash: H256,
 	// Size of the accoun code.
@@ -80,6 +83,7 @@ impl From<BasicAccount> for Account {
 			storage_root: basic.storage_root,
 			storage_cache: Self::empty_storage_cache(),
 			storage_changes: HashMap::new(),
+			original_storage_cache: HashMap::new(),
 			code_hash: basic.code_hash,
 			code_size: None,
 			code_cache: Arc::new(vec![]),
@@ -99,6 +103,7 @@ impl Account {
 			storage_root: KECCAK_NULL_RLP,
 			storage_cache: Self::empty_storage_cache(),
 			storage_changes: storage,
+			original_storage_cache: HashMap::new(),
 			code_hash: keccak(&code),
 			code_size: Some(code.len()),
 			code_cache: Arc::new(code),
@@ -119,6 +124,7 @@ impl Account {
 			storage_root: KECCAK_NULL_RLP,
 			storage_cache: Self::empty_storage_cache(),
 			storage_changes: pod.storage.into_iter().collect(),
+			original_storage_cache: HashMap::new(),
 			code_hash: pod.code.as_ref().map_or(KECCAK_EMPTY, |c| keccak(c)),
 			code_filth: Filth::Dirty,
 			code_size: Some(pod.code.as_ref().map_or(0, |c| c.len())),
@@ -135,6 +141,7 @@ impl Account {
 			storage_root: KECCAK_NULL_RLP,
 			storage_cache: Self::empty_storage_cache(),
 			storage_changes: HashMap::new(),
+			original_storage_cache: HashMap::new(),
 			code_hash: KECCAK_EMPTY,
 			code_cache: Arc::new(vec![]),
 			code_size: Some(0),
@@ -158,6 +165,7 @@ impl Account {
 			storage_root: KECCAK_NULL_RLP,
 			storage_cache: Self::empty_storage_cache(),
 			storage_changes: HashMap::new(),
+			original_storage_cache: HashMap::new(),
 			code_hash: KECCAK_EMPTY,
 			code_cache: Arc::new(vec![]),
 			code_size: None,
@@ -185,6 +193,23 @@ impl Account {
 		self.storage_changes.insert(key, value);
 	}
 
+	/// Note `value` as the storage at `key` at the start of the current transaction,
+	/// unless the key has already been changed since.
+	pub fn note_original_storage(&mut self, key: H256, value: H256) {
+		self.original_storage_cache.entry(key).or_insert(value);
+	}
+
+	/// Get the storage at `key` as it was at the start of the current transaction.
+	/// Returns `None` if the key has not been changed since, so it is still the current value.
+	pub fn original_storage_at(&self, key: &H256) -> Option<H256> {
+		self.original_storage_cache.get(key).cloned()
+	}
+
+	/// Forget the original storage values, making the current storage the original one.
+	pub fn clear_original_storage(&mut self) {
+		self.original_storage_cache.clear();
+	}
+
 	/// Get (and cache) the contents of the trie's storage at `key`.
 	/// Takes modifed storage into account.
 	pub fn storage_at(&self, db: &HashDB, key: &H256) -> trie::Result<H256> {
@@ -349,6 +374,23 @@ impl Account {
 		self.nonce = self.nonce + U256::from(1u8);
 	}
 
+	/// Set the nonce of the account.
+	pub fn set_nonce(&mut self, nonce: U256) {
+		self.nonce = nonce;
+	}
+
+	/// Set the balance of the account.
+	pub fn set_balance(&mut self, balance: U256) {
+		self.balance = balance;
+	}
+
+	/// Replace the whole storage of the account, all keys not in `storage` become empty.
+	pub fn reset_storage(&mut self, storage: HashMap<H256, H256>) {
+		self.storage_root = KECCAK_NULL_RLP;
+		self.storage_cache = Self::empty_storage_cache();
+		self.storage_changes = storage;
+	}
+
 	/// Increase account balance.
 	pub fn add_balance(&mut self, x: &U256) {
 		self.balance = self.balance + *x;
@@ -363,6 +405,7 @@ impl Account {
 
 	/// Commit the `storage_changes` to the backing DB and update `storage_root`.
 	pub fn commit_storage(&mut self, trie_factory: &TrieFactory, db: &mut HashDB) -> trie::Result<()> {
+		self.original_storage_cache.clear();
 		let mut t = trie_factory.from_existing(db, &mut self.storage_root)?;
 		for (k, v) in self.storage_changes.drain() {
 			// cast key and value to trait type,
@@ -412,6 +455,7 @@ impl Account {
 			storage_root: self.storage_root.clone(),
 			storage_cache: Self::empty_storage_cache(),
 			storage_changes: HashMap::new(),
+			original_storage_cache: HashMap::new(),
 			code_hash: self.code_hash.clone(),
 			code_size: self.code_size.clone(),
 			code_cache: self.code_cache.clone(),
@@ -424,6 +468,7 @@ impl Account {
 	pub fn clone_dirty(&self) -> Account {
 		let mut account = self.clone_basic();
 		account.storage_changes = self.storage_changes.clone();
+		account.original_storage_cache = self.original_storage_cache.clone();
 		account.code_cache = self.code_cache.clone();
 		account
 	}
@@ -452,6 +497,7 @@ impl Account {
 			cache.insert(k.clone() , v.clone()); //TODO: cloning should not be required here
 		}
 		self.storage_changes = other.storage_changes;
+		self.original_storage_cache = other.original_storage_cache;
 	}
 }
 
@@ -38,6 +38,7 @@ use pod_state::{self, PodState};
 use types::basic_account::BasicAccount;
 use executed::{Executed, ExecutionError};
 use types::state_diff::StateDiff;
+use types::state_override::{StateOverride, StorageOverride};
 use transaction::SignedTransaction;
 use state_db::StateDB;
 use factory::VmFactory;
@@ -56,6 +57,8 @@ mod account;
 mod substate;
 
 pub mod backend;
+pub mod prune;
+pub mod walk;
 
 pub use self::account::Account;
 pub use self::backend::Backend;
@@ -312,6 +315,8 @@ pub struct State<B: Backend> {
 	cache: RefCell<HashMap<Address, AccountEntry>>,
 	// The original account is preserved in
 	checkpoints: RefCell<Vec<HashMap<Address, Option<AccountEntry>>>>,
+	// Accounts and storage keys queried since `record_access`, if recording.
+	accessed: RefCell<Option<BTreeMap<Address, BTreeSet<H256>>>>,
 	account_start_nonce: U256,
 	factories: Factories,
 }
@@ -352,6 +357,7 @@ impl<B: Backend> State<B> {
 			root: root,
 			cache: RefCell::new(HashMap::new()),
 			checkpoints: RefCell::new(Vec::new()),
+			accessed: RefCell::new(None),
 			account_start_nonce: account_start_nonce,
 			factories: factories,
 		}
@@ -368,6 +374,7 @@ impl<B: Backend> State<B> {
 			root: root,
 			cache: RefCell::new(HashMap::new()),
 			checkpoints: RefCell::new(Vec::new()),
+			accessed: RefCell::new(None),
 			account_start_nonce: account_start_nonce,
 			factories: factories
 		};
@@ -388,6 +395,7 @@ impl<B: Backend> State<B> {
 			root: self.root,
 			cache: self.cache,
 			checkpoints: self.checkpoints,
+			accessed: self.accessed,
 			account_start_nonce: self.account_start_nonce,
 			factories: self.factories,
 		}
@@ -526,6 +534,8 @@ impl<B: Backend> State<B> {
 
 	/// Mutate storage of account `address` so that it is `value` for `key`.
 	pub fn storage_at(&self, address: &Address, key: &H256) -> trie::Result<H256> {
+		self.note_access(address, Some(key));
+
 		// Storage key search and update works like this:
 		// 1. If there's an entry for the account in the local cache check for the key and return it if found.
 		// 2. If there's an entry for the account in the global cache check for the key or load it into that account.
@@ -585,6 +595,47 @@ impl<B: Backend> State<B> {
 		r
 	}
 
+	/// Start recording the accounts and storage keys read or written from now on, see `accessed`.
+	pub fn record_access(&mut self) {
+		*self.accessed.get_mut() = Some(BTreeMap::new());
+	}
+
+	/// Accounts read or written since `record_access`, with the storage keys read or written in each.
+	pub fn accessed(&self) -> BTreeMap<Address, BTreeSet<H256>> {
+		self.accessed.borrow().clone().unwrap_or_default()
+	}
+
+	fn note_access(&self, address: &Address, key: Option<&H256>) {
+		if let Some(ref mut accessed) = *self.accessed.borrow_mut() {
+			let keys = accessed.entry(*address).or_insert_with(BTreeSet::new);
+			if let Some(key) = key {
+				keys.insert(*key);
+			}
+		}
+	}
+
+	/// Get the value of storage as it was at the start of the current transaction.
+	pub fn original_storage_at(&self, address: &Address, key: &H256) -> trie::Result<H256> {
+		let original = self.cache.borrow().get(address)
+			.and_then(|entry| entry.account.as_ref())
+			.and_then(|account| account.original_storage_at(key));
+
+		match original {
+			Some(value) => Ok(value),
+			// not changed in this transaction.
+			None => self.storage_at(address, key),
+		}
+	}
+
+	/// Make the current storage of all accounts the original storage of the next transaction.
+	pub fn clear_original_storage(&mut self) {
+		for entry in self.cache.get_mut().values_mut() {
+			if let Some(ref mut account) = entry.account {
+				account.clear_original_storage();
+			}
+		}
+	}
+
 	/// Get accounts' code.
 	pub fn code(&self, a: &Address) -> trie::Result<Option<Arc<Bytes>>> {
 		self.ensure_cached(a, RequireCache::Code, true,
@@ -645,8 +696,11 @@ impl<B: Backend> State<B> {
 	/// Mutate storage of account `a` so that it is `value` for `key`.
 	pub fn set_storage(&mut self, a: &Address, key: H256, value: H256) -> trie::Result<()> {
 		trace!(target: "state", "set_storage({}:{:x} to {:x})", a, key, value);
-		if self.storage_at(a, &key)? != value {
-			self.require(a, false)?.set_storage(key, value)
+		let current = self.storage_at(a, &key)?;
+		if current != value {
+			let mut account = self.require(a, false)?;
+			account.note_original_storage(key, current);
+			account.set_storage(key, value)
 		}
 
 		Ok(())
@@ -929,6 +983,8 @@ impl<B: Backend> State<B> {
 	/// Populates local cache if nothing found.
 	fn ensure_cached<F, U>(&self, a: &Address, require: RequireCache, check_null: bool, f: F) -> trie::Result<U>
 		where F: Fn(Option<&Account>) -> U {
+		self.note_access(a, None);
+
 		// check local cache first
 		if let Some(ref mut maybe_acc) = self.cache.borrow_mut().get_mut(a) {
 			if let Some(ref mut account) = maybe_acc.account {
@@ -976,6 +1032,8 @@ impl<B: Backend> State<B> {
 	fn require_or_from<'a, F, G>(&'a self, a: &Address, require_code: bool, default: F, not_default: G) -> trie::Result<RefMut<'a, Account>>
 		where F: FnOnce() -> Account, G: FnOnce(&mut Account),
 	{
+		self.note_access(a, None);
+
 		let contains_key = self.cache.borrow().contains_key(a);
 		if !contains_key {
 			match self.db.get_cached_account(a) {
@@ -1068,6 +1126,45 @@ impl<B: Backend> fmt::Debug for State<B> {
 	}
 }
 
+impl State<StateDB> {
+	/// Apply the overrides and commit them, so that virtual calls can be executed on top.
+	pub fn apply_overrides(&mut self, overrides: &StateOverride) -> Result<(), Error> {
+		if overrides.is_empty() {
+			return Ok(());
+		}
+
+		for (address, account) in overrides {
+			if let Some(ref code) = account.code {
+				self.reset_code(address, code.clone())?;
+			}
+
+			{
+				let mut acc = self.require(address, false)?;
+				if let Some(balance) = account.balance {
+					acc.set_balance(balance);
+				}
+				if let Some(nonce) = account.nonce {
+					acc.set_nonce(nonce);
+				}
+				if let Some(StorageOverride::Full(ref storage)) = account.storage {
+					acc.reset_storage(storage.iter().map(|(k, v)| (*k, *v)).collect());
+				}
+			}
+
+			if let Some(StorageOverride::Diff(ref storage)) = account.storage {
+				for (key, value) in storage {
+					self.set_storage(address, *key, *value)?;
+				}
+			}
+		}
+
+		self.commit()?;
+		// the shared account cache still holds the original accounts.
+		self.db = self.db.boxed_clone();
+		Ok(())
+	}
+}
+
 // TODO: cloning for `State` shouldn't be possible in general; Remove this and use
 // checkpoints where possible.
 impl Clone for State<StateDB> {
@@ -1087,6 +1184,7 @@ impl Clone for State<StateDB> {
 			root: self.root.clone(),
 			cache: RefCell::new(cache),
 			checkpoints: RefCell::new(Vec::new()),
+			accessed: RefCell::new(None),
 			account_start_nonce: self.account_start_nonce.clone(),
 			factories: self.factories.clone(),
 		}
@@ -2154,6 +2252,73 @@ mod tests {
 		assert_eq!(state.balance(&a).unwrap(), U256::from(0));
 	}
 
+	#[test]
+	fn original_storage_at() {
+		let mut state = get_temp_state();
+		let a = Address::zero();
+		let k = H256::from(U256::from(0));
+		let c0 = H256::from(U256::from(0xc0));
+		let c1 = H256::from(U256::from(0xc1));
+
+		state.set_storage(&a, k, c0).unwrap();
+		state.commit().unwrap();
+		assert_eq!(state.original_storage_at(&a, &k).unwrap(), c0);
+
+		state.checkpoint();
+		state.set_storage(&a, k, c1).unwrap();
+		state.checkpoint();
+		state.set_storage(&a, k, H256::new()).unwrap();
+		assert_eq!(state.original_storage_at(&a, &k).unwrap(), c0);
+		assert_eq!(state.storage_at(&a, &k).unwrap(), H256::new());
+
+		// nested checkpoints do not change the original value
+		state.revert_to_checkpoint();
+		assert_eq!(state.original_storage_at(&a, &k).unwrap(), c0);
+		state.discard_checkpoint();
+		assert_eq!(state.original_storage_at(&a, &k).unwrap(), c0);
+		assert_eq!(state.storage_at(&a, &k).unwrap(), c1);
+
+		// uncommitted changes from an earlier transaction are the original value of the next one
+		state.clear_original_storage();
+		assert_eq!(state.original_storage_at(&a, &k).unwrap(), c1);
+		state.checkpoint();
+		state.set_storage(&a, k, H256::new()).unwrap();
+		assert_eq!(state.original_storage_at(&a, &k).unwrap(), c1);
+
+		// reverting the whole transaction keeps it too
+		state.revert_to_checkpoint();
+		assert_eq!(state.original_storage_at(&a, &k).unwrap(), c1);
+		assert_eq!(state.storage_at(&a, &k).unwrap(), c1);
+	}
+
+	#[test]
+	fn records_accessed_accounts_and_storage() {
+		let mut state = get_temp_state();
+		let a = Address::zero();
+		let b = Address::from(1);
+		let c = Address::from(2);
+		let d = Address::from(3);
+		let k1 = H256::from(U256::from(1));
+		let k2 = H256::from(U256::from(2));
+
+		state.storage_at(&a, &k1).unwrap();
+		state.record_access();
+		assert!(state.accessed().is_empty());
+
+		state.storage_at(&a, &k2).unwrap();
+		state.set_storage(&b, k1, H256::from(U256::from(5))).unwrap();
+		state.storage_at(&b, &k1).unwrap();
+		state.balance(&c).unwrap();
+		state.code_size(&d).unwrap();
+
+		let accessed = state.accessed();
+		assert_eq!(accessed.len(), 4);
+		assert_eq!(accessed[&a].iter().cloned().collect::<Vec<_>>(), vec![k2]);
+		assert_eq!(accessed[&b].iter().cloned().collect::<Vec<_>>(), vec![k1]);
+		assert!(accessed[&c].is_empty());
+		assert!(accessed[&d].is_empty());
+	}
+
 	#[test]
 	fn create_empty() {
 		let mut state = get_temp_state();
@@ -2284,4 +2449,47 @@ mod tests {
 						   .into_iter().collect(),
 				   })).as_ref());
 	}
+
+	#[test]
+	fn should_apply_overrides() {
+		use std::collections::BTreeMap;
+		use types::state_override::{AccountOverride, StorageOverride};
+
+		let a = Address::from(1);
+		let b = Address::from(2);
+		let key = |k: u64| H256::from(&U256::from(k));
+
+		let (root, db) = {
+			let mut state = get_temp_state();
+			state.add_balance(&a, &100.into(), CleanupMode::NoEmpty).unwrap();
+			state.set_storage(&a, key(1), key(10)).unwrap();
+			state.set_storage(&a, key(2), key(20)).unwrap();
+			state.set_storage(&b, key(1), key(10)).unwrap();
+			state.set_storage(&b, key(2), key(20)).unwrap();
+			state.commit().unwrap();
+			state.drop()
+		};
+
+		let mut state = State::from_existing(db, root, U256::from(0u8), Default::default()).unwrap();
+		let mut overrides = BTreeMap::new();
+		overrides.insert(a, AccountOverride {
+			balance: Some(5.into()),
+			nonce: Some(7.into()),
+			code: Some(vec![0x60, 0x00]),
+			storage: Some(StorageOverride::Full(vec![(key(2), key(22))].into_iter().collect())),
+		});
+		overrides.insert(b, AccountOverride {
+			storage: Some(StorageOverride::Diff(vec![(key(2), key(22))].into_iter().collect())),
+			..Default::default()
+		});
+		state.apply_overrides(&overrides).unwrap();
+
+		assert_eq!(state.balance(&a).unwrap(), 5.into());
+		assert_eq!(state.nonce(&a).unwrap(), 7.into());
+		assert_eq!(state.code(&a).unwrap(), Some(Arc::new(vec![0x60, 0x00])));
+		assert_eq!(state.storage_at(&a, &key(1)).unwrap(), H256::zero());
+		assert_eq!(state.storage_at(&a, &key(2)).unwrap(), key(22));
+		assert_eq!(state.storage_at(&b, &key(1)).unwrap(), key(10));
+		assert_eq!(state.storage_at(&b, &key(2)).unwrap(), key(22));
+	}
 }
@@ -0,0 +1,535 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline pruning of an archive state database.
+//!
+//! State of the oldest kept canonical block is copied node by node into an empty
+//! database. Changes made by every following block are journalled under its own era,
+//! oldest first, exactly as the client would have journalled them when importing
+//! the block into an `OverlayRecentDB`. The client then prunes the copied states as
+//! they become ancient.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use account_db::{AccountDB, AccountDBMut};
+use blockchain::{BlockChain, BlockProvider};
+use db::COL_STATE;
+use error::Error;
+use ethereum_types::H256;
+use hash::{keccak, KECCAK_EMPTY, KECCAK_NULL_RLP};
+use hashdb::{HashDB, DBValue};
+use header::Header;
+use journaldb::{self, Algorithm};
+use kvdb::KeyValueDB;
+use trie::{Trie, TrieDB, TrieError};
+use types::basic_account::BasicAccount;
+
+use super::walk::{TrieVisitor, walk_trie, node_hash_at, nibbles_to_hash};
+
+/// Number of nodes written between database flushes.
+const FLUSH_INTERVAL: u64 = 64 * 1024;
+
+/// Summary of a finished prune.
+#[derive(Debug, PartialEq)]
+pub struct PruneReport {
+	/// Number of the most recent block with state in the pruned database.
+	pub best_block: u64,
+	/// Number of blocks which state was copied.
+	pub blocks: u64,
+	/// Number of copied trie nodes and code entries.
+	pub nodes: u64,
+}
+
+/// Copies state of the most recent `keep` canonical blocks from `source` into `target`
+/// and journals it so that `target` can be opened with `Algorithm::OverlayRecent`.
+///
+/// `source` has to contain full state of these blocks, i.e. be an archive database.
+/// Columns other than `COL_STATE` are not copied.
+pub fn prune_state(source: Arc<KeyValueDB>, target: Arc<KeyValueDB>, genesis: &[u8], keep: u64) -> Result<PruneReport, Error> {
+	let chain = BlockChain::new(Default::default(), genesis, source.clone());
+	let best_block = chain.best_block_number();
+	let first_block = best_block.saturating_sub(keep.saturating_sub(1));
+
+	let canon_header = |number: u64| -> Result<(H256, Header), Error> {
+		chain.block_hash(number)
+			.and_then(|hash| chain.block_header(&hash).map(|header| (hash, header)))
+			.ok_or_else(|| ::kvdb::Error::from(format!("Missing canonical block #{}", number)).into())
+	};
+
+	let (hash, header) = canon_header(first_block)?;
+	info!("Copying state of block #{} ({})", first_block, header.state_root());
+	let mut copy = StateCopy::new(&*source, &*target);
+	copy.copy(header.state_root())?;
+	target.flush()?;
+
+	let mut journal = journaldb::new(target.clone(), Algorithm::OverlayRecent, COL_STATE);
+	let mut batch = target.transaction();
+	journal.journal_under(&mut batch, first_block, &hash)?;
+	target.write(batch)?;
+
+	let source_db = ColumnDB { db: &*source, col: COL_STATE };
+	let mut parent_root = *header.state_root();
+	let mut nodes = copy.copied;
+	for number in first_block + 1..best_block + 1 {
+		let (hash, header) = canon_header(number)?;
+		info!("Journalling state of block #{} ({})", number, header.state_root());
+		nodes += journal_changes(&source_db, journal.as_hashdb_mut(), &parent_root, header.state_root())?;
+
+		let mut batch = target.transaction();
+		journal.journal_under(&mut batch, number, &hash)?;
+		target.write(batch)?;
+		parent_root = *header.state_root();
+	}
+
+	Ok(PruneReport {
+		best_block,
+		blocks: best_block - first_block + 1,
+		nodes,
+	})
+}
+
+/// Inserts nodes of the state at `root` which are not part of the state at `parent_root` into `journal`
+/// and removes the nodes which are only part of the latter, like a commit of the block would.
+/// Returns the number of inserted nodes.
+fn journal_changes(source: &HashDB, journal: &mut HashDB, parent_root: &H256, root: &H256) -> Result<u64, Error> {
+	let (inserted, missing) = {
+		let mut diff = StateDiff::new(source, &mut *journal, *parent_root, true);
+		walk_trie(source, root, &mut diff);
+		(diff.nodes.changed, diff.nodes.missing)
+	};
+
+	let missing = missing.or_else(|| {
+		let mut diff = StateDiff::new(source, &mut *journal, *root, false);
+		walk_trie(source, parent_root, &mut diff);
+		diff.nodes.missing
+	});
+
+	match missing {
+		Some(hash) => Err(TrieError::IncompleteDatabase(hash).into()),
+		None => Ok(inserted),
+	}
+}
+
+/// `HashDB` view of a single database column. Writes are buffered.
+#[derive(Clone, Copy)]
+struct ColumnDB<'a> {
+	db: &'a KeyValueDB,
+	col: Option<u32>,
+}
+
+impl<'a> HashDB for ColumnDB<'a> {
+	fn keys(&self) -> HashMap<H256, i32> {
+		self.db.iter(self.col).map(|(key, _)| (H256::from_slice(&*key), 1)).collect()
+	}
+
+	fn get(&self, key: &H256) -> Option<DBValue> {
+		self.db.get(self.col, key).expect("Low-level database error. Some issue with your hard disk?")
+	}
+
+	fn contains(&self, key: &H256) -> bool {
+		self.get(key).is_some()
+	}
+
+	fn insert(&mut self, value: &[u8]) -> H256 {
+		let key = keccak(value);
+		self.emplace(key, DBValue::from_slice(value));
+		key
+	}
+
+	fn emplace(&mut self, key: H256, value: DBValue) {
+		let mut batch = self.db.transaction();
+		batch.put(self.col, &key, &value);
+		self.db.write_buffered(batch);
+	}
+
+	fn remove(&mut self, key: &H256) {
+		let mut batch = self.db.transaction();
+		batch.delete(self.col, key);
+		self.db.write_buffered(batch);
+	}
+}
+
+/// Copies the node unless it's already in `target`. Returns true if the node was copied.
+fn copy_node(target: &mut HashDB, hash: &H256, rlp: &[u8]) -> bool {
+	if target.contains(hash) {
+		return false;
+	}
+
+	target.emplace(*hash, DBValue::from_slice(rlp));
+	true
+}
+
+/// Copies nodes missing from `target`. Subtrees already present in `target` are skipped.
+struct NodeCopy<'a> {
+	target: &'a mut HashDB,
+	copied: u64,
+	missing: Option<H256>,
+}
+
+impl<'a> NodeCopy<'a> {
+	fn new(target: &'a mut HashDB) -> Self {
+		NodeCopy {
+			target,
+			copied: 0,
+			missing: None,
+		}
+	}
+}
+
+impl<'a> TrieVisitor for NodeCopy<'a> {
+	fn node(&mut self, hash: &H256, _path: &[u8], rlp: &[u8]) -> bool {
+		let copied = copy_node(&mut *self.target, hash, rlp);
+		self.copied += copied as u64;
+		copied
+	}
+
+	fn missing(&mut self, hash: &H256, _path: &[u8]) {
+		self.missing = self.missing.or(Some(*hash));
+	}
+
+	fn value(&mut self, _key: &[u8], _value: &[u8]) {}
+}
+
+/// Inserts nodes of a trie into `target`, or removes them from it, unless they are found
+/// at the same path of the trie with `other_root`. Subtrees found there are skipped.
+struct NodeDiff<'a> {
+	source: &'a HashDB,
+	target: &'a mut HashDB,
+	other_root: H256,
+	insert: bool,
+	changed: u64,
+	missing: Option<H256>,
+}
+
+impl<'a> NodeDiff<'a> {
+	fn new(source: &'a HashDB, target: &'a mut HashDB, other_root: H256, insert: bool) -> Self {
+		NodeDiff {
+			source,
+			target,
+			other_root,
+			insert,
+			changed: 0,
+			missing: None,
+		}
+	}
+}
+
+impl<'a> TrieVisitor for NodeDiff<'a> {
+	fn node(&mut self, hash: &H256, path: &[u8], rlp: &[u8]) -> bool {
+		if node_hash_at(self.source, &self.other_root, path) == Some(*hash) {
+			return false;
+		}
+
+		match self.insert {
+			true => self.target.emplace(*hash, DBValue::from_slice(rlp)),
+			false => self.target.remove(hash),
+		}
+		self.changed += 1;
+		true
+	}
+
+	fn missing(&mut self, hash: &H256, _path: &[u8]) {
+		self.missing = self.missing.or(Some(*hash));
+	}
+
+	fn value(&mut self, _key: &[u8], _value: &[u8]) {}
+}
+
+/// `NodeDiff` of the account trie together with storage and code of every changed account.
+/// Code is only ever inserted, the client never removes it either.
+struct StateDiff<'a> {
+	nodes: NodeDiff<'a>,
+}
+
+impl<'a> StateDiff<'a> {
+	fn new(source: &'a HashDB, target: &'a mut HashDB, other_root: H256, insert: bool) -> Self {
+		StateDiff {
+			nodes: NodeDiff::new(source, target, other_root, insert),
+		}
+	}
+
+	fn other_account(&self, address_hash: &H256) -> Result<Option<BasicAccount>, H256> {
+		let other = TrieDB::new(self.nodes.source, &self.nodes.other_root)
+			.and_then(|trie| trie.get(address_hash));
+
+		match other {
+			Ok(other) => Ok(other.map(|rlp| ::rlp::decode(&rlp))),
+			Err(e) => match *e {
+				TrieError::InvalidStateRoot(hash) | TrieError::IncompleteDatabase(hash) => Err(hash),
+			},
+		}
+	}
+}
+
+impl<'a> TrieVisitor for StateDiff<'a> {
+	fn node(&mut self, hash: &H256, path: &[u8], rlp: &[u8]) -> bool {
+		self.nodes.node(hash, path, rlp)
+	}
+
+	fn missing(&mut self, hash: &H256, path: &[u8]) {
+		self.nodes.missing(hash, path)
+	}
+
+	fn value(&mut self, key: &[u8], value: &[u8]) {
+		let address_hash = match nibbles_to_hash(key) {
+			Some(address_hash) => address_hash,
+			None => return,
+		};
+
+		let account: BasicAccount = ::rlp::decode(value);
+		let other = match self.other_account(&address_hash) {
+			Ok(other) => other,
+			Err(hash) => {
+				self.nodes.missing = self.nodes.missing.or(Some(hash));
+				return;
+			},
+		};
+
+		let other_storage_root = other.as_ref().map_or(KECCAK_NULL_RLP, |other| other.storage_root);
+		let source = AccountDB::from_hash(self.nodes.source, address_hash);
+		let mut target = AccountDBMut::from_hash(&mut *self.nodes.target, address_hash);
+
+		let (changed, missing) = {
+			let mut storage = NodeDiff::new(&source, &mut target, other_storage_root, self.nodes.insert);
+			walk_trie(&source, &account.storage_root, &mut storage);
+			(storage.changed, storage.missing)
+		};
+		self.nodes.changed += changed;
+		self.nodes.missing = self.nodes.missing.or(missing);
+
+		let new_code = other.map_or(true, |other| other.code_hash != account.code_hash);
+		if self.nodes.insert && new_code && account.code_hash != KECCAK_EMPTY {
+			match source.get(&account.code_hash) {
+				Some(code) => {
+					target.emplace(account.code_hash, code);
+					self.nodes.changed += 1;
+				},
+				None => self.nodes.missing = self.nodes.missing.or(Some(account.code_hash)),
+			}
+		}
+	}
+}
+
+/// Copies the account trie together with storage and code of every account.
+struct StateCopy<'a> {
+	source: ColumnDB<'a>,
+	target: ColumnDB<'a>,
+	copied: u64,
+	flushed: u64,
+	missing: Option<H256>,
+}
+
+impl<'a> StateCopy<'a> {
+	fn new(source: &'a KeyValueDB, target: &'a KeyValueDB) -> Self {
+		StateCopy {
+			source: ColumnDB { db: source, col: COL_STATE },
+			target: ColumnDB { db: target, col: COL_STATE },
+			copied: 0,
+			flushed: 0,
+			missing: None,
+		}
+	}
+
+	fn copy(&mut self, root: &H256) -> Result<(), Error> {
+		let source = self.source;
+		walk_trie(&source, root, self);
+
+		match self.missing {
+			Some(hash) => Err(TrieError::IncompleteDatabase(hash).into()),
+			None => Ok(()),
+		}
+	}
+
+	fn copy_account(&mut self, address_hash: H256, account: BasicAccount) {
+		let source = AccountDB::from_hash(&self.source, address_hash);
+		let mut target = AccountDBMut::from_hash(&mut self.target, address_hash);
+
+		let (copied, missing) = {
+			let mut storage = NodeCopy::new(&mut target);
+			walk_trie(&source, &account.storage_root, &mut storage);
+			(storage.copied, storage.missing)
+		};
+		self.copied += copied;
+		self.missing = self.missing.or(missing);
+
+		if account.code_hash != KECCAK_EMPTY && !target.contains(&account.code_hash) {
+			match source.get(&account.code_hash) {
+				Some(code) => {
+					target.emplace(account.code_hash, code);
+					self.copied += 1;
+				},
+				None => self.missing = self.missing.or(Some(account.code_hash)),
+			}
+		}
+	}
+}
+
+impl<'a> TrieVisitor for StateCopy<'a> {
+	fn node(&mut self, hash: &H256, _path: &[u8], rlp: &[u8]) -> bool {
+		let copied = copy_node(&mut self.target, hash, rlp);
+		self.copied += copied as u64;
+		copied
+	}
+
+	fn missing(&mut self, hash: &H256, _path: &[u8]) {
+		self.missing = self.missing.or(Some(*hash));
+	}
+
+	fn value(&mut self, key: &[u8], value: &[u8]) {
+		let address_hash = match nibbles_to_hash(key) {
+			Some(address_hash) => address_hash,
+			None => return,
+		};
+
+		self.copy_account(address_hash, ::rlp::decode(value));
+
+		if self.copied - self.flushed >= FLUSH_INTERVAL {
+			self.target.db.flush().expect("Low-level database error. Some issue with your hard disk?");
+			self.flushed = self.copied;
+			debug!("Copied {} state nodes", self.copied);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+	use account_db::AccountDBMut;
+	use client::{BlockChainClient, BlockId, Client, ClientConfig, MiningBlockChainClient};
+	use db::{COL_STATE, NUM_COLUMNS};
+	use ethereum_types::{Address, H256, U256};
+	use ethkey::KeyPair;
+	use hash::{keccak, KECCAK_NULL_RLP};
+	use hashdb::HashDB;
+	use io::IoChannel;
+	use journaldb::{self, Algorithm};
+	use kvdb_memorydb;
+	use miner::Miner;
+	use rlp::encode;
+	use spec::Spec;
+	use transaction::{Action, Transaction};
+	use trie::{TrieMut, SecTrieDBMut};
+	use types::basic_account::BasicAccount;
+	use super::{ColumnDB, StateCopy, prune_state};
+
+	fn push_transfer(client: &Client, spec: &Spec, nonce: u64) -> Address {
+		let keypair = KeyPair::from_secret_slice(&keccak("")).unwrap();
+		let recipient = Address::from(0x100 + nonce);
+		let mut block = client.prepare_open_block(Address::default(), (3141562.into(), 31415620.into()), vec![]);
+		block.push_transaction(Transaction {
+			nonce: nonce.into(),
+			gas_price: U256::zero(),
+			gas: 21000.into(),
+			action: Action::Call(recipient),
+			value: 1.into(),
+			data: vec![],
+		}.sign(keypair.secret(), Some(spec.chain_id())), None).unwrap();
+
+		let block = block.close_and_lock().seal(&*spec.engine, vec![]).unwrap();
+		client.import_sealed_block(block).unwrap();
+		recipient
+	}
+
+	#[test]
+	fn copies_accounts_storage_and_code() {
+		let source = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let target = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let address_hash = H256::from(1);
+
+		let mut db = ColumnDB { db: &*source, col: COL_STATE };
+		let mut storage_root = H256::new();
+		let code_hash = {
+			let mut account_db = AccountDBMut::from_hash(&mut db, address_hash);
+			{
+				let mut storage = SecTrieDBMut::new(&mut account_db, &mut storage_root);
+				storage.insert(&[1], &[2; 32]).unwrap();
+				storage.insert(&[2], &[3; 32]).unwrap();
+			}
+			account_db.insert(b"code")
+		};
+
+		let mut state_root = H256::new();
+		{
+			let mut state = SecTrieDBMut::new(&mut db, &mut state_root);
+			let account = BasicAccount { nonce: 1.into(), balance: U256::zero(), storage_root, code_hash };
+			state.insert(&address_hash, &encode(&account)).unwrap();
+			let empty = BasicAccount { nonce: 0.into(), balance: 1.into(), storage_root: KECCAK_NULL_RLP, code_hash: ::hash::KECCAK_EMPTY };
+			state.insert(&H256::from(2), &encode(&empty)).unwrap();
+		}
+		source.flush().unwrap();
+
+		let mut copy = StateCopy::new(&*source, &*target);
+		copy.copy(&state_root).unwrap();
+		copy.copy(&state_root).unwrap();
+		target.flush().unwrap();
+
+		assert_eq!(copy.copied as usize, source.iter(COL_STATE).count());
+		assert_eq!(target.iter(COL_STATE).count(), source.iter(COL_STATE).count());
+
+		let journal = journaldb::new(target.clone(), Algorithm::OverlayRecent, COL_STATE);
+		assert!(journal.contains(&state_root));
+	}
+
+	#[test]
+	fn client_imports_blocks_and_prunes_copied_state() {
+		let spec = Spec::new_null();
+		let source = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let target = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+
+		let config = ClientConfig { pruning: Algorithm::Archive, ..Default::default() };
+		let client = Client::new(config, &spec, source.clone(), Arc::new(Miner::with_spec(&spec)), IoChannel::disconnected()).unwrap();
+		let recipients: Vec<_> = (0..10).map(|nonce| push_transfer(&client, &spec, nonce)).collect();
+		let first_root = client.block_header(BlockId::Number(7)).unwrap().state_root();
+		drop(client);
+
+		for col in (0..NUM_COLUMNS.unwrap()).map(Some).filter(|col| *col != COL_STATE) {
+			let mut batch = target.transaction();
+			for (key, value) in source.iter(col) {
+				batch.put(col, &key, &value);
+			}
+			target.write(batch).unwrap();
+		}
+
+		let report = prune_state(source, target.clone(), &spec.genesis_block(), 4).unwrap();
+		assert_eq!((report.best_block, report.blocks), (10, 4));
+
+		let config = ClientConfig { history: 8, history_mem: 0, ..Default::default() };
+		let client = Client::new(config, &spec, target.clone(), Arc::new(Miner::with_spec(&spec)), IoChannel::disconnected()).unwrap();
+		assert!(client.state_at(BlockId::Number(7)).is_some());
+		assert!(client.state_at(BlockId::Number(6)).is_none());
+		assert!(target.get(COL_STATE, &first_root).unwrap().is_some());
+
+		for nonce in 10..18 {
+			push_transfer(&client, &spec, nonce);
+		}
+		assert_eq!(client.chain_info().best_block_number, 18);
+		// state of the oldest copied block has become ancient
+		assert!(target.get(COL_STATE, &first_root).unwrap().is_none());
+		for recipient in &recipients {
+			assert_eq!(client.balance(recipient, BlockId::Latest), Some(1.into()));
+		}
+	}
+
+	#[test]
+	fn fails_on_incomplete_state() {
+		let source = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let target = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+
+		let mut copy = StateCopy::new(&*source, &*target);
+		assert!(copy.copy(&H256::from(1)).is_err());
+	}
+}
@@ -16,7 +16,7 @@
 
 //! Execution environment substate.
 use std::collections::HashSet;
-use ethereum_types::{U256, Address};
+use ethereum_types::Address;
 use log_entry::LogEntry;
 use evm::{Schedule, CleanDustMode};
 use super::CleanupMode;
@@ -34,8 +34,8 @@ pub struct Substate {
 	/// Any logs.
 	pub logs: Vec<LogEntry>,
 
-	/// Refund counter of SSTORE nonzero -> zero.
-	pub sstore_clears_count: U256,
+	/// Refund counter of SSTORE. May be negative within a nested call under EIP-1283.
+	pub sstore_clears_refund: i64,
 
 	/// Created contracts.
 	pub contracts_created: Vec<Address>,
@@ -52,7 +52,7 @@ impl Substate {
 		self.suicides.extend(s.suicides);
 		self.touched.extend(s.touched);
 		self.logs.extend(s.logs);
-		self.sstore_clears_count = self.sstore_clears_count + s.sstore_clears_count;
+		self.sstore_clears_refund += s.sstore_clears_refund;
 		self.contracts_created.extend(s.contracts_created);
 	}
 
@@ -86,7 +86,7 @@ mod tests {
 			topics: vec![],
 			data: vec![]
 		});
-		sub_state.sstore_clears_count = 5.into();
+		sub_state.sstore_clears_refund = 5;
 		sub_state.suicides.insert(10u64.into());
 
 		let mut sub_state_2 = Substate::new();
@@ -96,11 +96,11 @@ mod tests {
 			topics: vec![],
 			data: vec![]
 		});
-		sub_state_2.sstore_clears_count = 7.into();
+		sub_state_2.sstore_clears_refund = 7;
 
 		sub_state.accrue(sub_state_2);
 		assert_eq!(sub_state.contracts_created.len(), 2);
-		assert_eq!(sub_state.sstore_clears_count, 12.into());
+		assert_eq!(sub_state.sstore_clears_refund, 12);
 		assert_eq!(sub_state.suicides.len(), 1);
 	}
 }
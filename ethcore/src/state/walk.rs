@@ -0,0 +1,253 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Traversal of raw trie nodes.
+//!
+//! Unlike `TrieDB` iteration, the walk exposes every node stored in the database,
+//! lets the visitor skip whole subtrees and carries on past missing nodes.
+
+use ethereum_types::H256;
+use hash::KECCAK_NULL_RLP;
+use hashdb::HashDB;
+use rlp::Rlp;
+use trie::node::Node;
+
+/// Receives nodes of a trie during a walk.
+pub trait TrieVisitor {
+	/// Called for every node stored in the database, with the nibbles leading to it.
+	/// Children of the node are skipped if this returns false.
+	fn node(&mut self, hash: &H256, path: &[u8], rlp: &[u8]) -> bool;
+
+	/// Called for a node which is referenced by its parent but missing from the database.
+	fn missing(&mut self, hash: &H256, path: &[u8]);
+
+	/// Called for every value with the nibbles of its key.
+	fn value(&mut self, key: &[u8], value: &[u8]);
+}
+
+enum NodeRef {
+	Hash(H256),
+	Inline(Vec<u8>),
+}
+
+impl NodeRef {
+	fn from_raw(raw: &[u8]) -> Option<Self> {
+		let rlp = Rlp::new(raw);
+		if rlp.is_empty() {
+			None
+		} else if rlp.is_data() && rlp.size() == 32 {
+			Some(NodeRef::Hash(rlp.as_val()))
+		} else {
+			Some(NodeRef::Inline(raw.to_vec()))
+		}
+	}
+}
+
+/// Walks the trie with given root depth-first, in key order.
+pub fn walk_trie(db: &HashDB, root: &H256, visitor: &mut TrieVisitor) {
+	walk_trie_prefix(db, root, &[], visitor)
+}
+
+/// Walks the part of the trie with keys starting with `prefix` nibbles.
+/// Nodes on the way to the prefix are reported to the visitor as well.
+pub fn walk_trie_prefix(db: &HashDB, root: &H256, prefix: &[u8], visitor: &mut TrieVisitor) {
+	if *root == KECCAK_NULL_RLP {
+		return;
+	}
+
+	// whether keys below `path` may start with the prefix
+	let compatible = |path: &[u8]| path.iter().zip(prefix).all(|(a, b)| a == b);
+	let has_prefix = |key: &[u8]| key.len() >= prefix.len() && compatible(key);
+
+	let mut stack = vec![(NodeRef::Hash(*root), Vec::new())];
+	while let Some((node_ref, path)) = stack.pop() {
+		let rlp = match node_ref {
+			NodeRef::Hash(hash) => match db.get(&hash) {
+				Some(rlp) => match visitor.node(&hash, &path, &rlp) {
+					true => rlp.into_vec(),
+					false => continue,
+				},
+				None => {
+					visitor.missing(&hash, &path);
+					continue;
+				},
+			},
+			NodeRef::Inline(rlp) => rlp,
+		};
+
+		match Node::decoded(&rlp) {
+			Node::Empty => {},
+			Node::Leaf(partial, value) => {
+				let mut key = path;
+				key.extend(partial.iter());
+				if has_prefix(&key) {
+					visitor.value(&key, value);
+				}
+			},
+			Node::Extension(partial, child) => if let Some(child) = NodeRef::from_raw(child) {
+				let mut child_path = path;
+				child_path.extend(partial.iter());
+				if compatible(&child_path) {
+					stack.push((child, child_path));
+				}
+			},
+			Node::Branch(children, value) => {
+				if let Some(value) = value {
+					if has_prefix(&path) {
+						visitor.value(&path, value);
+					}
+				}
+
+				// push in reverse to visit children in key order
+				for (nibble, child) in children.iter().enumerate().rev() {
+					if let Some(child) = NodeRef::from_raw(child) {
+						let mut child_path = path.clone();
+						child_path.push(nibble as u8);
+						if compatible(&child_path) {
+							stack.push((child, child_path));
+						}
+					}
+				}
+			},
+		}
+	}
+}
+
+/// Returns hash of the node at `path` nibbles from the root of the trie.
+/// Returns `None` if there's no such node, it's inlined into its parent or it's missing from the database.
+pub fn node_hash_at(db: &HashDB, root: &H256, path: &[u8]) -> Option<H256> {
+	if *root == KECCAK_NULL_RLP {
+		return None;
+	}
+
+	let mut node_ref = NodeRef::Hash(*root);
+	let mut depth = 0;
+	loop {
+		let rlp = match node_ref {
+			NodeRef::Hash(hash) => match depth == path.len() {
+				true => return Some(hash),
+				false => db.get(&hash)?.into_vec(),
+			},
+			NodeRef::Inline(rlp) => match depth == path.len() {
+				true => return None,
+				false => rlp,
+			},
+		};
+
+		node_ref = match Node::decoded(&rlp) {
+			Node::Extension(partial, child) => {
+				let rest = &path[depth..];
+				if partial.len() > rest.len() || partial.iter().zip(rest).any(|(a, b)| a != *b) {
+					return None;
+				}
+				depth += partial.len();
+				NodeRef::from_raw(child)?
+			},
+			Node::Branch(children, _) => {
+				let child = NodeRef::from_raw(children[path[depth] as usize])?;
+				depth += 1;
+				child
+			},
+			Node::Empty | Node::Leaf(..) => return None,
+		};
+	}
+}
+
+/// Converts nibbles of a full secure trie key into the hash they represent.
+pub fn nibbles_to_hash(nibbles: &[u8]) -> Option<H256> {
+	if nibbles.len() != 64 {
+		return None;
+	}
+
+	let mut hash = H256::new();
+	for (i, pair) in nibbles.chunks(2).enumerate() {
+		hash[i] = (pair[0] << 4) | pair[1];
+	}
+	Some(hash)
+}
+
+/// Formats nibbles as a hex string.
+pub fn nibbles_to_hex(nibbles: &[u8]) -> String {
+	nibbles.iter().map(|n| format!("{:x}", n)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use ethereum_types::H256;
+	use hashdb::HashDB;
+	use memorydb::MemoryDB;
+	use trie::{TrieMut, SecTrieDBMut};
+	use super::{TrieVisitor, walk_trie, walk_trie_prefix, node_hash_at, nibbles_to_hash};
+
+	#[derive(Default)]
+	struct Collect {
+		nodes: usize,
+		paths: Vec<(H256, Vec<u8>)>,
+		values: Vec<(H256, Vec<u8>)>,
+		missing: Vec<H256>,
+	}
+
+	impl TrieVisitor for Collect {
+		fn node(&mut self, hash: &H256, path: &[u8], _rlp: &[u8]) -> bool {
+			self.nodes += 1;
+			self.paths.push((*hash, path.to_vec()));
+			true
+		}
+
+		fn missing(&mut self, hash: &H256, _path: &[u8]) {
+			self.missing.push(*hash);
+		}
+
+		fn value(&mut self, key: &[u8], value: &[u8]) {
+			self.values.push((nibbles_to_hash(key).unwrap(), value.to_vec()));
+		}
+	}
+
+	#[test]
+	fn walks_all_values_and_reports_missing_nodes() {
+		let mut db = MemoryDB::new();
+		let mut root = H256::new();
+		{
+			let mut trie = SecTrieDBMut::new(&mut db, &mut root);
+			for i in 0..100u8 {
+				trie.insert(&[i], &[i; 40]).unwrap();
+			}
+		}
+
+		let mut collect = Collect::default();
+		walk_trie(&db, &root, &mut collect);
+		assert_eq!(collect.values.len(), 100);
+		assert_eq!(collect.nodes, db.keys().len());
+		assert!(collect.missing.is_empty());
+		assert!(collect.values.iter().any(|&(ref key, _)| *key == ::hash::keccak(&[7u8])));
+
+		let mut collect_parts = Collect::default();
+		for nibble in 0..16 {
+			walk_trie_prefix(&db, &root, &[nibble], &mut collect_parts);
+		}
+		assert_eq!(collect_parts.values, collect.values);
+
+		for &(ref hash, ref path) in &collect.paths {
+			assert_eq!(node_hash_at(&db, &root, path), Some(*hash));
+		}
+		assert_eq!(node_hash_at(&db, &root, &[0; 64]), None);
+
+		db.remove_and_purge(&root);
+		let mut collect = Collect::default();
+		walk_trie(&db, &root, &mut collect);
+		assert_eq!(collect.missing, vec![root]);
+	}
+}
@@ -171,6 +171,20 @@ fn returns_logs_with_limit() {
 	assert_eq!(logs.len(), 0);
 }
 
+#[test]
+fn returns_paged_logs() {
+	let dummy_block = get_good_dummy_block();
+	let client = get_test_client_with_blocks(vec![dummy_block.clone()]);
+	let logs = client.logs_paged(Filter {
+		from_block: BlockId::Earliest,
+		to_block: BlockId::Latest,
+		address: None,
+		topics: vec![],
+		limit: None,
+	}, None, 10);
+	assert_eq!(logs, (vec![], None));
+}
+
 #[test]
 fn returns_block_body() {
 	let dummy_block = get_good_dummy_block();
@@ -368,3 +382,56 @@ fn transaction_proof() {
 	assert_eq!(state.balance(&Address::default()).unwrap(), 5.into());
 	assert_eq!(state.balance(&address).unwrap(), 95.into());
 }
+
+#[test]
+fn prestate_includes_read_and_written_storage() {
+	use std::collections::BTreeMap;
+	use client::{CallAnalytics, StateOverride, AccountOverride, StorageOverride};
+	use trace::{TracerPreset, PresetTrace};
+	use ethereum_types::H256;
+	use rustc_hex::FromHex;
+
+	let client = generate_dummy_client(0);
+	let sender = Address::from(0x10);
+	let contract = Address::from(0x20);
+
+	// SLOAD slot 1, SSTORE 5 into slot 2.
+	let mut storage = BTreeMap::new();
+	storage.insert(H256::from(1), H256::from(7));
+	storage.insert(H256::from(2), H256::from(3));
+	let mut state_override = StateOverride::new();
+	state_override.insert(sender, AccountOverride { balance: Some(1_000_000.into()), ..Default::default() });
+	state_override.insert(contract, AccountOverride {
+		code: Some("60015450600560025500".from_hex().unwrap()),
+		storage: Some(StorageOverride::Full(storage.clone())),
+		..Default::default()
+	});
+
+	let transaction = Transaction {
+		nonce: 0.into(),
+		gas_price: 0.into(),
+		gas: 100_000.into(),
+		action: Action::Call(contract),
+		value: 0.into(),
+		data: Vec::new(),
+	}.fake_sign(sender);
+
+	let presets = [TracerPreset::Prestate, TracerPreset::Call];
+	let (executed, traces) = client.call_with_presets(&transaction, CallAnalytics::default(), &presets, BlockId::Latest, &state_override).unwrap();
+	assert!(executed.trace.is_empty());
+	assert_eq!(traces.len(), 2);
+
+	match traces[0] {
+		PresetTrace::Prestate(ref prestate) => {
+			let accounts = prestate.get();
+			assert_eq!(accounts[&contract].storage, storage);
+			assert_eq!(accounts[&sender].balance, 1_000_000.into());
+			assert!(accounts[&sender].storage.is_empty());
+		},
+		_ => panic!("expected prestate trace"),
+	}
+	match traces[1] {
+		PresetTrace::Call(Some(ref frame)) => assert_eq!(frame.to, Some(contract)),
+		_ => panic!("expected call trace"),
+	}
+}
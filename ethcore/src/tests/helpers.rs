@@ -368,6 +368,7 @@ pub fn get_default_ethash_params() -> EthashParams {
 		metropolis_difficulty_increment_divisor: 9,
 		homestead_transition: 1150000,
 		duration_limit: 13,
+		difficulty_rules: Vec::new(),
 		block_reward: 0.into(),
 		difficulty_hardfork_transition: u64::max_value(),
 		difficulty_hardfork_bound_divisor: U256::from(0),
@@ -376,12 +377,8 @@ pub fn get_default_ethash_params() -> EthashParams {
 		ecip1010_pause_transition: u64::max_value(),
 		ecip1010_continue_transition: u64::max_value(),
 		ecip1017_era_rounds: u64::max_value(),
-		mcip3_transition: u64::max_value(),
-		mcip3_miner_reward: 0.into(),
-		mcip3_ubi_reward: 0.into(),
-		mcip3_ubi_contract: "0000000000000000000000000000000000000001".into(),
-		mcip3_dev_reward: 0.into(),
-		mcip3_dev_contract: "0000000000000000000000000000000000000001".into(),
+		reward_schedule: Vec::new(),
+		uncle_policy: Vec::new(),
 		eip649_transition: u64::max_value(),
 		eip649_delay: 3_000_000,
 		eip649_reward: None,
@@ -0,0 +1,173 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! Nested call tree tracer.
+
+use ethereum_types::{U256, Address};
+use bytes::Bytes;
+use vm::{ActionParams, CallType};
+use trace::trace::{Call, Create, RewardType};
+use trace::{Tracer, TraceError};
+
+/// Kind of a call frame.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallFrameType {
+	/// Message call of given type.
+	Call(CallType),
+	/// Contract creation.
+	Create,
+	/// Contract suicide.
+	Suicide,
+}
+
+/// Single frame of a nested call tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallFrame {
+	/// Kind of the frame.
+	pub frame_type: CallFrameType,
+	/// The sending account.
+	pub from: Address,
+	/// The destination account, the created contract or the suicide heir.
+	/// `None` for failed creations.
+	pub to: Option<Address>,
+	/// The value transferred.
+	pub value: U256,
+	/// The gas available for the frame.
+	pub gas: U256,
+	/// The gas used by the frame. Failed frames use all of the gas available,
+	/// frames reverted with `REVERT` only the gas spent before reverting.
+	pub gas_used: U256,
+	/// Call data or init code.
+	pub input: Bytes,
+	/// Returned data, revert data or code of the created contract.
+	pub output: Bytes,
+	/// Error, if the frame failed.
+	pub error: Option<TraceError>,
+	/// Frames entered from this one, in order.
+	pub calls: Vec<CallFrame>,
+}
+
+/// Tracer building a nested call tree with input, output, gas and error of every frame.
+#[derive(Default)]
+pub struct CallTracer {
+	frames: Vec<CallFrame>,
+}
+
+impl CallTracer {
+	fn push_call(&mut self, call: Option<Call>, gas_used: Option<U256>, output: Bytes, error: Option<TraceError>, subs: Vec<CallFrame>) {
+		let call = call.expect("self.prepare_trace_call().is_some(): so we must be tracing: qed");
+		self.frames.push(CallFrame {
+			frame_type: CallFrameType::Call(call.call_type),
+			from: call.from,
+			to: Some(call.to),
+			value: call.value,
+			gas: call.gas,
+			gas_used: gas_used.unwrap_or(call.gas),
+			input: call.input,
+			output: output,
+			error: error,
+			calls: subs,
+		});
+	}
+
+	fn push_create(&mut self, create: Option<Create>, address: Option<Address>, gas_used: Option<U256>, output: Bytes, error: Option<TraceError>, subs: Vec<CallFrame>) {
+		let create = create.expect("self.prepare_trace_create().is_some(): so we must be tracing: qed");
+		self.frames.push(CallFrame {
+			frame_type: CallFrameType::Create,
+			from: create.from,
+			to: address,
+			value: create.value,
+			gas: create.gas,
+			gas_used: gas_used.unwrap_or(create.gas),
+			input: create.init,
+			output: output,
+			error: error,
+			calls: subs,
+		});
+	}
+}
+
+impl Tracer for CallTracer {
+	type Output = CallFrame;
+
+	fn prepare_trace_call(&self, params: &ActionParams) -> Option<Call> {
+		Some(Call::from(params.clone()))
+	}
+
+	fn prepare_trace_create(&self, params: &ActionParams) -> Option<Create> {
+		Some(Create::from(params.clone()))
+	}
+
+	fn prepare_trace_output(&self) -> Option<Bytes> {
+		Some(vec![])
+	}
+
+	fn trace_call(&mut self, call: Option<Call>, gas_used: U256, output: Option<Bytes>, subs: Vec<CallFrame>) {
+		let output = output.expect("self.prepare_trace_output().is_some(): so we must be tracing: qed");
+		self.push_call(call, Some(gas_used), output, None, subs);
+	}
+
+	fn trace_create(&mut self, create: Option<Create>, gas_used: U256, code: Option<Bytes>, address: Address, subs: Vec<CallFrame>) {
+		let code = code.expect("self.prepare_trace_output().is_some(): so we must be tracing: qed");
+		self.push_create(create, Some(address), Some(gas_used), code, None, subs);
+	}
+
+	fn trace_failed_call(&mut self, call: Option<Call>, subs: Vec<CallFrame>, error: TraceError) {
+		self.push_call(call, None, vec![], Some(error), subs);
+	}
+
+	fn trace_failed_create(&mut self, create: Option<Create>, subs: Vec<CallFrame>, error: TraceError) {
+		self.push_create(create, None, None, vec![], Some(error), subs);
+	}
+
+	fn trace_reverted_call(&mut self, call: Option<Call>, gas_used: U256, output: Option<Bytes>, subs: Vec<CallFrame>) {
+		let output = output.expect("self.prepare_trace_output().is_some(): so we must be tracing: qed");
+		self.push_call(call, Some(gas_used), output, Some(TraceError::Reverted), subs);
+	}
+
+	fn trace_reverted_create(&mut self, create: Option<Create>, gas_used: U256, output: Option<Bytes>, subs: Vec<CallFrame>) {
+		let output = output.expect("self.prepare_trace_output().is_some(): so we must be tracing: qed");
+		self.push_create(create, None, Some(gas_used), output, Some(TraceError::Reverted), subs);
+	}
+
+	fn trace_suicide(&mut self, address: Address, balance: U256, refund_address: Address) {
+		self.frames.push(CallFrame {
+			frame_type: CallFrameType::Suicide,
+			from: address,
+			to: Some(refund_address),
+			value: balance,
+			gas: U256::zero(),
+			gas_used: U256::zero(),
+			input: vec![],
+			output: vec![],
+			error: None,
+			calls: vec![],
+		});
+	}
+
+	fn trace_reward(&mut self, _author: Address, _value: U256, _reward_type: RewardType) {
+		// rewards are not part of transaction execution.
+	}
+
+	fn subtracer(&self) -> Self {
+		CallTracer::default()
+	}
+
+	fn drain(self) -> Vec<CallFrame> {
+		self.frames
+	}
+}
@@ -16,17 +16,23 @@
 
 //! Tracing
 
+mod call_tracer;
 mod config;
 mod db;
 mod executive_tracer;
 mod import;
 mod noop_tracer;
+mod preset;
+mod struct_logger;
 mod types;
 
 pub use self::config::Config;
 pub use self::db::TraceDB;
 pub use self::noop_tracer::{NoopTracer, NoopVMTracer};
 pub use self::executive_tracer::{ExecutiveTracer, ExecutiveVMTracer};
+pub use self::struct_logger::{StructLogger, StructLoggerOptions, StructLog};
+pub use self::call_tracer::{CallTracer, CallFrame, CallFrameType};
+pub use self::preset::{TracerPreset, PresetTrace, PresetTracer};
 pub use self::import::ImportRequest;
 pub use self::localized::LocalizedTrace;
 
@@ -82,6 +88,18 @@ pub trait Tracer: Send {
 	/// Stores failed create trace.
 	fn trace_failed_create(&mut self, create: Option<Create>, subs: Vec<Self::Output>, error: TraceError);
 
+	/// Stores trace of a call reverted with `REVERT`, which still reports the gas it used and its output.
+	/// Defaults to a failed call trace.
+	fn trace_reverted_call(&mut self, call: Option<Call>, _gas_used: U256, _output: Option<Bytes>, subs: Vec<Self::Output>) {
+		self.trace_failed_call(call, subs, TraceError::Reverted)
+	}
+
+	/// Stores trace of a create reverted with `REVERT`, which still reports the gas it used and its output.
+	/// Defaults to a failed create trace.
+	fn trace_reverted_create(&mut self, create: Option<Create>, _gas_used: U256, _output: Option<Bytes>, subs: Vec<Self::Output>) {
+		self.trace_failed_create(create, subs, TraceError::Reverted)
+	}
+
 	/// Stores suicide info.
 	fn trace_suicide(&mut self, address: Address, balance: U256, refund_address: Address);
 
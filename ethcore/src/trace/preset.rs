@@ -0,0 +1,130 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! Named tracer presets.
+
+use ethereum_types::{U256, Address};
+use bytes::Bytes;
+use vm::ActionParams;
+use pod_state::PodState;
+use trace::trace::{Call, Create, RewardType};
+use trace::{Tracer, TraceError, FlatTrace, ExecutiveTracer, CallTracer, CallFrame};
+
+/// Tracer preset which can be requested in addition to the regular analytics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TracerPreset {
+	/// Nested call tree, see `CallFrame`.
+	Call,
+	/// Pre-execution state of touched accounts.
+	Prestate,
+}
+
+/// Result of tracing with a `TracerPreset`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PresetTrace {
+	/// Root frame of the call tree. `None` if the transaction failed before entering it.
+	Call(Option<CallFrame>),
+	/// Pre-execution state of touched accounts with the storage slots read or written by the transaction.
+	Prestate(PodState),
+}
+
+/// Tracer collecting the flat trace of a transaction together with its call tree,
+/// so that presets come from the same execution as the regular analytics.
+#[derive(Default)]
+pub struct PresetTracer {
+	flat: ExecutiveTracer,
+	calls: CallTracer,
+}
+
+fn split(subs: Vec<(Vec<FlatTrace>, Vec<CallFrame>)>) -> (Vec<FlatTrace>, Vec<CallFrame>) {
+	let mut flat = Vec::new();
+	let mut calls = Vec::new();
+	for (sub_flat, sub_calls) in subs {
+		flat.extend(sub_flat);
+		calls.extend(sub_calls);
+	}
+	(flat, calls)
+}
+
+impl Tracer for PresetTracer {
+	type Output = (Vec<FlatTrace>, Vec<CallFrame>);
+
+	fn prepare_trace_call(&self, params: &ActionParams) -> Option<Call> {
+		self.flat.prepare_trace_call(params)
+	}
+
+	fn prepare_trace_create(&self, params: &ActionParams) -> Option<Create> {
+		self.flat.prepare_trace_create(params)
+	}
+
+	fn prepare_trace_output(&self) -> Option<Bytes> {
+		self.flat.prepare_trace_output()
+	}
+
+	fn trace_call(&mut self, call: Option<Call>, gas_used: U256, output: Option<Bytes>, subs: Vec<Self::Output>) {
+		let (flat, calls) = split(subs);
+		self.flat.trace_call(call.clone(), gas_used, output.clone(), flat);
+		self.calls.trace_call(call, gas_used, output, calls);
+	}
+
+	fn trace_create(&mut self, create: Option<Create>, gas_used: U256, code: Option<Bytes>, address: Address, subs: Vec<Self::Output>) {
+		let (flat, calls) = split(subs);
+		self.flat.trace_create(create.clone(), gas_used, code.clone(), address, flat);
+		self.calls.trace_create(create, gas_used, code, address, calls);
+	}
+
+	fn trace_failed_call(&mut self, call: Option<Call>, subs: Vec<Self::Output>, error: TraceError) {
+		let (flat, calls) = split(subs);
+		self.flat.trace_failed_call(call.clone(), flat, error.clone());
+		self.calls.trace_failed_call(call, calls, error);
+	}
+
+	fn trace_failed_create(&mut self, create: Option<Create>, subs: Vec<Self::Output>, error: TraceError) {
+		let (flat, calls) = split(subs);
+		self.flat.trace_failed_create(create.clone(), flat, error.clone());
+		self.calls.trace_failed_create(create, calls, error);
+	}
+
+	fn trace_reverted_call(&mut self, call: Option<Call>, gas_used: U256, output: Option<Bytes>, subs: Vec<Self::Output>) {
+		let (flat, calls) = split(subs);
+		self.flat.trace_reverted_call(call.clone(), gas_used, output.clone(), flat);
+		self.calls.trace_reverted_call(call, gas_used, output, calls);
+	}
+
+	fn trace_reverted_create(&mut self, create: Option<Create>, gas_used: U256, output: Option<Bytes>, subs: Vec<Self::Output>) {
+		let (flat, calls) = split(subs);
+		self.flat.trace_reverted_create(create.clone(), gas_used, output.clone(), flat);
+		self.calls.trace_reverted_create(create, gas_used, output, calls);
+	}
+
+	fn trace_suicide(&mut self, address: Address, balance: U256, refund_address: Address) {
+		self.flat.trace_suicide(address, balance, refund_address);
+		self.calls.trace_suicide(address, balance, refund_address);
+	}
+
+	fn trace_reward(&mut self, author: Address, value: U256, reward_type: RewardType) {
+		self.flat.trace_reward(author, value, reward_type);
+	}
+
+	fn subtracer(&self) -> Self {
+		PresetTracer::default()
+	}
+
+	fn drain(self) -> Vec<Self::Output> {
+		vec![(self.flat.drain(), self.calls.drain())]
+	}
+}
@@ -0,0 +1,161 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! Geth-compatible struct logger.
+
+use std::collections::BTreeMap;
+use ethereum_types::{H256, U256};
+use bytes::Bytes;
+use evm::INSTRUCTIONS;
+use trace::VMTracer;
+
+/// Controls which parts of the machine state are captured by `StructLogger`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StructLoggerOptions {
+	/// Do not capture the stack.
+	pub disable_stack: bool,
+	/// Do not capture the memory.
+	pub disable_memory: bool,
+	/// Do not capture the storage.
+	pub disable_storage: bool,
+}
+
+/// Machine state right before a single instruction is executed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructLog {
+	/// Program counter.
+	pub pc: usize,
+	/// Executed instruction.
+	pub instruction: u8,
+	/// Gas available before the instruction.
+	pub gas: U256,
+	/// Gas cost of the instruction.
+	pub gas_cost: U256,
+	/// Call depth, starting from 1.
+	pub depth: usize,
+	/// Stack, bottom first.
+	pub stack: Option<Vec<U256>>,
+	/// Memory.
+	pub memory: Option<Bytes>,
+	/// Storage slots of the executing account touched so far in this frame.
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+impl StructLog {
+	/// Mnemonic of the executed instruction.
+	pub fn op_name(&self) -> &'static str {
+		INSTRUCTIONS[self.instruction as usize].name
+	}
+}
+
+/// VM tracer producing a flat list of `StructLog`s, one per executed instruction.
+///
+/// The tracer only sees the values pushed by each instruction and the memory it
+/// writes, so the stack and memory are reconstructed from those. Memory expanded
+/// without being written to (e.g. by `RETURN` or `SHA3`) is not reflected.
+pub struct StructLogger {
+	options: StructLoggerOptions,
+	depth: usize,
+	gas: U256,
+	instruction: u8,
+	stack: Vec<U256>,
+	memory: Bytes,
+	storage: BTreeMap<H256, H256>,
+	logs: Vec<StructLog>,
+}
+
+impl StructLogger {
+	/// Create a new top-level instance.
+	pub fn toplevel(options: StructLoggerOptions) -> Self {
+		StructLogger {
+			options: options,
+			depth: 0,
+			gas: U256::zero(),
+			instruction: 0,
+			stack: Vec::new(),
+			memory: Vec::new(),
+			storage: BTreeMap::new(),
+			logs: Vec::new(),
+		}
+	}
+}
+
+impl VMTracer for StructLogger {
+	type Output = Vec<StructLog>;
+
+	fn trace_next_instruction(&mut self, _pc: usize, _instruction: u8, current_gas: U256) -> bool {
+		self.gas = current_gas;
+		true
+	}
+
+	fn trace_prepare_execute(&mut self, pc: usize, instruction: u8, gas_cost: U256) {
+		self.instruction = instruction;
+
+		// like geth, the log of `SSTORE` already contains the stored value.
+		if !self.options.disable_storage && INSTRUCTIONS[instruction as usize].name == "SSTORE" && self.stack.len() >= 2 {
+			let len = self.stack.len();
+			self.storage.insert(self.stack[len - 1].into(), self.stack[len - 2].into());
+		}
+
+		let log = StructLog {
+			pc: pc,
+			instruction: instruction,
+			gas: self.gas,
+			gas_cost: gas_cost,
+			depth: self.depth,
+			stack: if self.options.disable_stack { None } else { Some(self.stack.clone()) },
+			memory: if self.options.disable_memory { None } else { Some(self.memory.clone()) },
+			storage: if self.options.disable_storage { None } else { Some(self.storage.clone()) },
+		};
+		self.logs.push(log);
+	}
+
+	fn trace_executed(&mut self, _gas_used: U256, stack_push: &[U256], mem_diff: Option<(usize, &[u8])>, _store_diff: Option<(U256, U256)>) {
+		let info = &INSTRUCTIONS[self.instruction as usize];
+
+		if !self.options.disable_storage && info.name == "SLOAD" {
+			if let (Some(key), Some(value)) = (self.stack.last().cloned(), stack_push.first()) {
+				self.storage.insert(key.into(), H256::from(*value));
+			}
+		}
+
+		let len = self.stack.len();
+		self.stack.truncate(len.saturating_sub(info.args));
+		self.stack.extend_from_slice(stack_push);
+
+		if let (false, Some((offset, data))) = (self.options.disable_memory, mem_diff) {
+			let end = offset + data.len();
+			if self.memory.len() < end {
+				// memory is always expanded in 32-byte words.
+				self.memory.resize((end + 31) / 32 * 32, 0);
+			}
+			self.memory[offset..end].copy_from_slice(data);
+		}
+	}
+
+	fn prepare_subtrace(&self, _code: &[u8]) -> Self {
+		let mut sub = StructLogger::toplevel(self.options);
+		sub.depth = self.depth + 1;
+		sub
+	}
+
+	fn done_subtrace(&mut self, sub: Self) {
+		self.logs.extend(sub.logs);
+	}
+
+	fn drain(self) -> Option<Vec<StructLog>> { Some(self.logs) }
+}
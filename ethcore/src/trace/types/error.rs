@@ -21,7 +21,7 @@ use rlp::{Encodable, RlpStream, Decodable, DecoderError, UntrustedRlp};
 use vm::Error as VmError;
 
 /// Trace evm errors.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Error {
 	/// `OutOfGas` is returned when transaction execution runs out of gas.
 	OutOfGas,
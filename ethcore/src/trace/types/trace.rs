@@ -141,6 +141,8 @@ pub enum RewardType {
 	Block,
 	/// Uncle
 	Uncle,
+	/// Payout to a non-author recipient of the block reward schedule
+	External,
 }
 
 impl Encodable for RewardType {
@@ -148,6 +150,7 @@ impl Encodable for RewardType {
 		let v = match *self {
 			RewardType::Block => 0u32,
 			RewardType::Uncle => 1,
+			RewardType::External => 2,
 		};
 		Encodable::rlp_append(&v, s);
 	}
@@ -158,6 +161,7 @@ impl Decodable for RewardType {
 		rlp.as_val().and_then(|v| Ok(match v {
 			0u32 => RewardType::Block,
 			1 => RewardType::Uncle,
+			2 => RewardType::External,
 			_ => return Err(DecoderError::Custom("Invalid value of RewardType item")),
 		}))
 	}
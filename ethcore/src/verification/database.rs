@@ -0,0 +1,444 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline integrity checks of the client database.
+//!
+//! Walks the state trie at a given block looking for missing nodes and
+//! recomputes transactions, uncles and receipts roots of canonical blocks.
+
+use std::fmt;
+use std::sync::Arc;
+
+use account_db::AccountDB;
+use blockchain::{BlockChain, BlockProvider};
+use db::COL_STATE;
+use error::Error;
+use ethereum_types::H256;
+use hash::{keccak, KECCAK_EMPTY};
+use hashdb::HashDB;
+use header::BlockNumber;
+use ids::BlockId;
+use journaldb::{self, Algorithm};
+use kvdb::KeyValueDB;
+use state::walk::{TrieVisitor, walk_trie, nibbles_to_hash, nibbles_to_hex};
+use triehash::ordered_trie_root;
+use types::basic_account::BasicAccount;
+
+/// Maximal number of problems kept in `VerifyReport`. All problems are logged as they are found.
+pub const MAX_REPORTED_PROBLEMS: usize = 1000;
+
+/// Integrity problem found in the database.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Problem {
+	/// Trie node is missing. `account` is the address hash for nodes of storage tries.
+	MissingNode {
+		/// Hash of the node.
+		hash: H256,
+		/// Address hash of the account owning the storage trie.
+		account: Option<H256>,
+		/// Nibbles leading to the node.
+		path: Vec<u8>,
+	},
+	/// Code of an account is missing.
+	MissingCode {
+		/// Address hash of the account.
+		account: H256,
+		/// Hash of the missing code.
+		code_hash: H256,
+	},
+	/// Header or body of a canonical block is missing.
+	MissingBlock(BlockNumber),
+	/// Receipts of a canonical block are missing.
+	MissingReceipts(BlockNumber),
+	/// Transactions root in the header doesn't match the block body.
+	TransactionsRoot(BlockNumber, H256),
+	/// Uncles hash in the header doesn't match the block body.
+	UnclesHash(BlockNumber, H256),
+	/// Receipts root in the header doesn't match stored receipts.
+	ReceiptsRoot(BlockNumber, H256),
+}
+
+impl fmt::Display for Problem {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Problem::MissingNode { ref hash, account: None, ref path } =>
+				write!(f, "Missing state node {:?} at path 0x{}", hash, nibbles_to_hex(path)),
+			Problem::MissingNode { ref hash, account: Some(ref account), ref path } =>
+				write!(f, "Missing storage node {:?} of account {:?} at path 0x{}", hash, account, nibbles_to_hex(path)),
+			Problem::MissingCode { ref account, ref code_hash } =>
+				write!(f, "Missing code {:?} of account {:?}", code_hash, account),
+			Problem::MissingBlock(number) => write!(f, "Missing block #{}", number),
+			Problem::MissingReceipts(number) => write!(f, "Missing receipts of block #{}", number),
+			Problem::TransactionsRoot(number, ref found) =>
+				write!(f, "Transactions of block #{} don't match the header, computed root {:?}", number, found),
+			Problem::UnclesHash(number, ref found) =>
+				write!(f, "Uncles of block #{} don't match the header, computed hash {:?}", number, found),
+			Problem::ReceiptsRoot(number, ref found) =>
+				write!(f, "Receipts of block #{} don't match the header, computed root {:?}", number, found),
+		}
+	}
+}
+
+/// Database verification parameters.
+#[derive(Debug, PartialEq, Clone)]
+pub struct VerifyParams {
+	/// Block which state is verified.
+	pub at: BlockId,
+	/// Whether to walk storage tries and check code of every account.
+	pub storage: bool,
+	/// First block which roots are verified.
+	pub from: BlockId,
+	/// Last block which roots are verified.
+	pub to: BlockId,
+}
+
+/// Result of database verification.
+#[derive(Debug, PartialEq)]
+pub struct VerifyReport {
+	/// Number of the block which state was verified.
+	pub block: BlockNumber,
+	/// Number of visited trie nodes.
+	pub nodes: u64,
+	/// Number of visited accounts.
+	pub accounts: u64,
+	/// Number of blocks which roots were verified.
+	pub blocks: u64,
+	/// Number of found problems.
+	pub problem_count: u64,
+	/// First `MAX_REPORTED_PROBLEMS` found problems.
+	pub problems: Vec<Problem>,
+}
+
+/// Logs and counts found problems, keeping only the first `MAX_REPORTED_PROBLEMS`.
+#[derive(Default)]
+struct Problems {
+	count: u64,
+	kept: Vec<Problem>,
+}
+
+impl Problems {
+	fn push(&mut self, problem: Problem) {
+		warn!("{}", problem);
+		self.count += 1;
+		if self.kept.len() < MAX_REPORTED_PROBLEMS {
+			self.kept.push(problem);
+		}
+	}
+}
+
+/// Verifies the state and block roots stored in `db`.
+pub fn verify_database(db: Arc<KeyValueDB>, algorithm: Algorithm, genesis: &[u8], params: &VerifyParams) -> Result<VerifyReport, Error> {
+	let chain = BlockChain::new(Default::default(), genesis, db.clone());
+	let block = block_number(&chain, &params.at)?;
+	let from = block_number(&chain, &params.from)?;
+	let to = block_number(&chain, &params.to)?;
+
+	let state_root = chain.block_hash(block)
+		.and_then(|hash| chain.block_header(&hash))
+		.map(|header| *header.state_root())
+		.ok_or_else(|| ::kvdb::Error::from(format!("Missing header of block #{}", block)))?;
+
+	info!("Verifying state of block #{} ({})", block, state_root);
+	let journal = journaldb::new(db, algorithm, COL_STATE);
+	let mut verifier = StateVerifier::new(journal.as_hashdb(), params.storage);
+	walk_trie(journal.as_hashdb(), &state_root, &mut verifier);
+
+	info!("Verifying roots of blocks #{}..#{}", from, to);
+	let mut problems = verifier.problems;
+	for number in from..to + 1 {
+		verify_block(&chain, number, &mut problems);
+	}
+
+	Ok(VerifyReport {
+		block,
+		nodes: verifier.nodes,
+		accounts: verifier.accounts,
+		blocks: (to + 1).saturating_sub(from),
+		problem_count: problems.count,
+		problems: problems.kept,
+	})
+}
+
+fn block_number(chain: &BlockChain, id: &BlockId) -> Result<BlockNumber, Error> {
+	let number = match *id {
+		BlockId::Number(number) => Some(number),
+		BlockId::Hash(ref hash) => chain.block_number(hash),
+		BlockId::Earliest => Some(0),
+		BlockId::Latest | BlockId::Pending => Some(chain.best_block_number()),
+		BlockId::Timestamp(timestamp) => chain.block_number_at_timestamp(timestamp),
+	};
+
+	match number {
+		Some(number) if number <= chain.best_block_number() => Ok(number),
+		_ => Err(::kvdb::Error::from(format!("Unknown block {:?}", id)).into()),
+	}
+}
+
+fn verify_block(chain: &BlockChain, number: BlockNumber, problems: &mut Problems) {
+	let hash = match chain.block_hash(number) {
+		Some(hash) => hash,
+		None => return problems.push(Problem::MissingBlock(number)),
+	};
+
+	let (header, body) = match (chain.block_header(&hash), chain.block_body(&hash)) {
+		(Some(header), Some(body)) => (header, body),
+		_ => return problems.push(Problem::MissingBlock(number)),
+	};
+
+	let body = body.rlp();
+	let transactions = body.at(0);
+	let transactions_root = ordered_trie_root(transactions.iter().map(|tx| tx.as_raw()));
+	if transactions_root != *header.transactions_root() {
+		problems.push(Problem::TransactionsRoot(number, transactions_root));
+	}
+
+	let uncles_hash = keccak(body.at(1).as_raw());
+	if uncles_hash != *header.uncles_hash() {
+		problems.push(Problem::UnclesHash(number, uncles_hash));
+	}
+
+	// receipts of empty blocks are not always stored
+	let receipts = chain.block_receipts(&hash)
+		.map(|receipts| receipts.receipts)
+		.or_else(|| if transactions.item_count() == 0 { Some(Vec::new()) } else { None });
+
+	match receipts {
+		Some(receipts) => {
+			let receipts_root = ordered_trie_root(receipts.iter().map(::rlp::encode));
+			if receipts_root != *header.receipts_root() {
+				problems.push(Problem::ReceiptsRoot(number, receipts_root));
+			}
+		},
+		None => problems.push(Problem::MissingReceipts(number)),
+	}
+}
+
+/// Counts nodes of a storage trie and records missing ones.
+struct StorageVerifier<'a> {
+	account: H256,
+	nodes: &'a mut u64,
+	problems: &'a mut Problems,
+}
+
+impl<'a> TrieVisitor for StorageVerifier<'a> {
+	fn node(&mut self, _hash: &H256, _path: &[u8], _rlp: &[u8]) -> bool {
+		*self.nodes += 1;
+		true
+	}
+
+	fn missing(&mut self, hash: &H256, path: &[u8]) {
+		self.problems.push(Problem::MissingNode { hash: *hash, account: Some(self.account), path: path.to_vec() });
+	}
+
+	fn value(&mut self, _key: &[u8], _value: &[u8]) {}
+}
+
+/// Walks the account trie, optionally descending into storage tries.
+struct StateVerifier<'a> {
+	db: &'a HashDB,
+	storage: bool,
+	nodes: u64,
+	accounts: u64,
+	problems: Problems,
+}
+
+impl<'a> StateVerifier<'a> {
+	fn new(db: &'a HashDB, storage: bool) -> Self {
+		StateVerifier {
+			db,
+			storage,
+			nodes: 0,
+			accounts: 0,
+			problems: Problems::default(),
+		}
+	}
+}
+
+impl<'a> TrieVisitor for StateVerifier<'a> {
+	fn node(&mut self, _hash: &H256, _path: &[u8], _rlp: &[u8]) -> bool {
+		self.nodes += 1;
+		true
+	}
+
+	fn missing(&mut self, hash: &H256, path: &[u8]) {
+		self.problems.push(Problem::MissingNode { hash: *hash, account: None, path: path.to_vec() });
+	}
+
+	fn value(&mut self, key: &[u8], value: &[u8]) {
+		self.accounts += 1;
+		if self.accounts % 100_000 == 0 {
+			info!("Verified {} accounts, {} nodes", self.accounts, self.nodes);
+		}
+
+		let address_hash = match (self.storage, nibbles_to_hash(key)) {
+			(true, Some(address_hash)) => address_hash,
+			_ => return,
+		};
+
+		let account: BasicAccount = ::rlp::decode(value);
+		let db = self.db;
+		let account_db = AccountDB::from_hash(db, address_hash);
+		walk_trie(&account_db, &account.storage_root, &mut StorageVerifier {
+			account: address_hash,
+			nodes: &mut self.nodes,
+			problems: &mut self.problems,
+		});
+
+		if account.code_hash != KECCAK_EMPTY && !account_db.contains(&account.code_hash) {
+			self.problems.push(Problem::MissingCode { account: address_hash, code_hash: account.code_hash });
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+	use blockchain::BlockChain;
+	use blockchain::generator::Block;
+	use db::{COL_STATE, NUM_COLUMNS};
+	use ethereum_types::{Address, H256, U256};
+	use hash::{keccak, KECCAK_NULL_RLP, KECCAK_EMPTY_LIST_RLP};
+	use hashdb::HashDB;
+	use ids::BlockId;
+	use journaldb::Algorithm;
+	use kvdb::{DBTransaction, KeyValueDB};
+	use kvdb_memorydb;
+	use memorydb::MemoryDB;
+	use rlp::encode;
+	use trie::{TrieMut, SecTrieDBMut};
+	use types::basic_account::BasicAccount;
+	use super::{verify_database, Problem, Problems, VerifyParams, MAX_REPORTED_PROBLEMS};
+
+	fn all_blocks(storage: bool) -> VerifyParams {
+		VerifyParams {
+			at: BlockId::Latest,
+			storage,
+			from: BlockId::Earliest,
+			to: BlockId::Latest,
+		}
+	}
+
+	#[test]
+	fn reports_missing_state_root() {
+		let state_root = H256::from(1);
+		let mut genesis = Block::default();
+		genesis.header.set_state_root(state_root);
+		let genesis = genesis.encoded();
+
+		let db: Arc<KeyValueDB> = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let params = all_blocks(true);
+
+		let report = verify_database(db.clone(), Algorithm::Archive, &genesis, &params).unwrap();
+		assert_eq!(report.block, 0);
+		assert_eq!(report.blocks, 1);
+		assert_eq!(report.problems, vec![Problem::MissingNode { hash: state_root, account: None, path: vec![] }]);
+
+		let mut batch = db.transaction();
+		batch.put(COL_STATE, &state_root, &[0x80]);
+		db.write(batch).unwrap();
+		let report = verify_database(db, Algorithm::Archive, &genesis, &params).unwrap();
+		assert_eq!(report.problems, vec![]);
+		assert_eq!(report.nodes, 1);
+
+		let params = VerifyParams { at: BlockId::Hash(H256::from(2)), ..params };
+		let db = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		assert!(verify_database(db, Algorithm::Archive, &genesis, &params).is_err());
+	}
+
+	#[test]
+	fn reports_missing_storage_nodes_and_code() {
+		let address = Address::from(1);
+		let storage_root = H256::from(2);
+		let code_hash = H256::from(3);
+
+		let mut state = MemoryDB::new();
+		let mut state_root = H256::new();
+		{
+			let mut trie = SecTrieDBMut::new(&mut state, &mut state_root);
+			let account = BasicAccount { nonce: U256::zero(), balance: 1.into(), storage_root, code_hash };
+			trie.insert(&address, &encode(&account)).unwrap();
+		}
+
+		let db: Arc<KeyValueDB> = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let mut batch = db.transaction();
+		for key in state.keys().keys() {
+			batch.put(COL_STATE, key, &state.get(key).unwrap());
+		}
+		db.write(batch).unwrap();
+
+		let mut genesis = Block::default();
+		genesis.header.set_state_root(state_root);
+		let genesis = genesis.encoded();
+
+		let report = verify_database(db.clone(), Algorithm::Archive, &genesis, &all_blocks(true)).unwrap();
+		assert_eq!(report.accounts, 1);
+		assert_eq!(report.problem_count, 2);
+		assert_eq!(report.problems, vec![
+			Problem::MissingNode { hash: storage_root, account: Some(keccak(&address)), path: vec![] },
+			Problem::MissingCode { account: keccak(&address), code_hash },
+		]);
+
+		let report = verify_database(db, Algorithm::Archive, &genesis, &all_blocks(false)).unwrap();
+		assert_eq!(report.problems, vec![]);
+	}
+
+	#[test]
+	fn reports_corrupted_block_roots() {
+		let genesis = Block::default();
+		let db: Arc<KeyValueDB> = Arc::new(kvdb_memorydb::create(NUM_COLUMNS.unwrap()));
+		let chain = BlockChain::new(Default::default(), &genesis.encoded(), db.clone());
+
+		let mut parent = genesis.clone();
+		for number in 1..5 {
+			let mut block = Block::default();
+			block.header.set_parent_hash(parent.hash());
+			block.header.set_number(number);
+			block.header.set_difficulty(10.into());
+			match number {
+				1 => block.header.set_transactions_root(H256::from(1)),
+				2 => block.header.set_uncles_hash(H256::from(2)),
+				3 => block.header.set_receipts_root(H256::from(3)),
+				_ => {},
+			}
+
+			let mut batch = DBTransaction::new();
+			chain.insert_block(&mut batch, &block.encoded(), vec![]);
+			db.write(batch).unwrap();
+			chain.commit();
+			parent = block;
+		}
+
+		let report = verify_database(db, Algorithm::Archive, &genesis.encoded(), &all_blocks(true)).unwrap();
+		assert_eq!(report.blocks, 5);
+		assert_eq!(report.problems, vec![
+			Problem::TransactionsRoot(1, KECCAK_NULL_RLP),
+			Problem::UnclesHash(2, KECCAK_EMPTY_LIST_RLP),
+			Problem::ReceiptsRoot(3, KECCAK_NULL_RLP),
+		]);
+	}
+
+	#[test]
+	fn keeps_first_problems_only() {
+		let mut problems = Problems::default();
+		for number in 0..MAX_REPORTED_PROBLEMS as u64 + 10 {
+			problems.push(Problem::MissingBlock(number));
+		}
+
+		assert_eq!(problems.count, MAX_REPORTED_PROBLEMS as u64 + 10);
+		assert_eq!(problems.kept.len(), MAX_REPORTED_PROBLEMS);
+		assert_eq!(problems.kept[0], Problem::MissingBlock(0));
+	}
+}
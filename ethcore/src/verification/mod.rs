@@ -19,6 +19,7 @@
 pub mod verification;
 pub mod verifier;
 pub mod queue;
+pub mod database;
 mod canon_verifier;
 mod noop_verifier;
 
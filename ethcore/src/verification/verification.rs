@@ -135,6 +135,7 @@ pub fn verify_block_family(header: &Header, parent: &Header, engine: &EthEngine,
 fn verify_uncles(header: &Header, bytes: &[u8], bc: &BlockProvider, engine: &EthEngine) -> Result<(), Error> {
 	let num_uncles = UntrustedRlp::new(bytes).at(2)?.item_count()?;
 	let max_uncles = engine.maximum_uncle_count(header.number());
+	let max_uncle_age = engine.maximum_uncle_age(header.number());
 	if num_uncles != 0 {
 		if num_uncles > max_uncles {
 			return Err(From::from(BlockError::TooManyUncles(OutOfBounds {
@@ -148,7 +149,7 @@ fn verify_uncles(header: &Header, bytes: &[u8], bc: &BlockProvider, engine: &Eth
 		excluded.insert(header.hash());
 		let mut hash = header.parent_hash().clone();
 		excluded.insert(hash.clone());
-		for _ in 0..engine.maximum_uncle_age() {
+		for _ in 0..max_uncle_age {
 			match bc.block_details(&hash) {
 				Some(details) => {
 					excluded.insert(details.parent.clone());
@@ -182,7 +183,7 @@ fn verify_uncles(header: &Header, bytes: &[u8], bc: &BlockProvider, engine: &Eth
 			//												(8 Invalid)
 
 			let depth = if header.number() > uncle.number() { header.number() - uncle.number() } else { 0 };
-			if depth > engine.maximum_uncle_age() as u64 {
+			if depth > max_uncle_age as u64 {
 				return Err(From::from(BlockError::UncleTooOld(OutOfBounds { min: Some(header.number() - depth), max: Some(header.number() - 1), found: uncle.number() })));
 			}
 			else if depth < 1 {
@@ -502,6 +503,35 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn uncle_policy_limits_count_and_depth() {
+		let spec = ::ethereum::new_uncle_policy_test();
+		let engine = &*spec.engine;
+		let bc = TestBlockChain::new();
+
+		let mut header = Header::default();
+		header.set_number(10);
+		let uncle = |number: u64, extra: u8| {
+			let mut uncle = Header::default();
+			uncle.set_number(number);
+			uncle.extra_data_mut().push(extra);
+			uncle
+		};
+
+		let too_many = vec![uncle(9, 1), uncle(9, 2), uncle(9, 3), uncle(9, 4)];
+		check_fail(verify_uncles(&header, &create_test_block_with_data(&header, &[], &too_many), &bc, engine),
+			TooManyUncles(OutOfBounds { max: Some(3), min: None, found: 4 }));
+
+		let too_old = vec![uncle(6, 1)];
+		check_fail(verify_uncles(&header, &create_test_block_with_data(&header, &[], &too_old), &bc, engine),
+			UncleTooOld(OutOfBounds { min: Some(6), max: Some(9), found: 6 }));
+
+		// within the policy depth the uncle is checked further.
+		let old_enough = vec![uncle(7, 1)];
+		check_fail(verify_uncles(&header, &create_test_block_with_data(&header, &[], &old_enough), &bc, engine),
+			UnknownUncleParent(H256::default()));
+	}
+
 	#[test]
 	fn test_verify_block_basic_with_invalid_transactions() {
 		let spec = Spec::new_test();
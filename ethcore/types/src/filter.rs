@@ -18,7 +18,8 @@
 
 use ethereum_types::{H256, Address, Bloom, BloomInput};
 use ids::BlockId;
-use log_entry::LogEntry;
+use log_entry::{LogEntry, LocalizedLogEntry};
+use BlockNumber;
 
 /// Blockchain Filter.
 #[derive(Debug, PartialEq)]
@@ -107,6 +108,26 @@ impl Filter {
 	}
 }
 
+/// Position of a log in the canonical chain.
+///
+/// Used to resume a logs query which exceeded the result limit.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct LogCursor {
+	/// Number of the block containing the log.
+	pub block_number: BlockNumber,
+	/// Log position in the block.
+	pub log_index: usize,
+}
+
+impl<'a> From<&'a LocalizedLogEntry> for LogCursor {
+	fn from(log: &'a LocalizedLogEntry) -> Self {
+		LogCursor {
+			block_number: log.block_number,
+			log_index: log.log_index,
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use ethereum_types::Bloom;
@@ -33,6 +33,8 @@ pub enum BlockId {
 	Latest,
 	/// Pending block.
 	Pending,
+	/// Last block within canon blockchain with timestamp at or before the given one.
+	Timestamp(u64),
 }
 
 /// Uniquely identifies transaction.
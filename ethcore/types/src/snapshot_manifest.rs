@@ -16,11 +16,67 @@
 
 //! Snapshot manifest type definition
 
+use std::str::FromStr;
 use ethereum_types::H256;
 use rlp::*;
 use bytes::Bytes;
 
+/// Compression of snapshot chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChunkCompression {
+	/// Snappy, used by all snapshots before manifest version 3.
+	Snappy,
+	/// Zstandard.
+	Zstd,
+}
+
+impl ChunkCompression {
+	/// All compressions this client can read.
+	pub fn supported() -> Vec<ChunkCompression> {
+		vec![ChunkCompression::Snappy, ChunkCompression::Zstd]
+	}
+
+	/// Identifier of the compression on the wire.
+	pub fn id(&self) -> u8 {
+		match *self {
+			ChunkCompression::Snappy => 0,
+			ChunkCompression::Zstd => 1,
+		}
+	}
+
+	/// Compression with given identifier.
+	pub fn from_id(id: u8) -> Option<Self> {
+		match id {
+			0 => Some(ChunkCompression::Snappy),
+			1 => Some(ChunkCompression::Zstd),
+			_ => None,
+		}
+	}
+}
+
+impl Default for ChunkCompression {
+	fn default() -> Self {
+		ChunkCompression::Snappy
+	}
+}
+
+impl FromStr for ChunkCompression {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"snappy" => Ok(ChunkCompression::Snappy),
+			"zstd" => Ok(ChunkCompression::Zstd),
+			_ => Err("Invalid snapshot compression given. Expected snappy/zstd.".into()),
+		}
+	}
+}
+
 /// Manifest data.
+///
+/// Version 1 manifests have no version field, version 2 manifests prepend one and
+/// version 3 manifests append the chunk compression. Snappy-compressed snapshots
+/// are still encoded as version 2 so that older clients can read them.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ManifestData {
 	/// Snapshot format version.
@@ -35,18 +91,24 @@ pub struct ManifestData {
 	pub block_number: u64,
 	/// Block hash this snapshot was taken at.
 	pub block_hash: H256,
+	/// Compression of the chunks.
+	pub compression: ChunkCompression,
 }
 
 impl ManifestData {
 	/// Encode the manifest data to rlp.
 	pub fn into_rlp(self) -> Bytes {
-		let mut stream = RlpStream::new_list(6);
+		let with_compression = self.compression != ChunkCompression::Snappy;
+		let mut stream = RlpStream::new_list(if with_compression { 7 } else { 6 });
 		stream.append(&self.version);
 		stream.append_list(&self.state_hashes);
 		stream.append_list(&self.block_hashes);
 		stream.append(&self.state_root);
 		stream.append(&self.block_number);
 		stream.append(&self.block_hash);
+		if with_compression {
+			stream.append(&self.compression.id());
+		}
 
 		stream.out()
 	}
@@ -54,7 +116,8 @@ impl ManifestData {
 	/// Try to restore manifest data from raw bytes, interpreted as RLP.
 	pub fn from_rlp(raw: &[u8]) -> Result<Self, DecoderError> {
 		let decoder = UntrustedRlp::new(raw);
-		let (start, version) = if decoder.item_count()? == 5 {
+		let item_count = decoder.item_count()?;
+		let (start, version) = if item_count == 5 {
 			(0, 1)
 		} else {
 			(1, decoder.val_at(0)?)
@@ -65,6 +128,11 @@ impl ManifestData {
 		let state_root: H256 = decoder.val_at(start + 2)?;
 		let block_number: u64 = decoder.val_at(start + 3)?;
 		let block_hash: H256 = decoder.val_at(start + 4)?;
+		let compression = match item_count {
+			7 => ChunkCompression::from_id(decoder.val_at(6)?)
+				.ok_or(DecoderError::Custom("Unknown snapshot chunk compression"))?,
+			_ => ChunkCompression::Snappy,
+		};
 
 		Ok(ManifestData {
 			version: version,
@@ -73,7 +141,34 @@ impl ManifestData {
 			state_root: state_root,
 			block_number: block_number,
 			block_hash: block_hash,
+			compression: compression,
 		})
 	}
 }
 
+#[cfg(test)]
+mod tests {
+	use ethereum_types::H256;
+	use super::{ManifestData, ChunkCompression};
+
+	#[test]
+	fn manifest_roundtrip() {
+		let mut manifest = ManifestData {
+			version: 2,
+			state_hashes: vec![H256::from(1), H256::from(2)],
+			block_hashes: vec![H256::from(3)],
+			state_root: H256::from(4),
+			block_number: 5,
+			block_hash: H256::from(6),
+			compression: ChunkCompression::Snappy,
+		};
+
+		let v2 = manifest.clone().into_rlp();
+		assert_eq!(ManifestData::from_rlp(&v2).unwrap(), manifest);
+
+		manifest.compression = ChunkCompression::Zstd;
+		let v3 = manifest.clone().into_rlp();
+		assert!(v3.len() > v2.len());
+		assert_eq!(ManifestData::from_rlp(&v3).unwrap(), manifest);
+	}
+}
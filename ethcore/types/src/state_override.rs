@@ -0,0 +1,46 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! State overrides for virtual calls.
+
+use std::collections::BTreeMap;
+use ethereum_types::{H256, U256, Address};
+use bytes::Bytes;
+
+/// Replacement of account storage.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum StorageOverride {
+	/// Replace the whole storage, all other slots are considered empty.
+	Full(BTreeMap<H256, H256>),
+	/// Replace only the given slots.
+	Diff(BTreeMap<H256, H256>),
+}
+
+/// Fields of an account to override before a call.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct AccountOverride {
+	/// Account balance.
+	pub balance: Option<U256>,
+	/// Account nonce.
+	pub nonce: Option<U256>,
+	/// Account code.
+	pub code: Option<Bytes>,
+	/// Account storage.
+	pub storage: Option<StorageOverride>,
+}
+
+/// Per-account overrides of the state a call is executed on.
+pub type StateOverride = BTreeMap<Address, AccountOverride>;
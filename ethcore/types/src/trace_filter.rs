@@ -21,6 +21,7 @@ use ethereum_types::Address;
 use ids::BlockId;
 
 /// Easy to use trace filter.
+#[derive(Debug, Clone)]
 pub struct Filter {
 	/// Range of filtering.
 	pub range: Range<BlockId>,
@@ -22,7 +22,7 @@ pub struct Schedule {
 	pub exceptional_failed_code_deposit: bool,
 	/// Does it have a delegate cal
 	pub have_delegate_call: bool,
-	/// Does it have a CREATE_P2SH instruction
+	/// Does it have a CREATE2 instruction
 	pub have_create2: bool,
 	/// Does it have a REVERT instruction
 	pub have_revert: bool,
@@ -90,6 +90,8 @@ pub struct Schedule {
 	pub extcodesize_gas: usize,
 	/// Base price of EXTCODECOPY
 	pub extcodecopy_base_gas: usize,
+	/// Price of EXTCODEHASH
+	pub extcodehash_gas: usize,
 	/// Price of BALANCE
 	pub balance_gas: usize,
 	/// Price of SUICIDE
@@ -109,10 +111,16 @@ pub struct Schedule {
 	pub have_static_call: bool,
 	/// RETURNDATA and RETURNDATASIZE opcodes enabled.
 	pub have_return_data: bool,
+	/// SHL, SHR, SAR opcodes enabled.
+	pub have_bitwise_shifting: bool,
+	/// EXTCODEHASH opcode enabled.
+	pub have_extcodehash: bool,
 	/// Kill basic accounts below this balance if touched.
 	pub kill_dust: CleanDustMode,
 	/// Enable EIP-86 rules
 	pub eip86: bool,
+	/// Enable EIP-1283 net gas metering for SSTORE
+	pub eip1283: bool,
 	/// Wasm extra schedule settings, if wasm activated
 	pub wasm: Option<WasmCosts>,
 }
@@ -226,6 +234,7 @@ impl Schedule {
 			copy_gas: 3,
 			extcodesize_gas: 700,
 			extcodecopy_base_gas: 700,
+			extcodehash_gas: 400,
 			balance_gas: 400,
 			suicide_gas: 5000,
 			suicide_to_new_account_cost: 25000,
@@ -234,8 +243,11 @@ impl Schedule {
 			kill_empty: kill_empty,
 			blockhash_gas: 20,
 			have_static_call: false,
+			have_bitwise_shifting: false,
+			have_extcodehash: false,
 			kill_dust: CleanDustMode::Off,
 			eip86: false,
+			eip1283: false,
 			wasm: None,
 		}
 	}
@@ -243,13 +255,22 @@ impl Schedule {
 	/// Schedule for the Byzantium fork of the Ethereum main net.
 	pub fn new_byzantium() -> Schedule {
 		let mut schedule = Self::new_post_eip150(24576, true, true, true);
-		schedule.have_create2 = true;
 		schedule.have_revert = true;
 		schedule.have_static_call = true;
 		schedule.have_return_data = true;
 		schedule
 	}
 
+	/// Schedule for the Constantinople fork of the Ethereum main net.
+	pub fn new_constantinople() -> Schedule {
+		let mut schedule = Self::new_byzantium();
+		schedule.have_bitwise_shifting = true;
+		schedule.have_create2 = true;
+		schedule.have_extcodehash = true;
+		schedule.eip1283 = true;
+		schedule
+	}
+
 	fn new(efcd: bool, hdc: bool, tcg: usize) -> Schedule {
 		Schedule {
 			exceptional_failed_code_deposit: efcd,
@@ -289,6 +310,7 @@ impl Schedule {
 			copy_gas: 3,
 			extcodesize_gas: 20,
 			extcodecopy_base_gas: 20,
+			extcodehash_gas: 400,
 			balance_gas: 20,
 			suicide_gas: 0,
 			suicide_to_new_account_cost: 0,
@@ -297,8 +319,11 @@ impl Schedule {
 			kill_empty: false,
 			blockhash_gas: 20,
 			have_static_call: false,
+			have_bitwise_shifting: false,
+			have_extcodehash: false,
 			kill_dust: CleanDustMode::Off,
 			eip86: false,
+			eip1283: false,
 			wasm: None,
 		}
 	}
@@ -19,6 +19,7 @@ use std::collections::{HashMap, HashSet};
 
 use ethereum_types::{U256, H256, Address};
 use bytes::Bytes;
+use hash::keccak;
 use {
 	CallType, Schedule, EnvInfo,
 	ReturnData, Ext, ContractCreateResult, MessageCallResult,
@@ -52,9 +53,10 @@ pub struct FakeCall {
 #[derive(Default)]
 pub struct FakeExt {
 	pub store: HashMap<H256, H256>,
+	pub initial_store: HashMap<H256, H256>,
 	pub suicides: HashSet<Address>,
 	pub calls: HashSet<FakeCall>,
-	pub sstore_clears: usize,
+	pub sstore_clears: i64,
 	pub depth: usize,
 	pub blockhashes: HashMap<U256, H256>,
 	pub codes: HashMap<Address, Arc<Bytes>>,
@@ -88,6 +90,13 @@ impl FakeExt {
 		ext
 	}
 
+	/// New fake externalities with constantinople schedule rules
+	pub fn new_constantinople() -> Self {
+		let mut ext = FakeExt::default();
+		ext.schedule = Schedule::new_constantinople();
+		ext
+	}
+
 	/// Alter fake externalities to allow wasm
 	pub fn with_wasm(mut self) -> Self {
 		self.schedule.wasm = Some(Default::default());
@@ -100,6 +109,10 @@ impl Ext for FakeExt {
 		Ok(self.store.get(key).unwrap_or(&H256::new()).clone())
 	}
 
+	fn initial_storage_at(&self, key: &H256) -> Result<H256> {
+		Ok(self.initial_store.get(key).unwrap_or(&H256::new()).clone())
+	}
+
 	fn set_storage(&mut self, key: H256, value: H256) -> Result<()> {
 		self.store.insert(key, value);
 		Ok(())
@@ -169,6 +182,10 @@ impl Ext for FakeExt {
 		Ok(self.codes.get(address).map_or(0, |c| c.len()))
 	}
 
+	fn extcodehash(&self, address: &Address) -> Result<H256> {
+		Ok(self.codes.get(address).map_or(H256::zero(), |c| keccak(c.as_ref())))
+	}
+
 	fn log(&mut self, topics: Vec<H256>, data: &[u8]) -> Result<()> {
 		self.logs.push(FakeLogEntry {
 			topics: topics,
@@ -202,8 +219,12 @@ impl Ext for FakeExt {
 		self.is_static
 	}
 
-	fn inc_sstore_clears(&mut self) {
-		self.sstore_clears += 1;
+	fn add_sstore_refund(&mut self, value: usize) {
+		self.sstore_clears += value as i64;
+	}
+
+	fn sub_sstore_refund(&mut self, value: usize) {
+		self.sstore_clears -= value as i64;
 	}
 
 	fn trace_next_instruction(&mut self, _pc: usize, _instruction: u8, _gas: U256) -> bool {
@@ -268,7 +268,8 @@ impl<'a> Runtime<'a> {
 		self.ext.set_storage(key, val).map_err(|_| Error::StorageUpdateError)?;
 
 		if former_val != H256::zero() && val == H256::zero() {
-			self.ext.inc_sstore_clears();
+			let sstore_clears_schedule = self.ext.schedule().sstore_refund_gas;
+			self.ext.add_sstore_refund(sstore_clears_schedule);
 		}
 
 		Ok(())
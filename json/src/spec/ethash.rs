@@ -58,10 +58,14 @@ pub struct EthashParams {
 	#[serde(rename="daoHardforkAccounts")]
 	pub dao_hardfork_accounts: Option<Vec<Address>>,
 
-
+	/// Legacy Nekonium HF01 transition. Equivalent to a difficulty rule
+	/// with a bound divisor of 1024 and a 9 second shift. Defaults to 7777
+	/// when no `difficultyRules` are given either.
 	#[serde(rename="nekoniumTransition")]
-	pub nekonium_transition: Option<Uint>,	
-
+	pub nekonium_transition: Option<Uint>,
+	/// See main EthashParams docs.
+	#[serde(rename="difficultyRules")]
+	pub difficulty_rules: Option<Vec<EthashDifficultyRule>>,
 
 	/// See main EthashParams docs.
 	#[serde(rename="difficultyHardforkTransition")]
@@ -123,6 +127,14 @@ pub struct EthashParams {
 	#[serde(rename="mcip3DevContract")]
 	pub mcip3_dev_contract: Option<Address>,
 
+	/// See main EthashParams docs.
+	#[serde(rename="rewardSchedule")]
+	pub reward_schedule: Option<Vec<EthashRewardEra>>,
+
+	/// See main EthashParams docs.
+	#[serde(rename="unclePolicy")]
+	pub uncle_policy: Option<Vec<EthashUnclePolicy>>,
+
 	/// EIP-649 transition block.
 	#[serde(rename="eip649Transition")]
 	pub eip649_transition: Option<Uint>,
@@ -144,6 +156,90 @@ pub struct EthashParams {
 	pub expip2_duration_limit: Option<Uint>,
 }
 
+/// Difficulty calculation rules in effect from a given block onwards.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct EthashDifficultyRule {
+	/// Block number from which the rule applies.
+	pub transition: Uint,
+	/// Block duration target used before Homestead.
+	#[serde(rename="durationLimit")]
+	pub duration_limit: Option<Uint>,
+	/// Difficulty bound divisor.
+	#[serde(rename="boundDivisor")]
+	#[serde(default, deserialize_with="uint::validate_optional_non_zero")]
+	pub bound_divisor: Option<Uint>,
+	/// Difficulty increment divisor.
+	#[serde(rename="incrementDivisor")]
+	#[serde(default, deserialize_with="uint::validate_optional_non_zero")]
+	pub increment_divisor: Option<Uint>,
+	/// Number of seconds subtracted from the block time before adjusting.
+	pub shift: Option<Uint>,
+}
+
+/// Block reward split in effect from a given block onwards.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct EthashRewardEra {
+	/// Block number from which the era applies.
+	pub transition: Uint,
+	/// Total reward per block. Defaults to the engine block reward.
+	#[serde(rename="blockReward")]
+	pub block_reward: Option<Uint>,
+	/// Fixed reward for the block author. Defaults to whatever the recipients leave.
+	#[serde(rename="minerReward")]
+	pub miner_reward: Option<Uint>,
+	/// Additional reward recipients.
+	pub recipients: Option<Vec<EthashRewardRecipient>>,
+	/// Number of blocks after which all payouts decay.
+	#[serde(rename="decayEraRounds")]
+	#[serde(default, deserialize_with="uint::validate_optional_non_zero")]
+	pub decay_era_rounds: Option<Uint>,
+	/// Percentage by which all payouts decrease each decay era.
+	#[serde(rename="decayPercent")]
+	pub decay_percent: Option<Uint>,
+}
+
+/// Recipient of a share of the block reward.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct EthashRewardRecipient {
+	/// Recipient address.
+	pub address: Address,
+	/// Fixed amount paid per block.
+	pub amount: Option<Uint>,
+	/// Percentage of the era block reward paid per block.
+	pub percentage: Option<Uint>,
+}
+
+/// Uncle rules in effect from a given block onwards.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct EthashUnclePolicy {
+	/// Block number from which the policy applies.
+	pub transition: Uint,
+	/// Maximum number of uncles per block.
+	#[serde(rename="maxUncles")]
+	pub max_uncles: Option<Uint>,
+	/// Maximum number of generations an uncle can be behind the including block.
+	#[serde(rename="maxUncleDepth")]
+	pub max_uncle_depth: Option<Uint>,
+	/// Divisor of the block reward paid to the author per included uncle.
+	#[serde(rename="nephewRewardDivisor")]
+	#[serde(default, deserialize_with="uint::validate_optional_non_zero")]
+	pub nephew_reward_divisor: Option<Uint>,
+	/// Uncle reward curve.
+	#[serde(rename="uncleReward")]
+	pub uncle_reward: Option<EthashUncleReward>,
+}
+
+/// Uncle reward curve.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub enum EthashUncleReward {
+	/// `reward * (n - depth) / n`
+	#[serde(rename="depth")]
+	Depth(Uint),
+	/// `reward / n` regardless of depth.
+	#[serde(rename="flat")]
+	Flat(Uint),
+}
+
 /// Ethash engine deserialization.
 #[derive(Debug, PartialEq, Deserialize)]
 pub struct Ethash {
@@ -157,7 +253,8 @@ mod tests {
 	use uint::Uint;
 	use ethereum_types::{H160, U256};
 	use hash::Address;
-	use spec::ethash::{Ethash, EthashParams};
+	use spec::ethash::{Ethash, EthashParams, EthashDifficultyRule, EthashRewardEra, EthashRewardRecipient,
+		EthashUnclePolicy, EthashUncleReward};
 
 	#[test]
 	fn ethash_deserialization() {
@@ -192,6 +289,15 @@ mod tests {
 					"0xbb9bc244d798123fde783fcc1c72d3bb8c189413",
 					"0x807640a13483f8ac783c557fcdf27be11ea4ac7a"
 				],
+				"nekoniumTransition": 7777,
+				"difficultyRules": [
+					{
+						"transition": "0x3e8",
+						"boundDivisor": "0x0400",
+						"incrementDivisor": "0x0f",
+						"shift": 9
+					}
+				],
 				"difficultyHardforkTransition": "0x59d9",
 				"difficultyHardforkBoundDivisor": "0x0200",
 				"bombDefuseTransition": "0x41",
@@ -199,7 +305,32 @@ mod tests {
 				"eip150Transition": "0x43",
 				"eip160Transition": "0x45",
 				"eip161abcTransition": "0x46",
-				"eip161dTransition": "0x47"
+				"eip161dTransition": "0x47",
+				"rewardSchedule": [
+					{
+						"transition": "0x64",
+						"blockReward": "0x1000",
+						"recipients": [
+							{ "address": "0x00efdd5883ec628983e9063c7d969fe268bbf310", "percentage": 10 },
+							{ "address": "0x00756cf8159095948496617f5fb17ed95059f536", "amount": "0x100" }
+						],
+						"decayEraRounds": "0x2710",
+						"decayPercent": 20
+					}
+				],
+				"unclePolicy": [
+					{
+						"transition": "0x3e8",
+						"maxUncles": 4,
+						"maxUncleDepth": 3,
+						"nephewRewardDivisor": "0x40",
+						"uncleReward": { "depth": 4 }
+					},
+					{
+						"transition": "0x7d0",
+						"uncleReward": { "flat": "0x20" }
+					}
+				]
 			}
 		}"#;
 
@@ -238,6 +369,16 @@ mod tests {
 					Address(H160::from("0xbb9bc244d798123fde783fcc1c72d3bb8c189413")),
 					Address(H160::from("0x807640a13483f8ac783c557fcdf27be11ea4ac7a")),
 				]),
+				nekonium_transition: Some(Uint(U256::from(7777))),
+				difficulty_rules: Some(vec![
+					EthashDifficultyRule {
+						transition: Uint(U256::from(0x3e8)),
+						duration_limit: None,
+						bound_divisor: Some(Uint(U256::from(0x0400))),
+						increment_divisor: Some(Uint(U256::from(0x0f))),
+						shift: Some(Uint(U256::from(9))),
+					},
+				]),
 				difficulty_hardfork_transition: Some(Uint(U256::from(0x59d9))),
 				difficulty_hardfork_bound_divisor: Some(Uint(U256::from(0x0200))),
 				bomb_defuse_transition: Some(Uint(U256::from(0x41))),
@@ -255,6 +396,43 @@ mod tests {
 				mcip3_ubi_contract: None,
 				mcip3_dev_reward: None,
 				mcip3_dev_contract: None,
+				reward_schedule: Some(vec![
+					EthashRewardEra {
+						transition: Uint(U256::from(0x64)),
+						block_reward: Some(Uint(U256::from(0x1000))),
+						miner_reward: None,
+						recipients: Some(vec![
+							EthashRewardRecipient {
+								address: Address(H160::from("0x00efdd5883ec628983e9063c7d969fe268bbf310")),
+								amount: None,
+								percentage: Some(Uint(U256::from(10))),
+							},
+							EthashRewardRecipient {
+								address: Address(H160::from("0x00756cf8159095948496617f5fb17ed95059f536")),
+								amount: Some(Uint(U256::from(0x100))),
+								percentage: None,
+							},
+						]),
+						decay_era_rounds: Some(Uint(U256::from(0x2710))),
+						decay_percent: Some(Uint(U256::from(20))),
+					},
+				]),
+				uncle_policy: Some(vec![
+					EthashUnclePolicy {
+						transition: Uint(U256::from(0x3e8)),
+						max_uncles: Some(Uint(U256::from(4))),
+						max_uncle_depth: Some(Uint(U256::from(3))),
+						nephew_reward_divisor: Some(Uint(U256::from(0x40))),
+						uncle_reward: Some(EthashUncleReward::Depth(Uint(U256::from(4)))),
+					},
+					EthashUnclePolicy {
+						transition: Uint(U256::from(0x7d0)),
+						max_uncles: None,
+						max_uncle_depth: None,
+						nephew_reward_divisor: None,
+						uncle_reward: Some(EthashUncleReward::Flat(Uint(U256::from(0x20)))),
+					},
+				]),
 				eip649_transition: None,
 				eip649_delay: None,
 				eip649_reward: None,
@@ -286,6 +464,8 @@ mod tests {
 				dao_hardfork_transition: None,
 				dao_hardfork_beneficiary: None,
 				dao_hardfork_accounts: None,
+				nekonium_transition: None,
+				difficulty_rules: None,
 				difficulty_hardfork_transition: None,
 				difficulty_hardfork_bound_divisor: None,
 				bomb_defuse_transition: None,
@@ -303,6 +483,8 @@ mod tests {
 				mcip3_ubi_contract: None,
 				mcip3_dev_reward: None,
 				mcip3_dev_contract: None,
+				reward_schedule: None,
+				uncle_policy: None,
 				eip649_transition: None,
 				eip649_delay: None,
 				eip649_reward: None,
@@ -324,4 +506,20 @@ mod tests {
 
 		let _deserialized: Ethash = serde_json::from_str(s).unwrap();
 	}
+
+	#[test]
+	#[should_panic(expected = "a non-zero value")]
+	fn test_zero_value_difficulty_rule_divisor() {
+		let s = r#"{
+			"params": {
+				"difficultyBoundDivisor": "0x0800",
+				"minimumDifficulty": "0x020000",
+				"difficultyRules": [
+					{ "transition": "0x10", "boundDivisor": "0x0" }
+				]
+			}
+		}"#;
+
+		let _deserialized: Ethash = serde_json::from_str(s).unwrap();
+	}
 }
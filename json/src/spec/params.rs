@@ -82,6 +82,18 @@ pub struct Params {
 	#[serde(rename="eip210ContractGas")]
 	pub eip210_contract_gas: Option<Uint>,
 	/// See `CommonParams` docs.
+	#[serde(rename="eip145Transition")]
+	pub eip145_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip1014Transition")]
+	pub eip1014_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip1052Transition")]
+	pub eip1052_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip1283Transition")]
+	pub eip1283_transition: Option<Uint>,
+	/// See `CommonParams` docs.
 	#[serde(rename="eip211Transition")]
 	pub eip211_transition: Option<Uint>,
 	/// See `CommonParams` docs.
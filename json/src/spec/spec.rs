@@ -46,6 +46,22 @@ impl Spec {
 	pub fn load<R>(reader: R) -> Result<Self, Error> where R: Read {
 		serde_json::from_reader(reader)
 	}
+
+	/// Re-encodes json of a spec without the fields which don't affect consensus
+	/// (`name`, `dataDir` and `nodes`), with sorted object keys and no whitespace.
+	///
+	/// Keys are sorted because `serde_json::Map` is a `BTreeMap`; enabling serde_json's
+	/// `preserve_order` feature anywhere in the build would keep the source order instead
+	/// and change the encoding, so it must stay off.
+	pub fn consensus_json<R>(reader: R) -> Result<Vec<u8>, Error> where R: Read {
+		let mut value: serde_json::Value = serde_json::from_reader(reader)?;
+		if let Some(object) = value.as_object_mut() {
+			for key in &["name", "dataDir", "nodes"] {
+				object.remove(*key);
+			}
+		}
+		serde_json::to_vec(&value)
+	}
 }
 
 #[cfg(test)]
@@ -109,4 +125,20 @@ mod tests {
 		let _deserialized: Spec = serde_json::from_str(s).unwrap();
 		// TODO: validate all fields
 	}
+
+	#[test]
+	fn consensus_json_ignores_formatting_and_names() {
+		let a = r#"{"name": "A", "engine": {"null": {}}, "params": {"b": "0x1", "a": "0x2"}}"#;
+		let b = r#"{
+			"params": { "a": "0x2", "b": "0x1" },
+			"nodes": [],
+			"engine": { "null": {} },
+			"name": "B"
+		}"#;
+		let c = r#"{"name": "A", "engine": {"null": {}}, "params": {"b": "0x1", "a": "0x3"}}"#;
+
+		let a = Spec::consensus_json(a.as_bytes()).unwrap();
+		assert_eq!(a, Spec::consensus_json(b.as_bytes()).unwrap());
+		assert!(a != Spec::consensus_json(c.as_bytes()).unwrap());
+	}
 }
@@ -26,7 +26,7 @@ use ethereum_types::{U256, H256, Address};
 use bytes::ToPretty;
 use rlp::PayloadInfo;
 use ethcore::service::ClientService;
-use ethcore::client::{Mode, DatabaseCompactionProfile, VMType, BlockImportError, BlockChainClient, BlockId};
+use ethcore::client::{Mode, DatabaseCompactionProfile, DatabaseBackend, VMType, BlockImportError, BlockChainClient, BlockId};
 use ethcore::error::ImportError;
 use ethcore::miner::Miner;
 use ethcore::verification::queue::VerifierSettings;
@@ -88,6 +88,7 @@ pub struct ImportBlockchain {
 	pub pruning_history: u64,
 	pub pruning_memory: usize,
 	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
 	pub wal: bool,
 	pub tracing: Switch,
 	pub fat_db: Switch,
@@ -109,6 +110,7 @@ pub struct ExportBlockchain {
 	pub pruning_history: u64,
 	pub pruning_memory: usize,
 	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
 	pub wal: bool,
 	pub fat_db: Switch,
 	pub tracing: Switch,
@@ -128,6 +130,7 @@ pub struct ExportState {
 	pub pruning_history: u64,
 	pub pruning_memory: usize,
 	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
 	pub wal: bool,
 	pub fat_db: Switch,
 	pub tracing: Switch,
@@ -350,6 +353,7 @@ fn execute_import(cmd: ImportBlockchain) -> Result<(), String> {
 		tracing,
 		fat_db,
 		cmd.compaction,
+		cmd.db_backend,
 		cmd.wal,
 		cmd.vm_type,
 		"".into(),
@@ -483,6 +487,7 @@ fn start_client(
 	tracing: Switch,
 	fat_db: Switch,
 	compaction: DatabaseCompactionProfile,
+	db_backend: DatabaseBackend,
 	wal: bool,
 	cache_config: CacheConfig,
 	require_fat_db: bool,
@@ -535,6 +540,7 @@ fn start_client(
 		tracing,
 		fat_db,
 		compaction,
+		db_backend,
 		wal,
 		VMType::default(),
 		"".into(),
@@ -567,6 +573,7 @@ fn execute_export(cmd: ExportBlockchain) -> Result<(), String> {
 		cmd.tracing,
 		cmd.fat_db,
 		cmd.compaction,
+		cmd.db_backend,
 		cmd.wal,
 		cmd.cache_config,
 		false,
@@ -612,6 +619,7 @@ fn execute_export_state(cmd: ExportState) -> Result<(), String> {
 		cmd.tracing,
 		cmd.fat_db,
 		cmd.compaction,
+		cmd.db_backend,
 		cmd.wal,
 		cmd.cache_config,
 		true
@@ -191,6 +191,15 @@ usage! {
 		{
 			"Make a snapshot of the database",
 
+			CMD cmd_snapshot_inspect
+			{
+				"Print the manifest of a snapshot file without restoring it",
+
+				ARG arg_snapshot_inspect_file: (Option<String>) = None,
+				"<FILE>",
+				"Path to the snapshot file",
+			}
+
 			ARG arg_snapshot_at: (String) = "latest",
 			"--at=[BLOCK]",
 			"Take a snapshot at the given block, which may be an index, hash, or latest. Note that taking snapshots at non-recent blocks will only work with --pruning archive",
@@ -204,6 +213,10 @@ usage! {
 		{
 			"Restore database from snapshot",
 
+			FLAG flag_restore_allow_unchecked: (bool) = false,
+			"--allow-unchecked",
+			"Restore a snapshot file without a manifest checksum, which can't be checked against the chain spec, or one taken with a different chain spec. A snapshot of a different genesis is always refused.",
+
 			ARG arg_restore_file: (Option<String>) = None,
 			"[FILE]",
 			"Path to the file to restore from",
@@ -230,6 +243,49 @@ usage! {
 			CMD cmd_db_kill {
 				"Clean the database",
 			}
+
+			CMD cmd_db_convert
+			{
+				"Copy the database into another storage backend",
+
+				ARG arg_db_convert_to: (Option<String>) = None,
+				"--to=[BACKEND]",
+				"Storage backend to convert the database to. BACKEND may be one of: rocksdb, logdb.",
+			}
+
+			CMD cmd_db_prune
+			{
+				"Copy recent state of an archive database into a new pruned database",
+
+				ARG arg_db_prune_to: (String) = "fast",
+				"--to=[ALGORITHM]",
+				"Pruning algorithm of the new database. Only fast is supported.",
+
+				ARG arg_db_prune_keep: (u64) = 64u64,
+				"--keep=[N]",
+				"Number of most recent blocks which state is copied.",
+			}
+
+			CMD cmd_db_verify
+			{
+				"Check the database for missing state nodes and corrupted blocks",
+
+				FLAG flag_db_verify_no_storage: (bool) = false,
+				"--no-storage",
+				"Don't verify storage tries and code of accounts.",
+
+				ARG arg_db_verify_at: (String) = "latest",
+				"--at=[BLOCK]",
+				"Verify state at the given block, which may be an index, hash, or latest.",
+
+				ARG arg_db_verify_from: (Option<String>) = None,
+				"--from=[BLOCK]",
+				"First block which transactions, uncles and receipts roots are verified. (default: the --at block)",
+
+				ARG arg_db_verify_to: (Option<String>) = None,
+				"--to=[BLOCK]",
+				"Last block which transactions, uncles and receipts roots are verified. (default: the --at block)",
+			}
 		}
 	}
 	{
@@ -447,7 +503,7 @@ usage! {
 
 			ARG arg_jsonrpc_apis: (String) = "web3,eth,pubsub,net,parity,parity_pubsub,traces,rpc,shh,shh_pubsub", or |c: &Config| c.rpc.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
 			"--jsonrpc-apis=[APIS]",
-			"Specify the APIs available through the JSONRPC interface. APIS is a comma-delimited list of API name. Possible name are all, safe, web3, eth, net, personal, parity, parity_set, traces, rpc, parity_accounts, pubsub, parity_pubsub, shh, shh_pubsub, signer, secretstore. You can also disable a specific API by putting '-' in the front: all,-personal.",
+			"Specify the APIs available through the JSONRPC interface. APIS is a comma-delimited list of API name. Possible name are all, safe, web3, eth, net, personal, parity, parity_set, traces, debug, rpc, parity_accounts, pubsub, parity_pubsub, shh, shh_pubsub, signer, secretstore. You can also disable a specific API by putting '-' in the front: all,-personal.",
 
 			ARG arg_jsonrpc_hosts: (String) = "none", or |c: &Config| c.rpc.as_ref()?.hosts.as_ref().map(|vec| vec.join(",")),
 			"--jsonrpc-hosts=[HOSTS]",
@@ -465,6 +521,34 @@ usage! {
 			"--jsonrpc-server-threads=[NUM]",
 			"Enables multiple threads handling incoming connections for HTTP JSON-RPC server.",
 
+			ARG arg_jsonrpc_logs_max_block_range: (Option<u64>) = None, or |c: &Config| c.rpc.as_ref()?.logs_max_block_range,
+			"--jsonrpc-logs-max-block-range=[BLOCKS]",
+			"Maximal number of blocks a single eth_getLogs query can span. Larger queries fail with the logs of the first blocks and a cursor to continue from.",
+
+			ARG arg_jsonrpc_logs_max_results: (Option<usize>) = None, or |c: &Config| c.rpc.as_ref()?.logs_max_results,
+			"--jsonrpc-logs-max-results=[NUM]",
+			"Maximal number of logs returned by a single eth_getLogs query. Larger results fail with the first logs and a cursor to continue from.",
+
+			ARG arg_jsonrpc_allow_methods: (Option<String>) = None, or |c: &Config| c.rpc.as_ref()?.allow_methods.as_ref().map(|vec| vec.join(",")),
+			"--jsonrpc-allow-methods=[METHODS]",
+			"Comma-delimited list of methods callable over the JSONRPC interface, e.g. eth_*,net_version. A '*' matches any sequence of characters. All enabled methods are allowed if the list is empty.",
+
+			ARG arg_jsonrpc_deny_methods: (Option<String>) = None, or |c: &Config| c.rpc.as_ref()?.deny_methods.as_ref().map(|vec| vec.join(",")),
+			"--jsonrpc-deny-methods=[METHODS]",
+			"Comma-delimited list of methods rejected over the JSONRPC interface, e.g. eth_sign,personal_*@http://example.com. Append @ORIGIN to reject a pattern only for requests from matching origins. The Origin header is set by the caller, so this only restricts browsers and is not access control. Takes precedence over --jsonrpc-allow-methods.",
+
+			ARG arg_jsonrpc_rate_limit: (Option<u32>) = None, or |c: &Config| c.rpc.as_ref()?.rate_limit,
+			"--jsonrpc-rate-limit=[UNITS]",
+			"Limit the request cost units each client of the JSONRPC and WebSockets servers can spend per second. HTTP clients are identified by their remote IP. WebSockets clients are identified by their UI authorization token, all other WebSockets connections each have a limit of their own. No limit is applied by default.",
+
+			ARG arg_jsonrpc_rate_burst: (Option<u32>) = None, or |c: &Config| c.rpc.as_ref()?.rate_burst,
+			"--jsonrpc-rate-burst=[UNITS]",
+			"Maximal number of cost units a client can accumulate while idle. Defaults to ten seconds worth of --jsonrpc-rate-limit. A batch is charged the total cost of its calls. Requests costing more than the burst wait for a full bucket and the client then waits until the whole cost is refilled.",
+
+			ARG arg_jsonrpc_method_costs: (Option<String>) = None, or |c: &Config| c.rpc.as_ref()?.method_costs.as_ref().map(|vec| vec.join(",")),
+			"--jsonrpc-method-costs=[COSTS]",
+			"Comma-delimited list of METHOD=COST pairs overriding the cost units charged for a method call, e.g. trace_filter=100,eth_call=2. Other methods cost 1 unit unless they are known to be expensive.",
+
 		["API and console options – WebSockets"]
 			FLAG flag_no_ws: (bool) = false, or |c: &Config| c.websockets.as_ref()?.disable.clone(),
 			"--no-ws",
@@ -480,7 +564,7 @@ usage! {
 
 			ARG arg_ws_apis: (String) = "web3,eth,pubsub,net,parity,parity_pubsub,traces,rpc,shh,shh_pubsub", or |c: &Config| c.websockets.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
 			"--ws-apis=[APIS]",
-			"Specify the APIs available through the WebSockets interface. APIS is a comma-delimited list of API name. Possible name are web3, eth, pubsub, net, personal, parity, parity_set, traces, rpc, parity_accounts, pubsub, parity_pubsub, shh, shh_pubsub, signer, secretstore.",
+			"Specify the APIs available through the WebSockets interface. APIS is a comma-delimited list of API name. Possible name are web3, eth, pubsub, net, personal, parity, parity_set, traces, debug, rpc, parity_accounts, pubsub, parity_pubsub, shh, shh_pubsub, signer, secretstore.",
 
 			ARG arg_ws_origins: (String) = "parity://*,chrome-extension://*,moz-extension://*", or |c: &Config| c.websockets.as_ref()?.origins.as_ref().map(|vec| vec.join(",")),
 			"--ws-origins=[URL]",
@@ -490,6 +574,14 @@ usage! {
 			"--ws-hosts=[HOSTS]",
 			"List of allowed Host header values. This option will validate the Host header sent by the browser, it is additional security against some attack vectors. Special options: \"all\", \"none\".",
 
+			ARG arg_ws_allow_methods: (Option<String>) = None, or |c: &Config| c.websockets.as_ref()?.allow_methods.as_ref().map(|vec| vec.join(",")),
+			"--ws-allow-methods=[METHODS]",
+			"Comma-delimited list of methods callable over the WebSockets interface. See --jsonrpc-allow-methods for the pattern syntax.",
+
+			ARG arg_ws_deny_methods: (Option<String>) = None, or |c: &Config| c.websockets.as_ref()?.deny_methods.as_ref().map(|vec| vec.join(",")),
+			"--ws-deny-methods=[METHODS]",
+			"Comma-delimited list of methods rejected over the WebSockets interface. See --jsonrpc-deny-methods for the pattern syntax and origin scoped rules. Takes precedence over --ws-allow-methods.",
+
 		["API and console options – IPC"]
 			FLAG flag_no_ipc: (bool) = false, or |c: &Config| c.ipc.as_ref()?.disable.clone(),
 			"--no-ipc",
@@ -503,6 +595,14 @@ usage! {
 			"--ipc-apis=[APIS]",
 			"Specify custom API set available via JSON-RPC over IPC.",
 
+			ARG arg_ipc_allow_methods: (Option<String>) = None, or |c: &Config| c.ipc.as_ref()?.allow_methods.as_ref().map(|vec| vec.join(",")),
+			"--ipc-allow-methods=[METHODS]",
+			"Comma-delimited list of methods callable over IPC. See --jsonrpc-allow-methods for the pattern syntax.",
+
+			ARG arg_ipc_deny_methods: (Option<String>) = None, or |c: &Config| c.ipc.as_ref()?.deny_methods.as_ref().map(|vec| vec.join(",")),
+			"--ipc-deny-methods=[METHODS]",
+			"Comma-delimited list of methods rejected over IPC. Takes precedence over --ipc-allow-methods.",
+
 		["API and console options – Dapps"]
 			FLAG flag_no_dapps: (bool) = false, or |c: &Config| c.dapps.as_ref()?.disable.clone(),
 			"--no-dapps",
@@ -762,6 +862,14 @@ usage! {
 			"--scale-verifiers",
 			"Automatically scale amount of verifier threads based on workload. Not guaranteed to be faster.",
 
+			FLAG flag_account_tx_index: (bool) = false, or |c: &Config| c.footprint.as_ref()?.account_tx_index.clone(),
+			"--account-tx-index",
+			"Maintain an index of transactions sent from or to each account, queried with parity_listTransactions. Contract creations are indexed for the sender only. Only blocks imported while enabled are indexed.",
+
+			FLAG flag_token_index: (bool) = false, or |c: &Config| c.footprint.as_ref()?.token_index.clone(),
+			"--token-index",
+			"Maintain an index of ERC-20 token balances built from Transfer events, queried with parity_tokenBalances and parity_tokenHolders. Only blocks imported while enabled are indexed.",
+
 			ARG arg_tracing: (String) = "auto", or |c: &Config| c.footprint.as_ref()?.tracing.clone(),
 			"--tracing=[BOOL]",
 			"Indicates if full transaction tracing should be enabled. Works only if client had been fully synced with tracing enabled. BOOL may be one of auto, on, off. auto uses last used value of this option (off if it does not exist).", // footprint option
@@ -798,6 +906,10 @@ usage! {
 			"--db-compaction=[TYPE]",
 			"Database compaction type. TYPE may be one of: ssd - suitable for SSDs and fast HDDs; hdd - suitable for slow HDDs; auto - determine automatically.",
 
+			ARG arg_db_backend: (String) = "rocksdb", or |c: &Config| c.footprint.as_ref()?.db_backend.clone(),
+			"--db-backend=[BACKEND]",
+			"Storage backend of the client database. BACKEND may be one of: rocksdb - RocksDB; logdb - pure-Rust append-only log store, which keeps every key of the database in memory at roughly 100 bytes per key, so it needs tens of GiB of RAM for a mainnet node with pruned state and hundreds of GiB for an archive node. It suits test networks and small chains. Use `parity db convert` to change the backend of an existing database.",
+
 			ARG arg_fat_db: (String) = "auto", or |c: &Config| c.footprint.as_ref()?.fat_db.clone(),
 			"--fat-db=[BOOL]",
 			"Build appropriate information to allow enumeration of all accounts and storage keys. Doubles the size of the state database. BOOL may be one of on, off or auto.",
@@ -820,6 +932,10 @@ usage! {
 			"--no-periodic-snapshot",
 			"Disable automated snapshots which usually occur once every 10000 blocks.",
 
+			ARG arg_snapshot_compression: (String) = "snappy", or |c: &Config| c.snapshots.as_ref()?.compression.clone(),
+			"--snapshot-compression=[ALGORITHM]",
+			"Compression of chunks in created snapshots. ALGORITHM is one of snappy, zstd. Peers running older versions can only restore snappy snapshots.",
+
 		["Virtual Machine options"]
 			FLAG flag_jitvm: (bool) = false, or |c: &Config| c.vm.as_ref()?.jit.clone(),
 			"--jitvm",
@@ -1066,6 +1182,13 @@ struct Rpc {
 	hosts: Option<Vec<String>>,
 	server_threads: Option<usize>,
 	processing_threads: Option<usize>,
+	logs_max_block_range: Option<u64>,
+	logs_max_results: Option<usize>,
+	allow_methods: Option<Vec<String>>,
+	deny_methods: Option<Vec<String>>,
+	rate_limit: Option<u32>,
+	rate_burst: Option<u32>,
+	method_costs: Option<Vec<String>>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1077,6 +1200,8 @@ struct Ws {
 	apis: Option<Vec<String>>,
 	origins: Option<Vec<String>>,
 	hosts: Option<Vec<String>>,
+	allow_methods: Option<Vec<String>>,
+	deny_methods: Option<Vec<String>>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1085,6 +1210,8 @@ struct Ipc {
 	disable: Option<bool>,
 	path: Option<String>,
 	apis: Option<Vec<String>>,
+	allow_methods: Option<Vec<String>>,
+	deny_methods: Option<Vec<String>>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1184,15 +1311,19 @@ struct Footprint {
 	cache_size_queue: Option<u32>,
 	cache_size_state: Option<u32>,
 	db_compaction: Option<String>,
+	db_backend: Option<String>,
 	fat_db: Option<String>,
 	scale_verifiers: Option<bool>,
 	num_verifiers: Option<usize>,
+	account_tx_index: Option<bool>,
+	token_index: Option<bool>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct Snapshots {
 	disable_periodic: Option<bool>,
+	compression: Option<String>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1414,6 +1545,9 @@ mod tests {
 			cmd_tools_hash: false,
 			cmd_db: false,
 			cmd_db_kill: false,
+			cmd_db_convert: false,
+			cmd_db_prune: false,
+			cmd_db_verify: false,
 
 			// Arguments
 			arg_daemon_pid_file: None,
@@ -1424,8 +1558,17 @@ mod tests {
 			arg_export_state_file: None,
 			arg_export_state_format: None,
 			arg_snapshot_file: None,
+			cmd_snapshot_inspect: false,
+			arg_snapshot_inspect_file: None,
 			arg_restore_file: None,
+			flag_restore_allow_unchecked: false,
 			arg_tools_hash_file: None,
+			arg_db_convert_to: None,
+			arg_db_prune_to: "fast".into(),
+			arg_db_prune_keep: 64u64,
+			arg_db_verify_at: "latest".into(),
+			arg_db_verify_from: None,
+			arg_db_verify_to: None,
 
 			arg_signer_sign_id: None,
 			arg_signer_reject_id: None,
@@ -1500,6 +1643,13 @@ mod tests {
 			arg_jsonrpc_hosts: "none".into(),
 			arg_jsonrpc_server_threads: None,
 			arg_jsonrpc_threads: 4,
+			arg_jsonrpc_logs_max_block_range: None,
+			arg_jsonrpc_logs_max_results: None,
+			arg_jsonrpc_allow_methods: None,
+			arg_jsonrpc_deny_methods: None,
+			arg_jsonrpc_rate_limit: None,
+			arg_jsonrpc_rate_burst: None,
+			arg_jsonrpc_method_costs: None,
 
 			// WS
 			flag_no_ws: false,
@@ -1508,11 +1658,15 @@ mod tests {
 			arg_ws_apis: "web3,eth,net,parity,traces,rpc,secretstore".into(),
 			arg_ws_origins: "none".into(),
 			arg_ws_hosts: "none".into(),
+			arg_ws_allow_methods: None,
+			arg_ws_deny_methods: None,
 
 			// IPC
 			flag_no_ipc: false,
 			arg_ipc_path: "$HOME/.parity/jsonrpc.ipc".into(),
 			arg_ipc_apis: "web3,eth,net,parity,parity_accounts,personal,traces,rpc,secretstore".into(),
+			arg_ipc_allow_methods: None,
+			arg_ipc_deny_methods: None,
 
 			// DAPPS
 			arg_dapps_path: "$HOME/.parity/dapps".into(),
@@ -1588,9 +1742,12 @@ mod tests {
 			arg_cache_size: Some(128),
 			flag_fast_and_loose: false,
 			arg_db_compaction: "ssd".into(),
+			arg_db_backend: "rocksdb".into(),
 			arg_fat_db: "auto".into(),
 			flag_scale_verifiers: true,
 			arg_num_verifiers: Some(6),
+			flag_account_tx_index: false,
+			flag_token_index: false,
 
 			// -- Import/Export Options
 			arg_export_blocks_from: "1".into(),
@@ -1598,6 +1755,7 @@ mod tests {
 			flag_no_seal_check: false,
 			flag_export_state_no_code: false,
 			flag_export_state_no_storage: false,
+			flag_db_verify_no_storage: false,
 			arg_export_state_min_balance: None,
 			arg_export_state_max_balance: None,
 
@@ -1605,6 +1763,7 @@ mod tests {
 			arg_export_state_at: "latest".into(),
 			arg_snapshot_at: "latest".into(),
 			flag_no_periodic_snapshot: false,
+			arg_snapshot_compression: "snappy".into(),
 
 			// -- Virtual Machine Options
 			flag_jitvm: false,
@@ -1746,6 +1905,8 @@ mod tests {
 				apis: None,
 				origins: Some(vec!["none".into()]),
 				hosts: None,
+				allow_methods: None,
+				deny_methods: None,
 			}),
 			rpc: Some(Rpc {
 				disable: Some(true),
@@ -1756,11 +1917,20 @@ mod tests {
 				hosts: None,
 				server_threads: None,
 				processing_threads: None,
+				logs_max_block_range: None,
+				logs_max_results: None,
+				allow_methods: None,
+				deny_methods: None,
+				rate_limit: None,
+				rate_burst: None,
+				method_costs: None,
 			}),
 			ipc: Some(Ipc {
 				disable: None,
 				path: None,
 				apis: Some(vec!["rpc".into(), "eth".into()]),
+				allow_methods: None,
+				deny_methods: None,
 			}),
 			dapps: Some(Dapps {
 				disable: None,
@@ -1837,12 +2007,16 @@ mod tests {
 				cache_size_queue: Some(100),
 				cache_size_state: Some(25),
 				db_compaction: Some("ssd".into()),
+				db_backend: None,
 				fat_db: Some("off".into()),
 				scale_verifiers: Some(false),
 				num_verifiers: None,
+				account_tx_index: None,
+				token_index: None,
 			}),
 			snapshots: Some(Snapshots {
 				disable_periodic: Some(true),
+				compression: None,
 			}),
 			vm: Some(VM {
 				jit: Some(false),
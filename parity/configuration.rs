@@ -36,6 +36,8 @@ use ethcore::verification::queue::VerifierSettings;
 use rpc::{IpcConfiguration, HttpConfiguration, WsConfiguration, UiConfiguration};
 use rpc_apis::ApiSet;
 use parity_rpc::NetworkSettings;
+use parity_rpc::access::MethodAccess;
+use parity_rpc::ratelimit::RateLimit;
 use cache::CacheConfig;
 use helpers::{to_duration, to_mode, to_block_id, to_u256, to_pending_set, to_price, geth_ipc_path, parity_ipc_path,
 to_bootnodes, to_addresses, to_address, to_gas_limit, to_queue_strategy};
@@ -52,6 +54,7 @@ use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, KillBlockcha
 use presale::ImportWallet;
 use account::{AccountCmd, NewAccount, ListAccounts, ImportAccounts, ImportFromGethAccounts};
 use snapshot::{self, SnapshotCommand};
+use db::{DbCmd, ConvertDb, PruneDb, VerifyDb};
 use network::{IpFilter};
 
 #[derive(Debug, PartialEq)]
@@ -79,6 +82,7 @@ pub enum Cmd {
 	},
 	Snapshot(SnapshotCommand),
 	Hash(Option<String>),
+	Db(DbCmd),
 }
 
 pub struct Execute {
@@ -126,6 +130,7 @@ impl Configuration {
 		let tracing = self.args.arg_tracing.parse()?;
 		let fat_db = self.args.arg_fat_db.parse()?;
 		let compaction = self.args.arg_db_compaction.parse()?;
+		let db_backend = self.args.arg_db_backend.parse()?;
 		let wal = !self.args.flag_fast_and_loose;
 		let public_node = self.args.flag_public_node;
 		let warp_sync = !self.args.flag_no_warp;
@@ -174,6 +179,47 @@ impl Configuration {
 				dirs: dirs,
 				pruning: pruning,
 			}))
+		} else if self.args.cmd_db && self.args.cmd_db_convert {
+			let to = self.args.arg_db_convert_to.as_ref()
+				.ok_or_else(|| "Target backend is required. Use --to=[BACKEND].".to_owned())?
+				.parse()?;
+			Cmd::Db(DbCmd::Convert(ConvertDb {
+				spec: spec,
+				dirs: dirs,
+				pruning: pruning,
+				compaction: compaction,
+				to: to,
+			}))
+		} else if self.args.cmd_db && self.args.cmd_db_prune {
+			Cmd::Db(DbCmd::Prune(PruneDb {
+				spec: spec,
+				dirs: dirs,
+				pruning: pruning,
+				compaction: compaction,
+				to: self.args.arg_db_prune_to.parse()?,
+				keep: self.args.arg_db_prune_keep,
+			}))
+		} else if self.args.cmd_db && self.args.cmd_db_verify {
+			let at = to_block_id(&self.args.arg_db_verify_at)?;
+			let from = match self.args.arg_db_verify_from {
+				Some(ref from) => to_block_id(from)?,
+				None => at.clone(),
+			};
+			let to = match self.args.arg_db_verify_to {
+				Some(ref to) => to_block_id(to)?,
+				None => at.clone(),
+			};
+
+			Cmd::Db(DbCmd::Verify(VerifyDb {
+				spec: spec,
+				dirs: dirs,
+				pruning: pruning,
+				compaction: compaction,
+				at: at,
+				storage: !self.args.flag_db_verify_no_storage,
+				from: from,
+				to: to,
+			}))
 		} else if self.args.cmd_account {
 			let account_cmd = if self.args.cmd_account_new {
 				let new_acc = NewAccount {
@@ -229,6 +275,7 @@ impl Configuration {
 				pruning_history: pruning_history,
 				pruning_memory: self.args.arg_pruning_memory,
 				compaction: compaction,
+				db_backend: db_backend,
 				wal: wal,
 				tracing: tracing,
 				fat_db: fat_db,
@@ -251,6 +298,7 @@ impl Configuration {
 					pruning_history: pruning_history,
 					pruning_memory: self.args.arg_pruning_memory,
 					compaction: compaction,
+					db_backend: db_backend,
 					wal: wal,
 					tracing: tracing,
 					fat_db: fat_db,
@@ -270,6 +318,7 @@ impl Configuration {
 					pruning_history: pruning_history,
 					pruning_memory: self.args.arg_pruning_memory,
 					compaction: compaction,
+					db_backend: db_backend,
 					wal: wal,
 					tracing: tracing,
 					fat_db: fat_db,
@@ -294,10 +343,19 @@ impl Configuration {
 				tracing: tracing,
 				fat_db: fat_db,
 				compaction: compaction,
-				file_path: self.args.arg_snapshot_file.clone(),
+				db_backend: db_backend,
+				file_path: match self.args.cmd_snapshot_inspect {
+					true => self.args.arg_snapshot_inspect_file.clone(),
+					false => self.args.arg_snapshot_file.clone(),
+				},
 				wal: wal,
-				kind: snapshot::Kind::Take,
+				kind: match self.args.cmd_snapshot_inspect {
+					true => snapshot::Kind::Inspect,
+					false => snapshot::Kind::Take,
+				},
 				block_at: to_block_id(&self.args.arg_snapshot_at)?,
+				compression: self.args.arg_snapshot_compression.parse()?,
+				allow_unchecked: false,
 			};
 			Cmd::Snapshot(snapshot_cmd)
 		} else if self.args.cmd_restore {
@@ -311,10 +369,13 @@ impl Configuration {
 				tracing: tracing,
 				fat_db: fat_db,
 				compaction: compaction,
+				db_backend: db_backend,
 				file_path: self.args.arg_restore_file.clone(),
 				wal: wal,
 				kind: snapshot::Kind::Restore,
 				block_at: to_block_id("latest")?, // unimportant.
+				compression: Default::default(), // unimportant.
+				allow_unchecked: self.args.flag_restore_allow_unchecked,
 			};
 			Cmd::Snapshot(restore_cmd)
 		} else {
@@ -338,6 +399,8 @@ impl Configuration {
 				logger_config: logger_config.clone(),
 				miner_options: self.miner_options()?,
 				gas_price_percentile: self.args.arg_gas_price_percentile,
+				logs_max_block_range: self.args.arg_jsonrpc_logs_max_block_range,
+				logs_max_results: self.args.arg_jsonrpc_logs_max_results,
 				ntp_servers: self.ntp_servers(),
 				ws_conf: ws_conf,
 				http_conf: http_conf,
@@ -352,7 +415,10 @@ impl Configuration {
 				mode: mode,
 				tracing: tracing,
 				fat_db: fat_db,
+				account_tx_index: self.args.flag_account_tx_index,
+				token_index: self.args.flag_token_index,
 				compaction: compaction,
+				db_backend: db_backend,
 				wal: wal,
 				vm_type: vm_type,
 				warp_sync: warp_sync,
@@ -369,6 +435,7 @@ impl Configuration {
 				name: self.args.arg_identity,
 				custom_bootnodes: self.args.arg_bootnodes.is_some(),
 				no_periodic_snapshot: self.args.flag_no_periodic_snapshot,
+				snapshot_compression: self.args.arg_snapshot_compression.parse()?,
 				check_seal: !self.args.flag_no_seal_check,
 				download_old_blocks: !self.args.flag_no_ancient_blocks,
 				verifier_settings: verifier_settings,
@@ -852,6 +919,25 @@ impl Configuration {
 		self.hosts(&self.args.arg_ipfs_api_hosts, &self.ipfs_interface())
 	}
 
+	fn method_access(allow: &Option<String>, deny: &Option<String>) -> Result<MethodAccess, String> {
+		let list = |rules: &Option<String>| rules.clone().unwrap_or_default();
+		MethodAccess::parse(&list(allow), &list(deny))
+	}
+
+	fn rate_limit(&self) -> Result<Option<RateLimit>, String> {
+		let rate = match self.args.arg_jsonrpc_rate_limit {
+			Some(rate) => rate,
+			None => return Ok(None),
+		};
+		let burst = self.args.arg_jsonrpc_rate_burst.unwrap_or_else(|| rate.saturating_mul(10));
+		let limit = RateLimit::new(rate, burst);
+
+		match self.args.arg_jsonrpc_method_costs {
+			Some(ref costs) => limit.with_costs(costs).map(Some),
+			None => Ok(Some(limit)),
+		}
+	}
+
 	fn ipc_config(&self) -> Result<IpcConfiguration, String> {
 		let conf = IpcConfiguration {
 			enabled: !(self.args.flag_ipcdisable || self.args.flag_ipc_off || self.args.flag_no_ipc),
@@ -866,6 +952,7 @@ impl Configuration {
 				}
 				apis.parse()?
 			},
+			methods: Self::method_access(&self.args.arg_ipc_allow_methods, &self.args.arg_ipc_deny_methods)?,
 		};
 
 		Ok(conf)
@@ -882,6 +969,8 @@ impl Configuration {
 			},
 			hosts: self.rpc_hosts(),
 			cors: self.rpc_cors(),
+			methods: Self::method_access(&self.args.arg_jsonrpc_allow_methods, &self.args.arg_jsonrpc_deny_methods)?,
+			rate_limit: self.rate_limit()?,
 			server_threads: match self.args.arg_jsonrpc_server_threads {
 				Some(threads) if threads > 0 => threads,
 				_ => 1,
@@ -909,6 +998,8 @@ impl Configuration {
 			apis: self.args.arg_ws_apis.parse()?,
 			hosts: self.ws_hosts(),
 			origins: self.ws_origins(),
+			methods: Self::method_access(&self.args.arg_ws_allow_methods, &self.args.arg_ws_deny_methods)?,
+			rate_limit: self.rate_limit()?,
 			signer_path: self.directories().signer.into(),
 			support_token_api,
 			ui_address: ui.address(),
@@ -1165,15 +1256,20 @@ mod tests {
 	use std::str::FromStr;
 
 	use tempdir::TempDir;
-	use ethcore::client::{VMType, BlockId};
+	use ethcore::client::{VMType, BlockId, DatabaseBackend};
 	use ethcore::miner::MinerOptions;
+	use ethcore::snapshot::ChunkCompression;
 	use miner::transaction_queue::PrioritizationStrategy;
 	use parity_rpc::NetworkSettings;
+	use parity_rpc::access::MethodAccess;
+	use parity_rpc::ratelimit::RateLimit;
 	use updater::{UpdatePolicy, UpdateFilter, ReleaseTrack};
 
 	use account::{AccountCmd, NewAccount, ImportAccounts, ListAccounts};
 	use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, DataFormat, ExportState};
 	use cli::Args;
+	use db::{DbCmd, ConvertDb, PruneDb, VerifyDb};
+	use journaldb::Algorithm;
 	use dir::{Directories, default_hypervisor_path};
 	use helpers::{default_network_config};
 	use params::SpecType;
@@ -1253,6 +1349,103 @@ mod tests {
 		}));
 	}
 
+	#[test]
+	fn test_command_db_convert() {
+		let args = vec!["parity", "db", "convert", "--to=logdb"];
+		let conf = parse(&args);
+		assert_eq!(conf.into_command().unwrap().cmd, Cmd::Db(DbCmd::Convert(ConvertDb {
+			spec: Default::default(),
+			dirs: Default::default(),
+			pruning: Default::default(),
+			compaction: Default::default(),
+			to: DatabaseBackend::LogDb,
+		})));
+
+		let args = vec!["parity", "db", "convert"];
+		assert!(parse(&args).into_command().is_err());
+	}
+
+	#[test]
+	fn test_command_db_prune() {
+		let args = vec!["parity", "db", "prune", "--to=fast", "--keep=128"];
+		let conf = parse(&args);
+		assert_eq!(conf.into_command().unwrap().cmd, Cmd::Db(DbCmd::Prune(PruneDb {
+			spec: Default::default(),
+			dirs: Default::default(),
+			pruning: Default::default(),
+			compaction: Default::default(),
+			to: Algorithm::OverlayRecent,
+			keep: 128,
+		})));
+	}
+
+	#[test]
+	fn test_command_db_verify() {
+		let args = vec!["parity", "db", "verify", "--at=100", "--from=1", "--no-storage"];
+		let conf = parse(&args);
+		assert_eq!(conf.into_command().unwrap().cmd, Cmd::Db(DbCmd::Verify(VerifyDb {
+			spec: Default::default(),
+			dirs: Default::default(),
+			pruning: Default::default(),
+			compaction: Default::default(),
+			at: BlockId::Number(100),
+			storage: false,
+			from: BlockId::Number(1),
+			to: BlockId::Number(100),
+		})));
+	}
+
+	#[test]
+	fn test_command_snapshot() {
+		let args = vec!["parity", "snapshot", "--snapshot-compression=zstd", "snapshot.bin"];
+		let conf = parse(&args);
+		assert_eq!(conf.into_command().unwrap().cmd, Cmd::Snapshot(SnapshotCommand {
+			cache_config: Default::default(),
+			dirs: Default::default(),
+			spec: Default::default(),
+			pruning: Default::default(),
+			pruning_history: 64,
+			pruning_memory: 32,
+			tracing: Default::default(),
+			fat_db: Default::default(),
+			compaction: Default::default(),
+			db_backend: Default::default(),
+			file_path: Some("snapshot.bin".into()),
+			wal: true,
+			kind: snapshot::Kind::Take,
+			block_at: BlockId::Latest,
+			compression: ChunkCompression::Zstd,
+			allow_unchecked: false,
+		}));
+	}
+
+	#[test]
+	fn test_command_restore_unchecked() {
+		let args = vec!["parity", "restore", "--allow-unchecked", "snapshot.bin"];
+		let conf = parse(&args);
+		match conf.into_command().unwrap().cmd {
+			Cmd::Snapshot(cmd) => {
+				assert_eq!(cmd.kind, snapshot::Kind::Restore);
+				assert_eq!(cmd.file_path, Some("snapshot.bin".into()));
+				assert!(cmd.allow_unchecked);
+			},
+			_ => panic!("Should be snapshot command"),
+		}
+	}
+
+	#[test]
+	fn test_command_snapshot_inspect() {
+		let args = vec!["parity", "snapshot", "inspect", "snapshot.bin"];
+		let conf = parse(&args);
+		match conf.into_command().unwrap().cmd {
+			Cmd::Snapshot(cmd) => {
+				assert_eq!(cmd.kind, snapshot::Kind::Inspect);
+				assert_eq!(cmd.file_path, Some("snapshot.bin".into()));
+			},
+			_ => panic!("Should be snapshot command"),
+		}
+	}
+
 	#[test]
 	fn test_command_blockchain_import() {
 		let args = vec!["parity", "import", "blockchain.json"];
@@ -1267,6 +1460,7 @@ mod tests {
 			pruning_history: 64,
 			pruning_memory: 32,
 			compaction: Default::default(),
+			db_backend: Default::default(),
 			wal: true,
 			tracing: Default::default(),
 			fat_db: Default::default(),
@@ -1292,6 +1486,7 @@ mod tests {
 			pruning_memory: 32,
 			format: Default::default(),
 			compaction: Default::default(),
+			db_backend: Default::default(),
 			wal: true,
 			tracing: Default::default(),
 			fat_db: Default::default(),
@@ -1315,6 +1510,7 @@ mod tests {
 			pruning_memory: 32,
 			format: Default::default(),
 			compaction: Default::default(),
+			db_backend: Default::default(),
 			wal: true,
 			tracing: Default::default(),
 			fat_db: Default::default(),
@@ -1340,6 +1536,7 @@ mod tests {
 			pruning_memory: 32,
 			format: Some(DataFormat::Hex),
 			compaction: Default::default(),
+			db_backend: Default::default(),
 			wal: true,
 			tracing: Default::default(),
 			fat_db: Default::default(),
@@ -1361,6 +1558,8 @@ mod tests {
 			apis: ApiSet::UnsafeContext,
 			origins: Some(vec!["parity://*".into(),"chrome-extension://*".into(), "moz-extension://*".into()]),
 			hosts: Some(vec![]),
+			methods: Default::default(),
+			rate_limit: None,
 			signer_path: expected.into(),
 			ui_address: Some("127.0.0.1:8180".into()),
 			dapps_address: Some("127.0.0.1:8545".into()),
@@ -1393,6 +1592,8 @@ mod tests {
 			logger_config: Default::default(),
 			miner_options: Default::default(),
 			gas_price_percentile: 50,
+			logs_max_block_range: None,
+			logs_max_results: None,
 			ntp_servers: vec![
 				"0.parity.pool.ntp.org:123".into(),
 				"1.parity.pool.ntp.org:123".into(),
@@ -1421,6 +1622,7 @@ mod tests {
 			mode: Default::default(),
 			tracing: Default::default(),
 			compaction: Default::default(),
+			db_backend: Default::default(),
 			wal: true,
 			vm_type: Default::default(),
 			geth_compatibility: false,
@@ -1434,7 +1636,10 @@ mod tests {
 			name: "".into(),
 			custom_bootnodes: false,
 			fat_db: Default::default(),
+			account_tx_index: false,
+			token_index: false,
 			no_periodic_snapshot: false,
+			snapshot_compression: Default::default(),
 			stratum: None,
 			check_seal: true,
 			download_old_blocks: true,
@@ -1579,6 +1784,40 @@ mod tests {
 		assert_eq!(conf3.rpc_hosts(), Some(vec!["parity.io".into(), "something.io".into()]));
 	}
 
+	#[test]
+	fn should_parse_method_access_rules() {
+		// given
+
+		// when
+		let conf0 = parse(&["parity"]);
+		let conf1 = parse(&["parity", "--jsonrpc-allow-methods", "eth_*,net_*", "--jsonrpc-deny-methods", "eth_sign"]);
+		let conf2 = parse(&["parity", "--ws-deny-methods", "@http://parity.io"]);
+
+		// then
+		assert_eq!(conf0.http_config().unwrap().methods, MethodAccess::default());
+		assert_eq!(conf1.http_config().unwrap().methods, MethodAccess::parse("eth_*,net_*", "eth_sign").unwrap());
+		assert_eq!(conf1.ws_config().unwrap().methods, MethodAccess::default());
+		assert!(conf2.ws_config().is_err());
+	}
+
+	#[test]
+	fn should_parse_rate_limit() {
+		// given
+
+		// when
+		let conf0 = parse(&["parity"]);
+		let conf1 = parse(&["parity", "--jsonrpc-rate-limit", "20"]);
+		let conf2 = parse(&["parity", "--jsonrpc-rate-limit", "20", "--jsonrpc-rate-burst", "50", "--jsonrpc-method-costs", "eth_call=3"]);
+		let conf3 = parse(&["parity", "--jsonrpc-rate-limit", "20", "--jsonrpc-method-costs", "eth_call"]);
+
+		// then
+		assert_eq!(conf0.http_config().unwrap().rate_limit, None);
+		assert_eq!(conf1.http_config().unwrap().rate_limit, Some(RateLimit::new(20, 200)));
+		assert_eq!(conf1.ws_config().unwrap().rate_limit, Some(RateLimit::new(20, 200)));
+		assert_eq!(conf2.http_config().unwrap().rate_limit, Some(RateLimit::new(20, 50).with_costs("eth_call=3").unwrap()));
+		assert!(conf3.http_config().is_err());
+	}
+
 	#[test]
 	fn should_parse_ipfs_hosts() {
 		// given
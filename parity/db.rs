@@ -0,0 +1,337 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Database maintenance subcommands.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use ethcore::client::{BlockId, DatabaseCompactionProfile, DatabaseBackend};
+use ethcore::db::{COL_STATE, NUM_COLUMNS};
+use ethcore::state::prune::{prune_state, PruneReport};
+use ethcore::verification::database::{verify_database, VerifyParams};
+use journaldb::Algorithm;
+use kvdb::KeyValueDB;
+use kvdb_rocksdb::DatabaseConfig;
+use params::{SpecType, Pruning};
+use helpers::execute_upgrades;
+use dir::Directories;
+use user_defaults::UserDefaults;
+
+/// Number of keys written in a single transaction while copying.
+const COPY_BATCH_SIZE: usize = 16 * 1024;
+
+#[derive(Debug, PartialEq)]
+pub enum DbCmd {
+	Convert(ConvertDb),
+	Prune(PruneDb),
+	Verify(VerifyDb),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ConvertDb {
+	pub spec: SpecType,
+	pub dirs: Directories,
+	pub pruning: Pruning,
+	pub compaction: DatabaseCompactionProfile,
+	pub to: DatabaseBackend,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PruneDb {
+	pub spec: SpecType,
+	pub dirs: Directories,
+	pub pruning: Pruning,
+	pub compaction: DatabaseCompactionProfile,
+	pub to: Algorithm,
+	pub keep: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifyDb {
+	pub spec: SpecType,
+	pub dirs: Directories,
+	pub pruning: Pruning,
+	pub compaction: DatabaseCompactionProfile,
+	pub at: BlockId,
+	pub storage: bool,
+	pub from: BlockId,
+	pub to: BlockId,
+}
+
+pub fn execute(cmd: DbCmd) -> Result<String, String> {
+	match cmd {
+		DbCmd::Convert(convert_cmd) => convert_db(convert_cmd),
+		DbCmd::Prune(prune_cmd) => prune_db(prune_cmd),
+		DbCmd::Verify(verify_cmd) => verify_db(verify_cmd),
+	}
+}
+
+fn all_columns() -> Vec<Option<u32>> {
+	Some(None).into_iter().chain((0..NUM_COLUMNS.unwrap_or(0)).map(Some)).collect()
+}
+
+/// Copies all columns of `from` into `to`. Returns the number of copied keys.
+pub fn copy_columns(from: &KeyValueDB, to: &KeyValueDB) -> Result<u64, String> {
+	let mut copied = 0;
+	for col in all_columns() {
+		copied += copy_column(from, to, col)?;
+		info!("Copied column {:?}, {} keys in total", col, copied);
+	}
+
+	to.flush().map_err(|e| format!("Error writing database: {}", e))?;
+	Ok(copied)
+}
+
+fn copy_column(from: &KeyValueDB, to: &KeyValueDB, col: Option<u32>) -> Result<u64, String> {
+	let mut batch = to.transaction();
+	let mut batch_len = 0;
+	let mut copied = 0;
+
+	for (key, value) in from.iter(col) {
+		batch.put_vec(col, &key, value.into_vec());
+		batch_len += 1;
+
+		if batch_len == COPY_BATCH_SIZE {
+			to.write(batch).map_err(|e| format!("Error writing database: {}", e))?;
+			batch = to.transaction();
+			copied += batch_len as u64;
+			batch_len = 0;
+		}
+	}
+
+	to.write(batch).map_err(|e| format!("Error writing database: {}", e))?;
+	Ok(copied + batch_len as u64)
+}
+
+/// Replaces database at `db_path` with the one at `new_path`.
+fn replace_db(db_path: &Path, new_path: &Path) -> Result<(), String> {
+	let backup_path = sibling_path(db_path, "temp_backup");
+	let _ = fs::remove_dir_all(&backup_path);
+
+	fs::rename(db_path, &backup_path).map_err(|e| format!("Error moving database: {}", e))?;
+	if let Err(e) = fs::rename(new_path, db_path) {
+		// bring back the original database
+		fs::rename(&backup_path, db_path).map_err(|e| format!("Error restoring database: {}", e))?;
+		return Err(format!("Error moving database: {}", e));
+	}
+
+	fs::remove_dir_all(&backup_path).map_err(|e| format!("Error removing database backup: {}", e))
+}
+
+fn sibling_path(path: &Path, name: &str) -> PathBuf {
+	let mut sibling = path.to_owned();
+	sibling.pop();
+	sibling.push(name);
+	sibling
+}
+
+fn convert_db(cmd: ConvertDb) -> Result<String, String> {
+	let spec = cmd.spec.spec(&cmd.dirs.cache)?;
+	let genesis_hash = spec.genesis_header().hash();
+	let db_dirs = cmd.dirs.database(genesis_hash, None, spec.data_dir);
+	let user_defaults = UserDefaults::load(&db_dirs.user_defaults_path())?;
+	let algorithm = cmd.pruning.to_algorithm(&user_defaults);
+	let compaction = cmd.compaction.compaction_profile(db_dirs.db_root_path().as_path());
+	let client_path = db_dirs.client_path(algorithm);
+
+	let from = DatabaseBackend::detect(&client_path)
+		.ok_or_else(|| format!("No database found at {}", client_path.display()))?;
+	if from == cmd.to {
+		return Ok(format!("Database at {} already uses {} backend.", client_path.display(), from));
+	}
+
+	execute_upgrades(&cmd.dirs.base, &db_dirs, algorithm, compaction)?;
+
+	let mut db_config = DatabaseConfig::with_columns(NUM_COLUMNS);
+	db_config.compaction = compaction;
+
+	let converted_path = sibling_path(&client_path, "temp_convert");
+	let _ = fs::remove_dir_all(&converted_path);
+
+	info!("Converting database at {} from {} to {}", client_path.display(), from, cmd.to);
+	let copied = {
+		let source = from.open(&db_config, &client_path).map_err(|e| format!("Error opening database: {}", e))?;
+		let target = cmd.to.open(&db_config, &converted_path).map_err(|e| format!("Error creating database: {}", e))?;
+		copy_columns(&*source, &*target)
+	};
+
+	let copied = match copied {
+		Ok(copied) => copied,
+		Err(e) => {
+			let _ = fs::remove_dir_all(&converted_path);
+			return Err(e);
+		},
+	};
+
+	replace_db(&client_path, &converted_path)?;
+	Ok(format!("Converted {} keys. Run Parity with --db-backend={} from now on.", copied, cmd.to))
+}
+
+/// Copies recent state and all other columns of `source` into `target`.
+fn copy_pruned(source: Arc<KeyValueDB>, target: Arc<KeyValueDB>, genesis: &[u8], keep: u64) -> Result<PruneReport, String> {
+	for col in all_columns().into_iter().filter(|col| *col != COL_STATE) {
+		copy_column(&*source, &*target, col)?;
+	}
+	target.flush().map_err(|e| format!("Error writing database: {}", e))?;
+
+	prune_state(source, target, genesis, keep).map_err(|e| format!("Error pruning state: {}", e))
+}
+
+fn prune_db(cmd: PruneDb) -> Result<String, String> {
+	if cmd.to != Algorithm::OverlayRecent {
+		return Err(format!("Pruning into {} database is not supported. Use --to=fast.", cmd.to));
+	}
+	if cmd.keep == 0 {
+		return Err("State of at least one block has to be kept. Use --keep=[N] with N > 0.".into());
+	}
+
+	let spec = cmd.spec.spec(&cmd.dirs.cache)?;
+	let genesis = spec.genesis_block();
+	let genesis_hash = spec.genesis_header().hash();
+	let db_dirs = cmd.dirs.database(genesis_hash, None, spec.data_dir);
+	let user_defaults_path = db_dirs.user_defaults_path();
+	let mut user_defaults = UserDefaults::load(&user_defaults_path)?;
+	let algorithm = cmd.pruning.to_algorithm(&user_defaults);
+	let compaction = cmd.compaction.compaction_profile(db_dirs.db_root_path().as_path());
+
+	if algorithm != Algorithm::Archive {
+		return Err(format!("Only archive databases can be pruned, the selected database uses {} pruning.", algorithm));
+	}
+
+	let client_path = db_dirs.client_path(algorithm);
+	let target_path = db_dirs.client_path(cmd.to);
+	if target_path.exists() {
+		return Err(format!("Database already exists at {}. Remove it before pruning.", target_path.display()));
+	}
+
+	let backend = DatabaseBackend::detect(&client_path)
+		.ok_or_else(|| format!("No database found at {}", client_path.display()))?;
+
+	execute_upgrades(&cmd.dirs.base, &db_dirs, algorithm, compaction)?;
+
+	let mut db_config = DatabaseConfig::with_columns(NUM_COLUMNS);
+	db_config.compaction = compaction;
+
+	let pruned_path = sibling_path(&client_path, "temp_prune");
+	let _ = fs::remove_dir_all(&pruned_path);
+
+	info!("Pruning database at {} keeping state of {} most recent blocks", client_path.display(), cmd.keep);
+	let source = backend.open(&db_config, &client_path).map_err(|e| format!("Error opening database: {}", e))?;
+	let report = backend.open(&db_config, &pruned_path)
+		.map_err(|e| format!("Error creating database: {}", e))
+		.and_then(|target| copy_pruned(source, target, &genesis, cmd.keep));
+
+	let report = match report {
+		Ok(report) => report,
+		Err(e) => {
+			let _ = fs::remove_dir_all(&pruned_path);
+			return Err(e);
+		},
+	};
+
+	fs::create_dir_all(db_dirs.db_path(cmd.to)).map_err(|e| format!("Error creating database directory: {}", e))?;
+	fs::rename(&pruned_path, &target_path).map_err(|e| format!("Error moving database: {}", e))?;
+	execute_upgrades(&cmd.dirs.base, &db_dirs, cmd.to, compaction)?;
+
+	user_defaults.pruning = cmd.to;
+	// fat db entries are not part of the copied tries
+	user_defaults.fat_db = false;
+	user_defaults.save(&user_defaults_path)?;
+
+	Ok(format!(
+		"Copied {} state nodes of blocks #{}..#{} into {}. The archive database at {} is no longer used and can be removed.",
+		report.nodes,
+		report.best_block + 1 - report.blocks,
+		report.best_block,
+		target_path.display(),
+		client_path.display(),
+	))
+}
+
+fn verify_db(cmd: VerifyDb) -> Result<String, String> {
+	let spec = cmd.spec.spec(&cmd.dirs.cache)?;
+	let genesis = spec.genesis_block();
+	let genesis_hash = spec.genesis_header().hash();
+	let db_dirs = cmd.dirs.database(genesis_hash, None, spec.data_dir);
+	let user_defaults = UserDefaults::load(&db_dirs.user_defaults_path())?;
+	let algorithm = cmd.pruning.to_algorithm(&user_defaults);
+	let compaction = cmd.compaction.compaction_profile(db_dirs.db_root_path().as_path());
+	let client_path = db_dirs.client_path(algorithm);
+
+	let backend = DatabaseBackend::detect(&client_path)
+		.ok_or_else(|| format!("No database found at {}", client_path.display()))?;
+
+	execute_upgrades(&cmd.dirs.base, &db_dirs, algorithm, compaction)?;
+
+	let mut db_config = DatabaseConfig::with_columns(NUM_COLUMNS);
+	db_config.compaction = compaction;
+	let db = backend.open(&db_config, &client_path).map_err(|e| format!("Error opening database: {}", e))?;
+
+	let params = VerifyParams {
+		at: cmd.at,
+		storage: cmd.storage,
+		from: cmd.from,
+		to: cmd.to,
+	};
+	// problems are logged by the verification as they are found
+	let report = verify_database(db, algorithm, &genesis, &params).map_err(|e| format!("Error verifying database: {}", e))?;
+
+	let summary = format!(
+		"Visited {} accounts and {} trie nodes at block #{}, verified roots of {} blocks",
+		report.accounts,
+		report.nodes,
+		report.block,
+		report.blocks,
+	);
+
+	match report.problem_count {
+		0 => Ok(format!("{}. No problems found.", summary)),
+		n => Err(format!("{}. Found {} problems.", summary, n)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ethcore::client::DatabaseBackend;
+	use ethcore::db::NUM_COLUMNS;
+	use kvdb_rocksdb::DatabaseConfig;
+	use tempdir::TempDir;
+	use super::copy_columns;
+
+	#[test]
+	fn should_copy_all_columns_between_backends() {
+		let tempdir = TempDir::new("").unwrap();
+		let config = DatabaseConfig::with_columns(NUM_COLUMNS);
+		let from = DatabaseBackend::RocksDb.open(&config, &tempdir.path().join("from")).unwrap();
+		let to = DatabaseBackend::LogDb.open(&config, &tempdir.path().join("to")).unwrap();
+
+		let mut batch = from.transaction();
+		batch.put(None, b"version", b"1");
+		for col in 0..NUM_COLUMNS.unwrap() {
+			batch.put(Some(col), b"key", &[col as u8]);
+		}
+		from.write(batch).unwrap();
+
+		assert_eq!(copy_columns(&*from, &*to).unwrap(), NUM_COLUMNS.unwrap() as u64 + 1);
+		assert_eq!(&*to.get(None, b"version").unwrap().unwrap(), b"1");
+		for col in 0..NUM_COLUMNS.unwrap() {
+			assert_eq!(&*to.get(Some(col), b"key").unwrap().unwrap(), &[col as u8]);
+		}
+		assert_eq!(DatabaseBackend::detect(&tempdir.path().join("to")), Some(DatabaseBackend::LogDb));
+	}
+}
@@ -21,7 +21,7 @@ use std::fs::File;
 use ethereum_types::{U256, clean_0x, Address};
 use kvdb_rocksdb::CompactionProfile;
 use journaldb::Algorithm;
-use ethcore::client::{Mode, BlockId, VMType, DatabaseCompactionProfile, ClientConfig, VerifierType};
+use ethcore::client::{Mode, BlockId, VMType, DatabaseCompactionProfile, DatabaseBackend, ClientConfig, VerifierType};
 use ethcore::miner::{PendingSet, GasLimit};
 use miner::transaction_queue::PrioritizationStrategy;
 use cache::CacheConfig;
@@ -211,6 +211,7 @@ pub fn to_client_config(
 		tracing: bool,
 		fat_db: bool,
 		compaction: DatabaseCompactionProfile,
+		db_backend: DatabaseBackend,
 		wal: bool,
 		vm_type: VMType,
 		name: String,
@@ -247,6 +248,7 @@ pub fn to_client_config(
 	client_config.pruning = pruning;
 	client_config.history = pruning_history;
 	client_config.db_compaction = compaction;
+	client_config.db_backend = db_backend;
 	client_config.db_wal = wal;
 	client_config.vm_type = vm_type;
 	client_config.name = name;
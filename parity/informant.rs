@@ -39,6 +39,9 @@ use ethereum_types::H256;
 use bytes::Bytes;
 use parking_lot::{RwLock, Mutex};
 
+/// Number of most called RPC methods included in the report.
+const RPC_METHODS_REPORTED: usize = 10;
+
 /// Format byte counts to standard denominations.
 pub fn format_bytes(b: usize) -> String {
 	match binary_prefix(b as f64) {
@@ -345,6 +348,16 @@ impl<T: InformantData> Informant<T> {
 				_ => String::new(),
 			},
 		);
+
+		if let Some(rpc_stats) = rpc_stats {
+			let calls = rpc_stats.method_calls(RPC_METHODS_REPORTED);
+			if !calls.is_empty() {
+				debug!(target: "rpc", "Most called methods: {}", calls.iter()
+					.map(|&(ref method, count)| format!("{} {}", method, count))
+					.collect::<Vec<_>>()
+					.join(", "));
+			}
+		}
 	}
 }
 
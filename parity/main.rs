@@ -104,6 +104,7 @@ mod cache;
 mod cli;
 mod configuration;
 mod dapps;
+mod db;
 mod ipfs;
 mod deprecated;
 mod helpers;
@@ -173,6 +174,7 @@ fn execute(command: Execute, can_restart: bool) -> Result<PostExecutionAction, S
 		Cmd::SignerList { port, authfile } => rpc_cli::signer_list(port, authfile).map(|s| PostExecutionAction::Print(s)),
 		Cmd::SignerReject { id, port, authfile } => rpc_cli::signer_reject(id, port, authfile).map(|s| PostExecutionAction::Print(s)),
 		Cmd::Snapshot(snapshot_cmd) => snapshot::execute(snapshot_cmd).map(|s| PostExecutionAction::Print(s)),
+		Cmd::Db(db_cmd) => db::execute(db_cmd).map(|s| PostExecutionAction::Print(s)),
 	}
 }
 
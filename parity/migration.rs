@@ -25,7 +25,7 @@ use migrations;
 /// Database is assumed to be at default version, when no version file is found.
 const DEFAULT_VERSION: u32 = 5;
 /// Current version of database models.
-const CURRENT_VERSION: u32 = 12;
+const CURRENT_VERSION: u32 = 14;
 /// First version of the consolidated database.
 const CONSOLIDATION_VERSION: u32 = 9;
 /// Defines how many items are migrated to the new version of database at once.
@@ -136,6 +136,8 @@ fn consolidated_database_migrations(compaction_profile: &CompactionProfile) -> R
 	let mut manager = MigrationManager::new(default_migration_settings(compaction_profile));
 	manager.add_migration(migrations::TO_V11).map_err(|_| Error::MigrationImpossible)?;
 	manager.add_migration(migrations::TO_V12).map_err(|_| Error::MigrationImpossible)?;
+	manager.add_migration(migrations::TO_V13).map_err(|_| Error::MigrationImpossible)?;
+	manager.add_migration(migrations::TO_V14).map_err(|_| Error::MigrationImpossible)?;
 	Ok(manager)
 }
 
@@ -25,7 +25,9 @@ use dir::helpers::replace_home;
 use helpers::parity_ipc_path;
 use jsonrpc_core::MetaIoHandler;
 use parity_reactor::TokioRemote;
+use parity_rpc::access::{MethodAccess, AccessMiddleware};
 use parity_rpc::informant::{RpcStats, Middleware};
+use parity_rpc::ratelimit::{RateLimit, RateLimitMiddleware};
 use parity_rpc::{self as rpc, Metadata, DomainsValidation};
 use rpc_apis::{self, ApiSet};
 
@@ -43,6 +45,8 @@ pub struct HttpConfiguration {
 	pub apis: ApiSet,
 	pub cors: Option<Vec<String>>,
 	pub hosts: Option<Vec<String>>,
+	pub methods: MethodAccess,
+	pub rate_limit: Option<RateLimit>,
 	pub server_threads: usize,
 	pub processing_threads: usize,
 }
@@ -62,6 +66,8 @@ impl Default for HttpConfiguration {
 			apis: ApiSet::UnsafeContext,
 			cors: Some(vec![]),
 			hosts: Some(vec![]),
+			methods: MethodAccess::default(),
+			rate_limit: None,
 			server_threads: 1,
 			processing_threads: 4,
 		}
@@ -102,6 +108,8 @@ impl From<UiConfiguration> for HttpConfiguration {
 			apis: rpc_apis::ApiSet::UnsafeContext,
 			cors: Some(vec![]),
 			hosts: conf.hosts,
+			methods: MethodAccess::default(),
+			rate_limit: None,
 			server_threads: 1,
 			processing_threads: 0,
 		}
@@ -125,6 +133,7 @@ pub struct IpcConfiguration {
 	pub enabled: bool,
 	pub socket_addr: String,
 	pub apis: ApiSet,
+	pub methods: MethodAccess,
 }
 
 impl Default for IpcConfiguration {
@@ -138,6 +147,7 @@ impl Default for IpcConfiguration {
 				parity_ipc_path(&data_dir, "$BASE/jsonrpc.ipc", 0)
 			},
 			apis: ApiSet::IpcContext,
+			methods: MethodAccess::default(),
 		}
 	}
 }
@@ -150,6 +160,8 @@ pub struct WsConfiguration {
 	pub apis: ApiSet,
 	pub origins: Option<Vec<String>>,
 	pub hosts: Option<Vec<String>>,
+	pub methods: MethodAccess,
+	pub rate_limit: Option<RateLimit>,
 	pub signer_path: PathBuf,
 	pub support_token_api: bool,
 	pub ui_address: Option<rpc::Host>,
@@ -166,6 +178,8 @@ impl Default for WsConfiguration {
 			apis: ApiSet::UnsafeContext,
 			origins: Some(vec!["parity://*".into(),"chrome-extension://*".into(), "moz-extension://*".into()]),
 			hosts: Some(Vec::new()),
+			methods: MethodAccess::default(),
+			rate_limit: None,
 			signer_path: replace_home(&data_dir, "$BASE/signer").into(),
 			support_token_api: true,
 			ui_address: Some("127.0.0.1:8180".into()),
@@ -211,11 +225,13 @@ pub fn new_ws<D: rpc_apis::Dependencies>(
 	let addr = url.parse().map_err(|_| format!("Invalid WebSockets listen host/port given: {}", url))?;
 
 
-	let full_handler = setup_apis(rpc_apis::ApiSet::SafeContext, deps);
+	let full_handler = setup_apis(rpc_apis::ApiSet::SafeContext, MethodAccess::default(), None, deps);
 	let handler = {
 		let mut handler = MetaIoHandler::with_middleware((
 			rpc::WsDispatcher::new(full_handler),
-			Middleware::new(deps.stats.clone(), deps.apis.activity_notifier(), deps.pool.clone())
+			Middleware::new(deps.stats.clone(), deps.apis.activity_notifier(), deps.pool.clone()),
+			AccessMiddleware::new(conf.methods.clone()),
+			RateLimitMiddleware::new(conf.rate_limit.clone()),
 		));
 		let apis = conf.apis.list_apis();
 		deps.apis.extend_with_set(&mut handler, &apis);
@@ -270,7 +286,7 @@ pub fn new_http<D: rpc_apis::Dependencies>(
 	let domain = DAPPS_DOMAIN;
 	let url = format!("{}:{}", conf.interface, conf.port);
 	let addr = url.parse().map_err(|_| format!("Invalid {} listen host/port given: {}", id, url))?;
-	let handler = setup_apis(conf.apis, deps);
+	let handler = setup_apis(conf.apis, conf.methods, conf.rate_limit, deps);
 	let remote = deps.remote.clone();
 
 	let cors_domains = into_domains(conf.cors);
@@ -304,7 +320,7 @@ pub fn new_ipc<D: rpc_apis::Dependencies>(
 		return Ok(None);
 	}
 
-	let handler = setup_apis(conf.apis, dependencies);
+	let handler = setup_apis(conf.apis, conf.methods, None, dependencies);
 	let remote = dependencies.remote.clone();
 	let path = PathBuf::from(&conf.socket_addr);
 	// Make sure socket file can be created on unix-like OS.
@@ -352,12 +368,20 @@ fn with_domain(items: Option<Vec<String>>, domain: &str, ui_address: &Option<rpc
 	})
 }
 
-fn setup_apis<D>(apis: ApiSet, deps: &Dependencies<D>) -> MetaIoHandler<Metadata, Middleware<D::Notifier>>
+fn setup_apis<D>(
+	apis: ApiSet,
+	methods: MethodAccess,
+	rate_limit: Option<RateLimit>,
+	deps: &Dependencies<D>,
+) -> MetaIoHandler<Metadata, (Middleware<D::Notifier>, AccessMiddleware, RateLimitMiddleware)>
 	where D: rpc_apis::Dependencies
 {
-	let mut handler = MetaIoHandler::with_middleware(
-		Middleware::new(deps.stats.clone(), deps.apis.activity_notifier(), deps.pool.clone())
-	);
+	let mut handler = MetaIoHandler::with_middleware((
+		Middleware::new(deps.stats.clone(), deps.apis.activity_notifier(), deps.pool.clone()),
+		// rejected methods are not charged.
+		AccessMiddleware::new(methods),
+		RateLimitMiddleware::new(rate_limit),
+	));
 	let apis = apis.list_apis();
 	deps.apis.extend_with_set(&mut handler, &apis);
 
@@ -66,6 +66,8 @@ pub enum Api {
 	ParitySet,
 	/// Traces (Safe)
 	Traces,
+	/// Debug - Geth-compatible struct logs (UNSAFE: Expensive to serve)
+	Debug,
 	/// Rpc (Safe)
 	Rpc,
 	/// SecretStore (UNSAFE: arbitrary hash signing)
@@ -96,6 +98,7 @@ impl FromStr for Api {
 			"parity_accounts" => Ok(ParityAccounts),
 			"parity_set" => Ok(ParitySet),
 			"traces" => Ok(Traces),
+			"debug" => Ok(Debug),
 			"rpc" => Ok(Rpc),
 			"secretstore" => Ok(SecretStore),
 			"shh" => Ok(Whisper),
@@ -181,6 +184,7 @@ fn to_modules(apis: &HashSet<Api>) -> BTreeMap<String, String> {
 			Api::ParityPubSub => ("parity_pubsub", "1.0"),
 			Api::ParitySet => ("parity_set", "1.0"),
 			Api::Traces => ("traces", "1.0"),
+			Api::Debug => ("debug", "1.0"),
 			Api::Rpc => ("rpc", "1.0"),
 			Api::SecretStore => ("secretstore", "1.0"),
 			Api::Whisper => ("shh", "1.0"),
@@ -230,9 +234,18 @@ pub struct FullDependencies {
 	pub remote: parity_reactor::Remote,
 	pub whisper_rpc: Option<::whisper::RpcFactory>,
 	pub gas_price_percentile: usize,
+	pub logs_max_block_range: Option<u64>,
+	pub logs_max_results: Option<usize>,
 }
 
 impl FullDependencies {
+	fn logs_limits(&self) -> ::parity_rpc::v1::LogsLimits {
+		::parity_rpc::v1::LogsLimits {
+			max_block_range: self.logs_max_block_range,
+			max_results: self.logs_max_results,
+		}
+	}
+
 	fn extend_api<S>(
 		&self,
 		handler: &mut MetaIoHandler<Metadata, S>,
@@ -283,12 +296,14 @@ impl FullDependencies {
 							allow_pending_receipt_query: !self.geth_compatibility,
 							send_block_number_in_get_work: !self.geth_compatibility,
 							gas_price_percentile: self.gas_price_percentile,
+							logs_max_block_range: self.logs_max_block_range,
+							logs_max_results: self.logs_max_results,
 						}
 					);
 					handler.extend_with(client.to_delegate());
 
 					if !for_generic_pubsub {
-						let filter_client = EthFilterClient::new(self.client.clone(), self.miner.clone());
+						let filter_client = EthFilterClient::new(self.client.clone(), self.miner.clone(), self.logs_limits());
 						handler.extend_with(filter_client.to_delegate());
 
 						add_signing_methods!(EthSigning, handler, self, nonces.clone());
@@ -296,7 +311,7 @@ impl FullDependencies {
 				},
 				Api::EthPubSub => {
 					if !for_generic_pubsub {
-						let client = EthPubSubClient::new(self.client.clone(), self.remote.clone());
+						let client = EthPubSubClient::new(self.client.clone(), self.remote.clone(), self.logs_limits());
 						let h = client.handler();
 						self.miner.add_transactions_listener(Box::new(move |hashes| if let Some(h) = h.upgrade() {
 							h.new_transactions(hashes);
@@ -363,6 +378,9 @@ impl FullDependencies {
 				Api::Traces => {
 					handler.extend_with(TracesClient::new(&self.client).to_delegate())
 				},
+				Api::Debug => {
+					handler.extend_with(DebugClient::new(&self.client).to_delegate())
+				},
 				Api::Rpc => {
 					let modules = to_modules(&apis);
 					handler.extend_with(RpcClient::new(modules).to_delegate());
@@ -574,6 +592,9 @@ impl<C: LightChainClient + 'static> LightDependencies<C> {
 				Api::Traces => {
 					handler.extend_with(light::TracesClient.to_delegate())
 				},
+				Api::Debug => {
+					handler.extend_with(light::DebugClient.to_delegate())
+				},
 				Api::Rpc => {
 					let modules = to_modules(&apis);
 					handler.extend_with(RpcClient::new(modules).to_delegate());
@@ -662,6 +683,7 @@ impl ApiSet {
 				public_list.insert(Api::Signer);
 				public_list.insert(Api::Personal);
 				public_list.insert(Api::SecretStore);
+				public_list.insert(Api::Debug);
 				public_list
 			},
 			ApiSet::PubSub => [
@@ -691,6 +713,7 @@ mod test {
 		assert_eq!(Api::ParityAccounts, "parity_accounts".parse().unwrap());
 		assert_eq!(Api::ParitySet, "parity_set".parse().unwrap());
 		assert_eq!(Api::Traces, "traces".parse().unwrap());
+		assert_eq!(Api::Debug, "debug".parse().unwrap());
 		assert_eq!(Api::Rpc, "rpc".parse().unwrap());
 		assert_eq!(Api::SecretStore, "secretstore".parse().unwrap());
 		assert_eq!(Api::Whisper, "shh".parse().unwrap());
@@ -747,7 +770,8 @@ mod test {
 			Api::Web3, Api::Net, Api::Eth, Api::EthPubSub, Api::Parity, Api::ParityPubSub, Api::Traces, Api::Rpc, Api::SecretStore, Api::Whisper, Api::WhisperPubSub,
 			Api::ParityAccounts,
 			Api::ParitySet, Api::Signer,
-			Api::Personal
+			Api::Personal,
+			Api::Debug,
 		].into_iter().collect()));
 	}
 
@@ -757,6 +781,7 @@ mod test {
 			Api::Web3, Api::Net, Api::Eth, Api::EthPubSub, Api::Parity, Api::ParityPubSub, Api::Traces, Api::Rpc, Api::SecretStore, Api::Whisper, Api::WhisperPubSub,
 			Api::ParityAccounts,
 			Api::ParitySet, Api::Signer,
+			Api::Debug,
 		].into_iter().collect()));
 	}
 
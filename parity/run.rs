@@ -23,12 +23,12 @@ use std::net::{TcpListener};
 use ansi_term::{Colour, Style};
 use ctrlc::CtrlC;
 use ethcore::account_provider::{AccountProvider, AccountProviderSettings};
-use ethcore::client::{Client, Mode, DatabaseCompactionProfile, VMType, BlockChainClient};
+use ethcore::client::{Client, Mode, DatabaseCompactionProfile, DatabaseBackend, VMType, BlockChainClient};
 use ethcore::ethstore::ethkey;
 use ethcore::miner::{Miner, MinerService, MinerOptions};
 use ethcore::miner::{StratumOptions, Stratum};
 use ethcore::service::ClientService;
-use ethcore::snapshot;
+use ethcore::snapshot::{self, ChunkCompression};
 use ethcore::spec::{SpecParams, OptimizeFor};
 use ethcore::verification::queue::VerifierSettings;
 use ethcore_logger::{Config as LogConfig, RotatingLogger};
@@ -92,6 +92,8 @@ pub struct RunCmd {
 	pub logger_config: LogConfig,
 	pub miner_options: MinerOptions,
 	pub gas_price_percentile: usize,
+	pub logs_max_block_range: Option<u64>,
+	pub logs_max_results: Option<usize>,
 	pub ntp_servers: Vec<String>,
 	pub ws_conf: rpc::WsConfiguration,
 	pub http_conf: rpc::HttpConfiguration,
@@ -108,7 +110,10 @@ pub struct RunCmd {
 	pub mode: Option<Mode>,
 	pub tracing: Switch,
 	pub fat_db: Switch,
+	pub account_tx_index: bool,
+	pub token_index: bool,
 	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
 	pub wal: bool,
 	pub vm_type: VMType,
 	pub geth_compatibility: bool,
@@ -123,6 +128,7 @@ pub struct RunCmd {
 	pub custom_bootnodes: bool,
 	pub stratum: Option<StratumOptions>,
 	pub no_periodic_snapshot: bool,
+	pub snapshot_compression: ChunkCompression,
 	pub check_seal: bool,
 	pub download_old_blocks: bool,
 	pub verifier_settings: VerifierSettings,
@@ -567,6 +573,7 @@ pub fn execute_impl(cmd: RunCmd, can_restart: bool, logger: Arc<RotatingLogger>)
 		tracing,
 		fat_db,
 		cmd.compaction,
+		cmd.db_backend,
 		cmd.wal,
 		cmd.vm_type,
 		cmd.name,
@@ -577,6 +584,9 @@ pub fn execute_impl(cmd: RunCmd, can_restart: bool, logger: Arc<RotatingLogger>)
 	);
 
 	client_config.queue.verifier_settings = cmd.verifier_settings;
+	client_config.blockchain.account_tx_index = cmd.account_tx_index;
+	client_config.token_index = cmd.token_index;
+	client_config.snapshot_compression = cmd.snapshot_compression;
 
 	// set up bootnodes
 	let mut net_conf = cmd.net_conf;
@@ -774,6 +784,8 @@ pub fn execute_impl(cmd: RunCmd, can_restart: bool, logger: Arc<RotatingLogger>)
 		remote: event_loop.remote(),
 		whisper_rpc: whisper_factory,
 		gas_price_percentile: cmd.gas_price_percentile,
+		logs_max_block_range: cmd.logs_max_block_range,
+		logs_max_results: cmd.logs_max_results,
 	});
 
 	let dependencies = rpc::Dependencies {
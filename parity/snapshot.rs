@@ -21,11 +21,11 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use hash::keccak;
-use ethcore::snapshot::{Progress, RestorationStatus, SnapshotService as SS};
+use ethcore::snapshot::{ChunkCompression, Progress, RestorationStatus, SnapshotService as SS};
 use ethcore::snapshot::io::{SnapshotReader, PackedReader, PackedWriter};
 use ethcore::snapshot::service::Service as SnapshotService;
 use ethcore::service::ClientService;
-use ethcore::client::{Mode, DatabaseCompactionProfile, VMType};
+use ethcore::client::{BlockChainClient, Mode, DatabaseCompactionProfile, DatabaseBackend, VMType};
 use ethcore::miner::Miner;
 use ethcore::ids::BlockId;
 
@@ -42,7 +42,9 @@ pub enum Kind {
 	/// Take a snapshot.
 	Take,
 	/// Restore a snapshot.
-	Restore
+	Restore,
+	/// Print the manifest of a snapshot.
+	Inspect,
 }
 
 /// Command for snapshot creation or restoration.
@@ -57,10 +59,13 @@ pub struct SnapshotCommand {
 	pub tracing: Switch,
 	pub fat_db: Switch,
 	pub compaction: DatabaseCompactionProfile,
+	pub db_backend: DatabaseBackend,
 	pub file_path: Option<String>,
 	pub wal: bool,
 	pub kind: Kind,
 	pub block_at: BlockId,
+	pub compression: ChunkCompression,
+	pub allow_unchecked: bool,
 }
 
 // helper for reading chunks from arbitrary reader and feeding them into the
@@ -165,13 +170,14 @@ impl SnapshotCommand {
 		execute_upgrades(&self.dirs.base, &db_dirs, algorithm, self.compaction.compaction_profile(db_dirs.db_root_path().as_path()))?;
 
 		// prepare client config
-		let client_config = to_client_config(
+		let mut client_config = to_client_config(
 			&self.cache_config,
 			spec.name.to_lowercase(),
 			Mode::Active,
 			tracing,
 			fat_db,
 			self.compaction,
+			self.db_backend,
 			self.wal,
 			VMType::default(),
 			"".into(),
@@ -180,6 +186,7 @@ impl SnapshotCommand {
 			self.pruning_memory,
 			true
 		);
+		client_config.snapshot_compression = self.compression;
 
 		let service = ClientService::start(
 			client_config,
@@ -195,6 +202,8 @@ impl SnapshotCommand {
 	/// restore from a snapshot
 	pub fn restore(self) -> Result<(), String> {
 		let file = self.file_path.clone();
+		let allow_unchecked = self.allow_unchecked;
+		let spec_hash = self.spec.spec(&self.dirs.cache)?.hash;
 		let service = self.start_service()?;
 
 		warn!("Snapshot restoration is experimental and the format may be subject to change.");
@@ -210,6 +219,17 @@ impl SnapshotCommand {
 				.and_then(|x| x.ok_or("Snapshot file has invalid format.".into()));
 
 			let reader = reader?;
+			match (reader.genesis_hash(), reader.spec_hash()) {
+				(Some(hash), _) if hash != service.client().chain_info().genesis_hash =>
+					return Err(format!("Snapshot was taken of a different chain with genesis {:?}.", hash)),
+				(_, Some(hash)) if hash != spec_hash && allow_unchecked =>
+					warn!("Snapshot was taken with a different chain spec {:?}; restoring it anyway.", hash),
+				(_, Some(hash)) if hash != spec_hash =>
+					return Err(format!("Snapshot was taken with a different chain spec {:?}. Engine or chain parameters don't match. Use --allow-unchecked to restore it anyway.", hash)),
+				(Some(_), Some(_)) => {},
+				_ if allow_unchecked => warn!("Snapshot file has no manifest checksum; restoring without checking it against the chain."),
+				_ => return Err("Snapshot file has no manifest checksum, so it can't be checked against the chain. Use --allow-unchecked to restore it anyway.".into()),
+			}
 			restore_using(snapshot, &reader, true)?;
 		} else {
 			info!("Attempting to restore from local snapshot.");
@@ -230,11 +250,13 @@ impl SnapshotCommand {
 		let file_path = self.file_path.clone().ok_or("No file path provided.".to_owned())?;
 		let file_path: PathBuf = file_path.into();
 		let block_at = self.block_at;
+		let spec_hash = self.spec.spec(&self.dirs.cache)?.hash;
 		let service = self.start_service()?;
 
 		warn!("Snapshots are currently experimental. File formats may be subject to change.");
 
-		let writer = PackedWriter::new(&file_path)
+		let genesis_hash = service.client().chain_info().genesis_hash;
+		let writer = PackedWriter::with_chain(&file_path, genesis_hash, spec_hash)
 			.map_err(|e| format!("Failed to open snapshot writer: {}", e))?;
 
 		let progress = Arc::new(Progress::default());
@@ -267,6 +289,48 @@ impl SnapshotCommand {
 
 		Ok(())
 	}
+
+	/// Describe the manifest of a snapshot file.
+	pub fn inspect(self) -> Result<String, String> {
+		let file = self.file_path.clone().ok_or("No file path provided.".to_owned())?;
+		let reader = PackedReader::new(Path::new(&file))
+			.map_err(|e| format!("Couldn't open snapshot file: {}", e))?
+			.ok_or("Snapshot file has invalid format.".to_owned())?;
+
+		let spec = self.spec.spec(&self.dirs.cache)?;
+		let chain = match reader.genesis_hash() {
+			Some(hash) if hash == spec.genesis_header().hash() => format!("{:?} (matches {})", hash, spec.name),
+			Some(hash) => format!("{:?} (doesn't match {})", hash, spec.name),
+			None => "unknown, manifest has no checksum".into(),
+		};
+		let spec_hash = match reader.spec_hash() {
+			Some(hash) if hash == spec.hash => format!("{:?} (matches {})", hash, spec.name),
+			Some(hash) => format!("{:?} (doesn't match {})", hash, spec.name),
+			None => "unknown, manifest has no checksum".into(),
+		};
+
+		let manifest = reader.manifest();
+		Ok(format!("Snapshot {}\n\
+			version: {}\n\
+			chunk compression: {:?}\n\
+			block: #{} ({:?})\n\
+			state root: {:?}\n\
+			state chunks: {}\n\
+			block chunks: {}\n\
+			genesis: {}\n\
+			chain spec: {}",
+			file,
+			manifest.version,
+			manifest.compression,
+			manifest.block_number,
+			manifest.block_hash,
+			manifest.state_root,
+			manifest.state_hashes.len(),
+			manifest.block_hashes.len(),
+			chain,
+			spec_hash
+		))
+	}
 }
 
 /// Execute this snapshot command.
@@ -274,6 +338,7 @@ pub fn execute(cmd: SnapshotCommand) -> Result<String, String> {
 	match cmd.kind {
 		Kind::Take => cmd.take_snapshot()?,
 		Kind::Restore => cmd.restore()?,
+		Kind::Inspect => return cmd.inspect(),
 	}
 
 	Ok(String::new())
@@ -26,6 +26,7 @@ extern crate cid;
 extern crate crypto as rust_crypto;
 extern crate futures_cpupool;
 extern crate itertools;
+extern crate lru_cache;
 extern crate multihash;
 extern crate order_stat;
 extern crate parking_lot;
@@ -108,7 +109,7 @@ pub use http::{
 	AccessControlAllowOrigin, Host, DomainsValidation
 };
 
-pub use v1::{NetworkSettings, Metadata, Origin, informant, dispatch, signer, dapps};
+pub use v1::{NetworkSettings, Metadata, Origin, access, informant, ratelimit, dispatch, signer, dapps};
 pub use v1::block_import::is_major_importing;
 pub use v1::extractors::{RpcExtractor, WsExtractor, WsStats, WsDispatcher};
 pub use authcodes::{AuthCodes, TimeProvider};
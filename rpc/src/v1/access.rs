@@ -0,0 +1,347 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Method-level access control for RPC servers.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use jsonrpc_core as rpc;
+use jsonrpc_core::futures::{future, Future};
+
+use v1::helpers::errors;
+use v1::{Metadata, Origin};
+
+/// Matches `name` against `pattern`, where `*` stands for any (possibly empty) sequence of characters.
+fn matches(pattern: &str, name: &str) -> bool {
+	let mut parts = pattern.split('*');
+	let prefix = parts.next().unwrap_or("");
+	if !name.starts_with(prefix) {
+		return false;
+	}
+
+	let parts = parts.collect::<Vec<_>>();
+	let mut rest = &name[prefix.len()..];
+	let (suffix, middle) = match parts.split_last() {
+		Some(split) => split,
+		// no wildcard at all
+		None => return rest.is_empty(),
+	};
+
+	for part in middle {
+		match rest.find(part) {
+			Some(idx) => rest = &rest[idx + part.len()..],
+			None => return false,
+		}
+	}
+
+	rest.ends_with(suffix)
+}
+
+/// Method name pattern, optionally scoped to requests coming from matching origins.
+///
+/// Parsed from `METHOD` or `METHOD@ORIGIN`, both parts may contain `*` wildcards.
+///
+/// NOTE: the origin is taken from the `Origin` header, which any client other than a browser
+/// can set to anything or leave out. Origin scoped rules are therefore accepted only as deny
+/// rules, to keep web pages away from some methods, and never grant access.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodRule {
+	method: String,
+	origin: Option<String>,
+}
+
+impl MethodRule {
+	fn applies_to(&self, origin: Option<&str>) -> bool {
+		match (self.origin.as_ref(), origin) {
+			(None, _) => true,
+			(Some(pattern), Some(origin)) => matches(pattern, origin),
+			(Some(_), None) => false,
+		}
+	}
+}
+
+impl FromStr for MethodRule {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut split = s.splitn(2, '@');
+		let method = split.next().map(str::trim).unwrap_or("");
+		if method.is_empty() {
+			return Err(format!("Invalid method rule: {}. Expected METHOD or METHOD@ORIGIN.", s));
+		}
+
+		let origin = match split.next().map(str::trim) {
+			Some("") => return Err(format!("Invalid method rule: {}. Origin cannot be empty.", s)),
+			origin => origin.map(Into::into),
+		};
+
+		Ok(MethodRule {
+			method: method.into(),
+			origin,
+		})
+	}
+}
+
+/// Allowed and denied methods of a single RPC interface.
+///
+/// A method is rejected if any applicable deny rule matches it.
+/// If there are allow rules, the method also has to match one of them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MethodAccess {
+	allow: Vec<MethodRule>,
+	deny: Vec<MethodRule>,
+}
+
+impl MethodAccess {
+	/// Creates new access rules.
+	pub fn new(allow: Vec<MethodRule>, deny: Vec<MethodRule>) -> Self {
+		MethodAccess {
+			allow,
+			deny,
+		}
+	}
+
+	/// Parses comma-delimited lists of allow and deny rules.
+	/// Only deny rules may be scoped to an origin.
+	pub fn parse(allow: &str, deny: &str) -> Result<Self, String> {
+		fn rules(list: &str) -> Result<Vec<MethodRule>, String> {
+			list.split(',')
+				.map(str::trim)
+				.filter(|rule| !rule.is_empty())
+				.map(str::parse)
+				.collect()
+		}
+
+		let allow = rules(allow)?;
+		if let Some(rule) = allow.iter().find(|rule| rule.origin.is_some()) {
+			return Err(format!(
+				"Invalid allow rule: {}@{}. The Origin header can be forged, so origins can only be used in deny rules.",
+				rule.method, rule.origin.as_ref().expect("checked by find; qed"),
+			));
+		}
+
+		Ok(MethodAccess::new(allow, rules(deny)?))
+	}
+
+	/// Returns true if no rules are configured.
+	pub fn is_unrestricted(&self) -> bool {
+		self.allow.is_empty() && self.deny.is_empty()
+	}
+
+	/// Returns true if `method` can be called by a request coming from `origin`.
+	pub fn is_allowed(&self, method: &str, origin: Option<&str>) -> bool {
+		let applies = |rule: &&MethodRule| rule.applies_to(origin);
+
+		if self.deny.iter().filter(&applies).any(|rule| matches(&rule.method, method)) {
+			return false;
+		}
+
+		// origin scoped allow rules are rejected by `parse` and never grant access.
+		let mut allow = self.allow.iter().filter(|rule| rule.origin.is_none()).peekable();
+		allow.peek().is_none() || allow.any(|rule| matches(&rule.method, method))
+	}
+}
+
+fn origin(meta: &Metadata) -> Option<&str> {
+	match meta.origin {
+		Origin::Dapps(ref dapp) | Origin::Ws { ref dapp, .. } | Origin::Signer { ref dapp, .. } if !dapp.0.is_empty() => Some(&dapp.0),
+		_ => None,
+	}
+}
+
+fn method(call: &rpc::Call) -> Option<&str> {
+	match *call {
+		rpc::Call::MethodCall(ref call) => Some(&call.method),
+		rpc::Call::Notification(ref notification) => Some(&notification.method),
+		_ => None,
+	}
+}
+
+fn reject(call: rpc::Call) -> Option<rpc::Output> {
+	match call {
+		rpc::Call::MethodCall(call) => Some(rpc::Output::from(
+			Err(errors::method_not_allowed(&call.method)),
+			call.id,
+			call.jsonrpc,
+		)),
+		_ => None,
+	}
+}
+
+/// Middleware rejecting calls to methods which are not allowed on the interface.
+pub struct AccessMiddleware {
+	access: Arc<MethodAccess>,
+}
+
+impl AccessMiddleware {
+	/// Creates new middleware enforcing given rules.
+	pub fn new(access: MethodAccess) -> Self {
+		AccessMiddleware {
+			access: Arc::new(access),
+		}
+	}
+
+	fn is_allowed(&self, call: &rpc::Call, meta: &Metadata) -> bool {
+		match method(call) {
+			Some(method) if !self.access.is_allowed(method, origin(meta)) => {
+				debug!(target: "rpc", "Rejecting call to {} from {}", method, meta.origin);
+				false
+			},
+			_ => true,
+		}
+	}
+}
+
+impl rpc::Middleware<Metadata> for AccessMiddleware {
+	type Future = rpc::futures::future::Either<
+		future::FutureResult<Option<rpc::Response>, ()>,
+		rpc::FutureResponse,
+	>;
+
+	fn on_request<F, X>(&self, request: rpc::Request, meta: Metadata, process: F) -> Self::Future where
+		F: FnOnce(rpc::Request, Metadata) -> X,
+		X: rpc::futures::Future<Item=Option<rpc::Response>, Error=()> + Send + 'static,
+	{
+		use self::rpc::futures::future::Either::{A, B};
+
+		if self.access.is_unrestricted() {
+			return B(Box::new(process(request, meta)));
+		}
+
+		match request {
+			rpc::Request::Single(call) => match self.is_allowed(&call, &meta) {
+				true => B(Box::new(process(rpc::Request::Single(call), meta))),
+				false => A(future::ok(reject(call).map(rpc::Response::Single))),
+			},
+			rpc::Request::Batch(calls) => {
+				let (allowed, denied): (Vec<_>, Vec<_>) = calls.into_iter().partition(|call| self.is_allowed(call, &meta));
+				if denied.is_empty() {
+					return B(Box::new(process(rpc::Request::Batch(allowed), meta)));
+				}
+
+				let rejected = denied.into_iter().filter_map(reject).collect::<Vec<_>>();
+				let into_response = |outputs: Vec<rpc::Output>| match outputs.is_empty() {
+					true => None,
+					false => Some(rpc::Response::Batch(outputs)),
+				};
+
+				if allowed.is_empty() {
+					return A(future::ok(into_response(rejected)));
+				}
+
+				B(Box::new(process(rpc::Request::Batch(allowed), meta).map(move |response| {
+					let mut outputs = match response {
+						Some(rpc::Response::Batch(outputs)) => outputs,
+						Some(rpc::Response::Single(output)) => vec![output],
+						None => Vec::new(),
+					};
+					outputs.extend(rejected);
+					into_response(outputs)
+				})))
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use jsonrpc_core::{MetaIoHandler, Value};
+	use v1::{Metadata, Origin};
+	use super::{matches, MethodAccess, AccessMiddleware};
+
+	#[test]
+	fn should_match_wildcards() {
+		assert!(matches("eth_*", "eth_call"));
+		assert!(matches("*", "eth_call"));
+		assert!(matches("eth_call", "eth_call"));
+		assert!(matches("*_get*Balance", "parity_getTokenBalance"));
+		assert!(!matches("eth_call", "eth_callMany"));
+		assert!(!matches("eth_*", "parity_call"));
+		assert!(!matches("*_sign", "eth_signTransaction"));
+	}
+
+	#[test]
+	fn should_reject_invalid_rules() {
+		assert!(MethodAccess::parse("eth_*,", "").is_ok());
+		assert!(MethodAccess::parse("@http://parity.io", "").is_err());
+		assert!(MethodAccess::parse("", "eth_sign@").is_err());
+		assert!(MethodAccess::parse("parity_*@https://*.parity.io", "").is_err());
+	}
+
+	#[test]
+	fn should_check_allow_and_deny_rules() {
+		// given
+		let access = MethodAccess::parse("eth_*,net_version", "eth_sign").unwrap();
+
+		// then
+		assert!(access.is_allowed("eth_call", None));
+		assert!(access.is_allowed("net_version", None));
+		assert!(!access.is_allowed("eth_sign", None));
+		assert!(!access.is_allowed("parity_enode", None));
+		assert!(MethodAccess::default().is_allowed("personal_sign", None));
+	}
+
+	#[test]
+	fn should_apply_origin_rules() {
+		// given
+		let access = MethodAccess::parse("eth_*", "eth_call@http://evil.com,eth_sign@https://*.parity.io").unwrap();
+
+		// then
+		assert!(access.is_allowed("eth_call", None));
+		assert!(access.is_allowed("eth_sign", None));
+		assert!(!access.is_allowed("parity_enode", Some("https://wallet.parity.io")));
+		assert!(access.is_allowed("eth_call", Some("https://wallet.parity.io")));
+		assert!(!access.is_allowed("eth_sign", Some("https://wallet.parity.io")));
+		assert!(!access.is_allowed("eth_call", Some("http://evil.com")));
+		assert!(access.is_allowed("eth_blockNumber", Some("http://evil.com")));
+	}
+
+	fn io(access: MethodAccess) -> MetaIoHandler<Metadata, AccessMiddleware> {
+		let mut io = MetaIoHandler::with_middleware(AccessMiddleware::new(access));
+		io.add_method("eth_blockNumber", |_| Ok(Value::String("0x1".into())));
+		io.add_method("eth_sign", |_| Ok(Value::String("0x2".into())));
+		io
+	}
+
+	#[test]
+	fn should_reject_denied_calls() {
+		// given
+		let io = io(MethodAccess::parse("", "eth_sign").unwrap());
+		let request = r#"{"jsonrpc":"2.0","method":"eth_sign","params":[],"id":1}"#;
+		let response = r#"{"jsonrpc":"2.0","error":{"code":-32006,"message":"Method eth_sign is not allowed on this interface."},"id":1}"#;
+
+		// then
+		assert_eq!(io.handle_request_sync(request, Metadata::default()), Some(response.to_owned()));
+	}
+
+	#[test]
+	fn should_reject_denied_calls_in_batch() {
+		// given
+		let io = io(MethodAccess::parse("", "eth_sign@http://evil.com").unwrap());
+		let request = r#"[{"jsonrpc":"2.0","method":"eth_sign","params":[],"id":1},{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":2}]"#;
+		let mut meta = Metadata::default();
+
+		// when
+		let allowed = io.handle_request_sync(request, meta.clone());
+		meta.origin = Origin::Dapps("http://evil.com".into());
+		let rejected = io.handle_request_sync(request, meta);
+
+		// then
+		assert_eq!(allowed, Some(r#"[{"jsonrpc":"2.0","result":"0x2","id":1},{"jsonrpc":"2.0","result":"0x1","id":2}]"#.to_owned()));
+		assert_eq!(rejected, Some(r#"[{"jsonrpc":"2.0","result":"0x1","id":2},{"jsonrpc":"2.0","error":{"code":-32006,"message":"Method eth_sign is not allowed on this interface."},"id":1}]"#.to_owned()));
+	}
+}
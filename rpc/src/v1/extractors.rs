@@ -16,6 +16,7 @@
 
 //! Parity-specific metadata extractors.
 
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -29,6 +30,7 @@ use ethereum_types::H256;
 
 use v1::{Metadata, Origin};
 use v1::informant::RpcStats;
+use v1::ratelimit::ClientId;
 
 /// Common HTTP & IPC metadata extractor.
 pub struct RpcExtractor;
@@ -36,7 +38,13 @@ pub struct RpcExtractor;
 impl HttpMetaExtractor for RpcExtractor {
 	type Metadata = Metadata;
 
-	fn read_metadata(&self, origin: Option<String>, user_agent: Option<String>, dapps_origin: Option<String>) -> Metadata {
+	fn read_metadata(
+		&self,
+		origin: Option<String>,
+		user_agent: Option<String>,
+		dapps_origin: Option<String>,
+		remote: Option<SocketAddr>,
+	) -> Metadata {
 		Metadata {
 			origin: match (origin.as_ref().map(|s| s.as_str()), user_agent, dapps_origin) {
 				(Some("null"), _, Some(dapp)) => Origin::Dapps(dapp.into()),
@@ -45,6 +53,8 @@ impl HttpMetaExtractor for RpcExtractor {
 				(None, _, _) => Origin::Rpc("unknown".into()),
 			},
 			session: None,
+			// headers are chosen by the client, so only the remote address identifies it.
+			client: remote.map(|remote| ClientId::Ip(remote.ip())),
 		}
 	}
 }
@@ -54,6 +64,7 @@ impl ipc::MetaExtractor<Metadata> for RpcExtractor {
 		Metadata {
 			origin: Origin::Ipc(req.session_id.into()),
 			session: Some(Arc::new(Session::new(req.sender.clone()))),
+			client: None,
 		}
 	}
 }
@@ -77,20 +88,22 @@ impl ws::MetaExtractor<Metadata> for WsExtractor {
 		let id = req.session_id as u64;
 
 		let dapp = req.origin.as_ref().map(|origin| (&**origin).into()).unwrap_or_default();
-		let origin = match self.authcodes_path {
+		// the remote address of a session is not known, so unauthorized sessions are limited one by one.
+		let (origin, client) = match self.authcodes_path {
 			Some(ref path) => {
 				let authorization = req.protocols.get(0).and_then(|p| auth_token_hash(&path, p, true));
 				match authorization {
-					Some(id) => Origin::Signer { session: id.into(), dapp: dapp },
-					None => Origin::Ws { session: id.into(), dapp: dapp },
+					Some(token) => (Origin::Signer { session: token.into(), dapp: dapp }, ClientId::Token(token)),
+					None => (Origin::Ws { session: id.into(), dapp: dapp }, ClientId::Session(id)),
 				}
 			},
-			None => Origin::Ws { session: id.into(), dapp: dapp },
+			None => (Origin::Ws { session: id.into(), dapp: dapp }, ClientId::Session(id)),
 		};
 		let session = Some(Arc::new(Session::new(req.sender())));
 		Metadata {
 			origin,
 			session,
+			client: Some(client),
 		}
 	}
 }
@@ -245,6 +258,7 @@ impl<M: core::Middleware<Metadata>> core::Middleware<Metadata> for WsDispatcher<
 #[cfg(test)]
 mod tests {
 	use super::RpcExtractor;
+	use v1::ratelimit::ClientId;
 	use {HttpMetaExtractor, Origin};
 
 	#[test]
@@ -253,9 +267,9 @@ mod tests {
 		let extractor = RpcExtractor;
 
 		// when
-		let meta1 = extractor.read_metadata(None, None, None);
-		let meta2 = extractor.read_metadata(None, Some("http://parity.io".to_owned()), None);
-		let meta3 = extractor.read_metadata(None, Some("http://parity.io".to_owned()), Some("ignored".into()));
+		let meta1 = extractor.read_metadata(None, None, None, None);
+		let meta2 = extractor.read_metadata(None, Some("http://parity.io".to_owned()), None, None);
+		let meta3 = extractor.read_metadata(None, Some("http://parity.io".to_owned()), Some("ignored".into()), None);
 
 		// then
 		assert_eq!(meta1.origin, Origin::Rpc("unknown".into()));
@@ -270,9 +284,24 @@ mod tests {
 		let dapp = "https://wallet.ethereum.org".to_owned();
 
 		// when
-		let meta = extractor.read_metadata(Some("null".into()), None, Some(dapp.clone()));
+		let meta = extractor.read_metadata(Some("null".into()), None, Some(dapp.clone()), None);
 
 		// then
 		assert_eq!(meta.origin, Origin::Dapps(dapp.into()));
 	}
+
+	#[test]
+	fn should_extract_client_id() {
+		// given
+		let extractor = RpcExtractor;
+		let remote = "10.0.0.1:4321".parse().unwrap();
+
+		// when
+		let meta1 = extractor.read_metadata(None, None, None, None);
+		let meta2 = extractor.read_metadata(None, None, None, Some(remote));
+
+		// then
+		assert_eq!(meta1.client, None);
+		assert_eq!(meta2.client, Some(ClientId::Ip("10.0.0.1".parse().unwrap())));
+	}
 }
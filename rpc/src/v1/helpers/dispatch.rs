@@ -43,13 +43,14 @@ use transaction::{Action, SignedTransaction, PendingTransaction, Transaction};
 use jsonrpc_core::{BoxFuture, Result, Error};
 use jsonrpc_core::futures::{future, Future, Poll, Async};
 use jsonrpc_core::futures::future::Either;
-use v1::helpers::{errors, nonce, TransactionRequest, FilledTransactionRequest, ConfirmationPayload};
+use v1::helpers::{errors, eip712, nonce, TransactionRequest, FilledTransactionRequest, ConfirmationPayload};
 use v1::types::{
 	H256 as RpcH256, H520 as RpcH520, Bytes as RpcBytes,
 	RichRawTransaction as RpcRichRawTransaction,
 	ConfirmationPayload as RpcConfirmationPayload,
 	ConfirmationResponse,
 	SignRequest as RpcSignRequest,
+	EIP712SignRequest as RpcEIP712SignRequest,
 	DecryptRequest as RpcDecryptRequest,
 };
 
@@ -687,6 +688,20 @@ pub fn execute<D: Dispatcher + 'static>(
 				);
 			Box::new(future::done(res))
 		},
+		ConfirmationPayload::EIP712SignMessage(address, data) => {
+			if accounts.is_hardware_address(&address) {
+				return Box::new(future::err(errors::unsupported("Signing via hardware wallets is not supported.", None)));
+			}
+
+			let hash = try_bf!(eip712::hash_structured_data(&data));
+			let res = signature(&accounts, address, hash, pass)
+				.map(|result| result
+					.map(|rsv| H520(rsv.into_electrum()))
+					.map(RpcH520::from)
+					.map(ConfirmationResponse::Signature)
+				);
+			Box::new(future::done(res))
+		},
 		ConfirmationPayload::Decrypt(address, data) => {
 			if accounts.is_hardware_address(&address) {
 				return Box::new(future::err(errors::unsupported("Decrypting via hardware wallets is not supported.", None)));
@@ -773,5 +788,10 @@ pub fn from_rpc<D>(payload: RpcConfirmationPayload, default_account: Address, di
 		RpcConfirmationPayload::EthSignMessage(RpcSignRequest { address, data }) => {
 			Box::new(future::ok(ConfirmationPayload::EthSignMessage(address.into(), data.into())))
 		},
+		RpcConfirmationPayload::EIP712SignMessage(RpcEIP712SignRequest { address, data }) => {
+			// make sure the typed data can be hashed before it reaches the signer.
+			try_bf!(eip712::hash_structured_data(&data));
+			Box::new(future::ok(ConfirmationPayload::EIP712SignMessage(address.into(), data)))
+		},
 	}
 }
@@ -0,0 +1,344 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! EIP-712 typed structured data hashing.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+use ethereum_types::{H256, U256, Address};
+use hash::keccak;
+use rustc_hex::FromHex;
+use serde_json::Value;
+
+use jsonrpc_core::{Error, Result};
+use v1::helpers::errors;
+use v1::types::{EIP712, EIP712Field};
+
+const DOMAIN_TYPE: &'static str = "EIP712Domain";
+
+/// Returns the hash of typed structured data to sign:
+/// `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+pub fn hash_structured_data(data: &EIP712) -> Result<H256> {
+	let encoder = Encoder { types: &data.types };
+	let domain_separator = encoder.hash_struct(DOMAIN_TYPE, &data.domain)?;
+	let message_hash = encoder.hash_struct(&data.primary_type, &data.message)?;
+
+	let mut encoded = Vec::with_capacity(66);
+	encoded.extend_from_slice(b"\x19\x01");
+	encoded.extend_from_slice(&domain_separator);
+	encoded.extend_from_slice(&message_hash);
+	Ok(keccak(encoded))
+}
+
+fn invalid<T: fmt::Debug>(details: T) -> Error {
+	errors::invalid_params("typed data", details)
+}
+
+/// Strips array suffixes, i.e. `Person[2][]` becomes `Person`.
+fn base_type(field_type: &str) -> &str {
+	match field_type.find('[') {
+		Some(index) => &field_type[..index],
+		None => field_type,
+	}
+}
+
+fn parse_bytes(value: &Value) -> Result<Vec<u8>> {
+	match value.as_str() {
+		Some(s) if s.starts_with("0x") => s[2..].from_hex().map_err(|e| invalid(format!("Invalid hex value {}: {}", s, e))),
+		_ => Err(invalid(format!("Expected a 0x-prefixed hex string, got {}.", value))),
+	}
+}
+
+fn parse_uint(value: &Value) -> Result<U256> {
+	match *value {
+		Value::Number(ref n) => n.as_u64().map(U256::from).ok_or_else(|| invalid(format!("Expected an unsigned integer, got {}.", n))),
+		Value::String(ref s) if s.starts_with("0x") => U256::from_str(&s[2..]).map_err(|e| invalid(format!("Invalid hex value {}: {:?}", s, e))),
+		Value::String(ref s) => U256::from_dec_str(s).map_err(|e| invalid(format!("Invalid decimal value {}: {:?}", s, e))),
+		_ => Err(invalid(format!("Expected a number, got {}.", value))),
+	}
+}
+
+/// Parses a signed integer of `bits` bits into its 256-bit two's complement representation.
+fn parse_int(value: &Value, bits: usize) -> Result<U256> {
+	let (negative, magnitude) = match *value {
+		Value::Number(ref n) => match (n.as_u64(), n.as_i64()) {
+			(Some(n), _) => (false, U256::from(n)),
+			(None, Some(n)) => (true, U256::from(n.wrapping_neg() as u64)),
+			_ => return Err(invalid(format!("Expected an integer, got {}.", n))),
+		},
+		Value::String(ref s) if s.starts_with('-') => (true, parse_uint(&Value::String(s[1..].into()))?),
+		_ => (false, parse_uint(value)?),
+	};
+
+	let limit = U256::one() << (bits - 1);
+	match negative {
+		true if magnitude <= limit => Ok((!magnitude).overflowing_add(U256::one()).0),
+		false if magnitude < limit => Ok(magnitude),
+		_ => Err(invalid(format!("Value {} does not fit into {} bits.", value, bits))),
+	}
+}
+
+/// Parses the bit size of `uintN`/`intN` types, `uint` and `int` are aliases for 256 bits.
+fn int_bits(field_type: &str, prefix: &str) -> Result<usize> {
+	match &field_type[prefix.len()..] {
+		"" => Ok(256),
+		bits => match bits.parse::<usize>() {
+			Ok(bits) if bits > 0 && bits <= 256 && bits % 8 == 0 => Ok(bits),
+			_ => Err(invalid(format!("Unknown type `{}`.", field_type))),
+		},
+	}
+}
+
+struct Encoder<'a> {
+	types: &'a BTreeMap<String, Vec<EIP712Field>>,
+}
+
+impl<'a> Encoder<'a> {
+	fn fields(&self, name: &str) -> Result<&'a [EIP712Field]> {
+		self.types.get(name)
+			.map(|fields| &fields[..])
+			.ok_or_else(|| invalid(format!("Unknown type `{}`.", name)))
+	}
+
+	/// Collects the given struct type and all struct types it references.
+	fn dependencies(&self, name: &str, found: &mut BTreeSet<String>) -> Result<()> {
+		if found.contains(name) {
+			return Ok(());
+		}
+
+		let fields = self.fields(name)?;
+		found.insert(name.to_owned());
+		for field in fields {
+			let base = base_type(&field.field_type);
+			if self.types.contains_key(base) {
+				self.dependencies(base, found)?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Encodes the type as `Name(type1 name1,type2 name2)`, followed by
+	/// all referenced struct types sorted by name.
+	fn encode_type(&self, name: &str) -> Result<String> {
+		let mut dependencies = BTreeSet::new();
+		self.dependencies(name, &mut dependencies)?;
+		dependencies.remove(name);
+
+		let mut encoded = String::new();
+		for name in Some(name).into_iter().chain(dependencies.iter().map(|d| d.as_str())) {
+			let members = self.fields(name)?.iter()
+				.map(|field| format!("{} {}", field.field_type, field.name))
+				.collect::<Vec<_>>();
+			encoded.push_str(&format!("{}({})", name, members.join(",")));
+		}
+		Ok(encoded)
+	}
+
+	/// `keccak256(typeHash ‖ encodeData(value))`
+	fn hash_struct(&self, name: &str, value: &Value) -> Result<H256> {
+		let object = value.as_object()
+			.ok_or_else(|| invalid(format!("Expected `{}` to be an object, got {}.", name, value)))?;
+
+		let mut encoded = keccak(self.encode_type(name)?).to_vec();
+		for field in self.fields(name)? {
+			let member = object.get(&field.name)
+				.ok_or_else(|| invalid(format!("Missing member `{}` of `{}`.", field.name, name)))?;
+			encoded.extend_from_slice(&self.encode_value(&field.field_type, member)?);
+		}
+		Ok(keccak(encoded))
+	}
+
+	/// Encodes a single value into 32 bytes. Dynamic types, arrays and structs are hashed.
+	fn encode_value(&self, field_type: &str, value: &Value) -> Result<H256> {
+		if field_type.ends_with(']') {
+			let open = field_type.rfind('[').ok_or_else(|| invalid(format!("Unknown type `{}`.", field_type)))?;
+			let items = value.as_array()
+				.ok_or_else(|| invalid(format!("Expected an array of `{}`, got {}.", field_type, value)))?;
+			match &field_type[open + 1..field_type.len() - 1] {
+				"" => {},
+				len if len.parse::<usize>().ok() == Some(items.len()) => {},
+				_ => return Err(invalid(format!("Invalid length of `{}` array.", field_type))),
+			}
+
+			let mut encoded = Vec::with_capacity(items.len() * 32);
+			for item in items {
+				encoded.extend_from_slice(&self.encode_value(&field_type[..open], item)?);
+			}
+			return Ok(keccak(encoded));
+		}
+
+		if self.types.contains_key(field_type) {
+			return self.hash_struct(field_type, value);
+		}
+
+		match field_type {
+			"string" => value.as_str()
+				.map(keccak)
+				.ok_or_else(|| invalid(format!("Expected a string, got {}.", value))),
+			"bytes" => parse_bytes(value).map(keccak),
+			"bool" => value.as_bool()
+				.map(|b| H256::from(U256::from(b as u8)))
+				.ok_or_else(|| invalid(format!("Expected a boolean, got {}.", value))),
+			"address" => match parse_bytes(value)? {
+				ref bytes if bytes.len() == 20 => Ok(Address::from_slice(bytes).into()),
+				_ => Err(invalid(format!("Invalid address {}.", value))),
+			},
+			t if t.starts_with("bytes") => {
+				let size = match t[5..].parse::<usize>() {
+					Ok(size) if size > 0 && size <= 32 => size,
+					_ => return Err(invalid(format!("Unknown type `{}`.", t))),
+				};
+				let bytes = parse_bytes(value)?;
+				if bytes.len() > size {
+					return Err(invalid(format!("Value {} does not fit into `{}`.", value, t)));
+				}
+				let mut encoded = H256::zero();
+				encoded[..bytes.len()].copy_from_slice(&bytes);
+				Ok(encoded)
+			},
+			t if t.starts_with("uint") => {
+				let bits = int_bits(t, "uint")?;
+				let value = parse_uint(value)?;
+				if bits < 256 && value >> bits != U256::zero() {
+					return Err(invalid(format!("Value {} does not fit into `{}`.", value, t)));
+				}
+				Ok(value.into())
+			},
+			t if t.starts_with("int") => {
+				let bits = int_bits(t, "int")?;
+				parse_int(value, bits).map(Into::into)
+			},
+			t => Err(invalid(format!("Unknown type `{}`.", t))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use v1::types::EIP712;
+	use super::{hash_structured_data, Encoder};
+
+	const MAIL: &'static str = r#"{
+		"primaryType": "Mail",
+		"domain": {
+			"name": "Ether Mail",
+			"version": "1",
+			"chainId": 1,
+			"verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+		},
+		"message": {
+			"from": {
+				"name": "Cow",
+				"wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+			},
+			"to": {
+				"name": "Bob",
+				"wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+			},
+			"contents": "Hello, Bob!"
+		},
+		"types": {
+			"EIP712Domain": [
+				{ "name": "name", "type": "string" },
+				{ "name": "version", "type": "string" },
+				{ "name": "chainId", "type": "uint256" },
+				{ "name": "verifyingContract", "type": "address" }
+			],
+			"Person": [
+				{ "name": "name", "type": "string" },
+				{ "name": "wallet", "type": "address" }
+			],
+			"Mail": [
+				{ "name": "from", "type": "Person" },
+				{ "name": "to", "type": "Person" },
+				{ "name": "contents", "type": "string" }
+			]
+		}
+	}"#;
+
+	#[test]
+	fn should_encode_type_with_dependencies() {
+		let data: EIP712 = serde_json::from_str(MAIL).unwrap();
+		let encoder = Encoder { types: &data.types };
+
+		assert_eq!(
+			encoder.encode_type("Mail").unwrap(),
+			"Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+		);
+	}
+
+	#[test]
+	fn should_hash_struct() {
+		let data: EIP712 = serde_json::from_str(MAIL).unwrap();
+		let encoder = Encoder { types: &data.types };
+
+		assert_eq!(
+			encoder.hash_struct("EIP712Domain", &data.domain).unwrap(),
+			"f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f".into()
+		);
+		assert_eq!(
+			encoder.hash_struct("Mail", &data.message).unwrap(),
+			"c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e".into()
+		);
+	}
+
+	#[test]
+	fn should_hash_structured_data() {
+		let data: EIP712 = serde_json::from_str(MAIL).unwrap();
+
+		assert_eq!(
+			hash_structured_data(&data).unwrap(),
+			"be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2".into()
+		);
+	}
+
+	#[test]
+	fn should_range_check_signed_integers() {
+		// given
+		let data: EIP712 = serde_json::from_str(MAIL).unwrap();
+		let encoder = Encoder { types: &data.types };
+
+		// when
+		let max = encoder.encode_value("int8", &serde_json::Value::from(127));
+		let min = encoder.encode_value("int8", &serde_json::Value::from(-128));
+		let over = encoder.encode_value("int8", &serde_json::Value::from(300));
+		let under = encoder.encode_value("int8", &serde_json::Value::from(-129));
+
+		// then
+		assert_eq!(max.unwrap(), "000000000000000000000000000000000000000000000000000000000000007f".into());
+		assert_eq!(min.unwrap(), "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff80".into());
+		assert!(over.is_err());
+		assert!(under.is_err());
+	}
+
+	#[test]
+	fn should_reject_invalid_typed_data() {
+		let mut data: EIP712 = serde_json::from_str(MAIL).unwrap();
+		data.primary_type = "Letter".into();
+		assert!(hash_structured_data(&data).is_err());
+
+		let mut data: EIP712 = serde_json::from_str(MAIL).unwrap();
+		data.message["to"]["wallet"] = "0xbBbB".into();
+		assert!(hash_structured_data(&data).is_err());
+
+		let mut data: EIP712 = serde_json::from_str(MAIL).unwrap();
+		data.types.remove("EIP712Domain");
+		assert!(hash_structured_data(&data).is_err());
+	}
+}
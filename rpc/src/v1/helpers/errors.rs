@@ -17,6 +17,7 @@
 //! RPC Error codes and error objects
 
 use std::fmt;
+use std::time::Duration;
 
 use ethcore::account_provider::{SignError as AccountError};
 use ethcore::error::{Error as EthcoreError, CallError};
@@ -31,6 +32,9 @@ mod codes {
 	pub const NO_AUTHOR: i64 = -32002;
 	pub const NO_NEW_WORK: i64 = -32003;
 	pub const NO_WORK_REQUIRED: i64 = -32004;
+	pub const LIMIT_EXCEEDED: i64 = -32005;
+	pub const METHOD_NOT_ALLOWED: i64 = -32006;
+	pub const RATE_LIMITED: i64 = -32007;
 	pub const UNKNOWN_ERROR: i64 = -32009;
 	pub const TRANSACTION_ERROR: i64 = -32010;
 	pub const EXECUTION_ERROR: i64 = -32015;
@@ -365,6 +369,41 @@ pub fn unknown_block() -> Error {
 	}
 }
 
+pub fn limit_exceeded(message: String, data: Option<Value>) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::LIMIT_EXCEEDED),
+		message: message,
+		data: data,
+	}
+}
+
+pub fn method_not_allowed(method: &str) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::METHOD_NOT_ALLOWED),
+		message: format!("Method {} is not allowed on this interface.", method),
+		data: None,
+	}
+}
+
+pub fn rate_limited(retry_after: Duration) -> Error {
+	let millis = retry_after.as_secs().saturating_mul(1_000).saturating_add(retry_after.subsec_nanos() as u64 / 1_000_000);
+	Error {
+		code: ErrorCode::ServerError(codes::RATE_LIMITED),
+		message: format!("Request rate limit exceeded. Retry in {}ms.", millis),
+		data: Some(Value::Number(millis.into())),
+	}
+}
+
+/// Returns true if the error shows that the call never reached its method,
+/// e.g. because the method does not exist or the caller may not call it.
+pub fn is_not_dispatched(error: &Error) -> bool {
+	match error.code {
+		ErrorCode::MethodNotFound => true,
+		ErrorCode::ServerError(code) => code == codes::METHOD_NOT_ALLOWED || code == codes::RATE_LIMITED,
+		_ => false,
+	}
+}
+
 pub fn no_light_peers() -> Error {
 	Error {
 		code: ErrorCode::ServerError(codes::NO_LIGHT_PEERS),
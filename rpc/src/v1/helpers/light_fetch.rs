@@ -28,7 +28,6 @@ use ethcore::receipt::Receipt;
 use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_core::futures::{future, Future};
 use jsonrpc_core::futures::future::Either;
-use jsonrpc_macros::Trailing;
 
 use light::cache::Cache;
 use light::client::LightChainClient;
@@ -189,14 +188,14 @@ impl LightFetch {
 	}
 
 	/// Helper for getting proved execution.
-	pub fn proved_execution(&self, req: CallRequest, num: Trailing<BlockNumber>) -> BoxFuture<ExecutionResult> {
+	pub fn proved_execution(&self, req: CallRequest, num: BlockNumber) -> BoxFuture<ExecutionResult> {
 		const DEFAULT_GAS_PRICE: u64 = 21_000;
 		// starting gas when gas not provided.
 		const START_GAS: u64 = 50_000;
 
 		let (sync, on_demand, client) = (self.sync.clone(), self.on_demand.clone(), self.client.clone());
 		let req: CallRequestHelper = req.into();
-		let id = num.unwrap_or_default().into();
+		let id = num.into();
 
 		let from = req.from.unwrap_or(Address::zero());
 		let nonce_fut = match req.nonce {
@@ -310,6 +309,7 @@ impl LightFetch {
 			BlockId::Latest | BlockId::Pending => Some(best_number),
 			BlockId::Hash(h) => self.client.block_header(BlockId::Hash(h)).map(|hdr| hdr.number()),
 			BlockId::Number(x) => Some(x),
+			BlockId::Timestamp(_) => None,
 		};
 
 		match (block_number(filter.to_block), block_number(filter.from_block)) {
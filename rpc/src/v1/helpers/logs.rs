@@ -0,0 +1,100 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Limits of the logs queries.
+
+use std::cmp;
+
+use ethcore::client::{BlockChainClient, BlockId};
+use ethcore::filter::{Filter as EthcoreFilter, LogCursor as EthLogCursor};
+use ethcore::log_entry::LocalizedLogEntry;
+use jsonrpc_core::Error;
+use serde_json;
+
+use v1::helpers::errors;
+use v1::types::{Log, LogCursor, LogsPage};
+
+/// Limits of a single logs query.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct LogsLimits {
+	/// Maximal number of blocks a single query can span.
+	pub max_block_range: Option<u64>,
+	/// Maximal number of logs returned by a single query.
+	pub max_results: Option<usize>,
+}
+
+impl LogsLimits {
+	/// Returns logs matching given filter starting at `cursor`, within the limits.
+	///
+	/// If the limits were hit, the position of the first log left out is returned along with the logs.
+	/// Once a query is paged, `limit` of the filter is the size of the page.
+	pub fn logs<C: BlockChainClient + ?Sized>(
+		&self,
+		client: &C,
+		mut filter: EthcoreFilter,
+		cursor: Option<EthLogCursor>,
+	) -> (Vec<LocalizedLogEntry>, Option<EthLogCursor>) {
+		if let Some(ref cursor) = cursor {
+			filter.from_block = BlockId::Number(cursor.block_number);
+		}
+
+		let mut next_range = None;
+		match self.max_block_range {
+			Some(max_blocks) if max_blocks > 0 => {
+				let from = client.block_number(filter.from_block);
+				let to = client.block_number(filter.to_block);
+				if let (Some(from), Some(to)) = (from, to) {
+					if to >= from && to - from >= max_blocks {
+						filter.to_block = BlockId::Number(from + max_blocks - 1);
+						next_range = Some(EthLogCursor { block_number: from + max_blocks, log_index: 0 });
+					}
+				}
+			},
+			_ => {},
+		}
+
+		let max_results = if cursor.is_some() || next_range.is_some() {
+			match (filter.limit, self.max_results) {
+				(Some(limit), Some(max)) => Some(cmp::min(limit, max)),
+				(limit, max) => Some(limit.or(max).unwrap_or(::std::usize::MAX)),
+			}
+		} else {
+			// a `limit` within the cap keeps the query bounded already.
+			match self.max_results {
+				Some(max) if filter.limit.map_or(true, |limit| limit > max) => Some(max),
+				_ => None,
+			}
+		};
+
+		match max_results {
+			None => (client.logs(filter), None),
+			Some(max_results) => {
+				let (logs, next) = client.logs_paged(filter, cursor, max_results);
+				(logs, next.or(next_range))
+			},
+		}
+	}
+}
+
+/// Error returned when a logs query exceeds the limits, carrying the logs fetched so far
+/// and the position to continue from.
+pub fn logs_limit_exceeded(logs: Vec<Log>, cursor: EthLogCursor) -> Error {
+	let page = LogsPage {
+		logs: logs,
+		cursor: LogCursor::from(cursor),
+	};
+	errors::limit_exceeded("Query exceeds the logs limits, continue from the cursor.".into(), serde_json::to_value(page).ok())
+}
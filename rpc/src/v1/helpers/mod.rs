@@ -21,6 +21,7 @@ pub mod accounts;
 pub mod block_import;
 pub mod dapps;
 pub mod dispatch;
+pub mod eip712;
 pub mod fake_sign;
 pub mod ipfs;
 pub mod light_fetch;
@@ -28,6 +29,7 @@ pub mod nonce;
 pub mod oneshot;
 pub mod secretstore;
 
+mod logs;
 mod network_settings;
 mod poll_filter;
 mod poll_manager;
@@ -38,6 +40,7 @@ mod subscribers;
 mod subscription_manager;
 
 pub use self::dispatch::{Dispatcher, FullDispatcher};
+pub use self::logs::{LogsLimits, logs_limit_exceeded};
 pub use self::network_settings::NetworkSettings;
 pub use self::poll_manager::PollManager;
 pub use self::poll_filter::{PollFilter, limit_logs};
@@ -0,0 +1,52 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! Debug api implementation.
+
+use std::sync::Arc;
+
+use ethcore::client::{BlockChainClient, TransactionId};
+
+use jsonrpc_core::Result;
+use jsonrpc_macros::Trailing;
+use v1::traits::Debug;
+use v1::helpers::errors;
+use v1::types::{H256, StructLoggerOptions, ExecutionTrace};
+
+/// Debug api implementation.
+pub struct DebugClient<C> {
+	client: Arc<C>,
+}
+
+impl<C> DebugClient<C> {
+	/// Creates new Debug client.
+	pub fn new(client: &Arc<C>) -> Self {
+		DebugClient {
+			client: client.clone(),
+		}
+	}
+}
+
+impl<C> Debug for DebugClient<C> where C: BlockChainClient + 'static {
+	fn trace_transaction(&self, transaction_hash: H256, options: Trailing<StructLoggerOptions>) -> Result<ExecutionTrace> {
+		let options = options.unwrap_or_default();
+
+		self.client.replay_struct_logs(TransactionId::Hash(transaction_hash.into()), options.into())
+			.map(ExecutionTrace::from)
+			.map_err(errors::call)
+	}
+}
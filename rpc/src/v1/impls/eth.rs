@@ -21,6 +21,7 @@ use std::time::{Instant, Duration};
 use std::sync::Arc;
 
 use rlp::{self, UntrustedRlp};
+use hash::keccak;
 use time::get_time;
 use ethereum_types::{U256, H64, H160, H256, Address};
 use parking_lot::Mutex;
@@ -28,9 +29,9 @@ use parking_lot::Mutex;
 use ethash::SeedHashCompute;
 use ethcore::account_provider::{AccountProvider, DappId};
 use ethcore::block::IsBlock;
-use ethcore::client::{MiningBlockChainClient, BlockId, TransactionId, UncleId};
+use ethcore::client::{MiningBlockChainClient, ProvingBlockChainClient, BlockId, TransactionId, UncleId};
 use ethcore::ethereum::Ethash;
-use ethcore::filter::Filter as EthcoreFilter;
+use ethcore::filter::{Filter as EthcoreFilter, LogCursor as EthLogCursor};
 use ethcore::header::{Header as BlockHeader, BlockNumber as EthBlockNumber};
 use ethcore::log_entry::LogEntry;
 use ethcore::miner::MinerService;
@@ -43,14 +44,14 @@ use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_core::futures::future;
 use jsonrpc_macros::Trailing;
 
-use v1::helpers::{errors, limit_logs, fake_sign};
+use v1::helpers::{errors, limit_logs, fake_sign, LogsLimits, logs_limit_exceeded};
 use v1::helpers::dispatch::{FullDispatcher, default_gas_price};
 use v1::helpers::block_import::is_major_importing;
 use v1::helpers::accounts::unwrap_provider;
 use v1::traits::Eth;
 use v1::types::{
 	RichBlock, Block, BlockTransactions, BlockNumber, Bytes, SyncStatus, SyncInfo,
-	Transaction, CallRequest, Index, Filter, Log, Receipt, Work,
+	Transaction, CallRequest, Index, Filter, Log, Receipt, Work, EthAccount, StorageProof,
 	H64 as RpcH64, H256 as RpcH256, H160 as RpcH160, U256 as RpcU256,
 };
 use v1::metadata::Metadata;
@@ -67,6 +68,10 @@ pub struct EthClientOptions {
 	pub send_block_number_in_get_work: bool,
 	/// Gas Price Percentile used as default gas price.
 	pub gas_price_percentile: usize,
+	/// Maximal number of blocks a single `eth_getLogs` query can span.
+	pub logs_max_block_range: Option<u64>,
+	/// Maximal number of logs returned by a single `eth_getLogs` query.
+	pub logs_max_results: Option<usize>,
 }
 
 impl EthClientOptions {
@@ -86,6 +91,8 @@ impl Default for EthClientOptions {
 			allow_pending_receipt_query: true,
 			send_block_number_in_get_work: true,
 			gas_price_percentile: 50,
+			logs_max_block_range: None,
+			logs_max_results: None,
 		}
 	}
 }
@@ -262,7 +269,8 @@ pub fn pending_logs<M>(miner: &M, best_block: EthBlockNumber, filter: &EthcoreFi
 	result
 }
 
-fn check_known<C>(client: &C, number: BlockNumber) -> Result<()> where C: MiningBlockChainClient {
+/// Fails with unknown block error unless the block is in chain or pending.
+pub fn check_known<C>(client: &C, number: BlockNumber) -> Result<()> where C: MiningBlockChainClient {
 	use ethcore::block_status::BlockStatus;
 
 	match client.block_status(number.into()) {
@@ -275,7 +283,7 @@ fn check_known<C>(client: &C, number: BlockNumber) -> Result<()> where C: Mining
 const MAX_QUEUE_SIZE_TO_MINE_ON: usize = 4;	// because uncles go back 6.
 
 impl<C, SN: ?Sized, S: ?Sized, M, EM> Eth for EthClient<C, SN, S, M, EM> where
-	C: MiningBlockChainClient + 'static,
+	C: MiningBlockChainClient + ProvingBlockChainClient + 'static,
 	SN: SnapshotService + 'static,
 	S: SyncProvider + 'static,
 	M: MinerService + 'static,
@@ -383,6 +391,42 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM> Eth for EthClient<C, SN, S, M, EM> where
 		Box::new(future::done(res))
 	}
 
+	fn proof(&self, address: RpcH160, keys: Vec<RpcU256>, num: Trailing<BlockNumber>) -> BoxFuture<EthAccount> {
+		let address: Address = RpcH160::into(address);
+		let num = num.unwrap_or_default();
+
+		try_bf!(check_known(&*self.client, num.clone()));
+		let id: BlockId = num.into();
+		let (account_proof, account) = match self.client.prove_account(keccak(&address), id) {
+			Some(res) => res,
+			None => return Box::new(future::err(errors::state_pruned())),
+		};
+
+		let mut storage_proof = Vec::with_capacity(keys.len());
+		for key in keys {
+			let position: U256 = key.into();
+			let (proof, value) = match self.client.prove_storage(keccak(&address), keccak(&H256::from(position)), id) {
+				Some(res) => res,
+				None => return Box::new(future::err(errors::state_pruned())),
+			};
+			storage_proof.push(StorageProof {
+				key: position.into(),
+				value: U256::from(&*value).into(),
+				proof: proof.into_iter().map(Bytes::new).collect(),
+			});
+		}
+
+		Box::new(future::ok(EthAccount {
+			address: address.into(),
+			account_proof: account_proof.into_iter().map(Bytes::new).collect(),
+			balance: account.balance.into(),
+			nonce: account.nonce.into(),
+			code_hash: account.code_hash.into(),
+			storage_hash: account.storage_root.into(),
+			storage_proof: storage_proof,
+		}))
+	}
+
 	fn transaction_count(&self, address: RpcH160, num: Trailing<BlockNumber>) -> BoxFuture<RpcU256> {
 		let address: Address = RpcH160::into(address);
 
@@ -516,12 +560,22 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM> Eth for EthClient<C, SN, S, M, EM> where
 
 	fn logs(&self, filter: Filter) -> BoxFuture<Vec<Log>> {
 		let include_pending = filter.to_block == Some(BlockNumber::Pending);
+		let cursor: Option<EthLogCursor> = filter.cursor.map(Into::into);
 		let filter: EthcoreFilter = filter.into();
-		let mut logs = self.client.logs(filter.clone())
-			.into_iter()
+		let limits = LogsLimits {
+			max_block_range: self.options.logs_max_block_range,
+			max_results: self.options.logs_max_results,
+		};
+
+		let (logs, next) = limits.logs(&*self.client, filter.clone(), cursor);
+		let mut logs = logs.into_iter()
 			.map(From::from)
 			.collect::<Vec<Log>>();
 
+		if let Some(next) = next {
+			return Box::new(future::err(logs_limit_exceeded(logs, next)));
+		}
+
 		if include_pending {
 			let best_block = self.client.chain_info().best_block_number;
 			let pending = pending_logs(&*self.miner, best_block, &filter);
@@ -625,12 +679,12 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM> Eth for EthClient<C, SN, S, M, EM> where
 		self.send_raw_transaction(raw)
 	}
 
-	fn call(&self, meta: Self::Metadata, request: CallRequest, num: Trailing<BlockNumber>) -> BoxFuture<Bytes> {
+	fn call(&self, meta: Self::Metadata, request: CallRequest, num: BlockNumber, state_override: Trailing<StateOverride>) -> BoxFuture<Bytes> {
 		let request = CallRequest::into(request);
 		let signed = try_bf!(fake_sign::sign_call(request, meta.is_dapp()));
 
-		let num = num.unwrap_or_default();
-		let result = self.client.call(&signed, Default::default(), num.into());
+		let state_override = state_override.unwrap_or_default().into();
+		let result = self.client.call(&signed, Default::default(), num.into(), &state_override);
 
 		Box::new(future::done(result
 			.map(|b| b.output.into())
@@ -638,10 +692,11 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM> Eth for EthClient<C, SN, S, M, EM> where
 		))
 	}
 
-	fn estimate_gas(&self, meta: Self::Metadata, request: CallRequest, num: Trailing<BlockNumber>) -> BoxFuture<RpcU256> {
+	fn estimate_gas(&self, meta: Self::Metadata, request: CallRequest, num: BlockNumber, state_override: Trailing<StateOverride>) -> BoxFuture<RpcU256> {
 		let request = CallRequest::into(request);
 		let signed = try_bf!(fake_sign::sign_call(request, meta.is_dapp()));
-		Box::new(future::done(self.client.estimate_gas(&signed, num.unwrap_or_default().into())
+		let state_override = state_override.unwrap_or_default().into();
+		Box::new(future::done(self.client.estimate_gas(&signed, num.into(), &state_override)
 			.map(Into::into)
 			.map_err(errors::call)
 		))
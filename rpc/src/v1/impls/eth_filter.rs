@@ -30,7 +30,7 @@ use jsonrpc_core::futures::{future, Future};
 use jsonrpc_core::futures::future::Either;
 use v1::traits::EthFilter;
 use v1::types::{BlockNumber, Index, Filter, FilterChanges, Log, H256 as RpcH256, U256 as RpcU256};
-use v1::helpers::{PollFilter, PollManager, limit_logs};
+use v1::helpers::{PollFilter, PollManager, LogsLimits, limit_logs, logs_limit_exceeded};
 use v1::impls::eth::pending_logs;
 
 /// Something which provides data that can be filtered over.
@@ -62,15 +62,17 @@ pub struct EthFilterClient<C, M> where
 	client: Arc<C>,
 	miner: Arc<M>,
 	polls: Mutex<PollManager<PollFilter>>,
+	logs_limits: LogsLimits,
 }
 
 impl<C, M> EthFilterClient<C, M> where C: BlockChainClient, M: MinerService {
 	/// Creates new Eth filter client.
-	pub fn new(client: Arc<C>, miner: Arc<M>) -> Self {
+	pub fn new(client: Arc<C>, miner: Arc<M>, logs_limits: LogsLimits) -> Self {
 		EthFilterClient {
 			client: client,
 			miner: miner,
 			polls: Mutex::new(PollManager::new()),
+			logs_limits: logs_limits,
 		}
 	}
 }
@@ -89,7 +91,12 @@ impl<C, M> Filterable for EthFilterClient<C, M> where C: BlockChainClient, M: Mi
 	}
 
 	fn logs(&self, filter: EthcoreFilter) -> BoxFuture<Vec<Log>> {
-		Box::new(future::ok(self.client.logs(filter).into_iter().map(Into::into).collect()))
+		let (logs, next) = self.logs_limits.logs(&*self.client, filter, None);
+		let logs = logs.into_iter().map(Into::into).collect();
+		Box::new(match next {
+			Some(next) => future::err(logs_limit_exceeded(logs, next)),
+			None => future::ok(logs),
+		})
 	}
 
 	fn pending_logs(&self, block_number: u64, filter: &EthcoreFilter) -> Vec<Log> {
@@ -16,6 +16,7 @@
 
 //! Eth PUB-SUB rpc implementation.
 
+use std::cmp;
 use std::sync::{Arc, Weak};
 use std::collections::BTreeMap;
 
@@ -25,15 +26,16 @@ use jsonrpc_macros::Trailing;
 use jsonrpc_macros::pubsub::{Sink, Subscriber};
 use jsonrpc_pubsub::SubscriptionId;
 
-use v1::helpers::{errors, limit_logs, Subscribers};
+use v1::helpers::{errors, limit_logs, LogsLimits, Subscribers};
 use v1::helpers::light_fetch::LightFetch;
 use v1::metadata::Metadata;
 use v1::traits::EthPubSub;
-use v1::types::{pubsub, RichHeader, Log};
+use v1::types::{pubsub, RichHeader, Log, LocalizedTrace, TraceFilter};
 
 use ethcore::encoded;
 use ethcore::filter::Filter as EthFilter;
-use ethcore::client::{BlockChainClient, ChainNotify, BlockId};
+use ethcore::log_entry::LocalizedLogEntry;
+use ethcore::client::{BlockChainClient, ChainNotify, BlockId, TraceFilter as EthTraceFilter};
 use ethsync::LightSync;
 use light::cache::Cache;
 use light::on_demand::OnDemand;
@@ -51,36 +53,42 @@ pub struct EthPubSubClient<C> {
 	heads_subscribers: Arc<RwLock<Subscribers<Client>>>,
 	logs_subscribers: Arc<RwLock<Subscribers<(Client, EthFilter)>>>,
 	transactions_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	traces_subscribers: Arc<RwLock<Subscribers<(Client, EthTraceFilter)>>>,
 }
 
 impl<C> EthPubSubClient<C> {
 	/// Creates new `EthPubSubClient`.
-	pub fn new(client: Arc<C>, remote: Remote) -> Self {
+	pub fn new(client: Arc<C>, remote: Remote, logs_limits: LogsLimits) -> Self {
 		let heads_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 		let logs_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 		let transactions_subscribers = Arc::new(RwLock::new(Subscribers::default()));
+		let traces_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 
 		EthPubSubClient {
 			handler: Arc::new(ChainNotificationHandler {
 				client,
 				remote,
+				logs_limits,
 				heads_subscribers: heads_subscribers.clone(),
 				logs_subscribers: logs_subscribers.clone(),
 				transactions_subscribers: transactions_subscribers.clone(),
+				traces_subscribers: traces_subscribers.clone(),
 			}),
 			heads_subscribers,
 			logs_subscribers,
 			transactions_subscribers,
+			traces_subscribers,
 		}
 	}
 
 	/// Creates new `EthPubSubCient` with deterministic subscription ids.
 	#[cfg(test)]
 	pub fn new_test(client: Arc<C>, remote: Remote) -> Self {
-		let client = Self::new(client, remote);
+		let client = Self::new(client, remote, Default::default());
 		*client.heads_subscribers.write() = Subscribers::new_test();
 		*client.logs_subscribers.write() = Subscribers::new_test();
 		*client.transactions_subscribers.write() = Subscribers::new_test();
+		*client.traces_subscribers.write() = Subscribers::new_test();
 		client
 	}
 
@@ -107,7 +115,7 @@ impl EthPubSubClient<LightFetch> {
 			cache,
 			gas_price_percentile,
 		};
-		EthPubSubClient::new(Arc::new(fetch), remote)
+		EthPubSubClient::new(Arc::new(fetch), remote, Default::default())
 	}
 }
 
@@ -115,9 +123,11 @@ impl EthPubSubClient<LightFetch> {
 pub struct ChainNotificationHandler<C> {
 	client: Arc<C>,
 	remote: Remote,
+	logs_limits: LogsLimits,
 	heads_subscribers: Arc<RwLock<Subscribers<Client>>>,
 	logs_subscribers: Arc<RwLock<Subscribers<(Client, EthFilter)>>>,
 	transactions_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	traces_subscribers: Arc<RwLock<Subscribers<(Client, EthTraceFilter)>>>,
 }
 
 impl<C> ChainNotificationHandler<C> {
@@ -182,6 +192,63 @@ impl<C> ChainNotificationHandler<C> {
 	}
 }
 
+impl<C: BlockChainClient> ChainNotificationHandler<C> {
+	fn notify_chain_logs(&self, blocks: &[H256], removed: bool) {
+		let to_log = |entry: LocalizedLogEntry| {
+			let mut log = Log::from(entry);
+			if removed {
+				log.log_type = "removed".into();
+			}
+			log
+		};
+
+		for &(ref subscriber, ref filter) in self.logs_subscribers.read().values() {
+			let max_results = match self.logs_limits.max_results {
+				// a `limit` keeps the latest logs only, which bounds the query already.
+				Some(max) if filter.limit.is_none() => cmp::max(max, 1),
+				_ => {
+					let logs = blocks.iter()
+						.flat_map(|hash| self.client.logs(block_filter(filter, hash)))
+						.map(&to_log)
+						.collect();
+					for log in limit_logs(logs, filter.limit) {
+						Self::notify(&self.remote, subscriber, pubsub::Result::Log(log));
+					}
+					continue;
+				},
+			};
+
+			// notify about a page at a time, so the logs of a big block are never held at once.
+			for hash in blocks {
+				let mut cursor = None;
+				loop {
+					let (logs, next) = self.client.logs_paged(block_filter(filter, hash), cursor, max_results);
+					for log in logs {
+						Self::notify(&self.remote, subscriber, pubsub::Result::Log(to_log(log)));
+					}
+					match next {
+						Some(next) => cursor = Some(next),
+						None => break,
+					}
+				}
+			}
+		}
+	}
+
+	fn notify_traces(&self, enacted: &[H256]) {
+		for &(ref subscriber, ref filter) in self.traces_subscribers.read().values() {
+			for hash in enacted {
+				let mut filter = filter.clone();
+				filter.range = BlockId::Hash(*hash)..BlockId::Hash(*hash);
+
+				for trace in self.client.filter_traces(filter).unwrap_or_else(Vec::new) {
+					Self::notify(&self.remote, subscriber, pubsub::Result::Trace(LocalizedTrace::from(trace)));
+				}
+			}
+		}
+	}
+}
+
 /// A light client wrapper struct.
 pub trait LightClient: Send + Sync {
 	/// Get a recent block header.
@@ -243,20 +310,58 @@ impl<C: BlockChainClient> ChainNotify for ChainNotificationHandler<C> {
 		self.notify_heads(&headers);
 
 		// Enacted logs
-		self.notify_logs(&enacted, |filter| {
-			Ok(self.client.logs(filter).into_iter().map(Into::into).collect())
-		});
+		self.notify_chain_logs(&enacted, false);
 
 		// Retracted logs
-		self.notify_logs(&retracted, |filter| {
-			Ok(self.client.logs(filter).into_iter().map(Into::into).map(|mut log: Log| {
-				log.log_type = "removed".into();
-				log
-			}).collect())
-		});
+		self.notify_chain_logs(&retracted, true);
+
+		// Enacted traces
+		self.notify_traces(&enacted);
 	}
 }
 
+fn block_filter(filter: &EthFilter, hash: &H256) -> EthFilter {
+	let mut filter = filter.clone();
+	filter.from_block = BlockId::Hash(*hash);
+	filter.to_block = filter.from_block.clone();
+	filter
+}
+
+fn to_trace_filter(params: Option<pubsub::Params>) -> Result<EthTraceFilter> {
+	let filter = match params {
+		None | Some(pubsub::Params::None) => TraceFilter {
+			from_block: None,
+			to_block: None,
+			from_address: None,
+			to_address: None,
+			after: None,
+			count: None,
+		},
+		Some(pubsub::Params::Traces(filter)) => filter,
+		// a filter with just the block range is deserialized as logs filter.
+		Some(pubsub::Params::Logs(filter)) => {
+			if filter.address.is_some() || filter.topics.is_some() || filter.limit.is_some() || filter.cursor.is_some() {
+				return Err(errors::invalid_params("traces", "Expected a trace filter object."));
+			}
+			TraceFilter {
+				from_block: filter.from_block,
+				to_block: filter.to_block,
+				from_address: None,
+				to_address: None,
+				after: None,
+				count: None,
+			}
+		},
+	};
+
+	// subscribers are notified about each new block, a block range can't be honoured.
+	if filter.from_block.is_some() || filter.to_block.is_some() {
+		return Err(errors::invalid_params("traces", "fromBlock and toBlock are not supported in subscriptions."));
+	}
+
+	Ok(filter.into())
+}
+
 impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
 	type Metadata = Metadata;
 
@@ -289,6 +394,13 @@ impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
 			(pubsub::Kind::NewPendingTransactions, _) => {
 				errors::invalid_params("newPendingTransactions", "Expected no parameters.")
 			},
+			(pubsub::Kind::Traces, params) => match to_trace_filter(params) {
+				Ok(filter) => {
+					self.traces_subscribers.write().push(subscriber, filter);
+					return;
+				},
+				Err(error) => error,
+			},
 			_ => {
 				errors::unimplemented(None)
 			},
@@ -301,7 +413,8 @@ impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
 		let res = self.heads_subscribers.write().remove(&id).is_some();
 		let res2 = self.logs_subscribers.write().remove(&id).is_some();
 		let res3 = self.transactions_subscribers.write().remove(&id).is_some();
+		let res4 = self.traces_subscribers.write().remove(&id).is_some();
 
-		Ok(res || res2 || res3)
+		Ok(res || res2 || res3 || res4)
 	}
 }
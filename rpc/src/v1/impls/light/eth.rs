@@ -46,7 +46,7 @@ use v1::helpers::light_fetch::{self, LightFetch};
 use v1::traits::Eth;
 use v1::types::{
 	RichBlock, Block, BlockTransactions, BlockNumber, Bytes, SyncStatus, SyncInfo,
-	Transaction, CallRequest, Index, Filter, Log, Receipt, Work,
+	Transaction, CallRequest, StateOverride, Index, Filter, Log, Receipt, Work, EthAccount,
 	H64 as RpcH64, H256 as RpcH256, H160 as RpcH160, U256 as RpcU256,
 };
 use v1::metadata::Metadata;
@@ -272,6 +272,10 @@ impl<T: LightChainClient + 'static> Eth for EthClient<T> {
 		Box::new(future::err(errors::unimplemented(None)))
 	}
 
+	fn proof(&self, _address: RpcH160, _keys: Vec<RpcU256>, _num: Trailing<BlockNumber>) -> BoxFuture<EthAccount> {
+		Box::new(future::err(errors::light_unimplemented(None)))
+	}
+
 	fn block_by_hash(&self, hash: RpcH256, include_txs: bool) -> BoxFuture<Option<RichBlock>> {
 		Box::new(self.rich_block(BlockId::Hash(hash.into()), include_txs).map(Some))
 	}
@@ -376,7 +380,11 @@ impl<T: LightChainClient + 'static> Eth for EthClient<T> {
 		self.send_raw_transaction(raw)
 	}
 
-	fn call(&self, _meta: Self::Metadata, req: CallRequest, num: Trailing<BlockNumber>) -> BoxFuture<Bytes> {
+	fn call(&self, _meta: Self::Metadata, req: CallRequest, num: BlockNumber, state_override: Trailing<StateOverride>) -> BoxFuture<Bytes> {
+		if !state_override.unwrap_or_default().0.is_empty() {
+			return Box::new(future::err(errors::light_unimplemented(Some("State overrides are not supported.".into()))));
+		}
+
 		Box::new(self.fetcher().proved_execution(req, num).and_then(|res| {
 			match res {
 				Ok(exec) => Ok(exec.output.into()),
@@ -385,7 +393,11 @@ impl<T: LightChainClient + 'static> Eth for EthClient<T> {
 		}))
 	}
 
-	fn estimate_gas(&self, _meta: Self::Metadata, req: CallRequest, num: Trailing<BlockNumber>) -> BoxFuture<RpcU256> {
+	fn estimate_gas(&self, _meta: Self::Metadata, req: CallRequest, num: BlockNumber, state_override: Trailing<StateOverride>) -> BoxFuture<RpcU256> {
+		if !state_override.unwrap_or_default().0.is_empty() {
+			return Box::new(future::err(errors::light_unimplemented(Some("State overrides are not supported.".into()))));
+		}
+
 		// TODO: binary chop for more accurate estimates.
 		Box::new(self.fetcher().proved_execution(req, num).and_then(|res| {
 			match res {
@@ -39,8 +39,8 @@ use v1::helpers::light_fetch::LightFetch;
 use v1::metadata::Metadata;
 use v1::traits::Parity;
 use v1::types::{
-	Bytes, U256, U64, H160, H256, H512, CallRequest,
-	Peers, Transaction, RpcSettings, Histogram,
+	Bytes, U256, U64, H160, H256, H512, CallRequest, StateOverride,
+	Peers, Transaction, RpcSettings, Histogram, FeeHistory, LookupResult, TokenBalance,
 	TransactionStats, LocalTransactionStatus,
 	BlockNumber, ConsensusCapability, VersionInfo,
 	OperationsInfo, DappId, ChainStatus,
@@ -234,6 +234,10 @@ impl Parity for ParityClient {
 			.map(Into::into))
 	}
 
+	fn fee_history(&self, _block_count: u64, _percentiles: Trailing<Vec<f64>>) -> Result<FeeHistory> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn unsigned_transactions_count(&self) -> Result<usize> {
 		match self.signer {
 			None => Err(errors::signer_disabled()),
@@ -257,6 +261,26 @@ impl Parity for ParityClient {
 		Err(errors::light_unimplemented(None))
 	}
 
+	fn list_transactions(&self, _: H160, _: BlockNumber, _: BlockNumber, _: u64) -> Result<Option<Vec<Transaction>>> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn balances(&self, _: Vec<H160>, _: Trailing<BlockNumber>) -> Result<Vec<LookupResult<U256>>> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn storage_batch(&self, _: Vec<(H160, U256)>, _: Trailing<BlockNumber>) -> Result<Vec<LookupResult<H256>>> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn token_balances(&self, _: H160) -> Result<Option<Vec<TokenBalance>>> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn token_holders(&self, _: H160, _: u64) -> Result<Option<Vec<TokenBalance>>> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn encrypt_message(&self, key: H512, phrase: Bytes) -> Result<Bytes> {
 		ecies::encrypt(&key.into(), &DEFAULT_MAC, &phrase.0)
 			.map_err(errors::encryption)
@@ -412,7 +436,7 @@ impl Parity for ParityClient {
 		ipfs::cid(content)
 	}
 
-	fn call(&self, _meta: Self::Metadata, _requests: Vec<CallRequest>, _block: Trailing<BlockNumber>) -> Result<Vec<Bytes>> {
+	fn call(&self, _meta: Self::Metadata, _requests: Vec<CallRequest>, _block: BlockNumber, _state_override: Trailing<StateOverride>) -> Result<Vec<Bytes>> {
 		Err(errors::light_unimplemented(None))
 	}
 
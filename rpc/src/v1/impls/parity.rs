@@ -15,11 +15,12 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 //! Parity-specific rpc implementation.
+use std::cmp;
 use std::sync::Arc;
 use std::str::FromStr;
 use std::collections::{BTreeMap, HashSet};
 
-use ethereum_types::Address;
+use ethereum_types::{Address, H256 as EthH256, U256 as EthU256};
 use version::version_data;
 
 use crypto::{DEFAULT_MAC, ecies};
@@ -27,7 +28,7 @@ use ethkey::{Brain, Generator};
 use ethstore::random_phrase;
 use ethsync::{SyncProvider, ManageNetwork};
 use ethcore::account_provider::AccountProvider;
-use ethcore::client::{MiningBlockChainClient};
+use ethcore::client::{MiningBlockChainClient, GAS_PRICE_ORACLE_HISTORY};
 use ethcore::ids::BlockId;
 use ethcore::miner::MinerService;
 use ethcore::mode::Mode;
@@ -40,11 +41,12 @@ use jsonrpc_core::futures::{future, Future};
 use jsonrpc_macros::Trailing;
 use v1::helpers::{self, errors, fake_sign, ipfs, SigningQueue, SignerService, NetworkSettings};
 use v1::helpers::accounts::unwrap_provider;
+use v1::impls::eth::check_known;
 use v1::metadata::Metadata;
 use v1::traits::Parity;
 use v1::types::{
-	Bytes, U256, U64, H160, H256, H512, CallRequest,
-	Peers, Transaction, RpcSettings, Histogram,
+	Bytes, U256, U64, H160, H256, H512, CallRequest, StateOverride,
+	Peers, Transaction, RpcSettings, Histogram, FeeHistory, LookupResult, TokenBalance,
 	TransactionStats, LocalTransactionStatus,
 	BlockNumber, ConsensusCapability, VersionInfo,
 	OperationsInfo, DappId, ChainStatus,
@@ -52,6 +54,9 @@ use v1::types::{
 };
 use Host;
 
+/// Maximal number of transactions returned by a single `parity_listTransactions` call.
+const MAX_LISTED_TRANSACTIONS: u64 = 1000;
+
 /// Parity implementation.
 pub struct ParityClient<C, M, U>  {
 	client: Arc<C>,
@@ -259,6 +264,18 @@ impl<C, M, U> Parity for ParityClient<C, M, U> where
 		))
 	}
 
+	fn fee_history(&self, block_count: u64, percentiles: Trailing<Vec<f64>>) -> Result<FeeHistory> {
+		let percentiles = percentiles.unwrap_or_default();
+		let out_of_range = percentiles.iter().any(|p| *p < 0.0 || *p > 100.0);
+		if out_of_range || percentiles.windows(2).any(|pair| pair[0] > pair[1]) {
+			return Err(errors::invalid_params("percentiles", "Expected ascending percentiles between 0 and 100."));
+		}
+
+		let block_count = cmp::min(block_count, GAS_PRICE_ORACLE_HISTORY as u64) as usize;
+		let history = self.client.fee_history(block_count, &percentiles);
+		Ok(FeeHistory::new(history, self.miner.sensible_gas_price().into()))
+	}
+
 	fn unsigned_transactions_count(&self) -> Result<usize> {
 		match self.signer {
 			None => Err(errors::signer_disabled()),
@@ -286,6 +303,47 @@ impl<C, M, U> Parity for ParityClient<C, M, U> where
 			.map(|a| a.into_iter().map(Into::into).collect()))
 	}
 
+	fn list_transactions(&self, address: H160, from_block: BlockNumber, to_block: BlockNumber, limit: u64) -> Result<Option<Vec<Transaction>>> {
+		Ok(self.client
+			.account_transactions(&address.into(), from_block.into(), to_block.into(), cmp::min(limit, MAX_LISTED_TRANSACTIONS) as usize)
+			.map(|txs| txs.into_iter().map(|t| Transaction::from_localized(t, self.eip86_transition)).collect()))
+	}
+
+	fn balances(&self, addresses: Vec<H160>, block: Trailing<BlockNumber>) -> Result<Vec<LookupResult<U256>>> {
+		let block = block.unwrap_or_default();
+		check_known(&*self.client, block.clone())?;
+
+		let addresses = addresses.into_iter().map(Into::into).collect::<Vec<Address>>();
+		match self.client.balances(&addresses, block.into()) {
+			Some(balances) => Ok(balances.into_iter().map(|balance| balance.map(U256::from).into()).collect()),
+			None => Err(errors::state_pruned()),
+		}
+	}
+
+	fn storage_batch(&self, entries: Vec<(H160, U256)>, block: Trailing<BlockNumber>) -> Result<Vec<LookupResult<H256>>> {
+		let block = block.unwrap_or_default();
+		check_known(&*self.client, block.clone())?;
+
+		let entries = entries.into_iter()
+			.map(|(address, position)| {
+				let position: EthU256 = position.into();
+				(address.into(), EthH256::from(position))
+			})
+			.collect::<Vec<(Address, EthH256)>>();
+		match self.client.storage_batch(&entries, block.into()) {
+			Some(values) => Ok(values.into_iter().map(|value| value.map(H256::from).into()).collect()),
+			None => Err(errors::state_pruned()),
+		}
+	}
+
+	fn token_balances(&self, holder: H160) -> Result<Option<Vec<TokenBalance>>> {
+		Ok(self.client.token_holdings(&holder.into()).map(|holdings| holdings.into_iter().map(Into::into).collect()))
+	}
+
+	fn token_holders(&self, token: H160, limit: u64) -> Result<Option<Vec<TokenBalance>>> {
+		Ok(self.client.token_holders(&token.into(), limit as usize).map(|holders| holders.into_iter().map(Into::into).collect()))
+	}
+
 	fn encrypt_message(&self, key: H512, phrase: Bytes) -> Result<Bytes> {
 		ecies::encrypt(&key.into(), &DEFAULT_MAC, &phrase.0)
 			.map_err(errors::encryption)
@@ -414,17 +472,21 @@ impl<C, M, U> Parity for ParityClient<C, M, U> where
 		ipfs::cid(content)
 	}
 
-	fn call(&self, meta: Self::Metadata, requests: Vec<CallRequest>, block: Trailing<BlockNumber>) -> Result<Vec<Bytes>> {
+	fn call(&self, meta: Self::Metadata, requests: Vec<CallRequest>, block: BlockNumber, state_override: Trailing<StateOverride>) -> Result<Vec<Bytes>> {
+		// the overrides are applied to the state the first call is executed on.
+		let mut state_override = Some(state_override.unwrap_or_default().into());
 		let requests = requests
 			.into_iter()
-			.map(|request| Ok((
-				fake_sign::sign_call(request.into(), meta.is_dapp())?,
-				Default::default()
-			)))
+			.map(|request| {
+				let request = CallRequest::into(request);
+				Ok((
+					fake_sign::sign_call(request, meta.is_dapp())?,
+					Default::default(),
+					state_override.take().unwrap_or_default(),
+				))
+			})
 			.collect::<Result<Vec<_>>>()?;
 
-		let block = block.unwrap_or_default();
-
 		self.client.call_many(&requests, block.into())
 				.map(|res| res.into_iter().map(|res| res.output.into()).collect())
 				.map_err(errors::call)
@@ -32,6 +32,7 @@ use v1::traits::Personal;
 use v1::types::{
 	H160 as RpcH160, H256 as RpcH256, H520 as RpcH520, U128 as RpcU128,
 	Bytes as RpcBytes,
+	EIP712,
 	ConfirmationPayload as RpcConfirmationPayload,
 	ConfirmationResponse as RpcConfirmationResponse,
 	TransactionRequest,
@@ -157,6 +158,24 @@ impl<D: Dispatcher + 'static> Personal for PersonalClient<D> {
 				 }))
 	}
 
+	fn sign_typed_data(&self, typed_data: EIP712, account: RpcH160, password: String) -> BoxFuture<RpcH520> {
+		let dispatcher = self.dispatcher.clone();
+		let accounts = try_bf!(self.account_provider());
+
+		let payload = RpcConfirmationPayload::EIP712SignMessage((account.clone(), typed_data).into());
+
+		Box::new(dispatch::from_rpc(payload, account.into(), &dispatcher)
+				 .and_then(|payload| {
+					 dispatch::execute(dispatcher, accounts, payload, dispatch::SignWith::Password(password))
+				 })
+				 .map(|v| v.into_value())
+				 .then(|res| match res {
+					 Ok(RpcConfirmationResponse::Signature(signature)) => Ok(signature),
+					 Err(e) => Err(e),
+					 e => Err(errors::internal("Unexpected result", e)),
+				 }))
+	}
+
 	fn ec_recover(&self, data: RpcBytes, signature: RpcH520) -> BoxFuture<RpcH160> {
 		let signature: H520 = signature.into();
 		let signature = Signature::from_electrum(&signature);
@@ -32,7 +32,7 @@ use jsonrpc_pubsub::SubscriptionId;
 use jsonrpc_macros::pubsub::{Sink, Subscriber};
 use v1::helpers::accounts::unwrap_provider;
 use v1::helpers::dispatch::{self, Dispatcher, WithToken, eth_data_hash};
-use v1::helpers::{errors, SignerService, SigningQueue, ConfirmationPayload, FilledTransactionRequest, Subscribers};
+use v1::helpers::{errors, eip712, SignerService, SigningQueue, ConfirmationPayload, FilledTransactionRequest, Subscribers};
 use v1::metadata::Metadata;
 use v1::traits::Signer;
 use v1::types::{TransactionModification, ConfirmationRequest, ConfirmationResponse, ConfirmationResponseWithToken, U256, Bytes};
@@ -221,6 +221,16 @@ impl<D: Dispatcher + 'static> Signer for SignerClient<D> {
 						Err(err) => Err(errors::invalid_params("Invalid signature received.", err)),
 					}
 				},
+				ConfirmationPayload::EIP712SignMessage(address, data) => {
+					eip712::hash_structured_data(&data).and_then(|expected_hash| {
+						let signature = ethkey::Signature::from_electrum(&bytes.0);
+						match ethkey::verify_address(&address, &signature, &expected_hash) {
+							Ok(true) => Ok(ConfirmationResponse::Signature(bytes.0.as_slice().into())),
+							Ok(false) => Err(errors::invalid_params("Sender address does not match the signature.", ())),
+							Err(err) => Err(errors::invalid_params("Invalid signature received.", err)),
+						}
+					})
+				},
 				ConfirmationPayload::Decrypt(_address, _data) => {
 					// TODO [ToDr]: Decrypt can we verify if the answer is correct?
 					Ok(ConfirmationResponse::Decrypt(bytes))
@@ -30,6 +30,7 @@ use v1::traits::{EthSigning, ParitySigning};
 use v1::types::{
 	U256 as RpcU256,
 	H160 as RpcH160, H256 as RpcH256, H520 as RpcH520, Bytes as RpcBytes,
+	EIP712,
 	Either as RpcEither,
 	RichRawTransaction as RpcRichRawTransaction,
 	TransactionRequest as RpcTransactionRequest,
@@ -85,6 +86,15 @@ impl<D: Dispatcher + 'static> EthSigning for SigningUnsafeClient<D>
 			}))
 	}
 
+	fn sign_typed_data(&self, _: Metadata, address: RpcH160, typed_data: EIP712) -> BoxFuture<RpcH520> {
+		Box::new(self.handle(RpcConfirmationPayload::EIP712SignMessage((address.clone(), typed_data).into()), address.into())
+			.then(|res| match res {
+				Ok(RpcConfirmationResponse::Signature(signature)) => Ok(signature),
+				Err(e) => Err(e),
+				e => Err(errors::internal("Unexpected result", e)),
+			}))
+	}
+
 	fn send_transaction(&self, meta: Metadata, request: RpcTransactionRequest) -> BoxFuture<RpcH256> {
 		Box::new(self.handle(RpcConfirmationPayload::SendTransaction(request), meta.dapp_id().into())
 			.then(|res| match res {
@@ -18,7 +18,8 @@
 
 use std::sync::Arc;
 
-use ethcore::client::{MiningBlockChainClient, CallAnalytics, TransactionId, TraceId};
+use ethcore::client::{MiningBlockChainClient, CallAnalytics, Executed, TransactionId, TraceId};
+use ethcore::trace::{TracerPreset, PresetTrace};
 use rlp::UntrustedRlp;
 use transaction::SignedTransaction;
 
@@ -27,7 +28,7 @@ use jsonrpc_macros::Trailing;
 use v1::Metadata;
 use v1::traits::Traces;
 use v1::helpers::{errors, fake_sign};
-use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, TraceOptions, H256};
+use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, TraceOptions, H256, StateOverride};
 
 fn to_call_analytics(flags: TraceOptions) -> CallAnalytics {
 	CallAnalytics {
@@ -37,6 +38,25 @@ fn to_call_analytics(flags: TraceOptions) -> CallAnalytics {
 	}
 }
 
+fn to_tracer_presets(flags: &TraceOptions) -> Vec<TracerPreset> {
+	let mut presets = Vec::new();
+	if flags.contains(&("callTracer".to_owned())) {
+		presets.push(TracerPreset::Call);
+	}
+	if flags.contains(&("prestateTracer".to_owned())) {
+		presets.push(TracerPreset::Prestate);
+	}
+	presets
+}
+
+fn with_presets(executed: Executed, traces: Vec<PresetTrace>) -> TraceResults {
+	let mut results = TraceResults::from(executed);
+	for trace in traces {
+		results.add_preset(trace);
+	}
+	results
+}
+
 /// Traces api implementation.
 pub struct TracesClient<C> {
 	client: Arc<C>,
@@ -79,15 +99,22 @@ impl<C> Traces for TracesClient<C> where C: MiningBlockChainClient + 'static {
 			.map(LocalizedTrace::from))
 	}
 
-	fn call(&self, meta: Self::Metadata, request: CallRequest, flags: TraceOptions, block: Trailing<BlockNumber>) -> Result<TraceResults> {
-		let block = block.unwrap_or_default();
-
+	fn call(&self, meta: Self::Metadata, request: CallRequest, flags: TraceOptions, block: BlockNumber, state_override: Trailing<StateOverride>) -> Result<TraceResults> {
 		let request = CallRequest::into(request);
+		let state_override = state_override.unwrap_or_default().into();
 		let signed = fake_sign::sign_call(request, meta.is_dapp())?;
+		let presets = to_tracer_presets(&flags);
+		let analytics = to_call_analytics(flags);
 
-		self.client.call(&signed, to_call_analytics(flags), block.into())
-			.map(TraceResults::from)
-			.map_err(errors::call)
+		if presets.is_empty() {
+			return self.client.call(&signed, analytics, block.into(), &state_override)
+				.map(TraceResults::from)
+				.map_err(errors::call);
+		}
+
+		let (executed, traces) = self.client.call_with_presets(&signed, analytics, &presets, block.into(), &state_override)
+			.map_err(errors::call)?;
+		Ok(with_presets(executed, traces))
 	}
 
 	fn call_many(&self, meta: Self::Metadata, requests: Vec<(CallRequest, TraceOptions)>, block: Trailing<BlockNumber>) -> Result<Vec<TraceResults>> {
@@ -97,7 +124,7 @@ impl<C> Traces for TracesClient<C> where C: MiningBlockChainClient + 'static {
 			.map(|(request, flags)| {
 				let request = CallRequest::into(request);
 				let signed = fake_sign::sign_call(request, meta.is_dapp())?;
-				Ok((signed, to_call_analytics(flags)))
+				Ok((signed, to_call_analytics(flags), Default::default()))
 			})
 			.collect::<Result<Vec<_>>>()?;
 
@@ -112,15 +139,25 @@ impl<C> Traces for TracesClient<C> where C: MiningBlockChainClient + 'static {
 		let tx = UntrustedRlp::new(&raw_transaction.into_vec()).as_val().map_err(|e| errors::invalid_params("Transaction is not valid RLP", e))?;
 		let signed = SignedTransaction::new(tx).map_err(errors::transaction)?;
 
-		self.client.call(&signed, to_call_analytics(flags), block.into())
+		self.client.call(&signed, to_call_analytics(flags), block.into(), &Default::default())
 			.map(TraceResults::from)
 			.map_err(errors::call)
 	}
 
 	fn replay_transaction(&self, transaction_hash: H256, flags: TraceOptions) -> Result<TraceResults> {
-		self.client.replay(TransactionId::Hash(transaction_hash.into()), to_call_analytics(flags))
-			.map(TraceResults::from)
-			.map_err(errors::call)
+		let id = TransactionId::Hash(transaction_hash.into());
+		let presets = to_tracer_presets(&flags);
+		let analytics = to_call_analytics(flags);
+
+		if presets.is_empty() {
+			return self.client.replay(id, analytics)
+				.map(TraceResults::from)
+				.map_err(errors::call);
+		}
+
+		let (executed, traces) = self.client.replay_with_presets(id, analytics, &presets)
+			.map_err(errors::call)?;
+		Ok(with_presets(executed, traces))
 	}
 
 	fn replay_block_transactions(&self, block_number: BlockNumber, flags: TraceOptions) -> Result<Vec<TraceResults>> {
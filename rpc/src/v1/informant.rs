@@ -17,6 +17,7 @@
 //! RPC Requests Statistics
 
 use std::fmt;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::{self, AtomicUsize};
 use std::time;
@@ -24,11 +25,16 @@ use futures_cpupool as pool;
 use jsonrpc_core as rpc;
 use order_stat;
 use parking_lot::RwLock;
+use v1::helpers::errors;
 
 pub use self::pool::CpuPool;
 
 const RATE_SECONDS: usize = 10;
 const STATS_SAMPLES: usize = 60;
+/// Maximal number of distinct methods counted separately.
+const MAX_METHODS: usize = 1024;
+/// Name under which calls to methods over `MAX_METHODS` are counted.
+const OTHER_METHODS: &'static str = "other";
 
 struct RateCalculator {
 	era: time::Instant,
@@ -135,6 +141,7 @@ impl<T: Default + Copy + Ord> StatsCalculator<T> {
 pub struct RpcStats {
 	requests: RwLock<RateCalculator>,
 	roundtrips: RwLock<StatsCalculator<u32>>,
+	methods: RwLock<HashMap<String, u64>>,
 	active_sessions: AtomicUsize,
 }
 
@@ -155,6 +162,22 @@ impl RpcStats {
 		self.requests.write().tick()
 	}
 
+	/// Count call to given method. Only methods which are known to exist should be counted.
+	/// Once `MAX_METHODS` methods are known, calls to new ones are counted together as "other".
+	pub fn count_method(&self, method: &str) {
+		let mut methods = self.methods.write();
+		if let Some(count) = methods.get_mut(method) {
+			*count += 1;
+			return;
+		}
+
+		let method = match methods.len() < MAX_METHODS {
+			true => method,
+			false => OTHER_METHODS,
+		};
+		*methods.entry(method.to_owned()).or_insert(0) += 1;
+	}
+
 	/// Add roundtrip time (microseconds)
 	pub fn add_roundtrip(&self, microseconds: u32) {
 		self.roundtrips.write().add(microseconds)
@@ -170,6 +193,16 @@ impl RpcStats {
 		self.requests.read().rate()
 	}
 
+	/// Returns up to `limit` most called methods with the number of calls made so far.
+	pub fn method_calls(&self, limit: usize) -> Vec<(String, u64)> {
+		let mut calls = self.methods.read().iter()
+			.map(|(method, count)| (method.clone(), *count))
+			.collect::<Vec<_>>();
+		calls.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+		calls.truncate(limit);
+		calls
+	}
+
 	/// Returns approximated roundtrip in microseconds
 	pub fn approximated_roundtrip(&self) -> u32 {
 		self.roundtrips.read().approximated_median()
@@ -221,6 +254,13 @@ impl<M: rpc::Metadata, T: ActivityNotifier> rpc::Middleware<M> for Middleware<T>
 		self.notifier.active();
 		self.stats.count_request();
 
+		// method names are chosen by the caller, so calls are counted only once
+		// the response shows that the method exists and the call was let through.
+		let calls: Vec<_> = match request {
+			rpc::Request::Single(ref call) => method_call(call).into_iter().collect(),
+			rpc::Request::Batch(ref calls) => calls.iter().filter_map(method_call).collect(),
+		};
+
 		let id = match request {
 			rpc::Request::Single(rpc::Call::MethodCall(ref call)) => Some(call.id.clone()),
 			_ => None,
@@ -232,6 +272,7 @@ impl<M: rpc::Metadata, T: ActivityNotifier> rpc::Middleware<M> for Middleware<T>
 				debug!(target: "rpc", "[{:?}] Took {}ms", id, time / 1_000);
 			}
 			stats.add_roundtrip(time);
+			count_methods(&stats, calls, res.as_ref());
 			res
 		});
 
@@ -242,6 +283,31 @@ impl<M: rpc::Metadata, T: ActivityNotifier> rpc::Middleware<M> for Middleware<T>
 	}
 }
 
+fn method_call(call: &rpc::Call) -> Option<(rpc::Id, String)> {
+	match *call {
+		rpc::Call::MethodCall(ref call) => Some((call.id.clone(), call.method.clone())),
+		_ => None,
+	}
+}
+
+fn count_methods(stats: &RpcStats, calls: Vec<(rpc::Id, String)>, response: Option<&rpc::Response>) {
+	let outputs = match response {
+		Some(&rpc::Response::Single(ref output)) => vec![output],
+		Some(&rpc::Response::Batch(ref outputs)) => outputs.iter().collect(),
+		None => Vec::new(),
+	};
+	let not_dispatched = |id: &rpc::Id| outputs.iter().any(|output| match *output {
+		rpc::Output::Failure(ref failure) => failure.id == *id && errors::is_not_dispatched(&failure.error),
+		_ => false,
+	});
+
+	for (id, method) in calls {
+		if !not_dispatched(&id) {
+			stats.count_method(&method);
+		}
+	}
+}
+
 /// Client Notifier
 pub struct ClientNotifier {
 	/// Client
@@ -257,7 +323,16 @@ impl ActivityNotifier for ClientNotifier {
 #[cfg(test)]
 mod tests {
 
-	use super::{RateCalculator, StatsCalculator, RpcStats};
+	use std::sync::Arc;
+	use jsonrpc_core::{MetaIoHandler, Value};
+	use v1::Metadata;
+	use v1::access::{AccessMiddleware, MethodAccess};
+	use super::{RateCalculator, StatsCalculator, RpcStats, Middleware, ActivityNotifier, MAX_METHODS};
+
+	struct TestNotifier;
+	impl ActivityNotifier for TestNotifier {
+		fn active(&self) {}
+	}
 
 	#[test]
 	fn should_calculate_rate() {
@@ -307,11 +382,70 @@ mod tests {
 		stats.count_request();
 		stats.count_request();
 		stats.add_roundtrip(125);
+		stats.count_method("eth_call");
+		stats.count_method("eth_call");
+		stats.count_method("eth_sign");
 
 		// then
 		assert_eq!(stats.sessions(), 1);
 		assert_eq!(stats.requests_rate(), 2);
 		assert_eq!(stats.approximated_roundtrip(), 125);
+		assert_eq!(stats.method_calls(10), vec![("eth_call".into(), 2), ("eth_sign".into(), 1)]);
+		assert_eq!(stats.method_calls(1), vec![("eth_call".into(), 2)]);
+	}
+
+	#[test]
+	fn should_count_existing_methods_only() {
+		// given
+		let stats = Arc::new(RpcStats::default());
+		let mut io = MetaIoHandler::with_middleware(Middleware::new(stats.clone(), TestNotifier, None));
+		io.add_method("eth_blockNumber", |_| Ok(Value::String("0x1".into())));
+
+		// when
+		io.handle_request_sync(r#"{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1}"#, ());
+		io.handle_request_sync(r#"[{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1},{"jsonrpc":"2.0","method":"made_up","params":[],"id":2}]"#, ());
+		io.handle_request_sync(r#"{"jsonrpc":"2.0","method":"also_made_up","params":[],"id":1}"#, ());
+
+		// then
+		assert_eq!(stats.method_calls(10), vec![("eth_blockNumber".into(), 2)]);
+	}
+
+	#[test]
+	fn should_not_count_denied_methods() {
+		// given
+		let stats = Arc::new(RpcStats::default());
+		let mut io = MetaIoHandler::with_middleware((
+			Middleware::new(stats.clone(), TestNotifier, None),
+			AccessMiddleware::new(MethodAccess::parse("eth_*", "").unwrap()),
+		));
+		io.add_method("eth_blockNumber", |_| Ok(Value::String("0x1".into())));
+		io.add_method("parity_enode", |_| Ok(Value::String("enode://".into())));
+
+		// when
+		io.handle_request_sync(r#"{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1}"#, Metadata::default());
+		io.handle_request_sync(r#"{"jsonrpc":"2.0","method":"parity_enode","params":[],"id":1}"#, Metadata::default());
+		io.handle_request_sync(r#"{"jsonrpc":"2.0","method":"made_up","params":[],"id":1}"#, Metadata::default());
+
+		// then
+		assert_eq!(stats.method_calls(10), vec![("eth_blockNumber".into(), 1)]);
+	}
+
+	#[test]
+	fn should_bound_number_of_counted_methods() {
+		// given
+		let stats = RpcStats::default();
+		for i in 0..MAX_METHODS {
+			stats.count_method(&format!("method_{}", i));
+		}
+
+		// when
+		stats.count_method("method_0");
+		stats.count_method("one_too_many");
+		stats.count_method("two_too_many");
+
+		// then
+		assert_eq!(stats.method_calls(2), vec![("method_0".into(), 2), ("other".into(), 2)]);
+		assert_eq!(stats.methods.read().len(), MAX_METHODS + 1);
 	}
 
 	#[test]
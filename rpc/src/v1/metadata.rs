@@ -20,6 +20,7 @@ use std::sync::Arc;
 use jsonrpc_core;
 use jsonrpc_pubsub::{Session, PubSubMetadata};
 
+use v1::ratelimit::ClientId;
 use v1::types::{DappId, Origin};
 
 /// RPC methods metadata.
@@ -29,6 +30,8 @@ pub struct Metadata {
 	pub origin: Origin,
 	/// Request PubSub Session
 	pub session: Option<Arc<Session>>,
+	/// Remote client identity used for rate limiting
+	pub client: Option<ClientId>,
 }
 
 impl Metadata {
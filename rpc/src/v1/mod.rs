@@ -36,14 +36,16 @@ mod types;
 #[cfg(test)]
 mod tests;
 
+pub mod access;
 pub mod extractors;
 pub mod informant;
 pub mod metadata;
+pub mod ratelimit;
 pub mod traits;
 
-pub use self::traits::{Web3, Eth, EthFilter, EthPubSub, EthSigning, Net, Parity, ParityAccounts, ParitySet, ParitySigning, PubSub, Signer, Personal, Traces, Rpc, SecretStore};
+pub use self::traits::{Web3, Eth, EthFilter, EthPubSub, EthSigning, Net, Parity, ParityAccounts, ParitySet, ParitySigning, PubSub, Signer, Personal, Traces, Rpc, SecretStore, Debug};
 pub use self::impls::*;
-pub use self::helpers::{NetworkSettings, block_import, dispatch};
+pub use self::helpers::{NetworkSettings, LogsLimits, block_import, dispatch};
 pub use self::metadata::Metadata;
 pub use self::types::Origin;
 pub use self::extractors::{RpcExtractor, WsExtractor, WsStats, WsDispatcher};
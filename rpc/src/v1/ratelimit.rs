@@ -0,0 +1,390 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Per-client request rate limiting.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ethereum_types::H256;
+use jsonrpc_core as rpc;
+use jsonrpc_core::futures::future;
+use lru_cache::LruCache;
+use parking_lot::Mutex;
+
+use v1::helpers::errors;
+use v1::Metadata;
+
+/// Maximal number of tracked clients. The least recently seen ones are forgotten first.
+const MAX_CLIENTS: usize = 4096;
+
+/// Identity of a remote RPC client.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClientId {
+	/// Client authorized with a validated token (hash).
+	Token(H256),
+	/// Client identified by its remote address.
+	Ip(IpAddr),
+	/// Unauthorized WebSockets session. The WebSockets server doesn't expose the remote address
+	/// of a session, so every session has a bucket of its own.
+	Session(u64),
+}
+
+/// Rate limit configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimit {
+	/// Cost units refilled into each client's bucket per second.
+	pub rate: u32,
+	/// Maximal number of cost units a client can accumulate.
+	pub burst: u32,
+	/// Cost of methods missing from `costs`.
+	pub default_cost: u32,
+	/// Cost of individual methods.
+	pub costs: HashMap<String, u32>,
+}
+
+impl RateLimit {
+	/// Creates new rate limit with default method costs.
+	pub fn new(rate: u32, burst: u32) -> Self {
+		let costs = [
+			("eth_call", 5),
+			("eth_estimateGas", 5),
+			("eth_getLogs", 10),
+			("eth_getProof", 5),
+			("trace_call", 10),
+			("trace_rawTransaction", 10),
+			("trace_replayTransaction", 20),
+			("trace_replayBlockTransactions", 50),
+			("trace_block", 20),
+			("trace_filter", 50),
+			("debug_traceTransaction", 20),
+		];
+
+		RateLimit {
+			rate,
+			burst,
+			default_cost: 1,
+			costs: costs.iter().map(|&(method, cost)| (method.to_owned(), cost)).collect(),
+		}
+	}
+
+	/// Overrides method costs with comma-delimited `METHOD=COST` pairs.
+	pub fn with_costs(mut self, costs: &str) -> Result<Self, String> {
+		for entry in costs.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+			let mut split = entry.splitn(2, '=');
+			let method = split.next().map(str::trim).unwrap_or("");
+			let cost = split.next()
+				.and_then(|cost| cost.trim().parse().ok())
+				.ok_or_else(|| format!("Invalid method cost: {}. Expected METHOD=COST.", entry))?;
+
+			if method.is_empty() {
+				return Err(format!("Invalid method cost: {}. Expected METHOD=COST.", entry));
+			}
+			self.costs.insert(method.to_owned(), cost);
+		}
+
+		Ok(self)
+	}
+
+	/// Returns cost of calling given method.
+	pub fn cost(&self, method: &str) -> u32 {
+		self.costs.get(method).cloned().unwrap_or(self.default_cost)
+	}
+}
+
+struct Bucket {
+	units: f64,
+	updated: Instant,
+}
+
+/// Token bucket rate limiter keyed by client.
+pub struct RateLimiter {
+	limit: RateLimit,
+	buckets: Mutex<LruCache<ClientId, Bucket>>,
+}
+
+impl RateLimiter {
+	/// Creates new rate limiter.
+	pub fn new(limit: RateLimit) -> Self {
+		RateLimiter {
+			limit,
+			buckets: Mutex::new(LruCache::new(MAX_CLIENTS)),
+		}
+	}
+
+	/// Returns rate limit configuration.
+	pub fn limit(&self) -> &RateLimit {
+		&self.limit
+	}
+
+	/// Charges `cost` units to given client.
+	/// Returns time to wait before the request can be made if the client is over the limit.
+	///
+	/// Requests costlier than the whole bucket are let through only when the bucket is full
+	/// and leave it in debt, so the client waits until the full cost is paid back.
+	pub fn acquire(&self, client: &ClientId, cost: u32) -> Result<(), Duration> {
+		self.acquire_at(client, cost, Instant::now())
+	}
+
+	fn acquire_at(&self, client: &ClientId, cost: u32, now: Instant) -> Result<(), Duration> {
+		let rate = self.limit.rate as f64;
+		let burst = self.limit.burst as f64;
+		let cost = cost as f64;
+		// requests costlier than the bucket would never pass otherwise
+		let required = burst.min(cost);
+
+		let mut buckets = self.buckets.lock();
+		if !buckets.contains_key(client) {
+			buckets.insert(client.clone(), Bucket {
+				units: burst,
+				updated: now,
+			});
+		}
+
+		let bucket = buckets.get_mut(client).expect("inserted above if missing; qed");
+
+		bucket.units = burst.min(bucket.units + Self::elapsed(now, bucket.updated) * rate);
+		bucket.updated = ::std::cmp::max(now, bucket.updated);
+
+		if bucket.units >= required {
+			bucket.units -= cost;
+			Ok(())
+		} else if rate > 0.0 {
+			let wait = (required - bucket.units) / rate;
+			Err(Duration::from_millis((wait * 1_000.0).ceil() as u64))
+		} else {
+			Err(Duration::from_secs(u64::max_value()))
+		}
+	}
+
+	fn elapsed(now: Instant, since: Instant) -> f64 {
+		if now <= since {
+			return 0.0;
+		}
+
+		let dur = now - since;
+		dur.as_secs() as f64 + dur.subsec_nanos() as f64 / 1_000_000_000.0
+	}
+}
+
+fn method(call: &rpc::Call) -> Option<&str> {
+	match *call {
+		rpc::Call::MethodCall(ref call) => Some(&call.method),
+		rpc::Call::Notification(ref notification) => Some(&notification.method),
+		_ => None,
+	}
+}
+
+fn reject(call: rpc::Call, wait: Duration) -> Option<rpc::Output> {
+	match call {
+		rpc::Call::MethodCall(call) => Some(rpc::Output::from(
+			Err(errors::rate_limited(wait)),
+			call.id,
+			call.jsonrpc,
+		)),
+		_ => None,
+	}
+}
+
+/// Middleware rejecting requests of clients exceeding the rate limit.
+pub struct RateLimitMiddleware {
+	limiter: Option<Arc<RateLimiter>>,
+}
+
+impl RateLimitMiddleware {
+	/// Creates new middleware. Requests are not limited if `limit` is `None`.
+	pub fn new(limit: Option<RateLimit>) -> Self {
+		RateLimitMiddleware {
+			limiter: limit.map(RateLimiter::new).map(Arc::new),
+		}
+	}
+}
+
+impl rpc::Middleware<Metadata> for RateLimitMiddleware {
+	type Future = rpc::futures::future::Either<
+		future::FutureResult<Option<rpc::Response>, ()>,
+		rpc::FutureResponse,
+	>;
+
+	fn on_request<F, X>(&self, request: rpc::Request, meta: Metadata, process: F) -> Self::Future where
+		F: FnOnce(rpc::Request, Metadata) -> X,
+		X: rpc::futures::Future<Item=Option<rpc::Response>, Error=()> + Send + 'static,
+	{
+		use self::rpc::futures::future::Either::{A, B};
+
+		let (limiter, client) = match (self.limiter.as_ref(), meta.client.clone()) {
+			(Some(limiter), Some(client)) => (limiter, client),
+			_ => return B(Box::new(process(request, meta))),
+		};
+
+		let cost = {
+			let cost = |call: &rpc::Call| method(call).map_or(0, |method| limiter.limit().cost(method));
+			match request {
+				rpc::Request::Single(ref call) => cost(call),
+				rpc::Request::Batch(ref calls) => calls.iter().map(cost).fold(0u32, u32::saturating_add),
+			}
+		};
+
+		match limiter.acquire(&client, cost) {
+			Ok(()) => B(Box::new(process(request, meta))),
+			Err(wait) => {
+				debug!(target: "rpc", "Rate limit exceeded by {:?} ({})", client, meta.origin);
+				A(future::ok(match request {
+					rpc::Request::Single(call) => reject(call, wait).map(rpc::Response::Single),
+					rpc::Request::Batch(calls) => {
+						let outputs = calls.into_iter().filter_map(|call| reject(call, wait)).collect::<Vec<_>>();
+						match outputs.is_empty() {
+							true => None,
+							false => Some(rpc::Response::Batch(outputs)),
+						}
+					},
+				}))
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::{Duration, Instant};
+	use ethereum_types::H256;
+	use jsonrpc_core::{MetaIoHandler, Value};
+	use v1::Metadata;
+	use super::{ClientId, RateLimit, RateLimiter, RateLimitMiddleware, MAX_CLIENTS};
+
+	#[test]
+	fn should_parse_method_costs() {
+		// given
+		let limit = RateLimit::new(10, 100);
+
+		// when
+		let limit = limit.with_costs("eth_call=2, net_version=0").unwrap();
+
+		// then
+		assert_eq!(limit.cost("eth_call"), 2);
+		assert_eq!(limit.cost("net_version"), 0);
+		assert_eq!(limit.cost("trace_filter"), 50);
+		assert_eq!(limit.cost("eth_blockNumber"), 1);
+		assert!(RateLimit::new(10, 100).with_costs("eth_call").is_err());
+		assert!(RateLimit::new(10, 100).with_costs("=5").is_err());
+	}
+
+	#[test]
+	fn should_refill_buckets_over_time() {
+		// given
+		let limiter = RateLimiter::new(RateLimit::new(10, 20));
+		let client = ClientId::Ip("127.0.0.1".parse().unwrap());
+		let other = ClientId::Token(H256::from(1));
+		let now = Instant::now();
+
+		// when
+		assert_eq!(limiter.acquire_at(&client, 15, now), Ok(()));
+		let wait = limiter.acquire_at(&client, 15, now);
+		let other_result = limiter.acquire_at(&other, 15, now);
+		let refilled = limiter.acquire_at(&client, 15, now + Duration::from_secs(1));
+
+		// then
+		assert_eq!(wait, Err(Duration::from_secs(1)));
+		assert_eq!(other_result, Ok(()));
+		assert_eq!(refilled, Ok(()));
+	}
+
+	#[test]
+	fn should_charge_full_cost_of_requests_over_the_burst() {
+		// given
+		let limiter = RateLimiter::new(RateLimit::new(10, 20));
+		let client = ClientId::Ip("127.0.0.1".parse().unwrap());
+		let now = Instant::now();
+
+		// when
+		assert_eq!(limiter.acquire_at(&client, 100, now), Ok(()));
+		let wait = limiter.acquire_at(&client, 10, now + Duration::from_secs(1));
+		let paid_back = limiter.acquire_at(&client, 10, now + Duration::from_secs(9));
+
+		// then
+		assert_eq!(wait, Err(Duration::from_secs(8)));
+		assert_eq!(paid_back, Ok(()));
+	}
+
+	#[test]
+	fn should_forget_least_recently_seen_clients() {
+		// given
+		let limiter = RateLimiter::new(RateLimit::new(0, 10));
+		let client = |n: usize| ClientId::Ip(format!("10.0.{}.{}", n / 256, n % 256).parse().unwrap());
+		let now = Instant::now();
+		assert_eq!(limiter.acquire_at(&client(0), 10, now), Ok(()));
+		assert_eq!(limiter.acquire_at(&client(1), 10, now), Ok(()));
+
+		// when
+		for n in 2..MAX_CLIENTS {
+			assert_eq!(limiter.acquire_at(&client(n), 1, now), Ok(()));
+		}
+		// client 1 is seen again, so client 0 is the first one forgotten.
+		assert!(limiter.acquire_at(&client(1), 10, now).is_err());
+		assert_eq!(limiter.acquire_at(&client(MAX_CLIENTS), 1, now), Ok(()));
+		let still_limited = limiter.acquire_at(&client(1), 10, now);
+
+		// then
+		assert!(still_limited.is_err());
+		assert_eq!(limiter.buckets.lock().len(), MAX_CLIENTS);
+		assert_eq!(limiter.acquire_at(&client(0), 10, now), Ok(()));
+	}
+
+	#[test]
+	fn should_reject_requests_over_the_limit() {
+		// given
+		let mut io = MetaIoHandler::with_middleware(RateLimitMiddleware::new(Some(RateLimit::new(1, 50))));
+		io.add_method("trace_filter", |_| Ok(Value::Array(vec![])));
+		let request = r#"{"jsonrpc":"2.0","method":"trace_filter","params":[],"id":1}"#;
+		let mut meta = Metadata::default();
+		meta.client = Some(ClientId::Session(1));
+
+		// when
+		let first = io.handle_request_sync(request, meta.clone());
+		let second = io.handle_request_sync(request, meta.clone());
+		let unidentified = io.handle_request_sync(request, Metadata::default());
+
+		// then
+		let response = r#"{"jsonrpc":"2.0","result":[],"id":1}"#.to_owned();
+		assert_eq!(first, Some(response.clone()));
+		assert!(second.unwrap().contains(r#""code":-32007"#));
+		assert_eq!(unidentified, Some(response));
+	}
+
+	#[test]
+	fn should_throttle_batches_by_their_total_cost() {
+		// given
+		let mut io = MetaIoHandler::with_middleware(RateLimitMiddleware::new(Some(RateLimit::new(1, 50))));
+		io.add_method("trace_filter", |_| Ok(Value::Array(vec![])));
+		io.add_method("eth_blockNumber", |_| Ok(Value::String("0x0".into())));
+		let call = r#"{"jsonrpc":"2.0","method":"trace_filter","params":[],"id":1}"#;
+		let batch = format!("[{}]", vec![call; 100].join(","));
+		let mut meta = Metadata::default();
+		meta.client = Some(ClientId::Ip("127.0.0.1".parse().unwrap()));
+
+		// when
+		let first = io.handle_request_sync(&batch, meta.clone());
+		let cheap = io.handle_request_sync(r#"{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":2}"#, meta.clone());
+		let second = io.handle_request_sync(&batch, meta);
+
+		// then
+		assert!(!first.unwrap().contains(r#""code":-32007"#));
+		assert!(cheap.unwrap().contains(r#""code":-32007"#));
+		assert!(second.unwrap().contains(r#""code":-32007"#));
+	}
+}
@@ -205,6 +205,35 @@ fn eth_get_balance() {
 	assert_eq!(tester.handler.handle_request_sync(req_new_acc).unwrap(), res_new_acc);
 }
 
+#[test]
+fn eth_get_proof() {
+	use hash::keccak;
+	use rustc_hex::FromHex;
+	use serde_json::{self, Value};
+
+	let chain = extract_chain!("BlockchainTests/bcWalletTest/wallet2outOf3txs");
+	let tester = EthTester::from_chain(&chain);
+	let req = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getProof",
+		"params": ["0xaaaf5374fce5edbc8e2a8697c15331677e6ebaaa", ["0x0"], "latest"],
+		"id": 1
+	}"#;
+
+	let res: Value = serde_json::from_str(&tester.handler.handle_request_sync(req).unwrap()).unwrap();
+	let account = &res["result"];
+	assert_eq!(account["address"], "0xaaaf5374fce5edbc8e2a8697c15331677e6ebaaa");
+	assert_eq!(account["balance"], "0x9");
+	assert_eq!(account["storageProof"].as_array().unwrap().len(), 1);
+	assert_eq!(account["storageProof"][0]["key"], "0x0");
+	assert_eq!(account["storageProof"][0]["value"], "0x0");
+
+	// the first node of the account proof is the state trie root
+	let root_node: Vec<u8> = account["accountProof"][0].as_str().unwrap()[2..].from_hex().unwrap();
+	let state_root = tester.client.block_header(BlockId::Latest).unwrap().state_root();
+	assert_eq!(keccak(&root_node), state_root);
+}
+
 #[test]
 fn eth_block_number() {
 	let chain = extract_chain!("BlockchainTests/bcGasPricerTest/RPC_API_Test");
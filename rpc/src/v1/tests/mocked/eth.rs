@@ -36,7 +36,7 @@ use transaction::{Transaction, Action};
 
 use jsonrpc_core::IoHandler;
 use v1::{Eth, EthClient, EthClientOptions, EthFilter, EthFilterClient, EthSigning, SigningUnsafeClient};
-use v1::helpers::nonce;
+use v1::helpers::{nonce, LogsLimits};
 use v1::helpers::dispatch::FullDispatcher;
 use v1::tests::helpers::{TestSyncProvider, Config, TestMinerService, TestSnapshotService};
 use v1::metadata::Metadata;
@@ -93,8 +93,12 @@ impl EthTester {
 		let hashrates = Arc::new(Mutex::new(HashMap::new()));
 		let external_miner = Arc::new(ExternalMiner::new(hashrates.clone()));
 		let gas_price_percentile = options.gas_price_percentile;
+		let logs_limits = LogsLimits {
+			max_block_range: options.logs_max_block_range,
+			max_results: options.logs_max_results,
+		};
 		let eth = EthClient::new(&client, &snapshot, &sync, &opt_ap, &miner, &external_miner, options).to_delegate();
-		let filter = EthFilterClient::new(client.clone(), miner.clone()).to_delegate();
+		let filter = EthFilterClient::new(client.clone(), miner.clone(), logs_limits).to_delegate();
 		let reservations = Arc::new(Mutex::new(nonce::Reservations::new()));
 
 		let dispatcher = FullDispatcher::new(client.clone(), miner.clone(), reservations, gas_price_percentile);
@@ -236,6 +240,105 @@ fn rpc_eth_logs() {
 	assert_eq!(tester.io.handle_request_sync(request3), Some(response3.to_owned()));
 }
 
+#[test]
+fn rpc_eth_logs_limits() {
+	let tester = EthTester::new_with_options(EthClientOptions::with(|options| {
+		options.logs_max_block_range = Some(10);
+		options.logs_max_results = Some(1);
+	}));
+	tester.client.set_logs(vec![LocalizedLogEntry {
+		block_number: 1,
+		block_hash: H256::default(),
+		entry: LogEntry {
+			address: Address::default(),
+			topics: vec![],
+			data: vec![1,2,3],
+		},
+		transaction_index: 0,
+		transaction_log_index: 0,
+		transaction_hash: H256::default(),
+		log_index: 0,
+	}, LocalizedLogEntry {
+		block_number: 1,
+		block_hash: H256::default(),
+		entry: LogEntry {
+			address: Address::default(),
+			topics: vec![],
+			data: vec![1,2,3],
+		},
+		transaction_index: 0,
+		transaction_log_index: 1,
+		transaction_hash: H256::default(),
+		log_index: 1,
+	}]);
+
+	let request1 = r#"{"jsonrpc": "2.0", "method": "eth_getLogs", "params": [{"fromBlock":"0x0","toBlock":"0x14"}], "id": 1}"#;
+	let request2 = r#"{"jsonrpc": "2.0", "method": "eth_getLogs", "params": [{}], "id": 1}"#;
+	let request3 = r#"{"jsonrpc": "2.0", "method": "eth_getLogs", "params": [{"cursor":{"blockNumber":"0x1","logIndex":"0x1"}}], "id": 1}"#;
+	let request4 = r#"{"jsonrpc": "2.0", "method": "eth_getLogs", "params": [{"limit":1}], "id": 1}"#;
+
+	let response1 = r#"{"jsonrpc":"2.0","error":{"code":-32005,"message":"Query exceeds the logs limits, continue from the cursor.","data":{"cursor":{"blockNumber":"0x1","logIndex":"0x1"},"logs":[{"address":"0x0000000000000000000000000000000000000000","blockHash":"0x0000000000000000000000000000000000000000000000000000000000000000","blockNumber":"0x1","data":"0x010203","logIndex":"0x0","topics":[],"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0","transactionLogIndex":"0x0","type":"mined"}]}},"id":1}"#;
+	let response3 = r#"{"jsonrpc":"2.0","result":[{"address":"0x0000000000000000000000000000000000000000","blockHash":"0x0000000000000000000000000000000000000000000000000000000000000000","blockNumber":"0x1","data":"0x010203","logIndex":"0x1","topics":[],"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0","transactionLogIndex":"0x1","type":"mined"}],"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request1), Some(response1.to_owned()));
+	assert_eq!(tester.io.handle_request_sync(request2), Some(response1.to_owned()));
+	assert_eq!(tester.io.handle_request_sync(request3), Some(response3.to_owned()));
+	assert_eq!(tester.io.handle_request_sync(request4), Some(response3.to_owned()));
+}
+
+#[test]
+fn rpc_eth_logs_pages_cover_all_logs() {
+	use serde_json::{self, Value};
+
+	let tester = EthTester::new_with_options(EthClientOptions::with(|options| {
+		options.logs_max_block_range = Some(2);
+		options.logs_max_results = Some(2);
+	}));
+	let positions = vec![(1, 0), (1, 1), (2, 0), (4, 0), (4, 1), (5, 0)];
+	tester.client.set_logs(positions.iter().map(|&(block_number, log_index)| LocalizedLogEntry {
+		block_number: block_number,
+		block_hash: H256::default(),
+		entry: LogEntry {
+			address: Address::default(),
+			topics: vec![],
+			data: vec![],
+		},
+		transaction_index: 0,
+		transaction_log_index: log_index,
+		transaction_hash: H256::default(),
+		log_index: log_index,
+	}).collect());
+
+	let position = |log: &Value| (
+		u64::from_str_radix(&log["blockNumber"].as_str().unwrap()[2..], 16).unwrap(),
+		usize::from_str_radix(&log["logIndex"].as_str().unwrap()[2..], 16).unwrap(),
+	);
+
+	let mut delivered = Vec::new();
+	let mut cursor: Option<Value> = None;
+	loop {
+		let filter = match cursor {
+			Some(ref cursor) => format!(r#"{{"toBlock":"0x6","cursor":{}}}"#, cursor),
+			None => r#"{"fromBlock":"0x0","toBlock":"0x6"}"#.to_owned(),
+		};
+		let request = format!(r#"{{"jsonrpc": "2.0", "method": "eth_getLogs", "params": [{}], "id": 1}}"#, filter);
+		let response: Value = serde_json::from_str(&tester.io.handle_request_sync(&request).unwrap()).unwrap();
+
+		match response.get("error") {
+			Some(error) => {
+				delivered.extend(error["data"]["logs"].as_array().unwrap().iter().map(&position));
+				cursor = Some(error["data"]["cursor"].clone());
+			},
+			None => {
+				delivered.extend(response["result"].as_array().unwrap().iter().map(&position));
+				break;
+			},
+		}
+	}
+
+	assert_eq!(delivered, positions);
+}
+
 #[test]
 fn rpc_logs_filter() {
 	let tester = EthTester::default();
@@ -546,6 +649,22 @@ fn rpc_eth_transaction_count_by_number() {
 	assert_eq!(EthTester::default().io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_transaction_count_by_timestamp() {
+	let tester = EthTester::default();
+	tester.client.add_blocks(2, EachBlockWith::Transaction);
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getBlockTransactionCountByNumber",
+		"params": [{"timestamp": "0x0"}],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x1","id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_eth_transaction_count_by_number_pending() {
 	let request = r#"{
@@ -698,7 +817,7 @@ fn rpc_eth_call() {
 }
 
 #[test]
-fn rpc_eth_call_default_block() {
+fn rpc_eth_call_with_state_override() {
 	let tester = EthTester::default();
 	tester.client.set_execution_result(Ok(Executed {
 		exception: None,
@@ -724,6 +843,13 @@ fn rpc_eth_call_default_block() {
 			"gasPrice": "0x9184e72a000",
 			"value": "0x9184e72a",
 			"data": "0xd46e8dd67c5d32be8d46e8dd67c5d32be8058bb8eb970870f072445675058bb8eb970870f072445675"
+		},
+		"latest",
+		{
+			"0xd46e8dd67c5d32be8058bb8eb970870f07244567": {
+				"code": "0x6000",
+				"stateDiff": { "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002" }
+			}
 		}],
 		"id": 1
 	}"#;
@@ -769,7 +895,7 @@ fn rpc_eth_estimate_gas() {
 }
 
 #[test]
-fn rpc_eth_estimate_gas_default_block() {
+fn rpc_eth_estimate_gas_with_state_override() {
 	let tester = EthTester::default();
 	tester.client.set_execution_result(Ok(Executed {
 		exception: None,
@@ -795,6 +921,13 @@ fn rpc_eth_estimate_gas_default_block() {
 			"gasPrice": "0x9184e72a000",
 			"value": "0x9184e72a",
 			"data": "0xd46e8dd67c5d32be8d46e8dd67c5d32be8058bb8eb970870f072445675058bb8eb970870f072445675"
+		},
+		"latest",
+		{
+			"0xd46e8dd67c5d32be8058bb8eb970870f07244567": {
+				"code": "0x6000",
+				"stateDiff": { "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002" }
+			}
 		}],
 		"id": 1
 	}"#;
@@ -151,6 +151,79 @@ fn should_subscribe_to_logs() {
 }
 
 
+#[test]
+fn should_subscribe_to_traces() {
+	use ethcore::trace::LocalizedTrace;
+	use ethcore::trace::trace::{Action, Res, Call};
+	use vm::CallType;
+
+	// given
+	let el = EventLoop::spawn();
+	let mut client = TestBlockChainClient::new();
+	client.add_blocks(1, EachBlockWith::Nothing);
+	let h1 = client.block_hash_delta_minus(1);
+	*client.traces.write() = Some(vec![LocalizedTrace {
+		action: Action::Call(Call {
+			from: 0xf.into(),
+			to: 0x10.into(),
+			value: 0x1.into(),
+			gas: 0x100.into(),
+			input: vec![1, 2, 3],
+			call_type: CallType::Call,
+		}),
+		result: Res::None,
+		subtraces: 0,
+		trace_address: vec![0],
+		transaction_number: Some(0),
+		transaction_hash: Some(5.into()),
+		block_number: 1,
+		block_hash: h1,
+	}]);
+
+	let pubsub = EthPubSubClient::new_test(Arc::new(client), el.remote());
+	let handler = pubsub.handler().upgrade().unwrap();
+	let pubsub = pubsub.to_delegate();
+
+	let mut io = MetaIoHandler::default();
+	io.extend_with(pubsub);
+
+	let mut metadata = Metadata::default();
+	let (sender, receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	// Fail if a logs filter is provided
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["traces", {"address": "0x0000000000000000000000000000000000000005"}], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: traces","data":"\"Expected a trace filter object.\""},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
+
+	// Fail if a block range is provided
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["traces", {"fromBlock": "0x1", "toAddress": ["0x0000000000000000000000000000000000000010"]}], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: traces","data":"\"fromBlock and toBlock are not supported in subscriptions.\""},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["traces", {"toBlock": "latest"}], "id": 1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
+
+	// Subscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["traces", {"toAddress": ["0x0000000000000000000000000000000000000010"]}], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x416d77337e24399d","id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
+
+	// Check notifications
+	handler.new_blocks(vec![], vec![], vec![h1], vec![], vec![], vec![], 0);
+	let (res, receiver) = receiver.into_future().wait().unwrap();
+	let response = r#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"result":{"action":{"callType":"call","from":"0x000000000000000000000000000000000000000f","gas":"0x100","input":"0x010203","to":"0x0000000000000000000000000000000000000010","value":"0x1"},"blockHash":"0x3457d2fa2e3dd33c78ac681cf542e429becf718859053448748383af67e23218","blockNumber":1,"result":null,"subtraces":0,"traceAddress":[0],"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000005","transactionPosition":0,"type":"call"},"subscription":"0x416d77337e24399d"}}"#;
+	assert_eq!(res, Some(response.into()));
+
+	// And unsubscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_unsubscribe", "params": ["0x416d77337e24399d"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata), Some(response.to_owned()));
+
+	let (res, _receiver) = receiver.into_future().wait().unwrap();
+	assert_eq!(res, None);
+}
+
 #[test]
 fn should_subscribe_to_pending_transactions() {
 	// given
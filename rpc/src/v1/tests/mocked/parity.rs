@@ -16,7 +16,7 @@
 
 use std::sync::Arc;
 use ethcore::account_provider::AccountProvider;
-use ethcore::client::{TestBlockChainClient, Executed};
+use ethcore::client::{TestBlockChainClient, Executed, EachBlockWith};
 use ethcore::miner::LocalTransactionStatus;
 use ethcore_logger::RotatingLogger;
 use ethstore::ethkey::{Generator, Random};
@@ -246,6 +246,85 @@ fn rpc_parity_default_extra_data() {
 	assert_eq!(io.handle_request_sync(request), Some(response));
 }
 
+#[test]
+fn rpc_parity_fee_history() {
+	let deps = Dependencies::new();
+	deps.client.add_blocks(2, EachBlockWith::Transaction);
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_feeHistory", "params": [2, [50]], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"oldestBlock":"0x1","gasPrices":[["0x2e90edd000"],["0x2e90edd000"]],"gasUsedRatio":[0.0,0.0],"recommended":{"slow":"0x2e90edd000","standard":"0x2e90edd000","fast":"0x2e90edd000"}},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_feeHistory", "params": [2, [50, 10]], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: percentiles","data":"\"Expected ascending percentiles between 0 and 100.\""},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_list_transactions() {
+	use ethcore::client::{BlockChainClient, BlockId};
+
+	let deps = Dependencies::new();
+	deps.client.add_blocks(3, EachBlockWith::Transaction);
+	let io = deps.default_client();
+
+	let mut tx = deps.client.block(BlockId::Number(2)).unwrap().view().localized_transactions().remove(0);
+	let sender = tx.sender();
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_listTransactions", "params": ["0x{:?}", "0x0", "latest", 10], "id": 1}}"#, sender);
+	let response = io.handle_request_sync(&request).unwrap();
+	assert!(response.contains(&format!(r#""hash":"0x{:?}""#, tx.hash())));
+	assert!(response.contains(r#""blockNumber":"0x2""#));
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_listTransactions", "params": ["0x{:?}", "0x0", "0x1", 10], "id": 1}}"#, sender);
+	let response = r#"{"jsonrpc":"2.0","result":[],"id":1}"#;
+	assert_eq!(io.handle_request_sync(&request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_listTransactions", "params": ["0x0000000000000000000000000000000000000005", "earliest", "latest", 10], "id": 1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_get_balances() {
+	let deps = Dependencies::new();
+	deps.client.set_balance(Address::from(1), U256::from(5));
+	deps.client.set_balance(Address::from(2), U256::from(7));
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_getBalances", "params": [["0x0000000000000000000000000000000000000002", "0x0000000000000000000000000000000000000001", "0x0000000000000000000000000000000000000003"]], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[{"result":"0x7"},{"result":"0x5"},{"result":"0x0"}],"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_getBalances", "params": [["0x0000000000000000000000000000000000000001"], "0x10"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Unknown block number"},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_get_storage_batch() {
+	let deps = Dependencies::new();
+	deps.client.set_storage(Address::from(1), H256::from(4), H256::from(9));
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_getStorageBatch", "params": [[["0x0000000000000000000000000000000000000001", "0x4"], ["0x0000000000000000000000000000000000000001", "0x5"]], "latest"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[{"result":"0x0000000000000000000000000000000000000000000000000000000000000009"},{"result":"0x0000000000000000000000000000000000000000000000000000000000000000"}],"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_token_index_disabled() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_tokenBalances", "params": ["0x0000000000000000000000000000000000000005"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_tokenHolders", "params": ["0x0000000000000000000000000000000000000005", 10], "id": 1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_gas_floor_target() {
 	let deps = Dependencies::new();
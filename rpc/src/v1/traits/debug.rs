@@ -0,0 +1,31 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! Debug rpc interface.
+
+use jsonrpc_core::Result;
+use jsonrpc_macros::Trailing;
+use v1::types::{H256, StructLoggerOptions, ExecutionTrace};
+
+build_rpc_trait! {
+	/// Debug rpc interface.
+	pub trait Debug {
+		/// Replays the transaction with given hash and returns geth-compatible struct logs of its execution.
+		#[rpc(name = "debug_traceTransaction")]
+		fn trace_transaction(&self, H256, Trailing<StructLoggerOptions>) -> Result<ExecutionTrace>;
+	}
+}
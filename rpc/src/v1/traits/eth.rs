@@ -18,8 +18,8 @@
 use jsonrpc_core::{Result, BoxFuture};
 use jsonrpc_macros::Trailing;
 
-use v1::types::{RichBlock, BlockNumber, Bytes, CallRequest, Filter, FilterChanges, Index};
-use v1::types::{Log, Receipt, SyncStatus, Transaction, Work};
+use v1::types::{RichBlock, BlockNumber, Bytes, CallRequest, EthAccount, Filter, FilterChanges, Index};
+use v1::types::{Log, Receipt, StateOverride, SyncStatus, Transaction, Work};
 use v1::types::{H64, H160, H256, U256};
 
 build_rpc_trait! {
@@ -67,6 +67,10 @@ build_rpc_trait! {
 		#[rpc(name = "eth_getStorageAt")]
 		fn storage_at(&self, H160, U256, Trailing<BlockNumber>) -> BoxFuture<H256>;
 
+		/// Returns the account and storage values of the given account with Merkle proofs.
+		#[rpc(name = "eth_getProof")]
+		fn proof(&self, H160, Vec<U256>, Trailing<BlockNumber>) -> BoxFuture<EthAccount>;
+
 		/// Returns block with given hash.
 		#[rpc(name = "eth_getBlockByHash")]
 		fn block_by_hash(&self, H256, bool) -> BoxFuture<Option<RichBlock>>;
@@ -107,13 +111,13 @@ build_rpc_trait! {
 		#[rpc(name = "eth_submitTransaction")]
 		fn submit_transaction(&self, Bytes) -> Result<H256>;
 
-		/// Call contract, returning the output data.
+		/// Call contract on top of the state at given block with optional state overrides, returning the output data.
 		#[rpc(meta, name = "eth_call")]
-		fn call(&self, Self::Metadata, CallRequest, Trailing<BlockNumber>) -> BoxFuture<Bytes>;
+		fn call(&self, Self::Metadata, CallRequest, BlockNumber, Trailing<StateOverride>) -> BoxFuture<Bytes>;
 
-		/// Estimate gas needed for execution of given contract.
+		/// Estimate gas needed for execution of given contract with optional state overrides.
 		#[rpc(meta, name = "eth_estimateGas")]
-		fn estimate_gas(&self, Self::Metadata, CallRequest, Trailing<BlockNumber>) -> BoxFuture<U256>;
+		fn estimate_gas(&self, Self::Metadata, CallRequest, BlockNumber, Trailing<StateOverride>) -> BoxFuture<U256>;
 
 		/// Get transaction by its hash.
 		#[rpc(name = "eth_getTransactionByHash")]
@@ -23,8 +23,8 @@ use jsonrpc_macros::Trailing;
 
 use node_health::Health;
 use v1::types::{
-	H160, H256, H512, U256, U64, Bytes, CallRequest,
-	Peers, Transaction, RpcSettings, Histogram,
+	H160, H256, H512, U256, U64, Bytes, CallRequest, StateOverride,
+	Peers, Transaction, RpcSettings, Histogram, FeeHistory, LookupResult, TokenBalance,
 	TransactionStats, LocalTransactionStatus,
 	BlockNumber, ConsensusCapability, VersionInfo,
 	OperationsInfo, DappId, ChainStatus,
@@ -108,6 +108,11 @@ build_rpc_trait! {
 		#[rpc(name = "parity_gasPriceHistogram")]
 		fn gas_price_histogram(&self) -> BoxFuture<Histogram>;
 
+		/// Returns gas price percentiles and gas used ratios of given number of latest blocks,
+		/// along with recommended gas prices.
+		#[rpc(name = "parity_feeHistory")]
+		fn fee_history(&self, u64, Trailing<Vec<f64>>) -> Result<FeeHistory>;
+
 		/// Returns number of unsigned transactions waiting in the signer queue (if signer enabled)
 		/// Returns error when signer is disabled
 		#[rpc(name = "parity_unsignedTransactionsCount")]
@@ -134,6 +139,33 @@ build_rpc_trait! {
 		#[rpc(name = "parity_listStorageKeys")]
 		fn list_storage_keys(&self, H160, u64, Option<H256>, Trailing<BlockNumber>) -> Result<Option<Vec<H256>>>;
 
+		/// Returns up to `limit` (last parameter, at most 1000) transactions sent from or to the given
+		/// address between the given blocks (inclusive), oldest first, if the account transaction index
+		/// is enabled (`--account-tx-index`), or null if not. Contract creations are listed for their
+		/// sender only, not for the created contract.
+		#[rpc(name = "parity_listTransactions")]
+		fn list_transactions(&self, H160, BlockNumber, BlockNumber, u64) -> Result<Option<Vec<Transaction>>>;
+
+		/// Returns balances of the given addresses at the given block, in order.
+		/// Each entry is either `{"result": balance}` or `{"error": reason}`.
+		#[rpc(name = "parity_getBalances")]
+		fn balances(&self, Vec<H160>, Trailing<BlockNumber>) -> Result<Vec<LookupResult<U256>>>;
+
+		/// Returns storage values at the given `[address, position]` pairs at the given block, in order.
+		/// Each entry is either `{"result": value}` or `{"error": reason}`.
+		#[rpc(name = "parity_getStorageBatch")]
+		fn storage_batch(&self, Vec<(H160, U256)>, Trailing<BlockNumber>) -> Result<Vec<LookupResult<H256>>>;
+
+		/// Returns ERC-20 tokens held by the given address with their balances, if the token
+		/// index is enabled (`--token-index`), or null if not.
+		#[rpc(name = "parity_tokenBalances")]
+		fn token_balances(&self, H160) -> Result<Option<Vec<TokenBalance>>>;
+
+		/// Returns up to `limit` (last parameter) holders of the given ERC-20 token with the highest
+		/// balances, if the token index is enabled (`--token-index`), or null if not.
+		#[rpc(name = "parity_tokenHolders")]
+		fn token_holders(&self, H160, u64) -> Result<Option<Vec<TokenBalance>>>;
+
 		/// Encrypt some data with a public key under ECIES.
 		/// First parameter is the 512-byte destination public key, second is the message.
 		#[rpc(name = "parity_encryptMessage")]
@@ -214,9 +246,9 @@ build_rpc_trait! {
 		#[rpc(name = "parity_cidV0")]
 		fn ipfs_cid(&self, Bytes) -> Result<String>;
 
-		/// Call contract, returning the output data.
+		/// Call contracts one after another with optional state overrides, returning the output data.
 		#[rpc(meta, name = "parity_call")]
-		fn call(&self, Self::Metadata, Vec<CallRequest>, Trailing<BlockNumber>) -> Result<Vec<Bytes>>;
+		fn call(&self, Self::Metadata, Vec<CallRequest>, BlockNumber, Trailing<StateOverride>) -> Result<Vec<Bytes>>;
 
 		/// Returns node's health report.
 		#[rpc(name = "parity_nodeHealth")]
@@ -18,7 +18,7 @@
 
 use jsonrpc_core::Result;
 use jsonrpc_macros::Trailing;
-use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, H256, TraceOptions};
+use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, H256, TraceOptions, StateOverride};
 
 build_rpc_trait! {
 	/// Traces specific rpc interface.
@@ -43,7 +43,7 @@ build_rpc_trait! {
 
 		/// Executes the given call and returns a number of possible traces for it.
 		#[rpc(meta, name = "trace_call")]
-		fn call(&self, Self::Metadata, CallRequest, TraceOptions, Trailing<BlockNumber>) -> Result<TraceResults>;
+		fn call(&self, Self::Metadata, CallRequest, TraceOptions, BlockNumber, Trailing<StateOverride>) -> Result<TraceResults>;
 
 		/// Executes all given calls and returns a number of possible traces for each of it.
 		#[rpc(meta, name = "trace_callMany")]
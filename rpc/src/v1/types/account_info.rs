@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
+use v1::types::{Bytes, H160, H256, U256};
+
 /// Account information.
 #[derive(Debug, Default, Clone, PartialEq, Serialize)]
 pub struct AccountInfo {
@@ -42,3 +44,37 @@ pub struct HwAccountInfo {
 	pub manufacturer: String,
 }
 
+
+/// Account state with Merkle proofs (used by `eth_getProof`).
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct EthAccount {
+	/// Account address
+	pub address: H160,
+	/// Trie nodes proving the account, in order from the state root
+	#[serde(rename="accountProof")]
+	pub account_proof: Vec<Bytes>,
+	/// Account balance
+	pub balance: U256,
+	/// Account nonce
+	pub nonce: U256,
+	/// Hash of the account code
+	#[serde(rename="codeHash")]
+	pub code_hash: H256,
+	/// Root of the account storage trie
+	#[serde(rename="storageHash")]
+	pub storage_hash: H256,
+	/// Proofs for the requested storage keys
+	#[serde(rename="storageProof")]
+	pub storage_proof: Vec<StorageProof>,
+}
+
+/// Storage value with a Merkle proof (used by `eth_getProof`).
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct StorageProof {
+	/// Storage key
+	pub key: U256,
+	/// Storage value
+	pub value: U256,
+	/// Trie nodes proving the value, in order from the storage root
+	pub proof: Vec<Bytes>,
+}
@@ -16,8 +16,10 @@
 
 use std::fmt;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde::de::{Error, Visitor};
+use serde::de::{Error, Visitor, MapAccess};
+use serde::ser::SerializeMap;
 use ethcore::client::BlockId;
+use v1::types::U64;
 
 /// Represents rpc api block number param.
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
@@ -30,6 +32,8 @@ pub enum BlockNumber {
 	Earliest,
 	/// Pending block (being mined)
 	Pending,
+	/// Last block mined at or before the given unix timestamp
+	Timestamp(u64),
 }
 
 impl Default for BlockNumber {
@@ -61,6 +65,11 @@ impl Serialize for BlockNumber {
 			BlockNumber::Latest => serializer.serialize_str("latest"),
 			BlockNumber::Earliest => serializer.serialize_str("earliest"),
 			BlockNumber::Pending => serializer.serialize_str("pending"),
+			BlockNumber::Timestamp(ref x) => {
+				let mut map = serializer.serialize_map(Some(1))?;
+				map.serialize_entry("timestamp", &format!("0x{:x}", x))?;
+				map.end()
+			},
 		}
 	}
 }
@@ -71,7 +80,7 @@ impl<'a> Visitor<'a> for BlockNumberVisitor {
 	type Value = BlockNumber;
 
 	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-		write!(formatter, "a block number or 'latest', 'earliest' or 'pending'")
+		write!(formatter, "a block number, 'latest', 'earliest', 'pending' or a {{\"timestamp\": ...}} object")
 	}
 
 	fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> where E: Error {
@@ -89,6 +98,20 @@ impl<'a> Visitor<'a> for BlockNumberVisitor {
 	fn visit_string<E>(self, value: String) -> Result<Self::Value, E> where E: Error {
 		self.visit_str(value.as_ref())
 	}
+
+	fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error> where V: MapAccess<'a> {
+		let timestamp = match visitor.next_key::<String>()? {
+			Some(ref key) if key == "timestamp" => visitor.next_value::<U64>()?,
+			Some(key) => return Err(Error::custom(format!("Invalid block number: unknown field {}", key))),
+			None => return Err(Error::custom("Invalid block number: missing timestamp")),
+		};
+
+		if visitor.next_key::<String>()?.is_some() {
+			return Err(Error::custom("Invalid block number: only timestamp is allowed"));
+		}
+
+		Ok(BlockNumber::Timestamp(timestamp.into()))
+	}
 }
 
 impl Into<BlockId> for BlockNumber {
@@ -98,6 +121,7 @@ impl Into<BlockId> for BlockNumber {
 			BlockNumber::Earliest => BlockId::Earliest,
 			BlockNumber::Latest => BlockId::Latest,
 			BlockNumber::Pending => BlockId::Pending,
+			BlockNumber::Timestamp(t) => BlockId::Timestamp(t),
 		}
 	}
 }
@@ -110,9 +134,22 @@ mod tests {
 
 	#[test]
 	fn block_number_deserialization() {
-		let s = r#"["0xa", "latest", "earliest", "pending"]"#;
+		let s = r#"["0xa", "latest", "earliest", "pending", {"timestamp": "0x5b38d200"}]"#;
 		let deserialized: Vec<BlockNumber> = serde_json::from_str(s).unwrap();
-		assert_eq!(deserialized, vec![BlockNumber::Num(10), BlockNumber::Latest, BlockNumber::Earliest, BlockNumber::Pending])
+		assert_eq!(deserialized, vec![BlockNumber::Num(10), BlockNumber::Latest, BlockNumber::Earliest, BlockNumber::Pending, BlockNumber::Timestamp(0x5b38d200)])
+	}
+
+	#[test]
+	fn timestamp_serialization() {
+		let serialized = serde_json::to_string(&BlockNumber::Timestamp(0x5b38d200)).unwrap();
+		assert_eq!(serialized, r#"{"timestamp":"0x5b38d200"}"#);
+	}
+
+	#[test]
+	fn should_not_deserialize_unknown_object() {
+		assert!(serde_json::from_str::<BlockNumber>(r#"{"number": "0x1"}"#).is_err());
+		assert!(serde_json::from_str::<BlockNumber>(r#"{"timestamp": "0x1", "number": "0x1"}"#).is_err());
+		assert!(serde_json::from_str::<BlockNumber>(r#"{}"#).is_err());
 	}
 
 	#[test]
@@ -127,6 +164,7 @@ mod tests {
 		assert_eq!(BlockId::Earliest, BlockNumber::Earliest.into());
 		assert_eq!(BlockId::Latest, BlockNumber::Latest.into());
 		assert_eq!(BlockId::Pending, BlockNumber::Pending.into());
+		assert_eq!(BlockId::Timestamp(100), BlockNumber::Timestamp(100).into());
 	}
 }
 
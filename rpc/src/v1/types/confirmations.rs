@@ -21,7 +21,7 @@ use serde::{Serialize, Serializer};
 use ansi_term::Colour;
 use bytes::ToPretty;
 
-use v1::types::{U256, TransactionRequest, RichRawTransaction, H160, H256, H520, Bytes, TransactionCondition, Origin};
+use v1::types::{U256, TransactionRequest, RichRawTransaction, H160, H256, H520, Bytes, TransactionCondition, Origin, EIP712};
 use v1::helpers;
 
 /// Confirmation waiting in a queue
@@ -58,6 +58,7 @@ impl fmt::Display for ConfirmationPayload {
 			ConfirmationPayload::SendTransaction(ref transaction) => write!(f, "{}", transaction),
 			ConfirmationPayload::SignTransaction(ref transaction) => write!(f, "(Sign only) {}", transaction),
 			ConfirmationPayload::EthSignMessage(ref sign) => write!(f, "{}", sign),
+			ConfirmationPayload::EIP712SignMessage(ref sign) => write!(f, "{}", sign),
 			ConfirmationPayload::Decrypt(ref decrypt) => write!(f, "{}", decrypt),
 		}
 	}
@@ -93,6 +94,37 @@ impl fmt::Display for SignRequest {
 	}
 }
 
+/// EIP-712 typed data sign request
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EIP712SignRequest {
+	/// Address
+	pub address: H160,
+	/// Typed data to sign
+	pub data: EIP712,
+}
+
+impl From<(H160, EIP712)> for EIP712SignRequest {
+	fn from(tuple: (H160, EIP712)) -> Self {
+		EIP712SignRequest {
+			address: tuple.0,
+			data: tuple.1,
+		}
+	}
+}
+
+impl fmt::Display for EIP712SignRequest {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"sign typed data {} {} with {}",
+			self.data.primary_type,
+			self.data.message,
+			Colour::White.bold().paint(format!("0x{:?}", self.address)),
+		)
+	}
+}
+
 /// Decrypt request
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -170,6 +202,9 @@ pub enum ConfirmationPayload {
 	/// Signature
 	#[serde(rename="sign")]
 	EthSignMessage(SignRequest),
+	/// Typed data signature
+	#[serde(rename="signTypedData")]
+	EIP712SignMessage(EIP712SignRequest),
 	/// Decryption
 	#[serde(rename="decrypt")]
 	Decrypt(DecryptRequest),
@@ -184,6 +219,10 @@ impl From<helpers::ConfirmationPayload> for ConfirmationPayload {
 				address: address.into(),
 				data: data.into(),
 			}),
+			helpers::ConfirmationPayload::EIP712SignMessage(address, data) => ConfirmationPayload::EIP712SignMessage(EIP712SignRequest {
+				address: address.into(),
+				data: data,
+			}),
 			helpers::ConfirmationPayload::Decrypt(address, msg) => ConfirmationPayload::Decrypt(DecryptRequest {
 				address: address.into(),
 				msg: msg.into(),
@@ -267,6 +306,29 @@ mod tests {
 		assert_eq!(res.unwrap(), expected.to_owned());
 	}
 
+	#[test]
+	fn should_serialize_typed_data_sign_confirmation() {
+		// given
+		let data = serde_json::from_str(r#"{
+			"primaryType": "Mail",
+			"domain": {},
+			"message": { "contents": "Hello" },
+			"types": { "EIP712Domain": [], "Mail": [{ "name": "contents", "type": "string" }] }
+		}"#).unwrap();
+		let request = helpers::ConfirmationRequest {
+			id: 15.into(),
+			payload: helpers::ConfirmationPayload::EIP712SignMessage(1.into(), data),
+			origin: Origin::Rpc("test service".into()),
+		};
+
+		// when
+		let res = serde_json::to_string(&ConfirmationRequest::from(request));
+		let expected = r#"{"id":"0xf","payload":{"signTypedData":{"address":"0x0000000000000000000000000000000000000001","data":{"types":{"EIP712Domain":[],"Mail":[{"name":"contents","type":"string"}]},"primaryType":"Mail","domain":{},"message":{"contents":"Hello"}}}},"origin":{"rpc":"test service"}}"#;
+
+		// then
+		assert_eq!(res.unwrap(), expected.to_owned());
+	}
+
 	#[test]
 	fn should_serialize_transaction_confirmation() {
 		// given
@@ -0,0 +1,152 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! Debug API types.
+
+use std::collections::BTreeMap;
+use ethcore::client::Executed;
+use ethcore::trace::{FlatTrace, StructLog as EthStructLog, StructLoggerOptions as EthStructLoggerOptions};
+use ethereum_types::H256;
+use rustc_hex::ToHex;
+
+/// Struct logger options of `debug_traceTransaction`.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+pub struct StructLoggerOptions {
+	/// Do not capture the stack.
+	#[serde(rename="disableStack", default)]
+	pub disable_stack: bool,
+	/// Do not capture the memory.
+	#[serde(rename="disableMemory", default)]
+	pub disable_memory: bool,
+	/// Do not capture the storage.
+	#[serde(rename="disableStorage", default)]
+	pub disable_storage: bool,
+}
+
+impl Into<EthStructLoggerOptions> for StructLoggerOptions {
+	fn into(self) -> EthStructLoggerOptions {
+		EthStructLoggerOptions {
+			disable_stack: self.disable_stack,
+			disable_memory: self.disable_memory,
+			disable_storage: self.disable_storage,
+		}
+	}
+}
+
+/// Geth-compatible log of a single executed instruction.
+///
+/// Words are serialized as unprefixed, zero-padded hex, like geth does.
+#[derive(Debug, Serialize)]
+pub struct StructLog {
+	/// Program counter.
+	pub pc: usize,
+	/// Instruction mnemonic.
+	pub op: &'static str,
+	/// Gas available before the instruction.
+	pub gas: u64,
+	/// Gas cost of the instruction.
+	#[serde(rename="gasCost")]
+	pub gas_cost: u64,
+	/// Call depth.
+	pub depth: usize,
+	/// Stack, bottom first.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<String>>,
+	/// Memory split into 32-byte words.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<String>>,
+	/// Storage of the executing account.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<String, String>>,
+}
+
+impl From<EthStructLog> for StructLog {
+	fn from(l: EthStructLog) -> Self {
+		StructLog {
+			pc: l.pc,
+			op: l.op_name(),
+			gas: l.gas.low_u64(),
+			gas_cost: l.gas_cost.low_u64(),
+			depth: l.depth,
+			stack: l.stack.map(|stack| stack.into_iter().map(|v| H256::from(v).to_hex()).collect()),
+			memory: l.memory.map(|memory| memory.chunks(32).map(|word| word.to_hex()).collect()),
+			storage: l.storage.map(|storage| storage.into_iter().map(|(k, v)| (k.to_hex(), v.to_hex())).collect()),
+		}
+	}
+}
+
+/// Result of `debug_traceTransaction`.
+#[derive(Debug, Serialize)]
+pub struct ExecutionTrace {
+	/// Gas used by the transaction.
+	pub gas: u64,
+	/// Whether the transaction failed.
+	pub failed: bool,
+	/// Output of the transaction.
+	#[serde(rename="returnValue")]
+	pub return_value: String,
+	/// Logs of all executed instructions.
+	#[serde(rename="structLogs")]
+	pub struct_logs: Vec<StructLog>,
+}
+
+impl From<Executed<FlatTrace, Vec<EthStructLog>>> for ExecutionTrace {
+	fn from(t: Executed<FlatTrace, Vec<EthStructLog>>) -> Self {
+		ExecutionTrace {
+			gas: t.gas_used.low_u64(),
+			failed: t.exception.is_some(),
+			return_value: t.output.to_hex(),
+			struct_logs: t.vm_trace.unwrap_or_default().into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use std::collections::BTreeMap;
+	use super::*;
+
+	#[test]
+	fn should_deserialize_struct_logger_options() {
+		let s = r#"{"disableStack":true,"disableStorage":true}"#;
+		let deserialized: StructLoggerOptions = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, StructLoggerOptions {
+			disable_stack: true,
+			disable_memory: false,
+			disable_storage: true,
+		});
+	}
+
+	#[test]
+	fn should_serialize_struct_log() {
+		let mut storage = BTreeMap::new();
+		storage.insert(H256::from(0), H256::from(42));
+		let log = StructLog::from(EthStructLog {
+			pc: 4,
+			instruction: 0x55,
+			gas: 99_994.into(),
+			gas_cost: 20_000.into(),
+			depth: 1,
+			stack: Some(vec![42.into()]),
+			memory: None,
+			storage: Some(storage),
+		});
+		let serialized = serde_json::to_string(&log).unwrap();
+		assert_eq!(serialized, r#"{"pc":4,"op":"SSTORE","gas":99994,"gasCost":20000,"depth":1,"stack":["000000000000000000000000000000000000000000000000000000000000002a"],"storage":{"0000000000000000000000000000000000000000000000000000000000000000":"000000000000000000000000000000000000000000000000000000000000002a"}}"#);
+	}
+}
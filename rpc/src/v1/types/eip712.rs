@@ -0,0 +1,92 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! EIP-712 typed structured data.
+
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use serde_json::Value;
+
+/// A single member of a struct type definition.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EIP712Field {
+	/// Member name
+	pub name: String,
+	/// Member type (e.g. `uint256`, `address[]` or another struct type)
+	#[serde(rename="type")]
+	pub field_type: String,
+}
+
+/// Typed structured data to sign (as described in EIP-712).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EIP712 {
+	/// Struct type definitions, `EIP712Domain` has to be present.
+	pub types: BTreeMap<String, Vec<EIP712Field>>,
+	/// Type of the message
+	#[serde(rename="primaryType")]
+	pub primary_type: String,
+	/// Domain separator values
+	pub domain: Value,
+	/// Message to sign
+	pub message: Value,
+}
+
+// `Value` does not implement `Eq` nor `Hash`, but typed data never contains floats,
+// so comparing and hashing the serialized form is fine.
+impl Eq for EIP712 {}
+
+impl Hash for EIP712 {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.types.hash(state);
+		self.primary_type.hash(state);
+		self.domain.to_string().hash(state);
+		self.message.to_string().hash(state);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::{self, Value};
+	use super::{EIP712, EIP712Field};
+
+	#[test]
+	fn should_deserialize_typed_data() {
+		let s = r#"{
+			"primaryType": "Mail",
+			"domain": { "name": "Ether Mail", "version": "1", "chainId": 1 },
+			"message": { "contents": "Hello, Bob!" },
+			"types": {
+				"EIP712Domain": [
+					{ "name": "name", "type": "string" },
+					{ "name": "version", "type": "string" },
+					{ "name": "chainId", "type": "uint256" }
+				],
+				"Mail": [
+					{ "name": "contents", "type": "string" }
+				]
+			}
+		}"#;
+
+		let deserialized: EIP712 = serde_json::from_str(s).unwrap();
+
+		assert_eq!(deserialized.primary_type, "Mail");
+		assert_eq!(deserialized.types["Mail"], vec![EIP712Field { name: "contents".into(), field_type: "string".into() }]);
+		assert_eq!(deserialized.types["EIP712Domain"].len(), 3);
+		assert_eq!(deserialized.message["contents"], Value::String("Hello, Bob!".into()));
+	}
+}
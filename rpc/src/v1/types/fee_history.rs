@@ -0,0 +1,99 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Gas price history.
+
+use ethcore::client::{FeeHistory as EthFeeHistory, GasPriceRecommendation as EthGasPriceRecommendation};
+use v1::types::U256;
+
+/// Recommended gas prices for different confirmation speeds.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct GasPriceRecommendation {
+	/// Slow
+	pub slow: U256,
+	/// Standard
+	pub standard: U256,
+	/// Fast
+	pub fast: U256,
+}
+
+impl From<EthGasPriceRecommendation> for GasPriceRecommendation {
+	fn from(r: EthGasPriceRecommendation) -> Self {
+		GasPriceRecommendation {
+			slow: r.slow.into(),
+			standard: r.standard.into(),
+			fast: r.fast.into(),
+		}
+	}
+}
+
+/// Gas price history of recent blocks.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct FeeHistory {
+	/// Number of the oldest block returned
+	#[serde(rename="oldestBlock")]
+	pub oldest_block: Option<U256>,
+	/// Requested gas price percentiles of each block
+	#[serde(rename="gasPrices")]
+	pub gas_prices: Vec<Vec<U256>>,
+	/// Ratio of gas used to the gas limit of each block
+	#[serde(rename="gasUsedRatio")]
+	pub gas_used_ratio: Vec<f64>,
+	/// Recommended gas prices
+	pub recommended: GasPriceRecommendation,
+}
+
+impl FeeHistory {
+	/// Convert the history, using `default_price` as recommendation if there were no transactions.
+	pub fn new(history: EthFeeHistory, default_price: U256) -> Self {
+		FeeHistory {
+			oldest_block: history.oldest_block.map(Into::into),
+			gas_prices: history.gas_prices.into_iter()
+				.map(|prices| prices.into_iter().map(Into::into).collect())
+				.collect(),
+			gas_used_ratio: history.gas_used_ratio,
+			recommended: history.recommended.map(Into::into).unwrap_or(GasPriceRecommendation {
+				slow: default_price,
+				standard: default_price,
+				fast: default_price,
+			}),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use ethcore::client::{FeeHistory as EthFeeHistory, GasPriceRecommendation};
+	use super::FeeHistory;
+
+	#[test]
+	fn should_serialize_fee_history() {
+		let history = FeeHistory::new(EthFeeHistory {
+			oldest_block: Some(5),
+			gas_prices: vec![vec![1.into(), 2.into()], vec![0.into(), 0.into()]],
+			gas_used_ratio: vec![0.5, 0.0],
+			recommended: Some(GasPriceRecommendation {
+				slow: 1.into(),
+				standard: 2.into(),
+				fast: 3.into(),
+			}),
+		}, 10.into());
+
+		let serialized = serde_json::to_string(&history).unwrap();
+		assert_eq!(serialized, r#"{"oldestBlock":"0x5","gasPrices":[["0x1","0x2"],["0x0","0x0"]],"gasUsedRatio":[0.5,0.0],"recommended":{"slow":"0x1","standard":"0x2","fast":"0x3"}}"#);
+	}
+}
@@ -17,9 +17,9 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::{Error, DeserializeOwned};
 use serde_json::{Value, from_value};
-use ethcore::filter::Filter as EthFilter;
+use ethcore::filter::{Filter as EthFilter, LogCursor as EthLogCursor};
 use ethcore::client::BlockId;
-use v1::types::{BlockNumber, H160, H256, Log};
+use v1::types::{BlockNumber, H160, H256, U64, Log};
 
 /// Variadic value
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
@@ -68,6 +68,48 @@ pub struct Filter {
 	pub topics: Option<Vec<Topic>>,
 	/// Limit
 	pub limit: Option<usize>,
+	/// Position to resume the query from
+	pub cursor: Option<LogCursor>,
+}
+
+/// Position of a log, returned when a logs query exceeds the limits.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, Eq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct LogCursor {
+	/// Block number
+	#[serde(rename="blockNumber")]
+	pub block_number: U64,
+	/// Log index in the block
+	#[serde(rename="logIndex")]
+	pub log_index: U64,
+}
+
+/// Logs of a query which exceeded the limits and the position to continue from.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct LogsPage {
+	/// Logs before the cursor
+	pub logs: Vec<Log>,
+	/// Position of the first log left out
+	pub cursor: LogCursor,
+}
+
+impl From<EthLogCursor> for LogCursor {
+	fn from(c: EthLogCursor) -> Self {
+		LogCursor {
+			block_number: c.block_number.into(),
+			log_index: (c.log_index as u64).into(),
+		}
+	}
+}
+
+impl Into<EthLogCursor> for LogCursor {
+	fn into(self) -> EthLogCursor {
+		let log_index: u64 = self.log_index.into();
+		EthLogCursor {
+			block_number: self.block_number.into(),
+			log_index: log_index as usize,
+		}
+	}
 }
 
 impl Into<EthFilter> for Filter {
@@ -154,6 +196,7 @@ mod tests {
 			address: None,
 			topics: None,
 			limit: None,
+			cursor: None,
 		});
 	}
 
@@ -169,6 +212,7 @@ mod tests {
 				VariadicValue::Null,
 			]),
 			limit: None,
+			cursor: None,
 		};
 
 		let eth_filter: EthFilter = filter.into();
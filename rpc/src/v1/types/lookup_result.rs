@@ -0,0 +1,58 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! Result of a single lookup within a batch request.
+
+use std::fmt;
+
+/// Outcome of one entry of a batch lookup.
+#[derive(Debug, Serialize, PartialEq)]
+pub enum LookupResult<T> {
+	/// Looked up value
+	#[serde(rename="result")]
+	Value(T),
+	/// Reason the lookup failed
+	#[serde(rename="error")]
+	Error(String),
+}
+
+impl<T, E: fmt::Display> From<Result<T, E>> for LookupResult<T> {
+	fn from(result: Result<T, E>) -> Self {
+		match result {
+			Ok(value) => LookupResult::Value(value),
+			Err(err) => LookupResult::Error(format!("{}", err)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use v1::types::U256;
+	use super::LookupResult;
+
+	#[test]
+	fn lookup_result_serialization() {
+		let results = vec![
+			LookupResult::from(Ok::<U256, String>(5.into())),
+			LookupResult::from(Err::<U256, String>("Database missing trie node".into())),
+		];
+
+		let serialized = serde_json::to_string(&results).unwrap();
+		assert_eq!(serialized, r#"[{"result":"0x5"},{"error":"Database missing trie node"}]"#);
+	}
+}
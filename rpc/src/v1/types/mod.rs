@@ -25,18 +25,24 @@ mod call_request;
 mod confirmations;
 mod consensus_status;
 mod dapps;
+mod debug;
 mod derivation;
+mod eip712;
+mod fee_history;
 mod filter;
 mod hash;
 mod histogram;
 mod index;
 mod log;
+mod lookup_result;
 mod node_kind;
 mod provenance;
 mod receipt;
 mod rpc_settings;
+mod state_override;
 mod secretstore;
 mod sync;
+mod token_balance;
 mod trace;
 mod trace_filter;
 mod transaction;
@@ -47,32 +53,38 @@ mod work;
 
 pub mod pubsub;
 
-pub use self::account_info::{AccountInfo, ExtAccountInfo, HwAccountInfo};
+pub use self::account_info::{AccountInfo, ExtAccountInfo, HwAccountInfo, EthAccount, StorageProof};
 pub use self::bytes::Bytes;
 pub use self::block::{RichBlock, Block, BlockTransactions, Header, RichHeader, Rich};
 pub use self::block_number::BlockNumber;
 pub use self::call_request::CallRequest;
 pub use self::confirmations::{
 	ConfirmationPayload, ConfirmationRequest, ConfirmationResponse, ConfirmationResponseWithToken,
-	TransactionModification, SignRequest, DecryptRequest, Either
+	TransactionModification, SignRequest, EIP712SignRequest, DecryptRequest, Either
 };
 pub use self::consensus_status::*;
 pub use self::dapps::LocalDapp;
+pub use self::debug::{StructLoggerOptions, StructLog, ExecutionTrace};
 pub use self::derivation::{DeriveHash, DeriveHierarchical, Derive};
-pub use self::filter::{Filter, FilterChanges};
+pub use self::eip712::{EIP712, EIP712Field};
+pub use self::fee_history::{FeeHistory, GasPriceRecommendation};
+pub use self::filter::{Filter, FilterChanges, LogCursor, LogsPage};
 pub use self::hash::{H64, H160, H256, H512, H520, H2048};
 pub use self::histogram::Histogram;
 pub use self::index::Index;
 pub use self::log::Log;
+pub use self::lookup_result::LookupResult;
 pub use self::node_kind::{NodeKind, Availability, Capability};
 pub use self::provenance::{Origin, DappId};
 pub use self::receipt::Receipt;
 pub use self::rpc_settings::RpcSettings;
+pub use self::state_override::{StateOverride, AccountOverride, StorageOverride};
 pub use self::secretstore::EncryptedDocumentKey;
 pub use self::sync::{
 	SyncStatus, SyncInfo, Peers, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
 	TransactionStats, ChainStatus, EthProtocolInfo, PipProtocolInfo,
 };
+pub use self::token_balance::TokenBalance;
 pub use self::trace::{LocalizedTrace, TraceResults};
 pub use self::trace_filter::TraceFilter;
 pub use self::transaction::{Transaction, RichRawTransaction, LocalTransactionStatus};
@@ -19,7 +19,7 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::Error;
 use serde_json::{Value, from_value};
-use v1::types::{RichHeader, Filter, Log, H256};
+use v1::types::{RichHeader, Filter, Log, H256, LocalizedTrace, TraceFilter};
 
 /// Subscription result.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -30,6 +30,8 @@ pub enum Result {
 	Log(Log),
 	/// Transaction hash
 	TransactionHash(H256),
+	/// Trace
+	Trace(LocalizedTrace),
 }
 
 impl Serialize for Result {
@@ -40,6 +42,7 @@ impl Serialize for Result {
 			Result::Header(ref header) => header.serialize(serializer),
 			Result::Log(ref log) => log.serialize(serializer),
 			Result::TransactionHash(ref hash) => hash.serialize(serializer),
+			Result::Trace(ref trace) => trace.serialize(serializer),
 		}
 	}
 }
@@ -60,6 +63,9 @@ pub enum Kind {
 	/// Node syncing status subscription.
 	#[serde(rename="syncing")]
 	Syncing,
+	/// Traces of imported blocks subscription.
+	#[serde(rename="traces")]
+	Traces,
 }
 
 /// Subscription kind.
@@ -69,6 +75,8 @@ pub enum Params {
 	None,
 	/// Log parameters.
 	Logs(Filter),
+	/// Trace parameters.
+	Traces(TraceFilter),
 }
 
 impl Default for Params {
@@ -86,7 +94,9 @@ impl<'a> Deserialize<'a> for Params {
 			return Ok(Params::None);
 		}
 
+		// filters with only a block range are valid for both logs and traces and are parsed as `Logs`.
 		from_value(v.clone()).map(Params::Logs)
+			.or_else(|_| from_value(v).map(Params::Traces))
 			.map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {}", e)))
 	}
 }
@@ -94,8 +104,9 @@ impl<'a> Deserialize<'a> for Params {
 #[cfg(test)]
 mod tests {
 	use serde_json;
+	use ethereum_types::Address;
 	use super::{Result, Kind, Params};
-	use v1::types::{RichHeader, Header, Filter};
+	use v1::types::{RichHeader, Header, Filter, TraceFilter};
 	use v1::types::filter::VariadicValue;
 
 	#[test]
@@ -104,6 +115,22 @@ mod tests {
 		assert_eq!(serde_json::from_str::<Kind>(r#""logs""#).unwrap(), Kind::Logs);
 		assert_eq!(serde_json::from_str::<Kind>(r#""newPendingTransactions""#).unwrap(), Kind::NewPendingTransactions);
 		assert_eq!(serde_json::from_str::<Kind>(r#""syncing""#).unwrap(), Kind::Syncing);
+		assert_eq!(serde_json::from_str::<Kind>(r#""traces""#).unwrap(), Kind::Traces);
+	}
+
+	#[test]
+	fn should_deserialize_traces() {
+		let traces = serde_json::from_str::<Params>(
+			r#"{"toAddress":["0x000000000000000000000000000000000000000a"]}"#
+		).unwrap();
+		assert_eq!(traces, Params::Traces(TraceFilter {
+			from_block: None,
+			to_block: None,
+			from_address: None,
+			to_address: Some(vec![Address::from(10).into()]),
+			after: None,
+			count: None,
+		}));
 	}
 
 	#[test]
@@ -122,6 +149,7 @@ mod tests {
 			address: None,
 			topics: None,
 			limit: None,
+			cursor: None,
 		}));
 		assert_eq!(logs2, Params::Logs(Filter {
 			from_block: None,
@@ -129,6 +157,7 @@ mod tests {
 			address: None,
 			topics: None,
 			limit: Some(10),
+			cursor: None,
 		}));
 		assert_eq!(logs3, Params::Logs(Filter {
 			from_block: None,
@@ -138,6 +167,7 @@ mod tests {
 				VariadicValue::Single("000000000000000000000000a94f5374fce5edbc8e2a8697c15331677e6ebf0b".parse().unwrap()
 			)]),
 			limit: None,
+			cursor: None,
 		}));
 	}
 
@@ -0,0 +1,149 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! State overrides for calls.
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Deserializer};
+use serde::de::Error;
+use ethcore::client;
+use v1::types::{Bytes, H160, H256, U256};
+
+/// Account storage override.
+#[derive(Debug, PartialEq, Clone)]
+pub enum StorageOverride {
+	/// Replaces the whole storage.
+	Full(BTreeMap<H256, H256>),
+	/// Replaces only the given slots.
+	Diff(BTreeMap<H256, H256>),
+}
+
+/// Account fields to override.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct AccountOverride {
+	/// Balance
+	pub balance: Option<U256>,
+	/// Nonce
+	pub nonce: Option<U256>,
+	/// Code
+	pub code: Option<Bytes>,
+	/// Storage, either full (`state`) or partial (`stateDiff`)
+	pub storage: Option<StorageOverride>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawAccountOverride {
+	balance: Option<U256>,
+	nonce: Option<U256>,
+	code: Option<Bytes>,
+	state: Option<BTreeMap<H256, H256>>,
+	#[serde(rename="stateDiff")]
+	state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+impl<'a> Deserialize<'a> for AccountOverride {
+	fn deserialize<D>(deserializer: D) -> Result<AccountOverride, D::Error> where D: Deserializer<'a> {
+		let raw = RawAccountOverride::deserialize(deserializer)?;
+		let storage = match (raw.state, raw.state_diff) {
+			(Some(_), Some(_)) => return Err(D::Error::custom("`state` and `stateDiff` are mutually exclusive")),
+			(Some(state), None) => Some(StorageOverride::Full(state)),
+			(None, Some(diff)) => Some(StorageOverride::Diff(diff)),
+			(None, None) => None,
+		};
+
+		Ok(AccountOverride {
+			balance: raw.balance,
+			nonce: raw.nonce,
+			code: raw.code,
+			storage: storage,
+		})
+	}
+}
+
+/// Per-account state overrides.
+#[derive(Debug, Default, PartialEq, Clone, Deserialize)]
+pub struct StateOverride(pub BTreeMap<H160, AccountOverride>);
+
+fn into_storage(storage: BTreeMap<H256, H256>) -> BTreeMap<::ethereum_types::H256, ::ethereum_types::H256> {
+	storage.into_iter().map(|(k, v)| (k.into(), v.into())).collect()
+}
+
+impl Into<client::AccountOverride> for AccountOverride {
+	fn into(self) -> client::AccountOverride {
+		client::AccountOverride {
+			balance: self.balance.map(Into::into),
+			nonce: self.nonce.map(Into::into),
+			code: self.code.map(Into::into),
+			storage: self.storage.map(|storage| match storage {
+				StorageOverride::Full(state) => client::StorageOverride::Full(into_storage(state)),
+				StorageOverride::Diff(diff) => client::StorageOverride::Diff(into_storage(diff)),
+			}),
+		}
+	}
+}
+
+impl Into<client::StateOverride> for StateOverride {
+	fn into(self) -> client::StateOverride {
+		self.0.into_iter()
+			.map(|(address, account)| (address.into(), account.into()))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use v1::types::{H160, H256, U256};
+	use super::{AccountOverride, StateOverride, StorageOverride};
+
+	#[test]
+	fn should_deserialize_state_override() {
+		let s = r#"{
+			"0x0000000000000000000000000000000000000001": {
+				"balance": "0x10",
+				"nonce": "0x1",
+				"code": "0x6000",
+				"state": { "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002" }
+			},
+			"0x0000000000000000000000000000000000000002": {
+				"stateDiff": { "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002" }
+			}
+		}"#;
+		let deserialized: StateOverride = serde_json::from_str(s).unwrap();
+		let storage = vec![(H256::from(1), H256::from(2))].into_iter().collect();
+
+		assert_eq!(deserialized.0.len(), 2);
+		assert_eq!(deserialized.0[&H160::from(1)], AccountOverride {
+			balance: Some(U256::from(0x10)),
+			nonce: Some(U256::from(1)),
+			code: Some(vec![0x60, 0x00].into()),
+			storage: Some(StorageOverride::Full(storage.clone())),
+		});
+		assert_eq!(deserialized.0[&H160::from(2)], AccountOverride {
+			storage: Some(StorageOverride::Diff(storage)),
+			..Default::default()
+		});
+	}
+
+	#[test]
+	fn should_not_deserialize_full_and_partial_storage() {
+		let s = r#"{ "state": {}, "stateDiff": {} }"#;
+		let deserialized: Result<AccountOverride, _> = serde_json::from_str(s);
+
+		assert!(deserialized.is_err());
+	}
+}
@@ -0,0 +1,65 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! ERC-20 token balance.
+
+use ethcore::client::TokenHolding;
+use v1::types::{H160, U256};
+
+/// Balance of an ERC-20 token held by an account.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct TokenBalance {
+	/// Token contract address
+	pub token: H160,
+	/// Holder address
+	pub holder: H160,
+	/// Balance of the holder
+	pub balance: U256,
+	/// False if the holder spent tokens received before the index was enabled,
+	/// in which case `balance` is lower than the real one.
+	pub reliable: bool,
+}
+
+impl From<TokenHolding> for TokenBalance {
+	fn from(holding: TokenHolding) -> Self {
+		TokenBalance {
+			token: holding.token.into(),
+			holder: holding.holder.into(),
+			balance: holding.balance.into(),
+			reliable: holding.reliable,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use v1::types::H160;
+	use super::TokenBalance;
+
+	#[test]
+	fn token_balance_serialization() {
+		let balance = TokenBalance {
+			token: H160::from(1),
+			holder: H160::from(2),
+			balance: 1000.into(),
+			reliable: true,
+		};
+
+		let serialized = serde_json::to_string(&balance).unwrap();
+		assert_eq!(serialized, r#"{"token":"0x0000000000000000000000000000000000000001","holder":"0x0000000000000000000000000000000000000002","balance":"0x3e8","reliable":true}"#);
+	}
+}
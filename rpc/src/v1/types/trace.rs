@@ -213,7 +213,7 @@ impl From<state_diff::StateDiff> for StateDiff {
 }
 
 /// Create response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Create {
 	/// Sender
 	from: H160,
@@ -237,7 +237,7 @@ impl From<trace::Create> for Create {
 }
 
 /// Call type.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum CallType {
 	/// None
 	#[serde(rename="none")]
@@ -269,7 +269,7 @@ impl From<vm::CallType> for CallType {
 }
 
 /// Call response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Call {
 	/// Sender
 	from: H160,
@@ -300,7 +300,7 @@ impl From<trace::Call> for Call {
 }
 
 /// Reward type.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum RewardType {
 	/// Block
 	#[serde(rename="block")]
@@ -308,6 +308,9 @@ pub enum RewardType {
 	/// Uncle
 	#[serde(rename="uncle")]
 	Uncle,
+	/// External
+	#[serde(rename="external")]
+	External,
 }
 
 impl From<trace::RewardType> for RewardType {
@@ -315,13 +318,14 @@ impl From<trace::RewardType> for RewardType {
 		match c {
 			trace::RewardType::Block => RewardType::Block,
 			trace::RewardType::Uncle => RewardType::Uncle,
+			trace::RewardType::External => RewardType::External,
 		}
 	}
 }
 
 
 /// Reward action
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Reward {
 	/// Author's address.
 	pub author: H160,
@@ -343,7 +347,7 @@ impl From<trace::Reward> for Reward {
 }
 
 /// Suicide
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Suicide {
 	/// Address.
 	pub address: H160,
@@ -365,7 +369,7 @@ impl From<trace::Suicide> for Suicide {
 }
 
 /// Action
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
 	/// Call
 	Call(Call),
@@ -389,7 +393,7 @@ impl From<trace::Action> for Action {
 }
 
 /// Call Result
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct CallResult {
 	/// Gas used
 	#[serde(rename="gasUsed")]
@@ -408,7 +412,7 @@ impl From<trace::CallResult> for CallResult {
 }
 
 /// Craete Result
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct CreateResult {
 	/// Gas used
 	#[serde(rename="gasUsed")]
@@ -430,7 +434,7 @@ impl From<trace::CreateResult> for CreateResult {
 }
 
 /// Response
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Res {
 	/// Call
 	Call(CallResult),
@@ -457,7 +461,7 @@ impl From<trace::Res> for Res {
 }
 
 /// Trace
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LocalizedTrace {
 	/// Action
 	action: Action,
@@ -611,6 +615,29 @@ pub struct TraceResults {
 	/// The transaction trace.
 	#[serde(rename="stateDiff")]
 	pub state_diff: Option<StateDiff>,
+	/// The nested call tree, if requested with `callTracer`.
+	#[serde(rename="callTrace", skip_serializing_if = "Option::is_none")]
+	pub call_trace: Option<CallFrame>,
+	/// The pre-execution state of touched accounts, if requested with `prestateTracer`.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub prestate: Option<BTreeMap<H160, PrestateAccount>>,
+}
+
+impl TraceResults {
+	/// Adds the result of a tracer preset.
+	pub fn add_preset(&mut self, trace: et::PresetTrace) {
+		match trace {
+			et::PresetTrace::Call(frame) => self.call_trace = frame.map(Into::into),
+			et::PresetTrace::Prestate(state) => self.prestate = Some(state.drain().into_iter()
+				.map(|(address, account)| (address.into(), PrestateAccount {
+					balance: account.balance.into(),
+					nonce: account.nonce.into(),
+					code: account.code.map(Into::into),
+					storage: account.storage.into_iter().map(|(k, v)| (k.into(), v.into())).collect(),
+				}))
+				.collect()),
+		}
+	}
 }
 
 impl From<Executed> for TraceResults {
@@ -620,10 +647,83 @@ impl From<Executed> for TraceResults {
 			trace: t.trace.into_iter().map(Into::into).collect(),
 			vm_trace: t.vm_trace.map(Into::into),
 			state_diff: t.state_diff.map(Into::into),
+			call_trace: None,
+			prestate: None,
 		}
 	}
 }
 
+#[derive(Debug, Serialize)]
+/// A frame of the nested call tree.
+pub struct CallFrame {
+	/// Kind of the frame.
+	#[serde(rename="type")]
+	pub frame_type: &'static str,
+	/// The sending account.
+	pub from: H160,
+	/// The destination account, the created contract or the suicide heir.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub to: Option<H160>,
+	/// The value transferred.
+	pub value: U256,
+	/// The gas available for the frame.
+	pub gas: U256,
+	/// The gas used by the frame.
+	#[serde(rename="gasUsed")]
+	pub gas_used: U256,
+	/// Call data or init code.
+	pub input: Bytes,
+	/// Returned data or code of the created contract.
+	pub output: Bytes,
+	/// Error, if the frame failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// Frames entered from this one.
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub calls: Vec<CallFrame>,
+}
+
+impl From<et::CallFrame> for CallFrame {
+	fn from(f: et::CallFrame) -> Self {
+		let frame_type = match f.frame_type {
+			et::CallFrameType::Call(vm::CallType::CallCode) => "CALLCODE",
+			et::CallFrameType::Call(vm::CallType::DelegateCall) => "DELEGATECALL",
+			et::CallFrameType::Call(vm::CallType::StaticCall) => "STATICCALL",
+			et::CallFrameType::Call(_) => "CALL",
+			et::CallFrameType::Create => "CREATE",
+			et::CallFrameType::Suicide => "SELFDESTRUCT",
+		};
+
+		CallFrame {
+			frame_type: frame_type,
+			from: f.from.into(),
+			to: f.to.map(Into::into),
+			value: f.value.into(),
+			gas: f.gas.into(),
+			gas_used: f.gas_used.into(),
+			input: f.input.into(),
+			output: f.output.into(),
+			error: f.error.map(|e| e.to_string()),
+			calls: f.calls.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[derive(Debug, Serialize)]
+/// Pre-execution state of an account.
+pub struct PrestateAccount {
+	/// Balance.
+	pub balance: U256,
+	/// Nonce.
+	pub nonce: U256,
+	/// Code, if known.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	/// Storage slots read or written by the transaction.
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	pub storage: BTreeMap<H256, H256>,
+}
+
 #[cfg(test)]
 mod tests {
 	use serde_json;
@@ -639,11 +739,42 @@ mod tests {
 			trace: vec![],
 			vm_trace: None,
 			state_diff: None,
+			call_trace: None,
+			prestate: None,
 		};
 		let serialized = serde_json::to_string(&r).unwrap();
 		assert_eq!(serialized, r#"{"output":"0x60","trace":[],"vmTrace":null,"stateDiff":null}"#);
 	}
 
+	#[test]
+	fn should_serialize_call_frame() {
+		let frame = CallFrame::from(et::CallFrame {
+			frame_type: et::CallFrameType::Call(vm::CallType::Call),
+			from: 4.into(),
+			to: Some(5.into()),
+			value: 6.into(),
+			gas: 7.into(),
+			gas_used: 8.into(),
+			input: vec![0x12, 0x34],
+			output: vec![],
+			error: None,
+			calls: vec![et::CallFrame {
+				frame_type: et::CallFrameType::Create,
+				from: 5.into(),
+				to: None,
+				value: 0.into(),
+				gas: 3.into(),
+				gas_used: 3.into(),
+				input: vec![0x60],
+				output: vec![],
+				error: Some(TraceError::OutOfGas),
+				calls: vec![],
+			}],
+		});
+		let serialized = serde_json::to_string(&frame).unwrap();
+		assert_eq!(serialized, r#"{"type":"CALL","from":"0x0000000000000000000000000000000000000004","to":"0x0000000000000000000000000000000000000005","value":"0x6","gas":"0x7","gasUsed":"0x8","input":"0x1234","output":"0x","calls":[{"type":"CREATE","from":"0x0000000000000000000000000000000000000005","value":"0x0","gas":"0x3","gasUsed":"0x3","input":"0x60","output":"0x","error":"Out of gas"}]}"#);
+	}
+
 	#[test]
 	fn test_trace_call_serialize() {
 		let t = LocalizedTrace {
@@ -21,7 +21,7 @@ use ethcore::client;
 use v1::types::{BlockNumber, H160};
 
 /// Trace filter
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct TraceFilter {
 	/// From block
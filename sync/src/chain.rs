@@ -101,7 +101,7 @@ use network::{self, PeerId, PacketId};
 use ethcore::header::{BlockNumber, Header as BlockHeader};
 use ethcore::client::{BlockChainClient, BlockStatus, BlockId, BlockChainInfo, BlockImportError, BlockQueueInfo};
 use ethcore::error::*;
-use ethcore::snapshot::{ManifestData, RestorationStatus};
+use ethcore::snapshot::{ChunkCompression, ManifestData, RestorationStatus};
 use transaction::PendingTransaction;
 use sync_io::SyncIo;
 use time;
@@ -316,6 +316,8 @@ struct PeerInfo {
 	snapshot_hash: Option<H256>,
 	/// Best snapshot block number
 	snapshot_number: Option<BlockNumber>,
+	/// Snapshot chunk compressions advertised by the peer. `None` for peers which only know snappy.
+	snapshot_compressions: Option<Vec<ChunkCompression>>,
 	/// Block set requested
 	block_set: Option<BlockSet>,
 }
@@ -637,6 +639,11 @@ impl ChainSync {
 			asking_snapshot_data: None,
 			snapshot_hash: if warp_protocol { Some(r.val_at(5)?) } else { None },
 			snapshot_number: if warp_protocol { Some(r.val_at(6)?) } else { None },
+			snapshot_compressions: if warp_protocol && r.item_count()? > 7 {
+				Some(r.list_at::<u8>(7)?.into_iter().filter_map(ChunkCompression::from_id).collect())
+			} else {
+				None
+			},
 			block_set: None,
 		};
 
@@ -1401,7 +1408,16 @@ impl ChainSync {
 	/// Request snapshot manifest from a peer.
 	fn request_snapshot_manifest(&mut self, sync: &mut SyncIo, peer_id: PeerId) {
 		trace!(target: "sync", "{} <- GetSnapshotManifest", peer_id);
-		let rlp = RlpStream::new_list(0);
+		// peers which didn't advertise compressions ignore non-empty requests.
+		let advertised = self.peers.get(&peer_id).map_or(false, |p| p.snapshot_compressions.is_some());
+		let rlp = match advertised {
+			true => {
+				let mut rlp = RlpStream::new_list(1);
+				rlp.append_list(&supported_compression_ids());
+				rlp
+			},
+			false => RlpStream::new_list(0),
+		};
 		self.send_request(sync, peer_id, PeerAsking::SnapshotManifest, GET_SNAPSHOT_MANIFEST_PACKET, rlp.out());
 	}
 
@@ -1517,7 +1533,7 @@ impl ChainSync {
 		let warp_protocol = warp_protocol_version != 0;
 		let protocol = if warp_protocol { warp_protocol_version } else { PROTOCOL_VERSION_63 };
 		trace!(target: "sync", "Sending status to {}, protocol version {}", peer, protocol);
-		let mut packet = RlpStream::new_list(if warp_protocol { 7 } else { 5 });
+		let mut packet = RlpStream::new_list(if warp_protocol { 8 } else { 5 });
 		let chain = io.chain().chain_info();
 		packet.append(&(protocol as u32));
 		packet.append(&self.network_id);
@@ -1533,6 +1549,7 @@ impl ChainSync {
 			let manifest_hash = manifest.map_or(H256::new(), |m| keccak(m.into_rlp()));
 			packet.append(&manifest_hash);
 			packet.append(&block_number);
+			packet.append_list(&supported_compression_ids());
 		}
 		io.respond(STATUS_PACKET, packet.out())
 	}
@@ -1683,13 +1700,27 @@ impl ChainSync {
 
 	/// Respond to GetSnapshotManifest request
 	fn return_snapshot_manifest(io: &SyncIo, r: &UntrustedRlp, peer_id: PeerId) -> RlpResponseResult {
+		// Packet layout:
+		// [ compressions: [ P, ... ] ] or [] for peers which only read snappy
 		let count = r.item_count().unwrap_or(0);
 		trace!(target: "sync", "{} -> GetSnapshotManifest", peer_id);
-		if count != 0 {
-			debug!(target: "sync", "Invalid GetSnapshotManifest request, ignoring.");
-			return Ok(None);
-		}
-		let rlp = match io.snapshot_service().manifest() {
+		let compressions = match count {
+			0 => vec![ChunkCompression::Snappy],
+			1 => r.list_at::<u8>(0)?.into_iter().filter_map(ChunkCompression::from_id).collect(),
+			_ => {
+				debug!(target: "sync", "Invalid GetSnapshotManifest request, ignoring.");
+				return Ok(None);
+			},
+		};
+		let manifest = io.snapshot_service().manifest()
+			.and_then(|manifest| match compressions.contains(&manifest.compression) {
+				true => Some(manifest),
+				false => {
+					trace!(target: "sync", "{}: Manifest compression {:?} not supported by peer", peer_id, manifest.compression);
+					None
+				},
+			});
+		let rlp = match manifest {
 			Some(manifest) => {
 				trace!(target: "sync", "{} <- SnapshotManifest", peer_id);
 				let mut rlp = RlpStream::new_list(1);
@@ -2242,6 +2273,11 @@ fn accepts_service_transaction(client_id: &str) -> bool {
 	ver.len() == 2 && (ver[0] > SERVICE_TRANSACTIONS_VERSION.0 || (ver[0] == SERVICE_TRANSACTIONS_VERSION.0 && ver[1] >= SERVICE_TRANSACTIONS_VERSION.1))
 }
 
+/// Identifiers of snapshot chunk compressions advertised to peers.
+fn supported_compression_ids() -> Vec<u8> {
+	ChunkCompression::supported().iter().map(|c| c.id()).collect()
+}
+
 #[cfg(test)]
 mod tests {
 	use std::collections::{HashSet, VecDeque};
@@ -2354,6 +2390,23 @@ mod tests {
 		assert!(result.is_ok());
 	}
 
+	#[test]
+	fn return_snapshot_manifest_with_supported_compression() {
+		let mut client = TestBlockChainClient::new();
+		let queue = RwLock::new(VecDeque::new());
+		let ss = TestSnapshotService::new_with_snapshot(4, H256::new(), 100).with_compression(ChunkCompression::Zstd);
+		let io = TestIo::new(&mut client, &ss, &queue, None);
+
+		// peers sending an empty request only read snappy chunks
+		let (_, rlp) = ChainSync::return_snapshot_manifest(&io, &UntrustedRlp::new(&[0xc0]), 0).unwrap().unwrap();
+		assert_eq!(rlp.out(), vec![0xc0]);
+
+		let mut request = RlpStream::new_list(1);
+		request.append_list(&super::supported_compression_ids());
+		let (_, rlp) = ChainSync::return_snapshot_manifest(&io, &UntrustedRlp::new(&request.out()), 0).unwrap().unwrap();
+		assert_eq!(UntrustedRlp::new(&rlp.out()).item_count().unwrap(), 1);
+	}
+
 	#[test]
 	fn return_receipts() {
 		let mut client = TestBlockChainClient::new();
@@ -2505,6 +2558,7 @@ mod tests {
 				confirmation: super::ForkConfirmation::Confirmed,
 				snapshot_number: None,
 				snapshot_hash: None,
+				snapshot_compressions: None,
 				asking_snapshot_data: None,
 				block_set: None,
 			});
@@ -2626,6 +2680,7 @@ mod tests {
 				confirmation: super::ForkConfirmation::Confirmed,
 				snapshot_number: None,
 				snapshot_hash: None,
+				snapshot_compressions: None,
 				asking_snapshot_data: None,
 				block_set: None,
 			});
@@ -20,7 +20,7 @@ use hash::keccak;
 use ethereum_types::H256;
 use parking_lot::Mutex;
 use bytes::Bytes;
-use ethcore::snapshot::{SnapshotService, ManifestData, RestorationStatus};
+use ethcore::snapshot::{SnapshotService, ManifestData, ChunkCompression, RestorationStatus};
 use ethcore::header::BlockNumber;
 use ethcore::client::{EachBlockWith};
 use super::helpers::*;
@@ -58,6 +58,7 @@ impl TestSnapshotService {
 			state_root: H256::new(),
 			block_number: block_number,
 			block_hash: block_hash,
+			compression: Default::default(),
 		};
 		let mut chunks: HashMap<H256, Bytes> = state_chunks.into_iter().map(|data| (keccak(&data), data)).collect();
 		chunks.extend(block_chunks.into_iter().map(|data| (keccak(&data), data)));
@@ -69,6 +70,13 @@ impl TestSnapshotService {
 			block_restoration_chunks: Mutex::new(HashMap::new()),
 		}
 	}
+
+	pub fn with_compression(mut self, compression: ChunkCompression) -> TestSnapshotService {
+		if let Some(ref mut manifest) = self.manifest {
+			manifest.compression = compression;
+		}
+		self
+	}
 }
 
 impl SnapshotService for TestSnapshotService {
@@ -0,0 +1,1233 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Key-Value store abstraction with a pure-Rust append-only log backend.
+//!
+//! Every flush appends checksummed records to the log file, the last of which completes
+//! the transaction, so an interrupted write loses at most the transaction being flushed.
+//! Keys of all columns are kept in memory together with the position of their values in
+//! the log, which takes roughly 100 bytes per key: tens of GiB for a mainnet node with
+//! pruned state and hundreds of GiB for an archive node, so the backend suits test networks
+//! and small chains. Space taken by overwritten and deleted values is reclaimed by compacting
+//! the log in the background whenever most of it is garbage.
+
+#[macro_use]
+extern crate log;
+
+extern crate kvdb;
+extern crate parking_lot;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::mem;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use parking_lot::{Mutex, RwLock};
+use kvdb::{KeyValueDB, DBTransaction, DBValue, DBOp, Result};
+
+const LOG_FILE_NAME: &'static str = "data.log";
+const COMPACTION_FILE_NAME: &'static str = "data.log.compact";
+const MAGIC: &'static [u8; 4] = b"PLOG";
+const VERSION: u32 = 1;
+const HEADER_LEN: u64 = 12;
+/// Size of garbage above which the log is compacted if it also outweighs live data.
+const COMPACTION_THRESHOLD: u64 = 64 * 1024 * 1024;
+/// Approximate size of records written during compaction.
+const COMPACTION_RECORD_SIZE: usize = 16 * 1024 * 1024;
+/// Maximal size of a record payload, limited by its 32-bit length prefix.
+const MAX_RECORD_LEN: usize = ::std::u32::MAX as usize;
+
+const OP_INSERT: u8 = 0;
+const OP_DELETE: u8 = 1;
+/// Ends a record whose transaction continues in the next one.
+const OP_CONTINUE: u8 = 2;
+
+enum KeyState {
+	Insert(DBValue),
+	Delete,
+}
+
+/// Single operation of a log record: column, key and value to insert or `None` to delete.
+type RecordOp<'a> = (usize, &'a [u8], Option<&'a [u8]>);
+
+/// Location of a value in the log file.
+#[derive(Clone, Copy)]
+struct ValuePos {
+	offset: u64,
+	len: u32,
+}
+
+/// Database configuration
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DatabaseConfig {
+	/// Set number of columns
+	pub columns: Option<u32>,
+	/// Should every flush be synced to disk?
+	pub sync: bool,
+}
+
+impl DatabaseConfig {
+	/// Create new `DatabaseConfig` with default parameters and specified set of columns.
+	pub fn with_columns(columns: Option<u32>) -> Self {
+		let mut config = Self::default();
+		config.columns = columns;
+		config
+	}
+}
+
+/// Returns true if there is a log database at given path.
+pub fn is_database<P: AsRef<Path>>(path: P) -> bool {
+	path.as_ref().join(LOG_FILE_NAME).is_file() || path.as_ref().join(COMPACTION_FILE_NAME).is_file()
+}
+
+fn write_u32(out: &mut Vec<u8>, value: u32) {
+	for i in 0..4 {
+		out.push((value >> (i * 8)) as u8);
+	}
+}
+
+fn read_u32(data: &[u8]) -> u32 {
+	data[..4].iter().rev().fold(0u32, |acc, &b| (acc << 8) | b as u32)
+}
+
+fn read_u64(data: &[u8]) -> u64 {
+	data[..8].iter().rev().fold(0u64, |acc, &b| (acc << 8) | b as u64)
+}
+
+/// 64-bit FNV-1a hash of the record payload.
+fn checksum(data: &[u8]) -> u64 {
+	data.iter().fold(0xcbf29ce484222325u64, |hash, &b| (hash ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(unix)]
+fn read_exact_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<()> {
+	use std::os::unix::fs::FileExt;
+	file.read_exact_at(buf, offset)
+}
+
+#[cfg(windows)]
+fn read_exact_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<()> {
+	use std::os::windows::fs::FileExt;
+	let mut read = 0;
+	while read < buf.len() {
+		match file.seek_read(&mut buf[read..], offset + read as u64)? {
+			0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+			n => read += n,
+		}
+	}
+	Ok(())
+}
+
+/// Atomically replaces file at `to` with the one at `from`, even if `to` is open.
+/// On Windows `fs::rename` uses `MoveFileExW` with `MOVEFILE_REPLACE_EXISTING`, and files are
+/// opened with `FILE_SHARE_DELETE` by default, so the log never goes missing.
+fn replace_file(from: &Path, to: &Path) -> io::Result<()> {
+	fs::rename(from, to)
+}
+
+/// Operations of a transaction read from the log, with value positions in the log file.
+type Transaction = Vec<(usize, Vec<u8>, Option<ValuePos>)>;
+
+/// Decodes record payload into operations with value offsets relative to the payload,
+/// along with whether the transaction continues in the next record.
+fn decode(payload: &[u8], columns: usize) -> Option<(Transaction, bool)> {
+	let mut ops = Vec::new();
+	let mut pos = 0;
+	let take = |pos: &mut usize, len: usize| -> Option<usize> {
+		if payload.len() - *pos < len {
+			return None;
+		}
+		*pos += len;
+		Some(*pos - len)
+	};
+
+	while pos < payload.len() {
+		let kind = payload[take(&mut pos, 1)?];
+		if kind == OP_CONTINUE {
+			return match pos == payload.len() {
+				true => Some((ops, true)),
+				false => None,
+			};
+		}
+
+		let col = read_u32(&payload[take(&mut pos, 4)?..]) as usize;
+		let key_len = read_u32(&payload[take(&mut pos, 4)?..]) as usize;
+		let key = payload[take(&mut pos, key_len)?..][..key_len].to_vec();
+		if col >= columns {
+			return None;
+		}
+
+		let value = match kind {
+			OP_INSERT => {
+				let len = read_u32(&payload[take(&mut pos, 4)?..]);
+				let offset = take(&mut pos, len as usize)? as u64;
+				Some(ValuePos { offset, len })
+			},
+			OP_DELETE => None,
+			_ => return None,
+		};
+		ops.push((col, key, value));
+	}
+
+	Some((ops, false))
+}
+
+/// Reads records of the log file between `from` and `to` offsets, passing operations of every
+/// complete transaction to `f`. Returns the end of the last complete transaction.
+///
+/// Reads are positional, so the file cursor used by flushes is left alone.
+fn read_transactions<F>(file: &File, columns: usize, from: u64, to: u64, mut f: F) -> io::Result<u64> where
+	F: FnMut(Transaction) -> io::Result<()>,
+{
+	// start of the next record to read
+	let mut pos = from;
+	// end of the last complete transaction
+	let mut end = from;
+	// operations of the transaction being read, passed on once it is complete
+	let mut pending = Vec::new();
+
+	while to - pos >= 4 {
+		let mut len = [0u8; 4];
+		if read_exact_at(file, &mut len, pos).is_err() {
+			break;
+		}
+
+		// a torn length prefix must not make us allocate more than the file holds.
+		let len = read_u32(&len) as u64;
+		if to - pos < 4 + len + 8 {
+			break;
+		}
+
+		let mut record = vec![0u8; len as usize + 8];
+		if read_exact_at(file, &mut record, pos + 4).is_err() {
+			break;
+		}
+
+		let (payload, hash) = record.split_at(len as usize);
+		if checksum(payload) != read_u64(hash) {
+			break;
+		}
+
+		let (ops, continued) = match decode(payload, columns) {
+			Some(decoded) => decoded,
+			None => break,
+		};
+
+		let payload_start = pos + 4;
+		pending.extend(ops.into_iter().map(|(col, key, value)| {
+			(col, key, value.map(|pos| ValuePos { offset: payload_start + pos.offset, len: pos.len }))
+		}));
+		pos = payload_start + len + 8;
+
+		if !continued {
+			f(mem::replace(&mut pending, Vec::new()))?;
+			end = pos;
+		}
+	}
+
+	Ok(end)
+}
+
+/// Appends a record with given payload to `data`.
+fn seal(data: &mut Vec<u8>, payload: &[u8]) {
+	write_u32(data, payload.len() as u32);
+	data.extend_from_slice(payload);
+	let hash = checksum(payload);
+	for i in 0..8 {
+		data.push((hash >> (i * 8)) as u8);
+	}
+}
+
+/// Encodes operations as a single transaction split into records with payloads of at most
+/// `max_record_len` bytes. Returns the records along with value positions relative to their start.
+fn encode(ops: &[RecordOp], max_record_len: usize) -> io::Result<(Vec<u8>, Vec<Option<ValuePos>>)> {
+	let mut data = Vec::new();
+	let mut payload = Vec::new();
+	let mut positions = Vec::with_capacity(ops.len());
+	for &(col, key, value) in ops {
+		// kind, column, key and value lengths, plus room for a continuation marker
+		let op_len = 10 + key.len() + value.map_or(0, |value| 4 + value.len());
+		if op_len > max_record_len {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, format!(
+				"Key of {} bytes with value of {} bytes does not fit in a log record", key.len(), value.map_or(0, |value| value.len()),
+			)));
+		}
+
+		if payload.len() + op_len > max_record_len {
+			payload.push(OP_CONTINUE);
+			seal(&mut data, &payload);
+			payload.clear();
+		}
+
+		payload.push(if value.is_some() { OP_INSERT } else { OP_DELETE });
+		write_u32(&mut payload, col as u32);
+		write_u32(&mut payload, key.len() as u32);
+		payload.extend_from_slice(key);
+		if let Some(value) = value {
+			write_u32(&mut payload, value.len() as u32);
+			positions.push(Some(ValuePos { offset: (data.len() + 4 + payload.len()) as u64, len: value.len() as u32 }));
+			payload.extend_from_slice(value);
+		} else {
+			positions.push(None);
+		}
+	}
+	seal(&mut data, &payload);
+
+	Ok((data, positions))
+}
+
+
+/// Open log file along with the index of its contents.
+struct Log {
+	file: File,
+	// end of the last complete record
+	len: u64,
+	columns: Vec<BTreeMap<Vec<u8>, ValuePos>>,
+	// size of keys and values reachable through the index
+	live: u64,
+}
+
+impl Log {
+	fn create(file_path: &Path, columns: usize) -> io::Result<Log> {
+		let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(file_path)?;
+		let mut header = MAGIC.to_vec();
+		write_u32(&mut header, VERSION);
+		write_u32(&mut header, columns as u32);
+		file.write_all(&header)?;
+		file.sync_all()?;
+
+		Ok(Log {
+			file,
+			len: HEADER_LEN,
+			columns: (0..columns).map(|_| BTreeMap::new()).collect(),
+			live: 0,
+		})
+	}
+
+	fn open(path: &Path, columns: usize) -> io::Result<Log> {
+		fs::create_dir_all(path)?;
+
+		let file_path = path.join(LOG_FILE_NAME);
+		let compacted_path = path.join(COMPACTION_FILE_NAME);
+		if !file_path.exists() && compacted_path.exists() {
+			// the log is only replaced once the compacted one is synced, so it is complete.
+			warn!("Recovering database at {} from an interrupted compaction", path.display());
+			replace_file(&compacted_path, &file_path)?;
+		} else {
+			let _ = fs::remove_file(&compacted_path);
+		}
+
+		if !file_path.exists() {
+			return Log::create(&file_path, columns);
+		}
+
+		let mut file = OpenOptions::new().read(true).write(true).open(&file_path)?;
+		let size = file.metadata()?.len();
+		let mut header = [0u8; HEADER_LEN as usize];
+		file.read_exact(&mut header)?;
+		if &header[..4] != &MAGIC[..] || read_u32(&header[4..]) != VERSION {
+			return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{} is not a log database", file_path.display())));
+		}
+
+		let stored_columns = read_u32(&header[8..]) as usize;
+		if stored_columns < columns {
+			// make room for the new columns
+			file.seek(SeekFrom::Start(8))?;
+			let mut count = Vec::new();
+			write_u32(&mut count, columns as u32);
+			file.write_all(&count)?;
+		}
+
+		let mut log = Log {
+			file,
+			len: HEADER_LEN,
+			columns: (0..::std::cmp::max(columns, stored_columns)).map(|_| BTreeMap::new()).collect(),
+			live: 0,
+		};
+		log.replay(size)?;
+
+		if log.needs_compaction() {
+			info!("Compacting database at {}", path.display());
+			let log = RwLock::new(Some(log));
+			compact(&log, &Mutex::new(()), &Mutex::new(()), path)?;
+			return Ok(log.into_inner().expect("compaction puts the compacted log in place of the old one; qed"));
+		}
+
+		Ok(log)
+	}
+
+	/// Rebuilds the index from the log, discarding an incomplete transaction at its end.
+	fn replay(&mut self, size: u64) -> io::Result<()> {
+		let file = self.file.try_clone()?;
+		let columns = self.columns.len();
+		let end = read_transactions(&file, columns, HEADER_LEN, size, |ops| {
+			for (col, key, value) in ops {
+				self.apply(col, key, value);
+			}
+			Ok(())
+		})?;
+		self.len = end;
+
+		if self.len < size {
+			warn!("Discarding {} bytes of incomplete writes at the end of the database log", size - self.len);
+			self.file.set_len(self.len)?;
+			self.file.sync_all()?;
+		}
+
+		Ok(())
+	}
+
+	fn apply(&mut self, col: usize, key: Vec<u8>, value: Option<ValuePos>) {
+		let key_len = key.len() as u64;
+		let old = match value {
+			Some(pos) => {
+				self.live += key_len + pos.len as u64;
+				self.columns[col].insert(key, pos)
+			},
+			None => self.columns[col].remove(&key),
+		};
+
+		if let Some(old) = old {
+			self.live -= key_len + old.len as u64;
+		}
+	}
+
+	fn garbage(&self) -> u64 {
+		self.len - HEADER_LEN - self.live
+	}
+
+	fn needs_compaction(&self) -> bool {
+		let garbage = self.garbage();
+		garbage > COMPACTION_THRESHOLD && garbage > self.live
+	}
+
+	/// Appends given operations to the log as a single transaction.
+	fn append(&mut self, ops: &[RecordOp], sync: bool) -> io::Result<()> {
+		self.append_records(ops, sync, MAX_RECORD_LEN)
+	}
+
+	/// Appends given operations to the log as a single transaction, split into records
+	/// with payloads of at most `max_record_len` bytes.
+	fn append_records(&mut self, ops: &[RecordOp], sync: bool, max_record_len: usize) -> io::Result<()> {
+		let (data, positions) = encode(ops, max_record_len)?;
+		self.write_records(&data, sync)?;
+		self.index_records(ops, positions, data.len() as u64);
+		Ok(())
+	}
+
+	/// Writes encoded records past the end of the log. They stay invisible to readers until
+	/// `index_records` is called, so only a shared reference is needed. Writes must not overlap.
+	fn write_records(&self, data: &[u8], sync: bool) -> io::Result<()> {
+		// readers use positional reads, so the cursor is only moved here.
+		let mut file = &self.file;
+		let written = file.seek(SeekFrom::Start(self.len))
+			.and_then(|_| file.write_all(data))
+			.and_then(|_| if sync { file.sync_data() } else { Ok(()) });
+
+		if let Err(err) = written {
+			// best effort to leave no partial record behind
+			let _ = self.file.set_len(self.len);
+			return Err(err);
+		}
+
+		Ok(())
+	}
+
+	/// Makes operations of records written with `write_records` visible.
+	/// `positions` of values are relative to the start of the records, which take `len` bytes.
+	fn index_records(&mut self, ops: &[RecordOp], positions: Vec<Option<ValuePos>>, len: u64) {
+		let start = self.len;
+		for (&(col, key, _), pos) in ops.iter().zip(positions) {
+			let value = pos.map(|pos| ValuePos { offset: start + pos.offset, len: pos.len });
+			self.apply(col, key.to_vec(), value);
+		}
+		self.len += len;
+	}
+
+	fn read(&self, pos: ValuePos) -> io::Result<Vec<u8>> {
+		let mut value = vec![0u8; pos.len as usize];
+		read_exact_at(&self.file, &mut value, pos.offset)?;
+		Ok(value)
+	}
+
+	fn get(&self, col: usize, key: &[u8]) -> io::Result<Option<Vec<u8>>> {
+		match self.columns.get(col).and_then(|column| column.get(key)) {
+			Some(pos) => self.read(*pos).map(Some),
+			None => Ok(None),
+		}
+	}
+
+	/// Reads live entries of a column with keys above `from`, until their size reaches `max_size`.
+	fn entries_from(&self, col: usize, from: &Bound<Vec<u8>>, max_size: usize) -> io::Result<Vec<(Vec<u8>, Vec<u8>)>> {
+		let lower = match *from {
+			Bound::Included(ref key) => Bound::Included(&key[..]),
+			Bound::Excluded(ref key) => Bound::Excluded(&key[..]),
+			Bound::Unbounded => Bound::Unbounded,
+		};
+
+		let mut entries = Vec::new();
+		let mut size = 0;
+		for (key, pos) in self.columns[col].range::<[u8], _>((lower, Bound::Unbounded)) {
+			if size >= max_size {
+				break;
+			}
+			size += key.len() + pos.len as usize;
+			entries.push((key.clone(), self.read(*pos)?));
+		}
+		Ok(entries)
+	}
+
+	/// Copies transactions appended after `from` into `other`.
+	/// Returns the end of the log up to which they were copied.
+	fn copy_since(&self, from: u64, other: &mut Log) -> io::Result<u64> {
+		read_transactions(&self.file, self.columns.len(), from, self.len, |ops| {
+			let values = ops.iter()
+				.map(|&(_, _, pos)| match pos {
+					Some(pos) => self.read(pos).map(Some),
+					None => Ok(None),
+				})
+				.collect::<io::Result<Vec<_>>>()?;
+			let ops = ops.iter()
+				.zip(&values)
+				.map(|(&(col, ref key, _), value)| (col, &key[..], value.as_ref().map(|value| &value[..])))
+				.collect::<Vec<_>>();
+			other.append(&ops, false)
+		})
+	}
+}
+
+fn closed() -> io::Error {
+	io::Error::new(io::ErrorKind::Other, "Database is closed")
+}
+
+/// Rewrites the log with live values only.
+///
+/// Live values are copied into the compaction file in parts, each under a read lock, so reads
+/// and flushes go on meanwhile. Transactions flushed since are then copied over too and
+/// the compacted log takes the place of the old one under the write lock.
+fn compact(log: &RwLock<Option<Log>>, flushing_lock: &Mutex<()>, compaction_lock: &Mutex<()>, path: &Path) -> io::Result<()> {
+	let _compaction = compaction_lock.lock();
+	let (compacted, start) = copy_live(log, path)?;
+	finish_compaction(log, flushing_lock, compacted, start, path)
+}
+
+/// Copies live values of the log into a new compaction file.
+/// Returns the compacted log along with the end of the log when copying started.
+fn copy_live(log: &RwLock<Option<Log>>, path: &Path) -> io::Result<(Log, u64)> {
+	let (mut compacted, start) = {
+		let log = log.read();
+		let log = log.as_ref().ok_or_else(closed)?;
+		(Log::create(&path.join(COMPACTION_FILE_NAME), log.columns.len())?, log.len)
+	};
+
+	for col in 0..compacted.columns.len() {
+		let mut from = Bound::Unbounded;
+		loop {
+			let entries = {
+				let log = log.read();
+				let entries = log.as_ref().ok_or_else(closed)?.entries_from(col, &from, COMPACTION_RECORD_SIZE)?;
+				entries
+			};
+
+			if entries.is_empty() {
+				break;
+			}
+
+			{
+				let ops = entries.iter().map(|&(ref key, ref value)| (col, &key[..], Some(&value[..]))).collect::<Vec<_>>();
+				compacted.append(&ops, false)?;
+			}
+			from = Bound::Excluded(entries.last().expect("entries are not empty; qed").0.clone());
+		}
+	}
+
+	Ok((compacted, start))
+}
+
+/// Copies transactions flushed since `start` into the compacted log and puts it in place of the log.
+fn finish_compaction(log: &RwLock<Option<Log>>, flushing_lock: &Mutex<()>, mut compacted: Log, start: u64, path: &Path) -> io::Result<()> {
+	// most of them are copied while flushes go on.
+	let start = {
+		let log = log.read();
+		let end = log.as_ref().ok_or_else(closed)?.copy_since(start, &mut compacted)?;
+		end
+	};
+
+	let _flushing = flushing_lock.lock();
+	let mut log = log.write();
+	match *log {
+		Some(ref log) => { log.copy_since(start, &mut compacted)?; },
+		None => return Err(closed()),
+	}
+
+	compacted.file.sync_all()?;
+	replace_file(&path.join(COMPACTION_FILE_NAME), &path.join(LOG_FILE_NAME))?;
+	*log = Some(compacted);
+	Ok(())
+}
+
+/// Where the next value of an iterator comes from.
+enum NextValue {
+	Flushed(ValuePos),
+	// `None` if the key is deleted
+	Buffered(Option<DBValue>),
+}
+
+/// Database iterator over flushed data merged with the write buffer at its creation.
+pub struct DatabaseIterator<'a> {
+	db: &'a Database,
+	col: usize,
+	next: Bound<Vec<u8>>,
+	buffered: BTreeMap<Vec<u8>, Option<DBValue>>,
+}
+
+impl<'a> Iterator for DatabaseIterator<'a> {
+	type Item = (Box<[u8]>, Box<[u8]>);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let log = self.db.log.read();
+		let log = log.as_ref()?;
+		let column = log.columns.get(self.col)?;
+
+		loop {
+			let (key, value) = {
+				let lower = match self.next {
+					Bound::Included(ref key) => Bound::Included(&key[..]),
+					Bound::Excluded(ref key) => Bound::Excluded(&key[..]),
+					Bound::Unbounded => Bound::Unbounded,
+				};
+				let flushed = column.range::<[u8], _>((lower, Bound::Unbounded)).next();
+				let buffered = self.buffered.range::<[u8], _>((lower, Bound::Unbounded)).next();
+
+				// buffered changes shadow flushed values of the same key.
+				match (flushed, buffered) {
+					(None, None) => return None,
+					(Some((key, pos)), Some((buffered_key, _))) if key < buffered_key => (key.clone(), NextValue::Flushed(*pos)),
+					(Some((key, pos)), None) => (key.clone(), NextValue::Flushed(*pos)),
+					(_, Some((key, value))) => (key.clone(), NextValue::Buffered(value.clone())),
+				}
+			};
+			self.next = Bound::Excluded(key.clone());
+
+			let value = match value {
+				NextValue::Buffered(Some(value)) => value.into_vec(),
+				NextValue::Buffered(None) => continue,
+				NextValue::Flushed(pos) => match log.read(pos) {
+					Ok(value) => value,
+					Err(err) => {
+						warn!("Failed to read database value: {}", err);
+						return None;
+					},
+				},
+			};
+
+			return Some((key.into_boxed_slice(), value.into_boxed_slice()));
+		}
+	}
+}
+
+/// Key-Value database.
+pub struct Database {
+	log: Arc<RwLock<Option<Log>>>,
+	config: DatabaseConfig,
+	path: PathBuf,
+	// Dirty values added with `write_buffered`. Cleaned on `flush`.
+	overlay: RwLock<Vec<HashMap<Vec<u8>, KeyState>>>,
+	// Values being written to the log by `flush`.
+	flushing: RwLock<Vec<HashMap<Vec<u8>, KeyState>>>,
+	// Prevents concurrent flushes.
+	flushing_lock: Arc<Mutex<()>>,
+	// Prevents concurrent compactions.
+	compaction_lock: Arc<Mutex<()>>,
+	// Set while a compaction started by `flush` is running.
+	compacting: Arc<AtomicBool>,
+	compaction: Mutex<Option<thread::JoinHandle<()>>>,
+}
+
+impl Database {
+	/// Open database with default settings.
+	pub fn open_default(path: &str) -> Result<Database> {
+		Database::open(&DatabaseConfig::default(), path)
+	}
+
+	/// Open database file. Creates if it does not exist.
+	pub fn open(config: &DatabaseConfig, path: &str) -> Result<Database> {
+		let columns = config.columns.unwrap_or(0) as usize + 1;
+		let log = Log::open(Path::new(path), columns)?;
+		let columns = log.columns.len();
+
+		Ok(Database {
+			log: Arc::new(RwLock::new(Some(log))),
+			config: config.clone(),
+			path: path.into(),
+			overlay: RwLock::new((0..columns).map(|_| HashMap::new()).collect()),
+			flushing: RwLock::new((0..columns).map(|_| HashMap::new()).collect()),
+			flushing_lock: Arc::new(Mutex::new(())),
+			compaction_lock: Arc::new(Mutex::new(())),
+			compacting: Arc::new(AtomicBool::new(false)),
+			compaction: Mutex::new(None),
+		})
+	}
+
+	/// Helper to create new transaction for this database.
+	pub fn transaction(&self) -> DBTransaction {
+		DBTransaction::new()
+	}
+
+	fn to_overlay_column(col: Option<u32>) -> usize {
+		col.map_or(0, |c| (c + 1) as usize)
+	}
+
+	/// Commit transaction to database.
+	pub fn write_buffered(&self, tr: DBTransaction) {
+		let mut overlay = self.overlay.write();
+		for op in tr.ops {
+			let (col, key, state) = match op {
+				DBOp::Insert { col, key, value } => (col, key, KeyState::Insert(value)),
+				DBOp::Delete { col, key } => (col, key, KeyState::Delete),
+			};
+
+			match overlay.get_mut(Self::to_overlay_column(col)) {
+				Some(column) => {
+					column.insert(key.into_vec(), state);
+				},
+				None => warn!("Ignoring write to unknown column {:?}", col),
+			}
+		}
+	}
+
+	/// Commit buffered changes to database.
+	///
+	/// Buffered changes are moved aside while they are written, so reads only wait
+	/// for them to be indexed. Compaction, if needed, goes on in the background.
+	pub fn flush(&self) -> Result<()> {
+		let _lock = self.flushing_lock.lock();
+		if self.log.read().is_none() {
+			return Err("Database is closed".into());
+		}
+
+		mem::swap(&mut *self.overlay.write(), &mut *self.flushing.write());
+		if let Err(err) = self.write_flushing() {
+			// keep the changes which were not written, unless they were overwritten meanwhile.
+			let mut overlay = self.overlay.write();
+			let mut flushing = self.flushing.write();
+			for (column, unwritten) in overlay.iter_mut().zip(flushing.iter_mut()) {
+				for (key, state) in unwritten.drain() {
+					column.entry(key).or_insert(state);
+				}
+			}
+			return Err(err);
+		}
+
+		for column in self.flushing.write().iter_mut() {
+			column.clear();
+			column.shrink_to_fit();
+		}
+
+		let needs_compaction = self.log.read().as_ref().map_or(false, Log::needs_compaction);
+		if needs_compaction {
+			self.start_compaction();
+		}
+		Ok(())
+	}
+
+	fn write_flushing(&self) -> Result<()> {
+		let flushing = self.flushing.read();
+		let ops = flushing.iter()
+			.enumerate()
+			.flat_map(|(col, column)| column.iter().map(move |(key, state)| match *state {
+				KeyState::Insert(ref value) => (col, &key[..], Some(&value[..])),
+				KeyState::Delete => (col, &key[..], None),
+			}))
+			.collect::<Vec<_>>();
+
+		if ops.is_empty() {
+			return Ok(());
+		}
+
+		let (data, positions) = encode(&ops, MAX_RECORD_LEN)?;
+		match *self.log.read() {
+			Some(ref log) => log.write_records(&data, self.config.sync)?,
+			None => return Err("Database is closed".into()),
+		}
+		match *self.log.write() {
+			Some(ref mut log) => log.index_records(&ops, positions, data.len() as u64),
+			None => return Err("Database is closed".into()),
+		}
+		Ok(())
+	}
+
+	/// Compacts the log on a background thread, unless such a compaction is running already.
+	fn start_compaction(&self) {
+		let mut compaction = self.compaction.lock();
+		if self.compacting.swap(true, Ordering::SeqCst) {
+			return;
+		}
+
+		// the previous compaction is over, so this returns at once.
+		if let Some(handle) = compaction.take() {
+			let _ = handle.join();
+		}
+
+		let log = self.log.clone();
+		let flushing_lock = self.flushing_lock.clone();
+		let compaction_lock = self.compaction_lock.clone();
+		let compacting = self.compacting.clone();
+		let path = self.path.clone();
+		let spawned = thread::Builder::new().name("logdb-compaction".into()).spawn(move || {
+			info!("Compacting database at {}", path.display());
+			// the flushed data is safe either way, so a failed compaction is only reported.
+			if let Err(err) = compact(&log, &flushing_lock, &compaction_lock, &path) {
+				warn!("Failed to compact database at {}: {}", path.display(), err);
+			}
+			compacting.store(false, Ordering::SeqCst);
+		});
+
+		match spawned {
+			Ok(handle) => *compaction = Some(handle),
+			Err(err) => {
+				warn!("Failed to start compaction of database at {}: {}", self.path.display(), err);
+				self.compacting.store(false, Ordering::SeqCst);
+			},
+		}
+	}
+
+	/// Waits for the background compaction to finish.
+	fn join_compaction(&self) {
+		let handle = self.compaction.lock().take();
+		if let Some(handle) = handle {
+			let _ = handle.join();
+		}
+	}
+
+	/// Get value by key.
+	pub fn get(&self, col: Option<u32>, key: &[u8]) -> Result<Option<DBValue>> {
+		let c = Self::to_overlay_column(col);
+		// changes move from the overlay to the flushing buffer and then to the log,
+		// so looking them up in this order finds the latest one.
+		for buffer in &[&self.overlay, &self.flushing] {
+			match buffer.read().get(c).map(|column| column.get(key)) {
+				None => return Err(format!("No such column family: {:?}", col).into()),
+				Some(Some(&KeyState::Insert(ref value))) => return Ok(Some(value.clone())),
+				Some(Some(&KeyState::Delete)) => return Ok(None),
+				Some(None) => {},
+			}
+		}
+
+		match *self.log.read() {
+			Some(ref log) => Ok(log.get(c, key)?.map(DBValue::from_vec)),
+			None => Ok(None),
+		}
+	}
+
+	/// Get value by partial key.
+	pub fn get_by_prefix(&self, col: Option<u32>, prefix: &[u8]) -> Option<Box<[u8]>> {
+		self.iter_from_prefix(col, prefix)
+			.next()
+			.and_then(|(key, value)| if key.starts_with(prefix) { Some(value) } else { None })
+	}
+
+	/// Get database iterator, including buffered changes.
+	pub fn iter(&self, col: Option<u32>) -> DatabaseIterator {
+		self.iter_from(col, &[])
+	}
+
+	/// Get database iterator, including buffered changes, starting at the first key not less than `prefix`.
+	pub fn iter_from_prefix(&self, col: Option<u32>, prefix: &[u8]) -> DatabaseIterator {
+		self.iter_from(col, prefix)
+	}
+
+	fn iter_from(&self, col: Option<u32>, from: &[u8]) -> DatabaseIterator {
+		let c = Self::to_overlay_column(col);
+		let buffered = {
+			// locked in the same order as by `flush`.
+			let overlay = self.overlay.read();
+			let flushing = self.flushing.read();
+			// changes in the overlay are newer than the ones being flushed, so they come last.
+			let buffered = flushing.get(c).into_iter()
+				.chain(overlay.get(c))
+				.flat_map(|column| column.iter())
+				.filter(|&(key, _)| &key[..] >= from)
+				.map(|(key, state)| (key.clone(), match *state {
+					KeyState::Insert(ref value) => Some(value.clone()),
+					KeyState::Delete => None,
+				}))
+				.collect::<BTreeMap<_, _>>();
+			buffered
+		};
+
+		DatabaseIterator {
+			db: self,
+			col: c,
+			next: Bound::Included(from.to_vec()),
+			buffered,
+		}
+	}
+
+	/// Reclaim space taken by overwritten and deleted values.
+	pub fn compact(&self) -> Result<()> {
+		self.flush()?;
+		compact(&self.log, &self.flushing_lock, &self.compaction_lock, &self.path).map_err(Into::into)
+	}
+
+	/// Close the database
+	fn close(&self) {
+		{
+			let _lock = self.flushing_lock.lock();
+			*self.log.write() = None;
+			for column in self.overlay.write().iter_mut() {
+				column.clear();
+			}
+			for column in self.flushing.write().iter_mut() {
+				column.clear();
+			}
+		}
+
+		// a running compaction stops once it notices that the database is closed.
+		self.join_compaction();
+	}
+
+	/// Restore the database from a copy at given path.
+	pub fn restore(&self, new_db: &str) -> Result<()> {
+		self.close();
+
+		let mut backup_db = self.path.clone();
+		backup_db.pop();
+		backup_db.push("backup_db");
+
+		let existed = match fs::rename(&self.path, &backup_db) {
+			Ok(_) => true,
+			Err(e) => if let io::ErrorKind::NotFound = e.kind() {
+				false
+			} else {
+				return Err(e.into());
+			}
+		};
+
+		match fs::rename(&new_db, &self.path) {
+			Ok(_) => {
+				// clean up the backup.
+				if existed {
+					fs::remove_dir_all(&backup_db)?;
+				}
+			}
+			Err(e) => {
+				// restore the backup.
+				if existed {
+					fs::rename(&backup_db, &self.path)?;
+				}
+				return Err(e.into())
+			}
+		}
+
+		let columns = self.overlay.read().len();
+		*self.log.write() = Some(Log::open(&self.path, columns)?);
+		Ok(())
+	}
+
+	/// The number of non-default columns.
+	pub fn num_columns(&self) -> u32 {
+		self.overlay.read().len() as u32 - 1
+	}
+}
+
+// duplicate declaration of methods here to avoid trait import in certain existing cases
+// at time of addition.
+impl KeyValueDB for Database {
+	fn get(&self, col: Option<u32>, key: &[u8]) -> Result<Option<DBValue>> {
+		Database::get(self, col, key)
+	}
+
+	fn get_by_prefix(&self, col: Option<u32>, prefix: &[u8]) -> Option<Box<[u8]>> {
+		Database::get_by_prefix(self, col, prefix)
+	}
+
+	fn write_buffered(&self, transaction: DBTransaction) {
+		Database::write_buffered(self, transaction)
+	}
+
+	fn flush(&self) -> Result<()> {
+		Database::flush(self)
+	}
+
+	fn iter<'a>(&'a self, col: Option<u32>) -> Box<Iterator<Item=(Box<[u8]>, Box<[u8]>)> + 'a> {
+		Box::new(Database::iter(self, col))
+	}
+
+	fn iter_from_prefix<'a>(&'a self, col: Option<u32>, prefix: &'a [u8])
+		-> Box<Iterator<Item=(Box<[u8]>, Box<[u8]>)> + 'a>
+	{
+		Box::new(Database::iter_from_prefix(self, col, prefix))
+	}
+
+	fn restore(&self, new_db: &str) -> Result<()> {
+		Database::restore(self, new_db)
+	}
+}
+
+impl Drop for Database {
+	fn drop(&mut self) {
+		// write all buffered changes if we can.
+		let _ = self.flush();
+		self.close();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	extern crate kvdb_shared_tests as shared;
+	extern crate tempdir;
+
+	use std::fs::OpenOptions;
+	use std::io::Write;
+	use self::tempdir::TempDir;
+	use super::*;
+
+	#[test]
+	fn conforms_to_shared_tests() {
+		shared::run_all(|test| {
+			let tempdir = TempDir::new("").unwrap();
+			let db = Database::open(&DatabaseConfig::with_columns(Some(2)), tempdir.path().to_str().unwrap()).unwrap();
+			test(&db)
+		});
+	}
+
+	#[test]
+	fn should_persist_across_reopen_and_compaction() {
+		let tempdir = TempDir::new("").unwrap();
+		let path = tempdir.path().to_str().unwrap();
+		let config = DatabaseConfig::with_columns(Some(1));
+
+		{
+			let db = Database::open(&config, path).unwrap();
+			let mut batch = db.transaction();
+			batch.put(Some(0), b"foo", b"bar");
+			batch.put(Some(0), b"baz", b"qux");
+			db.write(batch).unwrap();
+
+			let mut batch = db.transaction();
+			batch.delete(Some(0), b"baz");
+			db.write_buffered(batch);
+		}
+
+		let db = Database::open(&config, path).unwrap();
+		assert_eq!(&*db.get(Some(0), b"foo").unwrap().unwrap(), b"bar");
+		assert!(db.get(Some(0), b"baz").unwrap().is_none());
+
+		db.compact().unwrap();
+		assert_eq!(&*db.get(Some(0), b"foo").unwrap().unwrap(), b"bar");
+		assert_eq!(db.iter(Some(0)).count(), 1);
+	}
+
+	#[test]
+	fn should_discard_incomplete_record() {
+		let tempdir = TempDir::new("").unwrap();
+		let path = tempdir.path().to_str().unwrap();
+
+		{
+			let db = Database::open_default(path).unwrap();
+			let mut batch = db.transaction();
+			batch.put(None, b"foo", b"bar");
+			db.write(batch).unwrap();
+		}
+
+		// simulate a torn write
+		let mut file = OpenOptions::new().append(true).open(tempdir.path().join(LOG_FILE_NAME)).unwrap();
+		file.write_all(&[20, 0, 0, 0, OP_INSERT, 0]).unwrap();
+		drop(file);
+
+		let db = Database::open_default(path).unwrap();
+		assert_eq!(&*db.get(None, b"foo").unwrap().unwrap(), b"bar");
+
+		let mut batch = db.transaction();
+		batch.put(None, b"baz", b"qux");
+		db.write(batch).unwrap();
+		drop(db);
+
+		let db = Database::open_default(path).unwrap();
+		assert_eq!(&*db.get(None, b"baz").unwrap().unwrap(), b"qux");
+	}
+
+	#[test]
+	fn should_not_trust_length_of_torn_record() {
+		let tempdir = TempDir::new("").unwrap();
+		let path = tempdir.path().to_str().unwrap();
+
+		{
+			let db = Database::open_default(path).unwrap();
+			let mut batch = db.transaction();
+			batch.put(None, b"foo", b"bar");
+			db.write(batch).unwrap();
+		}
+
+		let mut file = OpenOptions::new().append(true).open(tempdir.path().join(LOG_FILE_NAME)).unwrap();
+		file.write_all(&[0xff, 0xff, 0xff, 0xff, OP_INSERT]).unwrap();
+		drop(file);
+
+		let db = Database::open_default(path).unwrap();
+		assert_eq!(&*db.get(None, b"foo").unwrap().unwrap(), b"bar");
+	}
+
+	#[test]
+	fn should_split_transactions_into_records() {
+		let tempdir = TempDir::new("").unwrap();
+		let (foo, bar, baz, qux) = (&b"foo"[..], &b"bar"[..], &b"baz"[..], &b"qux"[..]);
+
+		let complete = {
+			let mut log = Log::open(tempdir.path(), 1).unwrap();
+			// every operation takes a record of its own
+			log.append_records(&[(0, foo, Some(bar)), (0, baz, Some(qux))], false, 30).unwrap();
+			assert_eq!(log.get(0, foo).unwrap(), Some(bar.to_vec()));
+			assert_eq!(log.get(0, baz).unwrap(), Some(qux.to_vec()));
+
+			let complete = log.len;
+			log.append_records(&[(0, foo, None), (0, baz, Some(bar))], false, 30).unwrap();
+			assert_eq!(log.get(0, foo).unwrap(), None);
+
+			// the last record of the transaction is torn
+			log.file.set_len(log.len - 1).unwrap();
+			complete
+		};
+
+		let mut log = Log::open(tempdir.path(), 1).unwrap();
+		assert_eq!(log.len, complete);
+		assert_eq!(log.get(0, foo).unwrap(), Some(bar.to_vec()));
+		assert_eq!(log.get(0, baz).unwrap(), Some(qux.to_vec()));
+
+		let large = [0u8; 30];
+		assert!(log.append_records(&[(0, foo, Some(&large[..]))], false, 30).is_err());
+		assert_eq!(log.len, complete);
+	}
+
+	#[test]
+	fn should_compact_while_running() {
+		let tempdir = TempDir::new("").unwrap();
+		let db = Database::open_default(tempdir.path().to_str().unwrap()).unwrap();
+		let value = vec![1u8; 1024 * 1024];
+
+		// the last write makes garbage exceed the threshold
+		for _ in 0..(COMPACTION_THRESHOLD / value.len() as u64 + 1) {
+			let mut batch = db.transaction();
+			batch.put(None, b"foo", &value);
+			db.write(batch).unwrap();
+		}
+		db.join_compaction();
+
+		let size = fs::metadata(tempdir.path().join(LOG_FILE_NAME)).unwrap().len();
+		assert!(size < 2 * value.len() as u64);
+		assert_eq!(&*db.get(None, b"foo").unwrap().unwrap(), &value[..]);
+	}
+
+	#[test]
+	fn should_keep_changes_flushed_during_compaction() {
+		let tempdir = TempDir::new("").unwrap();
+		let path = tempdir.path().to_str().unwrap();
+		let db = Database::open_default(path).unwrap();
+		let mut batch = db.transaction();
+		batch.put(None, b"foo", b"bar");
+		batch.put(None, b"baz", b"qux");
+		db.write(batch).unwrap();
+
+		let (compacted, start) = copy_live(&db.log, tempdir.path()).unwrap();
+		let mut batch = db.transaction();
+		batch.put(None, b"foo", b"new");
+		batch.delete(None, b"baz");
+		batch.put(None, b"quux", b"corge");
+		db.write(batch).unwrap();
+		// read while the compacted log is not in place yet
+		assert_eq!(&*db.get(None, b"foo").unwrap().unwrap(), b"new");
+		finish_compaction(&db.log, &db.flushing_lock, compacted, start, tempdir.path()).unwrap();
+
+		let check = |db: &Database| {
+			assert_eq!(&*db.get(None, b"foo").unwrap().unwrap(), b"new");
+			assert!(db.get(None, b"baz").unwrap().is_none());
+			assert_eq!(&*db.get(None, b"quux").unwrap().unwrap(), b"corge");
+			assert_eq!(db.iter(None).count(), 2);
+		};
+		check(&db);
+		drop(db);
+		check(&Database::open_default(path).unwrap());
+	}
+
+	#[test]
+	fn should_read_changes_being_flushed() {
+		let tempdir = TempDir::new("").unwrap();
+		let db = Database::open_default(tempdir.path().to_str().unwrap()).unwrap();
+		let mut batch = db.transaction();
+		batch.put(None, b"foo", b"bar");
+		batch.put(None, b"baz", b"qux");
+		db.write(batch).unwrap();
+
+		let mut batch = db.transaction();
+		batch.put(None, b"foo", b"flushing");
+		batch.delete(None, b"baz");
+		db.write_buffered(batch);
+		mem::swap(&mut *db.overlay.write(), &mut *db.flushing.write());
+		let mut batch = db.transaction();
+		batch.put(None, b"baz", b"buffered");
+		db.write_buffered(batch);
+
+		assert_eq!(&*db.get(None, b"foo").unwrap().unwrap(), b"flushing");
+		assert_eq!(&*db.get(None, b"baz").unwrap().unwrap(), b"buffered");
+		let entries = db.iter(None).map(|(key, value)| (key.into_vec(), value.into_vec())).collect::<Vec<_>>();
+		assert_eq!(entries, vec![(b"baz".to_vec(), b"buffered".to_vec()), (b"foo".to_vec(), b"flushing".to_vec())]);
+	}
+
+	#[test]
+	fn should_recover_from_interrupted_compaction() {
+		let tempdir = TempDir::new("").unwrap();
+		let path = tempdir.path().to_str().unwrap();
+
+		{
+			let db = Database::open_default(path).unwrap();
+			let mut batch = db.transaction();
+			batch.put(None, b"foo", b"bar");
+			db.write(batch).unwrap();
+		}
+
+		// the old log is gone, but the compacted one is not in its place yet
+		fs::rename(tempdir.path().join(LOG_FILE_NAME), tempdir.path().join(COMPACTION_FILE_NAME)).unwrap();
+		assert!(is_database(path));
+
+		let db = Database::open_default(path).unwrap();
+		assert_eq!(&*db.get(None, b"foo").unwrap().unwrap(), b"bar");
+		assert!(!tempdir.path().join(COMPACTION_FILE_NAME).exists());
+	}
+
+	#[test]
+	fn should_restore_from_another_database() {
+		let tempdir = TempDir::new("").unwrap();
+		let path = tempdir.path().join("db");
+		let new_path = tempdir.path().join("new");
+
+		{
+			let db = Database::open_default(new_path.to_str().unwrap()).unwrap();
+			let mut batch = db.transaction();
+			batch.put(None, b"foo", b"restored");
+			db.write(batch).unwrap();
+		}
+
+		let db = Database::open_default(path.to_str().unwrap()).unwrap();
+		db.restore(new_path.to_str().unwrap()).unwrap();
+		assert_eq!(&*db.get(None, b"foo").unwrap().unwrap(), b"restored");
+		assert!(is_database(&path));
+	}
+}
@@ -115,3 +115,15 @@ impl KeyValueDB for InMemory {
 		Err("Attempted to restore in-memory database".into())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	extern crate kvdb_shared_tests as shared;
+
+	use super::create;
+
+	#[test]
+	fn conforms_to_shared_tests() {
+		shared::run_all(|test| test(&create(2)));
+	}
+}
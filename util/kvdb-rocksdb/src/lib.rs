@@ -28,14 +28,14 @@ extern crate ethereum_types;
 extern crate kvdb;
 
 use std::cmp;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::marker::PhantomData;
 use std::path::{PathBuf, Path};
 use std::{fs, io, mem, result};
 
 use parking_lot::{Mutex, MutexGuard, RwLock};
 use rocksdb::{
-	DB, Writable, WriteBatch, WriteOptions, IteratorMode, DBIterator,
+	DB, Writable, WriteBatch, WriteOptions, IteratorMode,
 	Options, BlockBasedOptions, Direction, Cache, Column, ReadOptions
 };
 use interleaved_ordered::{interleave_ordered, InterleaveOrdered};
@@ -194,12 +194,12 @@ impl Default for DatabaseConfig {
 	}
 }
 
-/// Database iterator (for flushed data only)
+/// Database iterator over flushed data merged with the pending write buffer.
 // The compromise of holding only a virtual borrow vs. holding a lock on the
 // inner DB (to prevent closing via restoration) may be re-evaluated in the future.
 //
 pub struct DatabaseIterator<'a> {
-	iter: InterleaveOrdered<::std::vec::IntoIter<(Box<[u8]>, Box<[u8]>)>, DBIterator>,
+	iter: InterleaveOrdered<::std::vec::IntoIter<(Box<[u8]>, Box<[u8]>)>, Box<Iterator<Item=(Box<[u8]>, Box<[u8]>)> + 'a>>,
 	_marker: PhantomData<&'a Database>,
 }
 
@@ -524,8 +524,7 @@ impl Database {
 		}
 	}
 
-	/// Get value by partial key. Prefix size should match configured prefix size. Only searches flushed values.
-	// TODO: support prefix seek for unflushed data
+	/// Get value by partial key. Prefix size should match configured prefix size.
 	pub fn get_by_prefix(&self, col: Option<u32>, prefix: &[u8]) -> Option<Box<[u8]>> {
 		self.iter_from_prefix(col, prefix).and_then(|mut iter| {
 			match iter.next() {
@@ -536,43 +535,52 @@ impl Database {
 		})
 	}
 
-	/// Get database iterator for flushed data.
+	/// Get database iterator, including values still in the write buffer.
 	pub fn iter(&self, col: Option<u32>) -> Option<DatabaseIterator> {
-		match *self.db.read() {
-			Some(DBAndColumns { ref db, ref cfs }) => {
-				let overlay = &self.overlay.read()[Self::to_overlay_column(col)];
-				let mut overlay_data = overlay.iter()
-					.filter_map(|(k, v)| match *v {
-						KeyState::Insert(ref value) =>
-							Some((k.clone().into_vec().into_boxed_slice(), value.clone().into_vec().into_boxed_slice())),
-						KeyState::Delete => None,
-					}).collect::<Vec<_>>();
-				overlay_data.sort();
-
-				let iter = col.map_or_else(
-					|| db.iterator_opt(IteratorMode::Start, &self.read_opts),
-					|c| db.iterator_cf_opt(cfs[c as usize], IteratorMode::Start, &self.read_opts)
-						.expect("iterator params are valid; qed")
-				);
+		self.iter_with_mode(col, IteratorMode::Start, &[])
+	}
 
-				Some(DatabaseIterator {
-					iter: interleave_ordered(overlay_data, iter),
-					_marker: PhantomData,
-				})
-			},
-			None => None,
+	fn iter_from_prefix(&self, col: Option<u32>, prefix: &[u8]) -> Option<DatabaseIterator> {
+		self.iter_with_mode(col, IteratorMode::From(prefix, Direction::Forward), prefix)
+	}
+
+	/// Changes to the column which are not yet on disk, for keys at or after `from`.
+	/// The overlay is newer than the data being flushed, so it takes precedence.
+	fn buffered_changes(&self, col: Option<u32>, from: &[u8]) -> BTreeMap<Vec<u8>, Option<DBValue>> {
+		let overlay = &self.overlay.read()[Self::to_overlay_column(col)];
+		let flushing = &self.flushing.read()[Self::to_overlay_column(col)];
+		let mut changes = BTreeMap::new();
+		for (k, v) in flushing.iter().chain(overlay.iter()) {
+			if &k[..] < from {
+				continue;
+			}
+			let value = match *v {
+				KeyState::Insert(ref value) => Some(value.clone()),
+				KeyState::Delete => None,
+			};
+			changes.insert(k.to_vec(), value);
 		}
+		changes
 	}
 
-	fn iter_from_prefix(&self, col: Option<u32>, prefix: &[u8]) -> Option<DatabaseIterator> {
+	fn iter_with_mode(&self, col: Option<u32>, mode: IteratorMode, from: &[u8]) -> Option<DatabaseIterator> {
 		match *self.db.read() {
 			Some(DBAndColumns { ref db, ref cfs }) => {
-				let iter = col.map_or_else(|| db.iterator_opt(IteratorMode::From(prefix, Direction::Forward), &self.read_opts),
-					|c| db.iterator_cf_opt(cfs[c as usize], IteratorMode::From(prefix, Direction::Forward), &self.read_opts)
-						.expect("iterator params are valid; qed"));
+				let changes = self.buffered_changes(col, from);
+				let inserted = changes.iter()
+					.filter_map(|(k, v)| v.as_ref().map(|v| (k.clone().into_boxed_slice(), v.clone().into_vec().into_boxed_slice())))
+					.collect::<Vec<_>>();
+
+				let iter = match col {
+					None => db.iterator_opt(mode, &self.read_opts),
+					Some(c) => db.iterator_cf_opt(cfs[c as usize], mode, &self.read_opts)
+						.expect("iterator params are valid; qed"),
+				};
+				// keys changed in the buffer shadow whatever is on disk
+				let iter = iter.filter(move |&(ref k, _)| !changes.contains_key(&k[..]));
 
 				Some(DatabaseIterator {
-					iter: interleave_ordered(Vec::new(), iter),
+					iter: interleave_ordered(inserted, Box::new(iter)),
 					_marker: PhantomData,
 				})
 			},
@@ -714,6 +722,7 @@ impl Drop for Database {
 
 #[cfg(test)]
 mod tests {
+	extern crate kvdb_shared_tests as shared;
 	extern crate tempdir;
 
 	use std::str::FromStr;
@@ -768,6 +777,12 @@ mod tests {
 		db.write_buffered(transaction);
 		assert!(db.get(None, &key3).unwrap().is_none());
 		assert_eq!(&*db.get(None, &key1).unwrap().unwrap(), b"horse");
+		assert_eq!(&*db.get_by_prefix(None, &key1).unwrap(), b"horse");
+		assert!(db.get_by_prefix(None, &key3).is_none());
+		let contents: Vec<_> = db.iter(None).into_iter().flat_map(|inner| inner).collect();
+		assert_eq!(contents.len(), 2);
+		assert_eq!(&*contents[0].1, b"horse");
+		assert_eq!(&*contents[1].1, b"dog");
 
 		db.flush().unwrap();
 		assert!(db.get(None, &key3).unwrap().is_none());
@@ -781,6 +796,15 @@ mod tests {
 		test_db(&DatabaseConfig::default());
 	}
 
+	#[test]
+	fn conforms_to_shared_tests() {
+		shared::run_all(|test| {
+			let tempdir = TempDir::new("").unwrap();
+			let db = Database::open(&DatabaseConfig::with_columns(Some(2)), tempdir.path().to_str().unwrap()).unwrap();
+			test(&db)
+		});
+	}
+
 	#[test]
 	#[cfg(target_os = "linux")]
 	fn df_to_rotational() {
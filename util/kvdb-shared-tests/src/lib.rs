@@ -0,0 +1,196 @@
+// Copyright 2015-2017 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Conformance tests every `KeyValueDB` implementation must pass.
+//!
+//! Each test expects an empty database opened with at least two columns.
+
+extern crate kvdb;
+
+use kvdb::{KeyValueDB, Result};
+
+/// Runs every conformance test. `run` is called once per test and should pass it
+/// a freshly opened empty database.
+pub fn run_all<F>(mut run: F) where F: FnMut(fn(&KeyValueDB) -> Result<()>) -> Result<()> {
+	let tests: &[fn(&KeyValueDB) -> Result<()>] = &[
+		test_put_and_get,
+		test_delete_and_get,
+		test_write_buffered,
+		test_columns_are_isolated,
+		test_iter,
+		test_iter_buffered,
+		test_prefix,
+	];
+
+	for test in tests {
+		if let Err(err) = run(*test) {
+			panic!("Conformance test failed: {}", err);
+		}
+	}
+}
+
+/// Values written with `write` can be read back.
+pub fn test_put_and_get(db: &KeyValueDB) -> Result<()> {
+	let key1 = b"key1";
+
+	let mut transaction = db.transaction();
+	transaction.put(Some(0), key1, b"horse");
+	db.write(transaction)?;
+	assert_eq!(&*db.get(Some(0), key1)?.unwrap(), b"horse");
+
+	let mut transaction = db.transaction();
+	transaction.put(Some(0), key1, b"cat");
+	db.write(transaction)?;
+	assert_eq!(&*db.get(Some(0), key1)?.unwrap(), b"cat");
+	assert!(db.get(Some(0), b"key2")?.is_none());
+	Ok(())
+}
+
+/// Deleted values can no longer be read.
+pub fn test_delete_and_get(db: &KeyValueDB) -> Result<()> {
+	let key1 = b"key1";
+
+	let mut transaction = db.transaction();
+	transaction.put(Some(0), key1, b"horse");
+	db.write(transaction)?;
+	assert_eq!(&*db.get(Some(0), key1)?.unwrap(), b"horse");
+
+	let mut transaction = db.transaction();
+	transaction.delete(Some(0), key1);
+	db.write(transaction)?;
+	assert!(db.get(Some(0), key1)?.is_none());
+
+	// operations within a single transaction are applied in order
+	let mut transaction = db.transaction();
+	transaction.put(Some(0), key1, b"horse");
+	transaction.delete(Some(0), key1);
+	db.write(transaction)?;
+	assert!(db.get(Some(0), key1)?.is_none());
+	Ok(())
+}
+
+/// Buffered values are readable before and after `flush`.
+pub fn test_write_buffered(db: &KeyValueDB) -> Result<()> {
+	let mut transaction = db.transaction();
+	transaction.put(Some(0), b"key1", b"horse");
+	transaction.put(Some(0), b"key2", b"cat");
+	db.write(transaction)?;
+
+	let mut transaction = db.transaction();
+	transaction.put(Some(0), b"key1", b"dog");
+	transaction.delete(Some(0), b"key2");
+	db.write_buffered(transaction);
+	assert_eq!(&*db.get(Some(0), b"key1")?.unwrap(), b"dog");
+	assert!(db.get(Some(0), b"key2")?.is_none());
+
+	db.flush()?;
+	assert_eq!(&*db.get(Some(0), b"key1")?.unwrap(), b"dog");
+	assert!(db.get(Some(0), b"key2")?.is_none());
+	Ok(())
+}
+
+/// Keys written to one column are not visible in another one.
+pub fn test_columns_are_isolated(db: &KeyValueDB) -> Result<()> {
+	let mut transaction = db.transaction();
+	transaction.put(Some(0), b"key1", b"horse");
+	transaction.put(Some(1), b"key1", b"cat");
+	db.write(transaction)?;
+
+	assert_eq!(&*db.get(Some(0), b"key1")?.unwrap(), b"horse");
+	assert_eq!(&*db.get(Some(1), b"key1")?.unwrap(), b"cat");
+	assert!(db.get(None, b"key1")?.is_none());
+
+	let mut transaction = db.transaction();
+	transaction.delete(Some(1), b"key1");
+	db.write(transaction)?;
+	assert_eq!(&*db.get(Some(0), b"key1")?.unwrap(), b"horse");
+	assert!(db.get(Some(1), b"key1")?.is_none());
+	assert_eq!(db.iter(Some(1)).count(), 0);
+	Ok(())
+}
+
+/// Iteration yields all pairs of a column in key order.
+pub fn test_iter(db: &KeyValueDB) -> Result<()> {
+	let mut transaction = db.transaction();
+	transaction.put(Some(0), b"key3", b"elephant");
+	transaction.put(Some(0), b"key1", b"horse");
+	transaction.put(Some(0), b"key2", b"cat");
+	transaction.put(Some(1), b"key0", b"dog");
+	db.write(transaction)?;
+
+	let contents = db.iter(Some(0)).collect::<Vec<_>>();
+	assert_eq!(contents.len(), 3);
+	assert_eq!(&*contents[0].0, b"key1");
+	assert_eq!(&*contents[0].1, b"horse");
+	assert_eq!(&*contents[1].0, b"key2");
+	assert_eq!(&*contents[1].1, b"cat");
+	assert_eq!(&*contents[2].0, b"key3");
+	assert_eq!(&*contents[2].1, b"elephant");
+	Ok(())
+}
+
+/// Iteration sees buffered changes before they are flushed.
+pub fn test_iter_buffered(db: &KeyValueDB) -> Result<()> {
+	let mut transaction = db.transaction();
+	transaction.put(Some(0), b"key1", b"horse");
+	transaction.put(Some(0), b"key2", b"cat");
+	db.write(transaction)?;
+
+	let mut transaction = db.transaction();
+	transaction.put(Some(0), b"key0", b"dog");
+	transaction.put(Some(0), b"key2", b"elephant");
+	transaction.delete(Some(0), b"key1");
+	db.write_buffered(transaction);
+
+	let contents = db.iter(Some(0)).collect::<Vec<_>>();
+	assert_eq!(contents.len(), 2);
+	assert_eq!(&*contents[0].0, b"key0");
+	assert_eq!(&*contents[0].1, b"dog");
+	assert_eq!(&*contents[1].0, b"key2");
+	assert_eq!(&*contents[1].1, b"elephant");
+
+	let keys = db.iter_from_prefix(Some(0), b"key")
+		.take_while(|&(ref key, _)| key.starts_with(b"key"))
+		.map(|(key, _)| key.into_vec())
+		.collect::<Vec<_>>();
+	assert_eq!(keys, vec![b"key0".to_vec(), b"key2".to_vec()]);
+	assert_eq!(&*db.get_by_prefix(Some(0), b"key").unwrap(), b"dog");
+
+	db.flush()?;
+	assert_eq!(db.iter(Some(0)).count(), 2);
+	Ok(())
+}
+
+/// Prefix lookups and prefix iteration see data starting at the prefix.
+pub fn test_prefix(db: &KeyValueDB) -> Result<()> {
+	let mut transaction = db.transaction();
+	transaction.put(Some(0), b"aa1", b"horse");
+	transaction.put(Some(0), b"ab1", b"cat");
+	transaction.put(Some(0), b"ab2", b"dog");
+	transaction.put(Some(0), b"bb1", b"elephant");
+	db.write(transaction)?;
+
+	assert_eq!(&*db.get_by_prefix(Some(0), b"ab").unwrap(), b"cat");
+	assert_eq!(&*db.get_by_prefix(Some(0), b"b").unwrap(), b"elephant");
+	assert!(db.get_by_prefix(Some(0), b"c").is_none());
+
+	let keys = db.iter_from_prefix(Some(0), b"ab")
+		.take_while(|&(ref key, _)| key.starts_with(b"ab"))
+		.map(|(key, _)| key.into_vec())
+		.collect::<Vec<_>>();
+	assert_eq!(keys, vec![b"ab1".to_vec(), b"ab2".to_vec()]);
+	Ok(())
+}
@@ -166,10 +166,10 @@ pub trait KeyValueDB: Sync + Send {
 	/// Flush all buffered data.
 	fn flush(&self) -> Result<()>;
 
-	/// Iterate over flushed data for a given column.
+	/// Iterate over data for a given column, including buffered writes.
 	fn iter<'a>(&'a self, col: Option<u32>) -> Box<Iterator<Item=(Box<[u8]>, Box<[u8]>)> + 'a>;
 
-	/// Iterate over flushed data for a given column, starting from a given prefix.
+	/// Iterate over data for a given column, including buffered writes, starting from a given prefix.
 	fn iter_from_prefix<'a>(&'a self, col: Option<u32>, prefix: &'a [u8])
 		-> Box<Iterator<Item=(Box<[u8]>, Box<[u8]>)> + 'a>;
 